@@ -0,0 +1,40 @@
+-- Staff shift rostering: concrete shifts, weekly rota templates and minimum coverage rules
+CREATE TABLE shifts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    department_id UUID NOT NULL REFERENCES departments(id) ON DELETE CASCADE,
+    staff_id UUID NOT NULL REFERENCES staff(id) ON DELETE CASCADE,
+    role VARCHAR(50) NOT NULL CHECK (role IN ('DOCTOR', 'NURSE', 'ADMIN', 'SUPPORT')),
+    shift_type VARCHAR(20) NOT NULL CHECK (shift_type IN ('REGULAR', 'ON_CALL')),
+    start_time TIMESTAMPTZ NOT NULL,
+    end_time TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (end_time > start_time)
+);
+
+CREATE INDEX idx_shifts_hospital_id ON shifts(hospital_id);
+CREATE INDEX idx_shifts_staff_time ON shifts(staff_id, start_time, end_time);
+CREATE INDEX idx_shifts_department_time ON shifts(department_id, start_time, end_time);
+
+CREATE TABLE rota_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    department_id UUID NOT NULL REFERENCES departments(id) ON DELETE CASCADE,
+    staff_id UUID NOT NULL REFERENCES staff(id) ON DELETE CASCADE,
+    shift_type VARCHAR(20) NOT NULL CHECK (shift_type IN ('REGULAR', 'ON_CALL')),
+    day_of_week SMALLINT NOT NULL CHECK (day_of_week BETWEEN 0 AND 6), -- 0 = Monday
+    start_time TIME NOT NULL,
+    duration_minutes INT NOT NULL CHECK (duration_minutes > 0 AND duration_minutes <= 1440),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_rota_templates_hospital_id ON rota_templates(hospital_id);
+
+CREATE TABLE coverage_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    department_id UUID NOT NULL REFERENCES departments(id) ON DELETE CASCADE,
+    role VARCHAR(50) NOT NULL CHECK (role IN ('DOCTOR', 'NURSE', 'ADMIN', 'SUPPORT')),
+    min_staff INT NOT NULL CHECK (min_staff >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(department_id, role)
+);
@@ -0,0 +1,16 @@
+-- Professional licences held by clinical staff (e.g. MDCN for doctors, NMCN for nurses)
+CREATE TABLE staff_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    staff_id UUID NOT NULL REFERENCES staff(id) ON DELETE CASCADE,
+    licence_body VARCHAR(100) NOT NULL,
+    licence_number VARCHAR(100) NOT NULL,
+    specialty VARCHAR(100),
+    issued_on DATE NOT NULL,
+    expires_on DATE NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(licence_body, licence_number),
+    CHECK (expires_on > issued_on)
+);
+
+CREATE INDEX idx_staff_credentials_staff_id ON staff_credentials(staff_id);
+CREATE INDEX idx_staff_credentials_expires_on ON staff_credentials(expires_on);
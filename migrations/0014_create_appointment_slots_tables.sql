@@ -0,0 +1,30 @@
+-- Clinician availability slots and the audit trail of appointment changes
+CREATE TABLE availability_slots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    department_id UUID NOT NULL REFERENCES departments(id) ON DELETE CASCADE,
+    staff_id UUID NOT NULL REFERENCES staff(id) ON DELETE CASCADE,
+    start_time TIMESTAMPTZ NOT NULL,
+    end_time TIMESTAMPTZ NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'OPEN' CHECK (status IN ('OPEN', 'BOOKED')),
+    visit_id UUID REFERENCES visits(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(staff_id, start_time),
+    CHECK (end_time > start_time)
+);
+
+CREATE INDEX idx_availability_slots_open ON availability_slots(status, start_time);
+CREATE INDEX idx_availability_slots_department_id ON availability_slots(department_id);
+CREATE INDEX idx_availability_slots_visit_id ON availability_slots(visit_id);
+
+CREATE TABLE appointment_changes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    visit_id UUID NOT NULL REFERENCES visits(id) ON DELETE CASCADE,
+    change_type VARCHAR(20) NOT NULL CHECK (change_type IN ('BOOKED', 'RESCHEDULED', 'CANCELLED')),
+    from_slot_id UUID REFERENCES availability_slots(id) ON DELETE SET NULL,
+    to_slot_id UUID REFERENCES availability_slots(id) ON DELETE SET NULL,
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_appointment_changes_visit_id ON appointment_changes(visit_id);
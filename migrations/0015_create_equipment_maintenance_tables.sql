@@ -0,0 +1,40 @@
+-- Preventive maintenance plans, work orders and the equipment condition history
+CREATE TABLE maintenance_plans (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    equipment_id UUID NOT NULL REFERENCES equipment(id) ON DELETE CASCADE,
+    task VARCHAR(255) NOT NULL,
+    interval_days INT NOT NULL CHECK (interval_days > 0),
+    last_performed_on DATE,
+    next_due_on DATE NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_maintenance_plans_equipment_id ON maintenance_plans(equipment_id);
+CREATE INDEX idx_maintenance_plans_next_due_on ON maintenance_plans(next_due_on);
+
+CREATE TABLE work_orders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    equipment_id UUID NOT NULL REFERENCES equipment(id) ON DELETE CASCADE,
+    plan_id UUID REFERENCES maintenance_plans(id) ON DELETE SET NULL, -- Nullable: corrective work has no plan
+    description TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'OPEN' CHECK (status IN ('OPEN', 'CLOSED')),
+    resolution_notes TEXT,
+    opened_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    closed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_work_orders_equipment_id ON work_orders(equipment_id);
+CREATE INDEX idx_work_orders_status ON work_orders(status);
+
+CREATE TABLE equipment_condition_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    equipment_id UUID NOT NULL REFERENCES equipment(id) ON DELETE CASCADE,
+    old_condition VARCHAR(50), -- NULL for the initial registration entry
+    new_condition VARCHAR(50) NOT NULL,
+    old_is_operational BOOLEAN,
+    new_is_operational BOOLEAN NOT NULL,
+    note TEXT,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_equipment_condition_log_equipment_id ON equipment_condition_log(equipment_id, changed_at);
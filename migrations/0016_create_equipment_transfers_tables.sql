@@ -0,0 +1,42 @@
+-- Equipment loans/transfers between hospitals and their chain of custody
+ALTER TABLE equipment
+ADD COLUMN equipment_type VARCHAR(50) CHECK (equipment_type IN ('VENTILATOR', 'OXYGEN_CONCENTRATOR', 'AMBULANCE', 'OTHER')),
+ADD COLUMN custodian_hospital_id UUID REFERENCES hospitals(id) ON DELETE SET NULL; -- NULL: held by the owning hospital
+
+CREATE INDEX idx_equipment_custodian_hospital_id ON equipment(custodian_hospital_id);
+
+CREATE TABLE equipment_transfers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    equipment_id UUID NOT NULL REFERENCES equipment(id) ON DELETE CASCADE,
+    from_hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    to_hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    transfer_type VARCHAR(20) NOT NULL CHECK (transfer_type IN ('LOAN', 'TRANSFER')),
+    status VARCHAR(20) NOT NULL DEFAULT 'REQUESTED'
+        CHECK (status IN ('REQUESTED', 'APPROVED', 'REJECTED', 'DISPATCHED', 'RECEIVED', 'RETURNED')),
+    reason TEXT NOT NULL,
+    expected_return_on DATE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (from_hospital_id <> to_hospital_id)
+);
+
+CREATE INDEX idx_equipment_transfers_equipment_id ON equipment_transfers(equipment_id);
+CREATE INDEX idx_equipment_transfers_from_hospital_id ON equipment_transfers(from_hospital_id);
+CREATE INDEX idx_equipment_transfers_to_hospital_id ON equipment_transfers(to_hospital_id);
+
+-- A piece of equipment can only be in one open workflow at a time (a received loan stays open until returned)
+CREATE UNIQUE INDEX idx_equipment_transfers_one_active ON equipment_transfers(equipment_id)
+WHERE status IN ('REQUESTED', 'APPROVED', 'DISPATCHED') OR (transfer_type = 'LOAN' AND status = 'RECEIVED');
+
+CREATE TABLE equipment_custody_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    equipment_id UUID NOT NULL REFERENCES equipment(id) ON DELETE CASCADE,
+    transfer_id UUID NOT NULL REFERENCES equipment_transfers(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL,
+    from_hospital_id UUID NOT NULL,
+    to_hospital_id UUID NOT NULL,
+    note TEXT,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_equipment_custody_events_equipment_id ON equipment_custody_events(equipment_id, occurred_at);
@@ -0,0 +1,35 @@
+-- Consumable supplies (oxygen, medicines) held by each hospital and their stock movements.
+-- Blood is not stocked here: units are tracked by group and expiry in blood_units.
+CREATE TABLE inventory_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    category VARCHAR(20) NOT NULL CHECK (category IN ('OXYGEN', 'MEDICINE', 'OTHER')),
+    name VARCHAR(255) NOT NULL,
+    unit VARCHAR(50) NOT NULL,
+    quantity_on_hand INT NOT NULL DEFAULT 0 CHECK (quantity_on_hand >= 0),
+    reorder_level INT NOT NULL DEFAULT 0 CHECK (reorder_level >= 0),
+    stock_status VARCHAR(10) GENERATED ALWAYS AS (
+        CASE
+            WHEN quantity_on_hand = 0 THEN 'OUT'
+            WHEN quantity_on_hand <= reorder_level THEN 'LOW'
+            ELSE 'OK'
+        END
+    ) STORED NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(hospital_id, name)
+);
+
+CREATE INDEX idx_inventory_items_hospital_category ON inventory_items(hospital_id, category);
+
+CREATE TABLE inventory_transactions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    item_id UUID NOT NULL REFERENCES inventory_items(id) ON DELETE CASCADE,
+    transaction_type VARCHAR(20) NOT NULL CHECK (transaction_type IN ('STOCK_IN', 'STOCK_OUT', 'ADJUSTMENT')),
+    quantity INT NOT NULL CHECK (quantity >= 0),
+    balance_after INT NOT NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_inventory_transactions_item_id ON inventory_transactions(item_id, created_at);
@@ -0,0 +1,14 @@
+-- Blood bank stock reported by hospitals, one row per batch of units sharing group, component and expiry
+CREATE TABLE blood_units (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    blood_group VARCHAR(3) NOT NULL CHECK (blood_group IN ('A+', 'A-', 'B+', 'B-', 'AB+', 'AB-', 'O+', 'O-')),
+    component VARCHAR(20) NOT NULL CHECK (component IN ('WHOLE_BLOOD', 'RED_CELLS', 'PLASMA', 'PLATELETS')),
+    units INT NOT NULL CHECK (units >= 0),
+    expires_on DATE NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_blood_units_hospital_id ON blood_units(hospital_id);
+CREATE INDEX idx_blood_units_search ON blood_units(blood_group, component, expires_on) WHERE units > 0;
@@ -0,0 +1,50 @@
+-- Coded visit diagnoses, configurable notifiable conditions and IDSR surveillance case reports
+ALTER TABLE hospitals
+ADD COLUMN lga TEXT; -- Local Government Area, used for surveillance aggregation
+
+CREATE TABLE visit_diagnoses (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    visit_id UUID NOT NULL REFERENCES visits(id) ON DELETE CASCADE,
+    code VARCHAR(10) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(visit_id, code)
+);
+
+CREATE INDEX idx_visit_diagnoses_code ON visit_diagnoses(code);
+
+CREATE TABLE notifiable_conditions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL UNIQUE,
+    code_prefix VARCHAR(10) NOT NULL UNIQUE, -- Matches any diagnosis code starting with this prefix
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO notifiable_conditions (name, code_prefix) VALUES
+('Cholera', 'A00'),
+('Lassa fever', 'A96.2'),
+('Yellow fever', 'A95'),
+('Ebola virus disease', 'A98.4'),
+('Meningococcal meningitis', 'A39'),
+('Measles', 'B05'),
+('Acute poliomyelitis', 'A80'),
+('Monkeypox', 'B04'),
+('COVID-19', 'U07.1');
+
+CREATE TABLE surveillance_cases (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    visit_id UUID NOT NULL REFERENCES visits(id) ON DELETE CASCADE,
+    patient_id UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    condition_id UUID NOT NULL REFERENCES notifiable_conditions(id) ON DELETE CASCADE,
+    diagnosis_code VARCHAR(10) NOT NULL,
+    state TEXT NOT NULL,
+    lga TEXT NOT NULL,
+    presented_at TIMESTAMPTZ NOT NULL,
+    reported_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(visit_id, condition_id)
+);
+
+CREATE INDEX idx_surveillance_cases_area_time ON surveillance_cases(state, lga, presented_at);
+CREATE INDEX idx_surveillance_cases_condition_id ON surveillance_cases(condition_id);
@@ -0,0 +1,19 @@
+-- Alerts raised when weekly case counts for a condition exceed the area's rolling baseline
+CREATE TABLE outbreak_alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    condition_id UUID NOT NULL REFERENCES notifiable_conditions(id) ON DELETE CASCADE,
+    state TEXT NOT NULL,
+    lga TEXT NOT NULL,
+    week_start DATE NOT NULL,
+    observed_cases INT NOT NULL,
+    baseline_mean FLOAT8 NOT NULL,
+    baseline_stddev FLOAT8 NOT NULL,
+    threshold FLOAT8 NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'ACTIVE' CHECK (status IN ('ACTIVE', 'RESOLVED')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    UNIQUE(condition_id, state, lga, week_start)
+);
+
+CREATE INDEX idx_outbreak_alerts_status ON outbreak_alerts(status, state);
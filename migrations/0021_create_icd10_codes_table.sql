@@ -0,0 +1,13 @@
+-- ICD-10 code table (rows are loaded from data/icd10_codes.tsv at startup) and primary/secondary visit diagnoses
+CREATE TABLE icd10_codes (
+    code VARCHAR(10) PRIMARY KEY,
+    description TEXT NOT NULL,
+    -- Three-character category (e.g. "A00" for "A00.9"), useful for roll-ups
+    category VARCHAR(3) NOT NULL GENERATED ALWAYS AS (LEFT(code, 3)) STORED
+);
+
+ALTER TABLE visit_diagnoses
+ADD COLUMN diagnosis_type VARCHAR(20) NOT NULL DEFAULT 'SECONDARY' CHECK (diagnosis_type IN ('PRIMARY', 'SECONDARY'));
+
+-- At most one primary diagnosis per visit
+CREATE UNIQUE INDEX idx_visit_diagnoses_one_primary ON visit_diagnoses(visit_id) WHERE diagnosis_type = 'PRIMARY';
@@ -0,0 +1,35 @@
+-- Vital signs recorded during visits, grouped into observation sets scored with NEWS2
+CREATE TABLE observation_sets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    visit_id UUID NOT NULL REFERENCES visits(id) ON DELETE CASCADE,
+    patient_id UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    recorded_by UUID NOT NULL REFERENCES staff(id),
+    recorded_at TIMESTAMPTZ NOT NULL,
+    on_supplemental_oxygen BOOLEAN NOT NULL DEFAULT FALSE,
+    consciousness VARCHAR(20) CHECK (consciousness IN ('ALERT', 'CONFUSION', 'VOICE', 'PAIN', 'UNRESPONSIVE')),
+    -- NULL when the set is missing one of the NEWS2 parameters
+    news2_score INT,
+    news2_risk VARCHAR(20) CHECK (news2_risk IN ('LOW', 'LOW_MEDIUM', 'MEDIUM', 'HIGH')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_observation_sets_visit_id ON observation_sets(visit_id);
+CREATE INDEX idx_observation_sets_hospital_time ON observation_sets(hospital_id, recorded_at);
+CREATE INDEX idx_observation_sets_patient_time ON observation_sets(patient_id, recorded_at);
+
+-- One row per measurement, stored in the canonical unit for its type
+CREATE TABLE observations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    observation_set_id UUID NOT NULL REFERENCES observation_sets(id) ON DELETE CASCADE,
+    patient_id UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    observation_type VARCHAR(30) NOT NULL CHECK (observation_type IN (
+        'RESPIRATORY_RATE', 'SPO2', 'SYSTOLIC_BP', 'DIASTOLIC_BP', 'HEART_RATE', 'TEMPERATURE'
+    )),
+    value FLOAT8 NOT NULL,
+    unit VARCHAR(10) NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    UNIQUE(observation_set_id, observation_type)
+);
+
+CREATE INDEX idx_observations_patient_type_time ON observations(patient_id, observation_type, recorded_at);
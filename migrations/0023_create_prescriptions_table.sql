@@ -0,0 +1,24 @@
+-- Medication orders written during a visit
+CREATE TABLE prescriptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    visit_id UUID NOT NULL REFERENCES visits(id) ON DELETE CASCADE,
+    patient_id UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    prescriber_id UUID NOT NULL REFERENCES staff(id),
+    drug_name VARCHAR(255) NOT NULL,
+    dose VARCHAR(50) NOT NULL, -- e.g. "500 mg"
+    route VARCHAR(20) NOT NULL CHECK (route IN ('ORAL', 'IV', 'IM', 'SC', 'TOPICAL', 'INHALED', 'RECTAL', 'SUBLINGUAL', 'OTHER')),
+    frequency VARCHAR(20) NOT NULL CHECK (frequency IN ('STAT', 'OD', 'BD', 'TDS', 'QDS', 'NOCTE', 'PRN', 'WEEKLY')),
+    duration_days INT CHECK (duration_days > 0), -- NULL for ongoing medication
+    instructions TEXT,
+    start_date DATE NOT NULL DEFAULT CURRENT_DATE,
+    status VARCHAR(20) NOT NULL DEFAULT 'ACTIVE' CHECK (status IN ('ACTIVE', 'DISPENSED', 'DISCONTINUED')),
+    status_reason TEXT,
+    dispensed_at TIMESTAMPTZ,
+    discontinued_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_prescriptions_visit_id ON prescriptions(visit_id);
+CREATE INDEX idx_prescriptions_patient_drug ON prescriptions(patient_id, LOWER(drug_name));
@@ -0,0 +1,59 @@
+-- Laboratory test catalogue, orders placed during visits, and their results
+CREATE TABLE lab_test_types (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(20) NOT NULL UNIQUE,
+    name VARCHAR(255) NOT NULL,
+    specimen_type VARCHAR(20) NOT NULL CHECK (specimen_type IN ('BLOOD', 'URINE', 'STOOL', 'SPUTUM', 'CSF', 'SWAB', 'OTHER')),
+    result_type VARCHAR(20) NOT NULL CHECK (result_type IN ('NUMERIC', 'QUALITATIVE')),
+    unit VARCHAR(20),
+    reference_low FLOAT8,
+    reference_high FLOAT8,
+    normal_text VARCHAR(50), -- Expected qualitative result, e.g. NEGATIVE
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO lab_test_types (code, name, specimen_type, result_type, unit, reference_low, reference_high, normal_text) VALUES
+('HB', 'Haemoglobin', 'BLOOD', 'NUMERIC', 'g/dL', 12.0, 17.5, NULL),
+('WBC', 'White cell count', 'BLOOD', 'NUMERIC', '10^9/L', 4.0, 11.0, NULL),
+('PLT', 'Platelet count', 'BLOOD', 'NUMERIC', '10^9/L', 150, 400, NULL),
+('FBG', 'Fasting blood glucose', 'BLOOD', 'NUMERIC', 'mmol/L', 3.9, 5.6, NULL),
+('CREAT', 'Serum creatinine', 'BLOOD', 'NUMERIC', 'umol/L', 60, 110, NULL),
+('NA', 'Serum sodium', 'BLOOD', 'NUMERIC', 'mmol/L', 135, 145, NULL),
+('K', 'Serum potassium', 'BLOOD', 'NUMERIC', 'mmol/L', 3.5, 5.1, NULL),
+('CRP', 'C-reactive protein', 'BLOOD', 'NUMERIC', 'mg/L', 0, 10, NULL),
+('MRDT', 'Malaria rapid diagnostic test', 'BLOOD', 'QUALITATIVE', NULL, NULL, NULL, 'NEGATIVE'),
+('HIV', 'HIV rapid test', 'BLOOD', 'QUALITATIVE', NULL, NULL, NULL, 'NON_REACTIVE'),
+('AFB', 'Sputum AFB microscopy', 'SPUTUM', 'QUALITATIVE', NULL, NULL, NULL, 'NEGATIVE'),
+('UPT', 'Urine pregnancy test', 'URINE', 'QUALITATIVE', NULL, NULL, NULL, NULL);
+
+CREATE TABLE lab_orders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    visit_id UUID NOT NULL REFERENCES visits(id) ON DELETE CASCADE,
+    patient_id UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    test_type_id UUID NOT NULL REFERENCES lab_test_types(id),
+    specimen_type VARCHAR(20) NOT NULL,
+    priority VARCHAR(20) NOT NULL DEFAULT 'ROUTINE' CHECK (priority IN ('ROUTINE', 'URGENT', 'STAT')),
+    clinical_notes TEXT,
+    ordered_by UUID NOT NULL REFERENCES staff(id),
+    status VARCHAR(20) NOT NULL DEFAULT 'ORDERED' CHECK (status IN ('ORDERED', 'COLLECTED', 'RESULTED', 'VERIFIED')),
+    collected_by UUID REFERENCES staff(id),
+    collected_at TIMESTAMPTZ,
+    -- Result; the reference range is copied from the catalogue when the result is entered
+    result_value FLOAT8,
+    result_text VARCHAR(255),
+    result_unit VARCHAR(20),
+    reference_low FLOAT8,
+    reference_high FLOAT8,
+    abnormal_flag VARCHAR(20) CHECK (abnormal_flag IN ('NORMAL', 'LOW', 'HIGH', 'ABNORMAL')),
+    result_notes TEXT,
+    resulted_by UUID REFERENCES staff(id),
+    resulted_at TIMESTAMPTZ,
+    verified_by UUID REFERENCES staff(id),
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_lab_orders_visit_id ON lab_orders(visit_id);
+CREATE INDEX idx_lab_orders_pending ON lab_orders(hospital_id, status) WHERE status <> 'VERIFIED';
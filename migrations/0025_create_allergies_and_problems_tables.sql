@@ -0,0 +1,30 @@
+-- Patient allergies and longitudinal problem list
+CREATE TABLE patient_allergies (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    patient_id UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    substance VARCHAR(255) NOT NULL,
+    reaction TEXT,
+    severity VARCHAR(20) NOT NULL CHECK (severity IN ('MILD', 'MODERATE', 'SEVERE', 'LIFE_THREATENING')),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE, -- FALSE once refuted or entered in error
+    recorded_by UUID REFERENCES staff(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A substance is recorded at most once while active
+CREATE UNIQUE INDEX idx_patient_allergies_active_substance ON patient_allergies(patient_id, LOWER(substance)) WHERE is_active;
+
+CREATE TABLE patient_problems (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    patient_id UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    code VARCHAR(10) REFERENCES icd10_codes(code),
+    description TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'ACTIVE' CHECK (status IN ('ACTIVE', 'RESOLVED')),
+    onset_date DATE,
+    resolved_date DATE,
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_patient_problems_patient_id ON patient_problems(patient_id, status);
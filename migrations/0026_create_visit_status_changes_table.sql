@@ -0,0 +1,14 @@
+-- History of visit status transitions, shown on the patient timeline
+CREATE TABLE visit_status_changes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    visit_id UUID NOT NULL REFERENCES visits(id) ON DELETE CASCADE,
+    from_status VARCHAR(20) NOT NULL,
+    to_status VARCHAR(20) NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_visit_status_changes_visit_id ON visit_status_changes(visit_id);
+
+-- Timeline lookups
+CREATE INDEX idx_visit_diagnoses_visit_id ON visit_diagnoses(visit_id);
+CREATE INDEX idx_lab_orders_patient_id ON lab_orders(patient_id);
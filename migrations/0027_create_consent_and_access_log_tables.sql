@@ -0,0 +1,32 @@
+-- Patient consent to share their record, and an audit trail of every read of patient data
+CREATE TABLE patient_consents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    patient_id UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    -- NULL hospital = any hospital (for the purpose); NULL purpose = any purpose (for the hospital)
+    hospital_id UUID REFERENCES hospitals(id) ON DELETE CASCADE,
+    purpose VARCHAR(20) CHECK (purpose IN ('TREATMENT', 'REFERRAL', 'PUBLIC_HEALTH', 'RESEARCH', 'ADMINISTRATION')),
+    recorded_by UUID REFERENCES staff(id),
+    notes TEXT,
+    granted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ,
+    revoke_reason TEXT,
+    CHECK (hospital_id IS NOT NULL OR purpose IS NOT NULL)
+);
+
+CREATE INDEX idx_patient_consents_patient_id ON patient_consents(patient_id) WHERE revoked_at IS NULL;
+
+CREATE TABLE patient_access_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    patient_id UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE, -- Calling hospital
+    staff_id UUID REFERENCES staff(id) ON DELETE SET NULL,
+    purpose VARCHAR(20) NOT NULL,
+    resource VARCHAR(50) NOT NULL, -- e.g. PATIENT_RECORD, TIMELINE, VISIT_DIAGNOSES
+    basis VARCHAR(20) NOT NULL CHECK (basis IN ('OWN_HOSPITAL', 'CONSENT', 'BREAK_GLASS', 'DENIED')),
+    consent_id UUID REFERENCES patient_consents(id) ON DELETE SET NULL,
+    break_glass_reason TEXT,
+    accessed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_patient_access_log_patient_time ON patient_access_log(patient_id, accessed_at);
+CREATE INDEX idx_patient_access_log_break_glass ON patient_access_log(hospital_id, accessed_at) WHERE basis = 'BREAK_GLASS';
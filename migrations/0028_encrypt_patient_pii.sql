@@ -0,0 +1,23 @@
+-- Patient PII is encrypted by the application (AES-256-GCM, see crypto::pii).
+-- Ciphertext is base64 and longer than the plaintext, so the columns become TEXT.
+ALTER TABLE patients
+    ALTER COLUMN first_name TYPE TEXT,
+    ALTER COLUMN last_name TYPE TEXT,
+    ALTER COLUMN contact_phone TYPE TEXT,
+    ALTER COLUMN emergency_contact TYPE TEXT,
+    ADD COLUMN national_id TEXT,
+    -- Key the PII columns were encrypted with; NULL for rows written before encryption (plaintext)
+    ADD COLUMN pii_key_version SMALLINT,
+    -- HMAC-SHA256 blind indexes of the normalized values, for exact-match lookups
+    ADD COLUMN contact_phone_index VARCHAR(64),
+    ADD COLUMN national_id_index VARCHAR(64),
+    -- Set when re-encryption could not read the row (e.g. its key was dropped); the sweep skips
+    -- it from then on, and any later write of the row's PII clears it
+    ADD COLUMN pii_reencrypt_failed_at TIMESTAMPTZ;
+
+-- Ordering by ciphertext is meaningless
+DROP INDEX IF EXISTS idx_patients_names;
+
+CREATE INDEX idx_patients_contact_phone_index ON patients(contact_phone_index);
+CREATE INDEX idx_patients_national_id_index ON patients(national_id_index);
+CREATE INDEX idx_patients_pii_key_version ON patients(pii_key_version);
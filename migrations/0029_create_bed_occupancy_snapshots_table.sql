@@ -0,0 +1,17 @@
+-- Bed counts over time: a row is written whenever a hospital's total or occupied beds change,
+-- and holds until the next row for that hospital
+CREATE TABLE bed_occupancy_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    total_beds INT NOT NULL,
+    occupied_beds INT NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_bed_occupancy_snapshots_hospital_time ON bed_occupancy_snapshots(hospital_id, recorded_at);
+
+-- Earlier history was never kept; start every existing hospital from its current counts
+INSERT INTO bed_occupancy_snapshots (hospital_id, total_beds, occupied_beds)
+SELECT id, COALESCE(total_beds, 0), occupied_beds FROM hospitals;
+
+CREATE INDEX idx_visits_start_time ON visits(start_time);
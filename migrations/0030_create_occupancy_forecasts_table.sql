@@ -0,0 +1,20 @@
+-- Hourly bed occupancy predictions (see jobs::forecast). Every run is kept so forecasts
+-- can be compared with what actually happened.
+CREATE TABLE occupancy_forecasts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    -- Data cutoff of the run: only history before this was used
+    generated_at TIMESTAMPTZ NOT NULL,
+    target_time TIMESTAMPTZ NOT NULL,
+    horizon_hours INT NOT NULL,
+    predicted_occupied FLOAT8 NOT NULL,
+    lower_bound FLOAT8 NOT NULL,
+    upper_bound FLOAT8 NOT NULL,
+    total_beds INT NOT NULL,
+    expected_visits FLOAT8 NOT NULL,
+    model VARCHAR(50) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (hospital_id, generated_at, target_time)
+);
+
+CREATE INDEX idx_occupancy_forecasts_hospital_target ON occupancy_forecasts(hospital_id, target_time);
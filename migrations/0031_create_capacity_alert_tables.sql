@@ -0,0 +1,65 @@
+-- Surge and capacity alerting (see jobs::capacity_alerts)
+CREATE TABLE alert_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    -- Scope: one hospital, every hospital in a state, or (both NULL) every hospital
+    hospital_id UUID REFERENCES hospitals(id) ON DELETE CASCADE,
+    state VARCHAR(100),
+    metric VARCHAR(30) NOT NULL CHECK (metric IN ('OCCUPANCY_ABOVE', 'OXYGEN_LOW', 'EMERGENCY_CLOSED', 'BROKEN_EQUIPMENT_ABOVE')),
+    -- Percent for OCCUPANCY_ABOVE, item count for BROKEN_EQUIPMENT_ABOVE, unused otherwise
+    threshold FLOAT8,
+    severity VARCHAR(20) NOT NULL DEFAULT 'WARNING' CHECK (severity IN ('INFO', 'WARNING', 'CRITICAL')),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_alert_rules_hospital_id ON alert_rules(hospital_id);
+
+CREATE TABLE capacity_alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rule_id UUID NOT NULL REFERENCES alert_rules(id) ON DELETE CASCADE,
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    metric VARCHAR(30) NOT NULL,
+    severity VARCHAR(20) NOT NULL,
+    observed_value FLOAT8,
+    threshold FLOAT8,
+    message TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'ACTIVE' CHECK (status IN ('ACTIVE', 'ACKNOWLEDGED', 'RESOLVED')),
+    triggered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    acknowledged_by UUID REFERENCES staff(id) ON DELETE SET NULL,
+    acknowledged_at TIMESTAMPTZ,
+    -- NULL resolver: cleared automatically when the condition stopped holding
+    resolved_by UUID REFERENCES staff(id) ON DELETE SET NULL,
+    resolved_at TIMESTAMPTZ,
+    resolution_note TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one open alert per rule and hospital
+CREATE UNIQUE INDEX idx_capacity_alerts_open ON capacity_alerts(rule_id, hospital_id) WHERE status <> 'RESOLVED';
+CREATE INDEX idx_capacity_alerts_hospital_status ON capacity_alerts(hospital_id, status);
+
+CREATE TABLE alert_subscriptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Same scoping as rules: alerts for this hospital, this state, or everywhere
+    hospital_id UUID REFERENCES hospitals(id) ON DELETE CASCADE,
+    state VARCHAR(100),
+    -- Partner systems get alerts through webhook_endpoints (capacity_alert.raised)
+    channel VARCHAR(20) NOT NULL CHECK (channel IN ('LOG')),
+    target TEXT,
+    min_severity VARCHAR(20) NOT NULL DEFAULT 'INFO' CHECK (min_severity IN ('INFO', 'WARNING', 'CRITICAL')),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE alert_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    alert_id UUID NOT NULL REFERENCES capacity_alerts(id) ON DELETE CASCADE,
+    subscription_id UUID NOT NULL REFERENCES alert_subscriptions(id) ON DELETE CASCADE,
+    channel VARCHAR(20) NOT NULL,
+    status VARCHAR(20) NOT NULL CHECK (status IN ('DELIVERED', 'FAILED')),
+    error TEXT,
+    delivered_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_alert_deliveries_alert_id ON alert_deliveries(alert_id);
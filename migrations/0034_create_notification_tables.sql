@@ -0,0 +1,71 @@
+-- Email and SMS notifications (see notify)
+CREATE TABLE notification_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    template_key VARCHAR(100) NOT NULL,
+    channel VARCHAR(10) NOT NULL CHECK (channel IN ('EMAIL', 'SMS')),
+    -- Email only. Subject and body use {{name}} placeholders filled from the send's variables
+    subject TEXT,
+    body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (template_key, channel),
+    CHECK (channel <> 'EMAIL' OR subject IS NOT NULL)
+);
+
+INSERT INTO notification_templates (template_key, channel, subject, body) VALUES
+    ('capacity_alert.raised', 'EMAIL', '[{{severity}}] Capacity alert: {{hospital_name}}',
+     E'A capacity alert was raised for {{hospital_name}}.\n\n{{message}}\n\nRaised at {{triggered_at}}.'),
+    ('capacity_alert.raised', 'SMS', NULL,
+     '[{{severity}}] {{message}}'),
+    ('referral.accepted', 'EMAIL', 'Referral accepted by {{hospital_name}}',
+     E'Your referral {{referral_id}} was accepted by {{hospital_name}}.\n\n{{note}}'),
+    ('referral.accepted', 'SMS', NULL,
+     'Referral {{referral_id}} accepted by {{hospital_name}}.'),
+    ('password_reset', 'EMAIL', 'Reset your password',
+     E'Use this link to reset your password: {{reset_url}}\n\nIt expires in {{expires_in}}. If you did not ask for a reset, ignore this message.'),
+    ('password_reset', 'SMS', NULL,
+     'Your password reset code is {{code}}. It expires in {{expires_in}}.');
+
+-- Absent row: every channel on, nothing muted
+CREATE TABLE notification_preferences (
+    staff_id UUID PRIMARY KEY REFERENCES staff(id) ON DELETE CASCADE,
+    email_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    sms_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Template keys this person has opted out of
+    muted_templates TEXT[] NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Queue and delivery log in one: rows stay after sending
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- NULL when sent straight to an address rather than to a staff member
+    staff_id UUID REFERENCES staff(id) ON DELETE SET NULL,
+    template_key VARCHAR(100) NOT NULL,
+    channel VARCHAR(10) NOT NULL CHECK (channel IN ('EMAIL', 'SMS')),
+    recipient VARCHAR(255) NOT NULL,
+    subject TEXT,
+    body TEXT NOT NULL,
+    -- Set by senders that may fire twice for the same cause (e.g. a repeated outbox relay)
+    dedupe_key VARCHAR(255),
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING' CHECK (status IN ('PENDING', 'SENT', 'FAILED')),
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    provider VARCHAR(50),
+    provider_message_id TEXT,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_notifications_dedupe ON notifications(dedupe_key, channel, recipient) WHERE dedupe_key IS NOT NULL;
+CREATE INDEX idx_notifications_due ON notifications(next_attempt_at) WHERE status = 'PENDING';
+CREATE INDEX idx_notifications_staff_id ON notifications(staff_id, created_at DESC);
+
+-- Capacity alerts can now go to a staff member (target = staff id) through their preferences
+ALTER TABLE alert_subscriptions DROP CONSTRAINT alert_subscriptions_channel_check;
+ALTER TABLE alert_subscriptions ADD CONSTRAINT alert_subscriptions_channel_check
+    CHECK (channel IN ('LOG', 'STAFF'));
+ALTER TABLE alert_subscriptions ADD CONSTRAINT alert_subscriptions_staff_target_check
+    CHECK (channel <> 'STAFF' OR target IS NOT NULL);
@@ -0,0 +1,10 @@
+-- Outbox relay retries: a failed event waits out a back-off before it is tried again, and after
+-- too many attempts it is parked so later events for the same aggregate can go through
+ALTER TABLE outbox_events
+    ADD COLUMN next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Set when the relay gives up; parked events are left for an operator to requeue
+    ADD COLUMN parked_at TIMESTAMPTZ;
+
+DROP INDEX idx_outbox_events_unpublished;
+CREATE INDEX idx_outbox_events_unpublished ON outbox_events(id) WHERE published_at IS NULL AND parked_at IS NULL;
+CREATE INDEX idx_outbox_events_parked ON outbox_events(parked_at) WHERE parked_at IS NOT NULL AND published_at IS NULL;
@@ -0,0 +1,10 @@
+-- Sign-in for staff. Patient-data requests carry a staff session, and the calling hospital is
+-- the signed-in staff member's own, so it cannot be claimed by a request header.
+CREATE TABLE staff_accounts (
+    staff_id UUID PRIMARY KEY REFERENCES staff(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Sessions issued before the last password change are no longer accepted
+    password_changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -10,6 +10,148 @@ pub struct Settings {
     
     #[serde(default = "default_port")]
     pub port: u16,
+
+    // Outbreak detection (see jobs::outbreak)
+    #[serde(default = "default_outbreak_baseline_weeks")]
+    pub outbreak_baseline_weeks: u32,
+
+    #[serde(default = "default_outbreak_threshold_sd")]
+    pub outbreak_threshold_sd: f64,
+
+    #[serde(default = "default_outbreak_min_cases")]
+    pub outbreak_min_cases: i64,
+
+    #[serde(default = "default_outbreak_check_interval_secs")]
+    pub outbreak_check_interval_secs: u64,
+
+    // Patient PII encryption (see crypto::pii)
+    pub pii_encryption_keys: Option<String>,
+
+    pub pii_active_key_version: Option<i16>,
+
+    pub pii_blind_index_key: Option<String>,
+
+    // Development only: use fixed, publicly known PII keys when none are configured
+    #[serde(default)]
+    pub pii_dev_keys: bool,
+
+    #[serde(default = "default_pii_reencrypt_interval_secs")]
+    pub pii_reencrypt_interval_secs: u64,
+
+    #[serde(default = "default_pii_reencrypt_batch_size")]
+    pub pii_reencrypt_batch_size: i64,
+
+    // Public data API (see routes::public): aggregate counts below this are withheld
+    #[serde(default = "default_public_min_cell_size")]
+    pub public_min_cell_size: i64,
+
+    // Bed occupancy forecasting (see jobs::forecast)
+    #[serde(default = "default_forecast_lookback_days")]
+    pub forecast_lookback_days: i64,
+
+    #[serde(default = "default_forecast_smoothing_alpha")]
+    pub forecast_smoothing_alpha: f64,
+
+    #[serde(default = "default_forecast_horizon_hours")]
+    pub forecast_horizon_hours: i64,
+
+    #[serde(default = "default_forecast_interval_secs")]
+    pub forecast_interval_secs: u64,
+
+    // Capacity alerting (see jobs::capacity_alerts): periodic sweep on top of change-driven checks
+    #[serde(default = "default_capacity_alert_interval_secs")]
+    pub capacity_alert_interval_secs: u64,
+
+    // Outbound webhooks (see jobs::webhooks)
+    #[serde(default = "default_webhook_max_attempts")]
+    pub webhook_max_attempts: i32,
+
+    #[serde(default = "default_webhook_retry_base_secs")]
+    pub webhook_retry_base_secs: i64,
+
+    #[serde(default = "default_webhook_retry_max_secs")]
+    pub webhook_retry_max_secs: i64,
+
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub webhook_timeout_secs: u64,
+
+    #[serde(default = "default_webhook_batch_size")]
+    pub webhook_batch_size: i64,
+
+    #[serde(default = "default_webhook_dispatch_interval_secs")]
+    pub webhook_dispatch_interval_secs: u64,
+
+    // Transactional outbox relay (see jobs::outbox)
+    #[serde(default = "default_outbox_dispatch_interval_secs")]
+    pub outbox_dispatch_interval_secs: u64,
+
+    #[serde(default = "default_outbox_batch_size")]
+    pub outbox_batch_size: i64,
+
+    #[serde(default = "default_outbox_retain_days")]
+    pub outbox_retain_days: i64,
+
+    #[serde(default = "default_outbox_max_attempts")]
+    pub outbox_max_attempts: i32,
+
+    #[serde(default = "default_outbox_retry_base_secs")]
+    pub outbox_retry_base_secs: i64,
+
+    #[serde(default = "default_outbox_retry_max_secs")]
+    pub outbox_retry_max_secs: i64,
+
+    // Live event feeds (see ws::hub): recent events kept for SSE clients resuming with Last-Event-ID
+    #[serde(default = "default_event_replay_buffer_size")]
+    pub event_replay_buffer_size: usize,
+
+    // Email and SMS notifications (see notify): smtp | file | log, and http | file | log
+    #[serde(default = "default_notify_provider")]
+    pub notify_email_provider: String,
+
+    #[serde(default = "default_notify_provider")]
+    pub notify_sms_provider: String,
+
+    #[serde(default = "default_notify_sink_path")]
+    pub notify_sink_path: String,
+
+    pub smtp_host: Option<String>,
+
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+
+    // starttls | tls | none
+    #[serde(default = "default_smtp_security")]
+    pub smtp_security: String,
+
+    pub smtp_username: Option<String>,
+
+    pub smtp_password: Option<String>,
+
+    pub smtp_from: Option<String>,
+
+    pub sms_gateway_url: Option<String>,
+
+    pub sms_gateway_token: Option<String>,
+
+    pub sms_sender: Option<String>,
+
+    #[serde(default = "default_notify_max_attempts")]
+    pub notify_max_attempts: i32,
+
+    #[serde(default = "default_notify_retry_base_secs")]
+    pub notify_retry_base_secs: i64,
+
+    #[serde(default = "default_notify_retry_max_secs")]
+    pub notify_retry_max_secs: i64,
+
+    #[serde(default = "default_notify_timeout_secs")]
+    pub notify_timeout_secs: u64,
+
+    #[serde(default = "default_notify_batch_size")]
+    pub notify_batch_size: i64,
+
+    #[serde(default = "default_notify_dispatch_interval_secs")]
+    pub notify_dispatch_interval_secs: u64,
 }
 
 fn default_host() -> String {
@@ -20,6 +162,146 @@ fn default_port() -> u16 {
     3000
 }
 
+fn default_outbreak_baseline_weeks() -> u32 {
+    8
+}
+
+fn default_outbreak_threshold_sd() -> f64 {
+    2.0
+}
+
+fn default_outbreak_min_cases() -> i64 {
+    3
+}
+
+fn default_outbreak_check_interval_secs() -> u64 {
+    3600
+}
+
+fn default_pii_reencrypt_interval_secs() -> u64 {
+    3600
+}
+
+fn default_pii_reencrypt_batch_size() -> i64 {
+    500
+}
+
+fn default_public_min_cell_size() -> i64 {
+    5
+}
+
+fn default_forecast_lookback_days() -> i64 {
+    28
+}
+
+fn default_forecast_smoothing_alpha() -> f64 {
+    0.3
+}
+
+fn default_forecast_horizon_hours() -> i64 {
+    24 * 7
+}
+
+fn default_forecast_interval_secs() -> u64 {
+    3600
+}
+
+fn default_capacity_alert_interval_secs() -> u64 {
+    300
+}
+
+fn default_webhook_max_attempts() -> i32 {
+    8
+}
+
+fn default_webhook_retry_base_secs() -> i64 {
+    30
+}
+
+fn default_webhook_retry_max_secs() -> i64 {
+    6 * 3600
+}
+
+fn default_webhook_timeout_secs() -> u64 {
+    10
+}
+
+fn default_webhook_batch_size() -> i64 {
+    100
+}
+
+fn default_webhook_dispatch_interval_secs() -> u64 {
+    15
+}
+
+fn default_outbox_dispatch_interval_secs() -> u64 {
+    5
+}
+
+fn default_outbox_batch_size() -> i64 {
+    200
+}
+
+fn default_outbox_retain_days() -> i64 {
+    7
+}
+
+fn default_outbox_max_attempts() -> i32 {
+    10
+}
+
+fn default_outbox_retry_base_secs() -> i64 {
+    5
+}
+
+fn default_outbox_retry_max_secs() -> i64 {
+    900
+}
+
+fn default_event_replay_buffer_size() -> usize {
+    1000
+}
+
+fn default_notify_provider() -> String {
+    "log".to_string()
+}
+
+fn default_notify_sink_path() -> String {
+    "notifications.jsonl".to_string()
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_security() -> String {
+    "starttls".to_string()
+}
+
+fn default_notify_max_attempts() -> i32 {
+    5
+}
+
+fn default_notify_retry_base_secs() -> i64 {
+    60
+}
+
+fn default_notify_retry_max_secs() -> i64 {
+    3600
+}
+
+fn default_notify_timeout_secs() -> u64 {
+    10
+}
+
+fn default_notify_batch_size() -> i64 {
+    100
+}
+
+fn default_notify_dispatch_interval_secs() -> u64 {
+    30
+}
+
 impl Settings {
     pub fn from_env() -> Result<Self, envy::Error> {
         envy::from_env()
@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::config::Settings;
+
+const NONCE_LEN: usize = 12;
+
+/// AES-256-GCM encryption of patient PII columns, with versioned keys and HMAC blind indexes.
+///
+/// Ciphertext is `base64(nonce || ciphertext)` and is bound to the patient and column it was
+/// written for, so values cannot be swapped between rows or fields. The key version used for a
+/// row is stored alongside it (`patients.pii_key_version`), letting old keys stay readable while
+/// the re-encryption job moves rows onto the active key.
+#[derive(Clone)]
+pub struct PiiCipher {
+    keys: Arc<HashMap<i16, Aes256Gcm>>,
+    active_version: i16,
+    index_key: Arc<Vec<u8>>,
+}
+
+#[derive(Debug)]
+pub enum PiiError {
+    UnknownKeyVersion(i16),
+    Malformed,
+    DecryptionFailed,
+}
+
+impl std::fmt::Display for PiiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PiiError::UnknownKeyVersion(v) => write!(f, "no PII key loaded for version {}", v),
+            PiiError::Malformed => write!(f, "PII ciphertext is malformed"),
+            PiiError::DecryptionFailed => write!(f, "PII ciphertext failed authentication"),
+        }
+    }
+}
+
+impl std::error::Error for PiiError {}
+
+impl From<PiiError> for sqlx::Error {
+    fn from(e: PiiError) -> Self {
+        sqlx::Error::Decode(Box::new(e))
+    }
+}
+
+impl PiiCipher {
+    /// Builds the cipher from `PII_ENCRYPTION_KEYS` (`version:base64key,...`, 32-byte keys),
+    /// `PII_ACTIVE_KEY_VERSION` (defaults to the highest version) and `PII_BLIND_INDEX_KEY`.
+    /// Missing keys are an error unless `PII_DEV_KEYS=true`, which substitutes fixed development
+    /// keys that protect nothing and must never be used with real patient data.
+    pub fn from_settings(settings: &Settings) -> Result<Self, String> {
+        let keys = match settings.pii_encryption_keys.as_deref() {
+            Some(spec) if !spec.trim().is_empty() => parse_keys(spec)?,
+            _ if settings.pii_dev_keys => {
+                tracing::warn!("PII_ENCRYPTION_KEYS is not set; using the development key (PII_DEV_KEYS=true)");
+                vec![(1, development_key("pii-encryption"))]
+            }
+            _ => return Err("PII_ENCRYPTION_KEYS is not set".to_string()),
+        };
+
+        let index_key = match settings.pii_blind_index_key.as_deref() {
+            Some(key) if !key.trim().is_empty() => decode_key(key)?,
+            _ if settings.pii_dev_keys => {
+                tracing::warn!("PII_BLIND_INDEX_KEY is not set; using the development key (PII_DEV_KEYS=true)");
+                development_key("pii-blind-index")
+            }
+            _ => return Err("PII_BLIND_INDEX_KEY is not set".to_string()),
+        };
+
+        let active = settings
+            .pii_active_key_version
+            .or_else(|| keys.iter().map(|(v, _)| *v).max())
+            .ok_or("No PII encryption keys configured")?;
+
+        Self::new(keys, active, index_key)
+    }
+
+    pub fn new(keys: Vec<(i16, Vec<u8>)>, active_version: i16, index_key: Vec<u8>) -> Result<Self, String> {
+        let keys: HashMap<i16, Aes256Gcm> = keys
+            .into_iter()
+            .map(|(version, key)| (version, Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))))
+            .collect();
+        if !keys.contains_key(&active_version) {
+            return Err(format!("Active PII key version {} is not among the loaded keys", active_version));
+        }
+
+        Ok(Self {
+            keys: Arc::new(keys),
+            active_version,
+            index_key: Arc::new(index_key),
+        })
+    }
+
+    pub fn active_version(&self) -> i16 {
+        self.active_version
+    }
+
+    /// Encrypts `plaintext` for `column` of patient `row_id` with the active key.
+    pub fn encrypt(&self, row_id: Uuid, column: &str, plaintext: &str) -> String {
+        let cipher = &self.keys[&self.active_version];
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let aad = associated_data(row_id, column);
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: plaintext.as_bytes(), aad: aad.as_bytes() })
+            .expect("AES-GCM encryption cannot fail for in-memory buffers");
+
+        let mut out = nonce.to_vec();
+        out.extend_from_slice(&ciphertext);
+        BASE64.encode(out)
+    }
+
+    pub fn encrypt_opt(&self, row_id: Uuid, column: &str, plaintext: Option<&str>) -> Option<String> {
+        plaintext.map(|p| self.encrypt(row_id, column, p))
+    }
+
+    /// Decrypts a value written with key `version`. `None` means the row predates encryption
+    /// and still holds plaintext.
+    pub fn decrypt(&self, version: Option<i16>, row_id: Uuid, column: &str, stored: &str) -> Result<String, PiiError> {
+        let Some(version) = version else {
+            return Ok(stored.to_string());
+        };
+        let cipher = self.keys.get(&version).ok_or(PiiError::UnknownKeyVersion(version))?;
+
+        let bytes = BASE64.decode(stored).map_err(|_| PiiError::Malformed)?;
+        if bytes.len() <= NONCE_LEN {
+            return Err(PiiError::Malformed);
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let aad = associated_data(row_id, column);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: aad.as_bytes() })
+            .map_err(|_| PiiError::DecryptionFailed)?;
+
+        String::from_utf8(plaintext).map_err(|_| PiiError::Malformed)
+    }
+
+    pub fn decrypt_opt(
+        &self,
+        version: Option<i16>,
+        row_id: Uuid,
+        column: &str,
+        stored: Option<&str>,
+    ) -> Result<Option<String>, PiiError> {
+        stored.map(|s| self.decrypt(version, row_id, column, s)).transpose()
+    }
+
+    /// Deterministic HMAC-SHA256 of a normalized value, for equality lookups on encrypted columns.
+    /// `kind` keeps indexes of different fields from matching each other.
+    pub fn blind_index(&self, kind: &str, normalized: &str) -> String {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.index_key).expect("HMAC accepts keys of any length");
+        mac.update(kind.as_bytes());
+        mac.update(b":");
+        mac.update(normalized.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    pub fn phone_index(&self, phone: &str) -> Option<String> {
+        normalize_phone(phone).map(|p| self.blind_index("phone", &p))
+    }
+
+    pub fn national_id_index(&self, national_id: &str) -> Option<String> {
+        normalize_national_id(national_id).map(|n| self.blind_index("national_id", &n))
+    }
+}
+
+/// Digits only, with the Nigerian country code folded into the local trunk prefix
+/// (`+234 803 ...` and `0803...` index the same). `None` if there are no digits.
+pub fn normalize_phone(phone: &str) -> Option<String> {
+    let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() {
+        return None;
+    }
+    match digits.strip_prefix("234") {
+        Some(rest) if digits.len() == 13 => Some(format!("0{}", rest)),
+        _ => Some(digits),
+    }
+}
+
+pub fn normalize_national_id(national_id: &str) -> Option<String> {
+    let normalized: String = national_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+fn associated_data(row_id: Uuid, column: &str) -> String {
+    format!("{}:{}", row_id, column)
+}
+
+fn parse_keys(spec: &str) -> Result<Vec<(i16, Vec<u8>)>, String> {
+    spec.split(',')
+        .map(|entry| {
+            let (version, key) = entry
+                .trim()
+                .split_once(':')
+                .ok_or_else(|| format!("PII key entry '{}' must be version:base64key", entry.trim()))?;
+            let version: i16 = version
+                .trim()
+                .parse()
+                .map_err(|_| format!("PII key version '{}' is not a number", version.trim()))?;
+            Ok((version, decode_key(key)?))
+        })
+        .collect()
+}
+
+fn decode_key(key: &str) -> Result<Vec<u8>, String> {
+    let bytes = BASE64.decode(key.trim()).map_err(|_| "PII keys must be base64".to_string())?;
+    if bytes.len() != 32 {
+        return Err(format!("PII keys must be 32 bytes, got {}", bytes.len()));
+    }
+    Ok(bytes)
+}
+
+/// A fixed key per purpose, independent of every other secret, so development data never
+/// depends on (or outlives) the JWT secret.
+fn development_key(label: &str) -> Vec<u8> {
+    Sha256::new()
+        .chain_update(b"health-intel-development:")
+        .chain_update(label.as_bytes())
+        .finalize()
+        .to_vec()
+}
@@ -0,0 +1,81 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::allergy::{Allergy, CreateAllergyRequest};
+
+pub async fn create_allergy(pool: &PgPool, patient_id: Uuid, payload: CreateAllergyRequest) -> Result<Allergy, sqlx::Error> {
+    sqlx::query_as!(
+        Allergy,
+        r#"
+        INSERT INTO patient_allergies (patient_id, substance, reaction, severity, recorded_by)
+        VALUES ($1, TRIM($2), $3, $4, $5)
+        RETURNING id, patient_id, substance, reaction, severity, is_active, recorded_by, created_at, updated_at
+        "#,
+        patient_id,
+        payload.substance,
+        payload.reaction,
+        payload.severity,
+        payload.recorded_by
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_patient_allergies(pool: &PgPool, patient_id: Uuid, include_inactive: bool) -> Result<Vec<Allergy>, sqlx::Error> {
+    sqlx::query_as!(
+        Allergy,
+        r#"
+        SELECT * FROM patient_allergies
+        WHERE patient_id = $1 AND (is_active OR $2)
+        ORDER BY is_active DESC, substance ASC
+        "#,
+        patient_id,
+        include_inactive
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn set_allergy_active(pool: &PgPool, allergy_id: Uuid, is_active: bool) -> Result<Allergy, sqlx::Error> {
+    sqlx::query_as!(
+        Allergy,
+        r#"
+        UPDATE patient_allergies SET is_active = $2, updated_at = NOW()
+        WHERE id = $1
+        RETURNING id, patient_id, substance, reaction, severity, is_active, recorded_by, created_at, updated_at
+        "#,
+        allergy_id,
+        is_active
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Active allergies whose substance and the given drug or test name match on whole words, in either
+/// direction and case-insensitively: an allergy to "Penicillin" matches "Penicillin V", but "Sulfa" does
+/// not match "Sulfasalazine". This is a name heuristic only; it knows nothing of drug classes or
+/// cross-reactivity, so an allergy to "Penicillin" does not flag "Amoxicillin".
+pub async fn find_matching_allergies(pool: &PgPool, patient_id: Uuid, drug_name: &str) -> Result<Vec<Allergy>, sqlx::Error> {
+    let drug = name_words(drug_name);
+    let allergies = get_patient_allergies(pool, patient_id, false).await?;
+
+    Ok(allergies
+        .into_iter()
+        .filter(|a| {
+            let substance = name_words(&a.substance);
+            contains_words(&drug, &substance) || contains_words(&substance, &drug)
+        })
+        .collect())
+}
+
+/// Lower-cased alphanumeric words of a substance, drug or test name.
+fn name_words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether `needle` appears as a run of consecutive words in `haystack`.
+fn contains_words(haystack: &[String], needle: &[String]) -> bool {
+    !needle.is_empty() && haystack.windows(needle.len()).any(|w| w == needle)
+}
@@ -0,0 +1,180 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::analytics::{OccupancyGroup, HospitalOccupancy, StateCoverage, DailyVisitVolume};
+
+// Occupancy is computed from `bed_occupancy_snapshots`: each snapshot holds from its
+// `recorded_at` until the hospital's next snapshot, clipped to the requested range, and
+// averages are weighted by how long each snapshot held.
+
+/// Time-weighted occupancy of active hospitals in `[from, to)`, grouped by `group_by`
+/// (`state`, `city` or `hospital_type`).
+pub async fn get_occupancy_by_group(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    group_by: &str,
+    state: Option<String>,
+) -> Result<Vec<OccupancyGroup>, sqlx::Error> {
+    sqlx::query_as!(
+        OccupancyGroup,
+        r#"
+        WITH spans AS (
+            SELECT
+                s.hospital_id, s.total_beds, s.occupied_beds,
+                GREATEST(s.recorded_at, $1) AS span_start,
+                LEAST(COALESCE(LEAD(s.recorded_at) OVER (PARTITION BY s.hospital_id ORDER BY s.recorded_at), $2), $2) AS span_end
+            FROM bed_occupancy_snapshots s
+            WHERE s.recorded_at < $2
+        ),
+        per_hospital AS (
+            SELECT
+                hospital_id,
+                SUM(total_beds * EXTRACT(EPOCH FROM span_end - span_start))::FLOAT8
+                    / SUM(EXTRACT(EPOCH FROM span_end - span_start))::FLOAT8 AS avg_total,
+                SUM(occupied_beds * EXTRACT(EPOCH FROM span_end - span_start))::FLOAT8
+                    / SUM(EXTRACT(EPOCH FROM span_end - span_start))::FLOAT8 AS avg_occupied,
+                MAX(occupied_beds::FLOAT8 / NULLIF(total_beds, 0)) AS peak_rate
+            FROM spans
+            WHERE span_end > span_start
+            GROUP BY hospital_id
+        )
+        SELECT
+            CASE $3
+                WHEN 'city' THEN h.city || ', ' || h.state
+                WHEN 'hospital_type' THEN h.hospital_type
+                ELSE h.state
+            END AS "group!",
+            COUNT(*) AS "hospitals!",
+            SUM(p.avg_total) AS "average_total_beds!",
+            SUM(p.avg_occupied) AS "average_occupied_beds!",
+            SUM(p.avg_occupied) / NULLIF(SUM(p.avg_total), 0) AS occupancy_rate,
+            MAX(p.peak_rate) AS peak_hospital_rate
+        FROM per_hospital p
+        JOIN hospitals h ON h.id = p.hospital_id
+        WHERE h.is_active = TRUE
+          AND ($4::TEXT IS NULL OR h.state ILIKE $4)
+        GROUP BY 1
+        ORDER BY occupancy_rate DESC NULLS LAST, 1 ASC
+        "#,
+        from,
+        to,
+        group_by,
+        state
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Time-weighted average and peak occupancy of each active hospital with beds in `[from, to)`.
+pub async fn get_hospital_occupancy(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    state: Option<String>,
+) -> Result<Vec<HospitalOccupancy>, sqlx::Error> {
+    sqlx::query_as!(
+        HospitalOccupancy,
+        r#"
+        WITH spans AS (
+            SELECT
+                s.hospital_id, s.total_beds, s.occupied_beds,
+                GREATEST(s.recorded_at, $1) AS span_start,
+                LEAST(COALESCE(LEAD(s.recorded_at) OVER (PARTITION BY s.hospital_id ORDER BY s.recorded_at), $2), $2) AS span_end
+            FROM bed_occupancy_snapshots s
+            WHERE s.recorded_at < $2
+        )
+        SELECT
+            h.id AS hospital_id, h.name, h.state,
+            SUM(sp.occupied_beds * EXTRACT(EPOCH FROM sp.span_end - sp.span_start))::FLOAT8
+                / NULLIF(SUM(sp.total_beds * EXTRACT(EPOCH FROM sp.span_end - sp.span_start))::FLOAT8, 0) AS average_rate,
+            MAX(sp.occupied_beds::FLOAT8 / NULLIF(sp.total_beds, 0)) AS peak_rate
+        FROM spans sp
+        JOIN hospitals h ON h.id = sp.hospital_id
+        WHERE sp.span_end > sp.span_start
+          AND h.is_active = TRUE
+          AND ($3::TEXT IS NULL OR h.state ILIKE $3)
+        GROUP BY h.id, h.name, h.state
+        HAVING MAX(sp.total_beds) > 0
+        ORDER BY average_rate DESC NULLS LAST, h.name ASC
+        "#,
+        from,
+        to,
+        state
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Emergency, oxygen, ventilator and ambulance coverage of active hospitals per state.
+pub async fn get_state_coverage(pool: &PgPool, state: Option<String>) -> Result<Vec<StateCoverage>, sqlx::Error> {
+    sqlx::query_as!(
+        StateCoverage,
+        r#"
+        SELECT
+            state,
+            COUNT(*) AS "hospitals!",
+            COUNT(*) FILTER (WHERE has_emergency) AS "with_emergency!",
+            COUNT(*) FILTER (WHERE has_oxygen) AS "with_oxygen!",
+            COUNT(*) FILTER (WHERE has_ventilators) AS "with_ventilators!",
+            COUNT(*) FILTER (WHERE has_ambulance) AS "with_ambulance!",
+            (COUNT(*) FILTER (WHERE has_emergency))::FLOAT8 / COUNT(*) AS "emergency_coverage!",
+            (COUNT(*) FILTER (WHERE has_oxygen))::FLOAT8 / COUNT(*) AS "oxygen_coverage!",
+            (COUNT(*) FILTER (WHERE has_ventilators))::FLOAT8 / COUNT(*) AS "ventilator_coverage!",
+            (COUNT(*) FILTER (WHERE has_ambulance))::FLOAT8 / COUNT(*) AS "ambulance_coverage!"
+        FROM hospitals
+        WHERE is_active = TRUE
+          AND ($1::TEXT IS NULL OR state ILIKE $1)
+        GROUP BY state
+        ORDER BY state ASC
+        "#,
+        state
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Visits started on each day of `[from, to)`, including days with none.
+pub async fn get_daily_visit_volumes(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    state: Option<String>,
+    hospital_id: Option<Uuid>,
+) -> Result<Vec<DailyVisitVolume>, sqlx::Error> {
+    sqlx::query_as!(
+        DailyVisitVolume,
+        r#"
+        WITH days AS (
+            SELECT generate_series($1::TIMESTAMPTZ::DATE, ($2::TIMESTAMPTZ - INTERVAL '1 microsecond')::DATE, INTERVAL '1 day')::DATE AS day
+        ),
+        counts AS (
+            SELECT
+                v.start_time::DATE AS day,
+                COUNT(*) AS visits,
+                COUNT(*) FILTER (WHERE v.status = 'COMPLETED') AS completed,
+                COUNT(*) FILTER (WHERE v.status = 'CANCELLED') AS cancelled
+            FROM visits v
+            JOIN hospitals h ON h.id = v.hospital_id
+            WHERE v.start_time >= $1 AND v.start_time < $2
+              AND ($3::TEXT IS NULL OR h.state ILIKE $3)
+              AND ($4::UUID IS NULL OR v.hospital_id = $4)
+            GROUP BY 1
+        )
+        SELECT
+            d.day AS "date!",
+            COALESCE(c.visits, 0) AS "visits!",
+            COALESCE(c.completed, 0) AS "completed!",
+            COALESCE(c.cancelled, 0) AS "cancelled!"
+        FROM days d
+        LEFT JOIN counts c ON c.day = d.day
+        ORDER BY d.day ASC
+        "#,
+        from,
+        to,
+        state,
+        hospital_id
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,381 @@
+use chrono::{Duration, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::db::visit_repo;
+use crate::models::{
+    appointment::{AvailabilitySlot, CreateSlotsRequest, AppointmentChange},
+    visit::{Visit, CreateVisitRequest, DEFAULT_VISIT_MINUTES},
+};
+
+/// Publishes `count` back-to-back slots for a clinician.
+/// Returns `Ok(None)` if any of them would overlap a slot the clinician already has, or a visit
+/// booked with them outside any slot.
+pub async fn create_slots(pool: &PgPool, payload: &CreateSlotsRequest) -> Result<Option<Vec<AvailabilitySlot>>, sqlx::Error> {
+    let count = payload.count.unwrap_or(1);
+    let length = Duration::minutes(payload.duration_minutes as i64);
+    let window_end = payload.start_time + length * count;
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!("SELECT id FROM staff WHERE id = $1 FOR UPDATE", payload.staff_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let overlapping = sqlx::query_scalar!(
+        r#"
+        SELECT (
+            EXISTS (
+                SELECT 1 FROM availability_slots
+                WHERE staff_id = $1 AND start_time < $3 AND end_time > $2
+            )
+            OR EXISTS (
+                SELECT 1 FROM visits v
+                WHERE v.staff_id = $1
+                  AND v.status IN ('PENDING', 'IN_PROGRESS')
+                  AND v.start_time < $3
+                  AND v.start_time + make_interval(mins => $4) > $2
+                  AND NOT EXISTS (SELECT 1 FROM availability_slots s WHERE s.visit_id = v.id)
+            )
+        ) AS "exists!"
+        "#,
+        payload.staff_id,
+        payload.start_time,
+        window_end,
+        DEFAULT_VISIT_MINUTES
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if overlapping {
+        return Ok(None);
+    }
+
+    let mut slots = Vec::with_capacity(count as usize);
+    for i in 0..count {
+        let start_time = payload.start_time + length * i;
+        let slot = sqlx::query_as!(
+            AvailabilitySlot,
+            r#"
+            INSERT INTO availability_slots (hospital_id, department_id, staff_id, start_time, end_time)
+            SELECT s.hospital_id, COALESCE($2, s.department_id), s.id, $3, $4
+            FROM staff s
+            WHERE s.id = $1
+            RETURNING id, hospital_id, department_id, staff_id, start_time, end_time, status, visit_id, created_at
+            "#,
+            payload.staff_id,
+            payload.department_id,
+            start_time,
+            start_time + length
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        slots.push(slot);
+    }
+
+    tx.commit().await?;
+    Ok(Some(slots))
+}
+
+pub async fn get_slot(pool: &PgPool, slot_id: Uuid) -> Result<Option<AvailabilitySlot>, sqlx::Error> {
+    sqlx::query_as!(
+        AvailabilitySlot,
+        "SELECT * FROM availability_slots WHERE id = $1",
+        slot_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Next open slots from now, optionally narrowed to a department and/or clinician.
+pub async fn get_available_slots(
+    pool: &PgPool,
+    department_id: Option<Uuid>,
+    staff_id: Option<Uuid>,
+    limit: i64,
+) -> Result<Vec<AvailabilitySlot>, sqlx::Error> {
+    sqlx::query_as!(
+        AvailabilitySlot,
+        r#"
+        SELECT * FROM availability_slots
+        WHERE status = 'OPEN'
+          AND start_time > NOW()
+          AND ($1::UUID IS NULL OR department_id = $1)
+          AND ($2::UUID IS NULL OR staff_id = $2)
+        ORDER BY start_time ASC
+        LIMIT $3
+        "#,
+        department_id,
+        staff_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Flips an OPEN slot to BOOKED. The conditional UPDATE is what makes booking race-free:
+/// of two concurrent callers only one sees a returned row.
+async fn claim_slot(tx: &mut Transaction<'_, Postgres>, slot_id: Uuid) -> Result<Option<AvailabilitySlot>, sqlx::Error> {
+    sqlx::query_as!(
+        AvailabilitySlot,
+        r#"
+        UPDATE availability_slots
+        SET status = 'BOOKED'
+        WHERE id = $1 AND status = 'OPEN' AND start_time > NOW()
+        RETURNING id, hospital_id, department_id, staff_id, start_time, end_time, status, visit_id, created_at
+        "#,
+        slot_id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+async fn release_slots_for_visit(tx: &mut Transaction<'_, Postgres>, visit_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE availability_slots
+        SET status = 'OPEN', visit_id = NULL
+        WHERE visit_id = $1
+        RETURNING id
+        "#,
+        visit_id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+async fn log_change(
+    tx: &mut Transaction<'_, Postgres>,
+    visit_id: Uuid,
+    change_type: &str,
+    from_slot_id: Option<Uuid>,
+    to_slot_id: Option<Uuid>,
+    reason: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO appointment_changes (visit_id, change_type, from_slot_id, to_slot_id, reason)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        visit_id,
+        change_type,
+        from_slot_id,
+        to_slot_id,
+        reason
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Books an open slot and creates the matching PENDING visit in one transaction.
+/// Returns `Ok(None)` if the slot is no longer available.
+pub async fn book_slot(pool: &PgPool, slot_id: Uuid, patient_id: Uuid, reason: &str) -> Result<Option<Visit>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let Some(slot) = claim_slot(&mut tx, slot_id).await? else {
+        return Ok(None);
+    };
+
+    let visit = sqlx::query_as!(
+        Visit,
+        r#"
+        INSERT INTO visits (hospital_id, patient_id, staff_id, reason, status, start_time)
+        VALUES ($1, $2, $3, $4, 'PENDING', $5)
+        RETURNING id, hospital_id, patient_id, staff_id, reason, status, start_time, end_time, created_at
+        "#,
+        slot.hospital_id,
+        patient_id,
+        slot.staff_id,
+        reason,
+        slot.start_time
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!("UPDATE availability_slots SET visit_id = $1 WHERE id = $2", visit.id, slot.id)
+        .execute(&mut *tx)
+        .await?;
+
+    log_change(&mut tx, visit.id, "BOOKED", None, Some(slot.id), None).await?;
+
+    tx.commit().await?;
+    Ok(Some(visit))
+}
+
+/// Creates a PENDING visit without going through `/slots`, subject to the clinician's slots: a
+/// visit inside one of their open slots books it, as if it had been booked directly. A visit
+/// outside any slot is taken to last `DEFAULT_VISIT_MINUTES` and must not run into a slot or
+/// another active visit. Returns `Ok(None)` if the clinician is already booked then, or has a slot
+/// at another hospital.
+pub async fn create_visit(pool: &PgPool, payload: CreateVisitRequest) -> Result<Option<Visit>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // Serializes with slot publication for the same clinician
+    sqlx::query!("SELECT id FROM staff WHERE id = $1 FOR UPDATE", payload.staff_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let start_time = payload.start_time.unwrap_or_else(Utc::now);
+    let slot = sqlx::query_as!(
+        AvailabilitySlot,
+        r#"
+        SELECT * FROM availability_slots
+        WHERE staff_id = $1 AND start_time <= $2 AND end_time > $2
+        FOR UPDATE
+        "#,
+        payload.staff_id,
+        start_time
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    match &slot {
+        Some(slot) if slot.status != "OPEN" || slot.hospital_id != payload.hospital_id => return Ok(None),
+        Some(_) => {}
+        None => {
+            // Outside any slot, the visit clashes with any slot or active visit it overlaps; a
+            // visit's own span is its slot's, or the default length if it has none
+            let clash = sqlx::query_scalar!(
+                r#"
+                SELECT (
+                    EXISTS (
+                        SELECT 1 FROM availability_slots
+                        WHERE staff_id = $1 AND start_time < $2::TIMESTAMPTZ + make_interval(mins => $3) AND end_time > $2
+                    )
+                    OR EXISTS (
+                        SELECT 1 FROM visits v
+                        LEFT JOIN availability_slots s ON s.visit_id = v.id
+                        WHERE v.staff_id = $1
+                          AND v.status IN ('PENDING', 'IN_PROGRESS')
+                          AND v.start_time < $2::TIMESTAMPTZ + make_interval(mins => $3)
+                          AND COALESCE(s.end_time, v.start_time + make_interval(mins => $3)) > $2
+                    )
+                ) AS "exists!"
+                "#,
+                payload.staff_id,
+                start_time,
+                DEFAULT_VISIT_MINUTES
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            if clash {
+                return Ok(None);
+            }
+        }
+    }
+
+    let visit = sqlx::query_as!(
+        Visit,
+        r#"
+        INSERT INTO visits (hospital_id, patient_id, staff_id, reason, status, start_time)
+        VALUES ($1, $2, $3, $4, 'PENDING', $5)
+        RETURNING id, hospital_id, patient_id, staff_id, reason, status, start_time, end_time, created_at
+        "#,
+        payload.hospital_id,
+        payload.patient_id,
+        payload.staff_id,
+        payload.reason,
+        start_time
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if let Some(slot) = slot {
+        sqlx::query!("UPDATE availability_slots SET status = 'BOOKED', visit_id = $1 WHERE id = $2", visit.id, slot.id)
+            .execute(&mut *tx)
+            .await?;
+        log_change(&mut tx, visit.id, "BOOKED", None, Some(slot.id), None).await?;
+    }
+
+    tx.commit().await?;
+    Ok(Some(visit))
+}
+
+/// Moves a PENDING visit onto a different open slot at the same hospital and frees the one it
+/// held. Returns `Ok(None)` if the new slot is no longer available or the visit is no longer
+/// pending or at that hospital.
+pub async fn reschedule_visit(pool: &PgPool, visit_id: Uuid, new_slot_id: Uuid, reason: &str) -> Result<Option<Visit>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let current = sqlx::query!("SELECT hospital_id, status FROM visits WHERE id = $1 FOR UPDATE", visit_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    if current.status != "PENDING" {
+        return Ok(None);
+    }
+
+    let Some(slot) = claim_slot(&mut tx, new_slot_id).await? else {
+        return Ok(None);
+    };
+    if slot.hospital_id != current.hospital_id {
+        return Ok(None);
+    }
+    let old_slot_id = release_slots_for_visit(&mut tx, visit_id).await?;
+
+    sqlx::query!("UPDATE availability_slots SET visit_id = $1 WHERE id = $2", visit_id, slot.id)
+        .execute(&mut *tx)
+        .await?;
+
+    let visit = sqlx::query_as!(
+        Visit,
+        r#"
+        UPDATE visits
+        SET staff_id = $2, start_time = $3
+        WHERE id = $1
+        RETURNING id, hospital_id, patient_id, staff_id, reason, status, start_time, end_time, created_at
+        "#,
+        visit_id,
+        slot.staff_id,
+        slot.start_time
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    log_change(&mut tx, visit_id, "RESCHEDULED", old_slot_id, Some(slot.id), Some(reason)).await?;
+
+    tx.commit().await?;
+    Ok(Some(visit))
+}
+
+/// Cancels a PENDING visit and returns its slot (if any) to the pool.
+/// Returns `Ok(None)` if the visit is no longer pending.
+pub async fn cancel_visit(pool: &PgPool, visit_id: Uuid, reason: &str) -> Result<Option<Visit>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let from_status = sqlx::query_scalar!("SELECT status FROM visits WHERE id = $1 FOR UPDATE", visit_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    if from_status != "PENDING" {
+        return Ok(None);
+    }
+
+    let visit = sqlx::query_as!(
+        Visit,
+        r#"
+        UPDATE visits
+        SET status = 'CANCELLED'
+        WHERE id = $1
+        RETURNING id, hospital_id, patient_id, staff_id, reason, status, start_time, end_time, created_at
+        "#,
+        visit_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let old_slot_id = release_slots_for_visit(&mut tx, visit_id).await?;
+    log_change(&mut tx, visit_id, "CANCELLED", old_slot_id, None, Some(reason)).await?;
+    visit_repo::log_status_change(&mut tx, visit_id, &from_status, &visit.status).await?;
+
+    tx.commit().await?;
+    Ok(Some(visit))
+}
+
+pub async fn get_appointment_changes(pool: &PgPool, visit_id: Uuid) -> Result<Vec<AppointmentChange>, sqlx::Error> {
+    sqlx::query_as!(
+        AppointmentChange,
+        "SELECT * FROM appointment_changes WHERE visit_id = $1 ORDER BY created_at ASC",
+        visit_id
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,112 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::blood_bank::{
+    BloodUnitBatch, ReportBloodUnitsRequest, BloodStockLevel, BloodSearchResult, BloodSearchQuery,
+};
+
+pub async fn report_units(pool: &PgPool, hospital_id: Uuid, payload: ReportBloodUnitsRequest) -> Result<BloodUnitBatch, sqlx::Error> {
+    sqlx::query_as!(
+        BloodUnitBatch,
+        r#"
+        INSERT INTO blood_units (hospital_id, blood_group, component, units, expires_on)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, hospital_id, blood_group, component, units, expires_on, created_at, updated_at
+        "#,
+        hospital_id,
+        payload.blood_group,
+        payload.component,
+        payload.units,
+        payload.expires_on
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_batch(pool: &PgPool, batch_id: Uuid) -> Result<Option<BloodUnitBatch>, sqlx::Error> {
+    sqlx::query_as!(
+        BloodUnitBatch,
+        "SELECT id, hospital_id, blood_group, component, units, expires_on, created_at, updated_at FROM blood_units WHERE id = $1",
+        batch_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Takes units out of an unexpired batch. Returns `Ok(None)` if the batch holds fewer than
+/// requested or has expired.
+pub async fn issue_units(pool: &PgPool, batch_id: Uuid, units: i32) -> Result<Option<BloodUnitBatch>, sqlx::Error> {
+    sqlx::query_as!(
+        BloodUnitBatch,
+        r#"
+        UPDATE blood_units
+        SET units = units - $2, updated_at = NOW()
+        WHERE id = $1 AND units >= $2 AND expires_on >= CURRENT_DATE
+        RETURNING id, hospital_id, blood_group, component, units, expires_on, created_at, updated_at
+        "#,
+        batch_id,
+        units
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Non-expired stock per group and component.
+pub async fn get_hospital_stock(pool: &PgPool, hospital_id: Uuid) -> Result<Vec<BloodStockLevel>, sqlx::Error> {
+    sqlx::query_as!(
+        BloodStockLevel,
+        r#"
+        SELECT
+            blood_group, component,
+            SUM(units)::BIGINT AS "units!",
+            MIN(expires_on) AS "earliest_expiry!"
+        FROM blood_units
+        WHERE hospital_id = $1 AND units > 0 AND expires_on >= CURRENT_DATE
+        GROUP BY blood_group, component
+        ORDER BY blood_group ASC, component ASC
+        "#,
+        hospital_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Nearest active hospitals holding unexpired units of a blood group (great-circle distance).
+pub async fn search_nearest(pool: &PgPool, query: &BloodSearchQuery) -> Result<Vec<BloodSearchResult>, sqlx::Error> {
+    sqlx::query_as!(
+        BloodSearchResult,
+        r#"
+        SELECT
+            h.id AS hospital_id, h.name AS hospital_name, h.state, h.city,
+            h.latitude AS "latitude!", h.longitude AS "longitude!",
+            (6371 * 2 * ASIN(SQRT(
+                POWER(SIN(RADIANS(h.latitude - $3) / 2), 2)
+                + COS(RADIANS($3)) * COS(RADIANS(h.latitude)) * POWER(SIN(RADIANS(h.longitude - $4) / 2), 2)
+            ))) AS "distance_km!",
+            b.units AS "units_available!",
+            b.earliest_expiry AS "earliest_expiry!"
+        FROM hospitals h
+        JOIN (
+            SELECT hospital_id, SUM(units)::BIGINT AS units, MIN(expires_on) AS earliest_expiry
+            FROM blood_units
+            WHERE blood_group = $1
+              AND ($2::VARCHAR IS NULL OR component = $2)
+              AND units > 0
+              AND expires_on >= CURRENT_DATE
+            GROUP BY hospital_id
+        ) b ON b.hospital_id = h.id
+        WHERE h.is_active
+          AND h.latitude IS NOT NULL AND h.longitude IS NOT NULL
+          AND b.units >= $5
+        ORDER BY 7 ASC
+        LIMIT $6
+        "#,
+        query.blood_group,
+        query.component,
+        query.lat,
+        query.lng,
+        query.min_units.unwrap_or(1),
+        query.limit.unwrap_or(10).clamp(1, 50)
+    )
+    .fetch_all(pool)
+    .await
+}
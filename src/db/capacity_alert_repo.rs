@@ -0,0 +1,426 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::db::outbox_repo;
+use crate::models::{
+    capacity_alert::{
+        AlertRule, CreateAlertRuleRequest, HospitalCapacityMetrics, CapacityAlert, AlertSubscription,
+        CreateAlertSubscriptionRequest, AlertDelivery,
+    },
+    outbox::{CapacityAlertChanged, AGGREGATE_CAPACITY_ALERT, EVENT_CAPACITY_ALERT_UPDATED},
+    webhook::EVENT_CAPACITY_ALERT_RAISED,
+};
+
+pub async fn create_rule(pool: &PgPool, payload: CreateAlertRuleRequest) -> Result<AlertRule, sqlx::Error> {
+    sqlx::query_as!(
+        AlertRule,
+        r#"
+        INSERT INTO alert_rules (name, hospital_id, state, metric, threshold, severity)
+        VALUES ($1, $2, $3, $4, $5, COALESCE($6, 'WARNING'))
+        RETURNING id, name, hospital_id, state, metric, threshold, severity, is_active, created_at
+        "#,
+        payload.name,
+        payload.hospital_id,
+        payload.state,
+        payload.metric,
+        payload.threshold,
+        payload.severity
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_rules(pool: &PgPool, hospital_id: Option<Uuid>, state: Option<String>) -> Result<Vec<AlertRule>, sqlx::Error> {
+    sqlx::query_as!(
+        AlertRule,
+        r#"
+        SELECT * FROM alert_rules
+        WHERE ($1::UUID IS NULL OR hospital_id = $1)
+          AND ($2::TEXT IS NULL OR state ILIKE $2)
+        ORDER BY created_at DESC
+        "#,
+        hospital_id,
+        state
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Returns `Ok(None)` if the rule does not exist or is already inactive.
+pub async fn deactivate_rule(pool: &PgPool, rule_id: Uuid) -> Result<Option<AlertRule>, sqlx::Error> {
+    sqlx::query_as!(
+        AlertRule,
+        r#"
+        UPDATE alert_rules SET is_active = FALSE
+        WHERE id = $1 AND is_active = TRUE
+        RETURNING id, name, hospital_id, state, metric, threshold, severity, is_active, created_at
+        "#,
+        rule_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Active rules that cover a hospital: its own, its state's, and global ones.
+pub async fn get_rules_for_hospital(pool: &PgPool, hospital_id: Uuid, state: &str) -> Result<Vec<AlertRule>, sqlx::Error> {
+    sqlx::query_as!(
+        AlertRule,
+        r#"
+        SELECT * FROM alert_rules
+        WHERE is_active = TRUE
+          AND (hospital_id = $1 OR hospital_id IS NULL)
+          AND (state ILIKE $2 OR state IS NULL)
+        ORDER BY created_at ASC
+        "#,
+        hospital_id,
+        state
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Beds, emergency status, oxygen stock status and broken equipment (counted where it
+/// physically is) for an active hospital.
+pub async fn get_capacity_metrics(pool: &PgPool, hospital_id: Uuid) -> Result<Option<HospitalCapacityMetrics>, sqlx::Error> {
+    sqlx::query_as!(
+        HospitalCapacityMetrics,
+        r#"
+        SELECT
+            h.id AS hospital_id, h.name, h.state,
+            COALESCE(h.total_beds, 0) AS "total_beds!",
+            h.occupied_beds,
+            COALESCE(h.has_emergency, FALSE) AS "has_emergency!",
+            COALESCE(o.status, 'UNKNOWN') AS "oxygen_status!",
+            (
+                SELECT COUNT(*) FROM equipment e
+                WHERE COALESCE(e.custodian_hospital_id, e.hospital_id) = h.id AND e.condition = 'BROKEN'
+            ) AS "broken_equipment!"
+        FROM hospitals h
+        LEFT JOIN LATERAL (
+            SELECT CASE
+                WHEN SUM(quantity_on_hand) = 0 THEN 'OUT'
+                WHEN SUM(quantity_on_hand) <= SUM(reorder_level) THEN 'LOW'
+                ELSE 'OK'
+            END AS status
+            FROM inventory_items
+            WHERE hospital_id = h.id AND category = 'OXYGEN'
+            HAVING COUNT(*) > 0
+        ) o ON TRUE
+        WHERE h.id = $1 AND h.is_active = TRUE
+        "#,
+        hospital_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn get_active_hospital_ids(pool: &PgPool) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!("SELECT id FROM hospitals WHERE is_active = TRUE ORDER BY id")
+        .fetch_all(pool)
+        .await
+}
+
+/// Opens an alert for the rule at this hospital, or refreshes the reading on the one already
+/// open. Returns the alert id and whether it was newly raised.
+pub async fn raise_alert(
+    pool: &PgPool,
+    rule: &AlertRule,
+    hospital_id: Uuid,
+    observed_value: Option<f64>,
+    message: &str,
+) -> Result<(Uuid, bool), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO capacity_alerts (rule_id, hospital_id, metric, severity, observed_value, threshold, message)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (rule_id, hospital_id) WHERE status <> 'RESOLVED' DO UPDATE
+        SET observed_value = EXCLUDED.observed_value, message = EXCLUDED.message, updated_at = NOW()
+        RETURNING id, (xmax = 0) AS "inserted!"
+        "#,
+        rule.id,
+        hospital_id,
+        rule.metric,
+        rule.severity,
+        observed_value,
+        rule.threshold,
+        message
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if row.inserted {
+        append_alert_event(&mut tx, EVENT_CAPACITY_ALERT_RAISED, row.id, hospital_id, "ACTIVE").await?;
+    }
+
+    tx.commit().await?;
+    Ok((row.id, row.inserted))
+}
+
+/// Resolves the open alert for a rule at a hospital whose condition no longer holds.
+pub async fn clear_alert(pool: &PgPool, rule_id: Uuid, hospital_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let alert_id = sqlx::query_scalar!(
+        r#"
+        UPDATE capacity_alerts
+        SET status = 'RESOLVED', resolved_at = NOW(), resolution_note = 'Condition cleared', updated_at = NOW()
+        WHERE rule_id = $1 AND hospital_id = $2 AND status <> 'RESOLVED'
+        RETURNING id
+        "#,
+        rule_id,
+        hospital_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some(alert_id) = alert_id {
+        append_alert_event(&mut tx, EVENT_CAPACITY_ALERT_UPDATED, alert_id, hospital_id, "RESOLVED").await?;
+    }
+
+    tx.commit().await?;
+    Ok(alert_id)
+}
+
+/// Open alerts for rules at a hospital that no longer apply to it (deactivated, or rescoped).
+pub async fn clear_orphaned_alerts(pool: &PgPool, hospital_id: Uuid, live_rule_ids: &[Uuid]) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let alert_ids = sqlx::query_scalar!(
+        r#"
+        UPDATE capacity_alerts
+        SET status = 'RESOLVED', resolved_at = NOW(), resolution_note = 'Rule no longer applies', updated_at = NOW()
+        WHERE hospital_id = $1 AND status <> 'RESOLVED' AND NOT (rule_id = ANY($2))
+        RETURNING id
+        "#,
+        hospital_id,
+        live_rule_ids
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for alert_id in &alert_ids {
+        append_alert_event(&mut tx, EVENT_CAPACITY_ALERT_UPDATED, *alert_id, hospital_id, "RESOLVED").await?;
+    }
+
+    tx.commit().await?;
+    Ok(alert_ids.len() as u64)
+}
+
+async fn append_alert_event(
+    tx: &mut Transaction<'_, Postgres>,
+    event_type: &str,
+    alert_id: Uuid,
+    hospital_id: Uuid,
+    status: &str,
+) -> Result<(), sqlx::Error> {
+    let data = CapacityAlertChanged { alert_id, hospital_id, status: status.to_string() };
+    outbox_repo::append(tx, AGGREGATE_CAPACITY_ALERT, alert_id, event_type, &data).await
+}
+
+pub async fn get_alert(pool: &PgPool, alert_id: Uuid) -> Result<Option<CapacityAlert>, sqlx::Error> {
+    sqlx::query_as!(
+        CapacityAlert,
+        r#"
+        SELECT
+            a.id, a.rule_id, r.name AS rule_name, a.hospital_id, h.name AS hospital_name, h.state, a.metric,
+            a.severity, a.observed_value, a.threshold, a.message, a.status, a.triggered_at, a.acknowledged_by,
+            a.acknowledged_at, a.resolved_by, a.resolved_at, a.resolution_note, a.updated_at
+        FROM capacity_alerts a
+        JOIN alert_rules r ON r.id = a.rule_id
+        JOIN hospitals h ON h.id = a.hospital_id
+        WHERE a.id = $1
+        "#,
+        alert_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Alerts, most severe and newest first. Without `status`, only open alerts.
+pub async fn get_alerts(
+    pool: &PgPool,
+    status: Option<String>,
+    hospital_id: Option<Uuid>,
+    state: Option<String>,
+) -> Result<Vec<CapacityAlert>, sqlx::Error> {
+    sqlx::query_as!(
+        CapacityAlert,
+        r#"
+        SELECT
+            a.id, a.rule_id, r.name AS rule_name, a.hospital_id, h.name AS hospital_name, h.state, a.metric,
+            a.severity, a.observed_value, a.threshold, a.message, a.status, a.triggered_at, a.acknowledged_by,
+            a.acknowledged_at, a.resolved_by, a.resolved_at, a.resolution_note, a.updated_at
+        FROM capacity_alerts a
+        JOIN alert_rules r ON r.id = a.rule_id
+        JOIN hospitals h ON h.id = a.hospital_id
+        WHERE (($1::VARCHAR IS NULL AND a.status <> 'RESOLVED') OR a.status = $1)
+          AND ($2::UUID IS NULL OR a.hospital_id = $2)
+          AND ($3::TEXT IS NULL OR h.state ILIKE $3)
+        ORDER BY
+            CASE a.severity WHEN 'CRITICAL' THEN 0 WHEN 'WARNING' THEN 1 ELSE 2 END ASC,
+            a.triggered_at DESC
+        "#,
+        status,
+        hospital_id,
+        state
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// ACTIVE -> ACKNOWLEDGED. Returns `Ok(None)` if the alert is not awaiting acknowledgement.
+pub async fn acknowledge_alert(pool: &PgPool, alert_id: Uuid, staff_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let hospital_id = sqlx::query_scalar!(
+        r#"
+        UPDATE capacity_alerts
+        SET status = 'ACKNOWLEDGED', acknowledged_by = $2, acknowledged_at = NOW(), updated_at = NOW()
+        WHERE id = $1 AND status = 'ACTIVE'
+        RETURNING hospital_id
+        "#,
+        alert_id,
+        staff_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(hospital_id) = hospital_id else {
+        return Ok(None);
+    };
+    append_alert_event(&mut tx, EVENT_CAPACITY_ALERT_UPDATED, alert_id, hospital_id, "ACKNOWLEDGED").await?;
+
+    tx.commit().await?;
+    Ok(Some(alert_id))
+}
+
+/// ACTIVE/ACKNOWLEDGED -> RESOLVED. Returns `Ok(None)` if the alert is already resolved.
+pub async fn resolve_alert(pool: &PgPool, alert_id: Uuid, staff_id: Uuid, note: Option<String>) -> Result<Option<Uuid>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let hospital_id = sqlx::query_scalar!(
+        r#"
+        UPDATE capacity_alerts
+        SET status = 'RESOLVED', resolved_by = $2, resolved_at = NOW(), resolution_note = $3, updated_at = NOW()
+        WHERE id = $1 AND status <> 'RESOLVED'
+        RETURNING hospital_id
+        "#,
+        alert_id,
+        staff_id,
+        note
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(hospital_id) = hospital_id else {
+        return Ok(None);
+    };
+    append_alert_event(&mut tx, EVENT_CAPACITY_ALERT_UPDATED, alert_id, hospital_id, "RESOLVED").await?;
+
+    tx.commit().await?;
+    Ok(Some(alert_id))
+}
+
+pub async fn create_subscription(pool: &PgPool, payload: CreateAlertSubscriptionRequest) -> Result<AlertSubscription, sqlx::Error> {
+    sqlx::query_as!(
+        AlertSubscription,
+        r#"
+        INSERT INTO alert_subscriptions (hospital_id, state, channel, target, min_severity)
+        VALUES ($1, $2, $3, $4, COALESCE($5, 'INFO'))
+        RETURNING id, hospital_id, state, channel, target, min_severity, is_active, created_at
+        "#,
+        payload.hospital_id,
+        payload.state,
+        payload.channel,
+        payload.target,
+        payload.min_severity
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_subscriptions(pool: &PgPool, hospital_id: Option<Uuid>, state: Option<String>) -> Result<Vec<AlertSubscription>, sqlx::Error> {
+    sqlx::query_as!(
+        AlertSubscription,
+        r#"
+        SELECT * FROM alert_subscriptions
+        WHERE is_active = TRUE
+          AND ($1::UUID IS NULL OR hospital_id = $1)
+          AND ($2::TEXT IS NULL OR state ILIKE $2)
+        ORDER BY created_at DESC
+        "#,
+        hospital_id,
+        state
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Returns `Ok(None)` if the subscription does not exist or is already inactive.
+pub async fn deactivate_subscription(pool: &PgPool, subscription_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "UPDATE alert_subscriptions SET is_active = FALSE WHERE id = $1 AND is_active = TRUE RETURNING id",
+        subscription_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Active subscriptions covering an alert's hospital whose minimum severity it meets, and which
+/// have not yet had it delivered.
+pub async fn get_subscriptions_for_alert(pool: &PgPool, alert: &CapacityAlert) -> Result<Vec<AlertSubscription>, sqlx::Error> {
+    sqlx::query_as!(
+        AlertSubscription,
+        r#"
+        SELECT * FROM alert_subscriptions
+        WHERE is_active = TRUE
+          AND (hospital_id = $1 OR hospital_id IS NULL)
+          AND (state ILIKE $2 OR state IS NULL)
+          AND CASE min_severity WHEN 'CRITICAL' THEN 2 WHEN 'WARNING' THEN 1 ELSE 0 END
+              <= CASE $3 WHEN 'CRITICAL' THEN 2 WHEN 'WARNING' THEN 1 ELSE 0 END
+          AND NOT EXISTS (
+              SELECT 1 FROM alert_deliveries d
+              WHERE d.alert_id = $4 AND d.subscription_id = alert_subscriptions.id AND d.status = 'DELIVERED'
+          )
+        "#,
+        alert.hospital_id,
+        alert.state,
+        alert.severity,
+        alert.id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn record_delivery(
+    pool: &PgPool,
+    alert_id: Uuid,
+    subscription: &AlertSubscription,
+    error: Option<String>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO alert_deliveries (alert_id, subscription_id, channel, status, error)
+        VALUES ($1, $2, $3, CASE WHEN $4::TEXT IS NULL THEN 'DELIVERED' ELSE 'FAILED' END, $4)
+        "#,
+        alert_id,
+        subscription.id,
+        subscription.channel,
+        error
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_deliveries(pool: &PgPool, alert_id: Uuid) -> Result<Vec<AlertDelivery>, sqlx::Error> {
+    sqlx::query_as!(
+        AlertDelivery,
+        "SELECT * FROM alert_deliveries WHERE alert_id = $1 ORDER BY delivered_at ASC",
+        alert_id
+    )
+    .fetch_all(pool)
+    .await
+}
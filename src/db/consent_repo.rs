@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::consent::{Consent, GrantConsentRequest, AccessContext, AccessBasis, AccessLogEntry};
+
+pub async fn grant_consent(
+    pool: &PgPool,
+    patient_id: Uuid,
+    recorded_by: Uuid,
+    payload: GrantConsentRequest,
+) -> Result<Consent, sqlx::Error> {
+    sqlx::query_as!(
+        Consent,
+        r#"
+        INSERT INTO patient_consents (patient_id, hospital_id, purpose, recorded_by, notes)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, patient_id, hospital_id, purpose, recorded_by, notes, granted_at, revoked_at, revoke_reason
+        "#,
+        patient_id,
+        payload.hospital_id,
+        payload.purpose,
+        recorded_by,
+        payload.notes
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_consent(pool: &PgPool, consent_id: Uuid) -> Result<Option<Consent>, sqlx::Error> {
+    sqlx::query_as!(Consent, "SELECT * FROM patient_consents WHERE id = $1", consent_id)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn get_patient_consents(pool: &PgPool, patient_id: Uuid, include_revoked: bool) -> Result<Vec<Consent>, sqlx::Error> {
+    sqlx::query_as!(
+        Consent,
+        r#"
+        SELECT * FROM patient_consents
+        WHERE patient_id = $1 AND (revoked_at IS NULL OR $2)
+        ORDER BY granted_at DESC
+        "#,
+        patient_id,
+        include_revoked
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Returns `Ok(None)` if the consent was already revoked.
+pub async fn revoke_consent(pool: &PgPool, consent_id: Uuid, reason: Option<String>) -> Result<Option<Consent>, sqlx::Error> {
+    sqlx::query_as!(
+        Consent,
+        r#"
+        UPDATE patient_consents
+        SET revoked_at = NOW(), revoke_reason = $2
+        WHERE id = $1 AND revoked_at IS NULL
+        RETURNING id, patient_id, hospital_id, purpose, recorded_by, notes, granted_at, revoked_at, revoke_reason
+        "#,
+        consent_id,
+        reason
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// An unrevoked consent covering this hospital and purpose, if the patient has given one.
+pub async fn find_active_consent(
+    pool: &PgPool,
+    patient_id: Uuid,
+    hospital_id: Uuid,
+    purpose: &str,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT id FROM patient_consents
+        WHERE patient_id = $1
+          AND revoked_at IS NULL
+          AND (hospital_id IS NULL OR hospital_id = $2)
+          AND (purpose IS NULL OR purpose = $3)
+        ORDER BY granted_at DESC
+        LIMIT 1
+        "#,
+        patient_id,
+        hospital_id,
+        purpose
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Whether the patient is registered at, or has been seen at, the hospital.
+pub async fn has_care_relationship(pool: &PgPool, patient_id: Uuid, hospital_id: Uuid) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM patients WHERE id = $1 AND hospital_id = $2
+            UNION ALL
+            SELECT 1 FROM visits WHERE patient_id = $1 AND hospital_id = $2
+        ) AS "exists!"
+        "#,
+        patient_id,
+        hospital_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Records one access-log row per patient read by the request.
+pub async fn log_access(
+    pool: &PgPool,
+    ctx: &AccessContext,
+    patient_ids: &[Uuid],
+    resource: &str,
+    basis: AccessBasis,
+    consent_id: Option<Uuid>,
+) -> Result<(), sqlx::Error> {
+    let break_glass_reason = match basis {
+        AccessBasis::BreakGlass => ctx.break_glass_reason.as_deref(),
+        _ => None,
+    };
+
+    sqlx::query!(
+        r#"
+        INSERT INTO patient_access_log (patient_id, hospital_id, staff_id, purpose, resource, basis, consent_id, break_glass_reason)
+        SELECT p.id, $2, $3, $4, $5, $6, $7, $8
+        FROM UNNEST($1::UUID[]) AS p(id)
+        "#,
+        patient_ids,
+        ctx.hospital_id,
+        ctx.staff_id,
+        ctx.purpose,
+        resource,
+        basis.as_str(),
+        consent_id,
+        break_glass_reason
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_access_log(
+    pool: &PgPool,
+    patient_id: Uuid,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    basis: Option<String>,
+) -> Result<Vec<AccessLogEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        AccessLogEntry,
+        r#"
+        SELECT
+            l.id, l.patient_id, l.hospital_id, h.name AS hospital_name,
+            l.staff_id, s.first_name || ' ' || s.last_name AS staff_name,
+            l.purpose, l.resource, l.basis, l.consent_id, l.break_glass_reason, l.accessed_at
+        FROM patient_access_log l
+        JOIN hospitals h ON h.id = l.hospital_id
+        LEFT JOIN staff s ON s.id = l.staff_id
+        WHERE l.patient_id = $1
+          AND ($2::TIMESTAMPTZ IS NULL OR l.accessed_at >= $2)
+          AND ($3::TIMESTAMPTZ IS NULL OR l.accessed_at < $3)
+          AND ($4::VARCHAR IS NULL OR l.basis = $4)
+        ORDER BY l.accessed_at DESC
+        "#,
+        patient_id,
+        from,
+        to,
+        basis
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,71 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::credential::{Credential, CreateCredentialRequest, ExpiringCredential};
+
+pub async fn create_credential(pool: &PgPool, staff_id: Uuid, payload: CreateCredentialRequest) -> Result<Credential, sqlx::Error> {
+    sqlx::query_as!(
+        Credential,
+        r#"
+        INSERT INTO staff_credentials (staff_id, licence_body, licence_number, specialty, issued_on, expires_on)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, staff_id, licence_body, licence_number, specialty, issued_on, expires_on, created_at
+        "#,
+        staff_id,
+        payload.licence_body,
+        payload.licence_number,
+        payload.specialty,
+        payload.issued_on,
+        payload.expires_on
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_staff_credentials(pool: &PgPool, staff_id: Uuid) -> Result<Vec<Credential>, sqlx::Error> {
+    sqlx::query_as!(
+        Credential,
+        "SELECT * FROM staff_credentials WHERE staff_id = $1 ORDER BY expires_on DESC",
+        staff_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// True if the staff member holds at least one licence valid on `on_date`.
+pub async fn has_valid_credential(pool: &PgPool, staff_id: Uuid, on_date: NaiveDate) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM staff_credentials
+            WHERE staff_id = $1 AND issued_on <= $2 AND expires_on >= $2
+        ) AS "exists!"
+        "#,
+        staff_id,
+        on_date
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_expiring_credentials(pool: &PgPool, hospital_id: Uuid, days: i32) -> Result<Vec<ExpiringCredential>, sqlx::Error> {
+    sqlx::query_as!(
+        ExpiringCredential,
+        r#"
+        SELECT
+            c.id AS credential_id, s.id AS staff_id, s.first_name, s.last_name, s.role,
+            c.licence_body, c.licence_number, c.expires_on,
+            (c.expires_on - CURRENT_DATE) AS "days_remaining!"
+        FROM staff_credentials c
+        JOIN staff s ON s.id = c.staff_id
+        WHERE s.hospital_id = $1
+          AND s.is_active
+          AND c.expires_on BETWEEN CURRENT_DATE AND CURRENT_DATE + $2::INT
+        ORDER BY c.expires_on ASC
+        "#,
+        hospital_id,
+        days
+    )
+    .fetch_all(pool)
+    .await
+}
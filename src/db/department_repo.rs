@@ -26,4 +26,21 @@ pub async fn get_departments_by_hospital(pool: &PgPool, hospital_id: Uuid) -> Re
     )
     .fetch_all(pool)
     .await
-}
\ No newline at end of file
+}
+
+/// Whether the department belongs to the same hospital as the staff member.
+pub async fn is_in_staff_hospital(pool: &PgPool, department_id: Uuid, staff_id: Uuid) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM departments d
+            JOIN staff s ON s.hospital_id = d.hospital_id
+            WHERE d.id = $1 AND s.id = $2
+        ) AS "exists!"
+        "#,
+        department_id,
+        staff_id
+    )
+    .fetch_one(pool)
+    .await
+}
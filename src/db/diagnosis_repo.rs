@@ -0,0 +1,78 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::db::surveillance_repo;
+use crate::models::{
+    diagnosis::{VisitDiagnosis, CreateDiagnosisRequest},
+    icd10::Icd10Code,
+};
+
+/// Records a diagnosis against a validated ICD-10 code.
+/// Returns `Ok(None)` if a primary diagnosis is requested and the visit already has one.
+pub async fn add_diagnosis(
+    pool: &PgPool,
+    visit_id: Uuid,
+    code: &Icd10Code,
+    payload: CreateDiagnosisRequest,
+) -> Result<Option<VisitDiagnosis>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let diagnosis_type = payload.diagnosis_type.unwrap_or_else(|| "SECONDARY".to_string());
+
+    // Lock the visit so concurrent requests cannot both add a primary diagnosis
+    sqlx::query!("SELECT id FROM visits WHERE id = $1 FOR UPDATE", visit_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    if diagnosis_type == "PRIMARY" {
+        let has_primary = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM visit_diagnoses WHERE visit_id = $1 AND diagnosis_type = 'PRIMARY'
+            ) AS "exists!"
+            "#,
+            visit_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if has_primary {
+            return Ok(None);
+        }
+    }
+
+    let diagnosis = sqlx::query_as!(
+        VisitDiagnosis,
+        r#"
+        INSERT INTO visit_diagnoses (visit_id, code, description, diagnosis_type)
+        VALUES ($1, $2, COALESCE($3, $4), $5)
+        RETURNING id, visit_id, code, description, diagnosis_type, created_at
+        "#,
+        visit_id,
+        code.code,
+        payload.description,
+        code.description,
+        diagnosis_type
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Diagnoses coded after the visit was closed still reach surveillance
+    surveillance_repo::record_cases_for_visit(&mut tx, visit_id).await?;
+
+    tx.commit().await?;
+    Ok(Some(diagnosis))
+}
+
+pub async fn get_visit_diagnoses(pool: &PgPool, visit_id: Uuid) -> Result<Vec<VisitDiagnosis>, sqlx::Error> {
+    sqlx::query_as!(
+        VisitDiagnosis,
+        r#"
+        SELECT id, visit_id, code, description, diagnosis_type, created_at
+        FROM visit_diagnoses
+        WHERE visit_id = $1
+        ORDER BY (diagnosis_type = 'PRIMARY') DESC, created_at ASC
+        "#,
+        visit_id
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -1,32 +1,123 @@
 use sqlx::PgPool;
 use uuid::Uuid;
-use crate::models::equipment::{Equipment, CreateEquipmentRequest};
+use crate::db::outbox_repo;
+use crate::models::{
+    equipment::{Equipment, CreateEquipmentRequest},
+    maintenance::{ConditionLogEntry, UpdateConditionRequest},
+};
 
 pub async fn create_equipment(pool: &PgPool, payload: CreateEquipmentRequest) -> Result<Equipment, sqlx::Error> {
-    sqlx::query_as!(
+    let mut tx = pool.begin().await?;
+
+    let item = sqlx::query_as!(
         Equipment,
         r#"
-        INSERT INTO equipment (hospital_id, department_id, name, serial_number, condition, is_operational)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, hospital_id, department_id, name, serial_number, condition, is_operational, created_at
+        INSERT INTO equipment (hospital_id, department_id, name, serial_number, condition, is_operational, equipment_type)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, hospital_id, department_id, name, serial_number, condition, is_operational, created_at,
+            equipment_type, custodian_hospital_id
         "#,
         payload.hospital_id,
         payload.department_id,
         payload.name,
         payload.serial_number,
         payload.condition,
-        payload.is_operational
+        payload.is_operational,
+        payload.equipment_type
     )
-    .fetch_one(pool)
-    .await
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Start the condition history with the registration state
+    sqlx::query!(
+        r#"
+        INSERT INTO equipment_condition_log (equipment_id, new_condition, new_is_operational, note)
+        VALUES ($1, $2, $3, 'Registered')
+        "#,
+        item.id,
+        item.condition,
+        item.is_operational
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    outbox_repo::append_capacity_changed(&mut tx, item.hospital_id).await?;
+    tx.commit().await?;
+    Ok(item)
 }
 
 pub async fn get_hospital_equipment(pool: &PgPool, hospital_id: Uuid) -> Result<Vec<Equipment>, sqlx::Error> {
     sqlx::query_as!(
         Equipment,
-        "SELECT * FROM equipment WHERE hospital_id = $1 ORDER BY name ASC",
+        r#"
+        SELECT * FROM equipment
+        WHERE hospital_id = $1 OR custodian_hospital_id = $1
+        ORDER BY name ASC
+        "#,
         hospital_id
     )
     .fetch_all(pool)
     .await
-}
\ No newline at end of file
+}
+
+/// Updates condition/operational state and records the transition in the condition log.
+pub async fn update_equipment_condition(
+    pool: &PgPool,
+    equipment_id: Uuid,
+    payload: UpdateConditionRequest,
+) -> Result<Equipment, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let previous = sqlx::query!(
+        "SELECT condition, is_operational FROM equipment WHERE id = $1 FOR UPDATE",
+        equipment_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let item = sqlx::query_as!(
+        Equipment,
+        r#"
+        UPDATE equipment
+        SET condition = $2, is_operational = $3
+        WHERE id = $1
+        RETURNING id, hospital_id, department_id, name, serial_number, condition, is_operational, created_at,
+            equipment_type, custodian_hospital_id
+        "#,
+        equipment_id,
+        payload.condition,
+        payload.is_operational
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO equipment_condition_log (equipment_id, old_condition, new_condition, old_is_operational, new_is_operational, note)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        equipment_id,
+        previous.condition,
+        item.condition,
+        previous.is_operational,
+        item.is_operational,
+        payload.note
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Counted where the equipment physically is
+    outbox_repo::append_capacity_changed(&mut tx, item.custodian_hospital_id.unwrap_or(item.hospital_id)).await?;
+    tx.commit().await?;
+    Ok(item)
+}
+
+pub async fn get_condition_history(pool: &PgPool, equipment_id: Uuid) -> Result<Vec<ConditionLogEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        ConditionLogEntry,
+        "SELECT * FROM equipment_condition_log WHERE equipment_id = $1 ORDER BY changed_at ASC",
+        equipment_id
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,233 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::db::outbox_repo;
+use crate::models::equipment_transfer::{EquipmentTransfer, CreateTransferRequest, CustodyEvent};
+
+pub async fn has_active_transfer(pool: &PgPool, equipment_id: Uuid) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM equipment_transfers
+            WHERE equipment_id = $1
+              AND (status IN ('REQUESTED', 'APPROVED', 'DISPATCHED') OR (transfer_type = 'LOAN' AND status = 'RECEIVED'))
+        ) AS "exists!"
+        "#,
+        equipment_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn create_transfer(pool: &PgPool, payload: CreateTransferRequest) -> Result<EquipmentTransfer, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // The lender is whoever currently holds the equipment
+    let transfer = sqlx::query_as!(
+        EquipmentTransfer,
+        r#"
+        INSERT INTO equipment_transfers (equipment_id, from_hospital_id, to_hospital_id, transfer_type, reason, expected_return_on)
+        SELECT e.id, COALESCE(e.custodian_hospital_id, e.hospital_id), $2, $3, $4, $5
+        FROM equipment e
+        WHERE e.id = $1
+        RETURNING id, equipment_id, from_hospital_id, to_hospital_id, transfer_type, status, reason,
+            expected_return_on, created_at, updated_at
+        "#,
+        payload.equipment_id,
+        payload.to_hospital_id,
+        payload.transfer_type,
+        payload.reason,
+        payload.expected_return_on
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    log_custody_event(&mut tx, &transfer, None).await?;
+
+    tx.commit().await?;
+    Ok(transfer)
+}
+
+/// Moves a transfer one step through its workflow and applies the custody side effects.
+/// Returns `Ok(None)` if the transfer is not in a state the step can start from.
+pub async fn advance_transfer(
+    pool: &PgPool,
+    transfer_id: Uuid,
+    allowed_from: &[&str],
+    next_status: &str,
+    note: Option<&str>,
+) -> Result<Option<EquipmentTransfer>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let allowed_from: Vec<String> = allowed_from.iter().map(|s| s.to_string()).collect();
+    let transfer = sqlx::query_as!(
+        EquipmentTransfer,
+        r#"
+        UPDATE equipment_transfers
+        SET status = $2, updated_at = NOW()
+        WHERE id = $1
+          AND status = ANY($3)
+          AND ($2 <> 'RETURNED' OR transfer_type = 'LOAN')
+        RETURNING id, equipment_id, from_hospital_id, to_hospital_id, transfer_type, status, reason,
+            expected_return_on, created_at, updated_at
+        "#,
+        transfer_id,
+        next_status,
+        &allowed_from
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(transfer) = transfer else {
+        return Ok(None);
+    };
+
+    match (transfer.status.as_str(), transfer.transfer_type.as_str()) {
+        ("RECEIVED", "LOAN") => {
+            sqlx::query!(
+                "UPDATE equipment SET custodian_hospital_id = $2 WHERE id = $1",
+                transfer.equipment_id,
+                transfer.to_hospital_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        ("RECEIVED", _) => {
+            sqlx::query!(
+                "UPDATE equipment SET hospital_id = $2, department_id = NULL, custodian_hospital_id = NULL WHERE id = $1",
+                transfer.equipment_id,
+                transfer.to_hospital_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        ("RETURNED", _) => {
+            sqlx::query!(
+                "UPDATE equipment SET custodian_hospital_id = NULL WHERE id = $1",
+                transfer.equipment_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        _ => {}
+    }
+
+    if matches!(transfer.status.as_str(), "DISPATCHED" | "RECEIVED" | "RETURNED") {
+        let equipment_type = sqlx::query_scalar!(
+            "SELECT equipment_type FROM equipment WHERE id = $1",
+            transfer.equipment_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(equipment_type) = equipment_type {
+            sync_capability_flag(&mut tx, transfer.from_hospital_id, &equipment_type).await?;
+            sync_capability_flag(&mut tx, transfer.to_hospital_id, &equipment_type).await?;
+        }
+
+        // Custody moves change where broken equipment counts
+        outbox_repo::append_capacity_changed(&mut tx, transfer.from_hospital_id).await?;
+        outbox_repo::append_capacity_changed(&mut tx, transfer.to_hospital_id).await?;
+    }
+
+    log_custody_event(&mut tx, &transfer, note).await?;
+
+    tx.commit().await?;
+    Ok(Some(transfer))
+}
+
+/// Re-derives the hospital capability flag backed by `equipment_type` from the operational
+/// equipment physically at the hospital (held, not in transit). Oxygen can also come from
+/// piped supply or cylinders, so concentrators only ever raise `has_oxygen`.
+async fn sync_capability_flag(tx: &mut Transaction<'_, Postgres>, hospital_id: Uuid, equipment_type: &str) -> Result<(), sqlx::Error> {
+    let present = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM equipment e
+            WHERE COALESCE(e.custodian_hospital_id, e.hospital_id) = $1
+              AND e.equipment_type = $2
+              AND e.is_operational
+              AND NOT EXISTS (
+                  SELECT 1 FROM equipment_transfers t WHERE t.equipment_id = e.id AND t.status = 'DISPATCHED'
+              )
+        ) AS "exists!"
+        "#,
+        hospital_id,
+        equipment_type
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    match equipment_type {
+        "VENTILATOR" => {
+            sqlx::query!("UPDATE hospitals SET has_ventilators = $2 WHERE id = $1", hospital_id, present)
+                .execute(&mut **tx)
+                .await?;
+        }
+        "AMBULANCE" => {
+            sqlx::query!("UPDATE hospitals SET has_ambulance = $2 WHERE id = $1", hospital_id, present)
+                .execute(&mut **tx)
+                .await?;
+        }
+        "OXYGEN_CONCENTRATOR" if present => {
+            sqlx::query!("UPDATE hospitals SET has_oxygen = TRUE WHERE id = $1", hospital_id)
+                .execute(&mut **tx)
+                .await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+async fn log_custody_event(tx: &mut Transaction<'_, Postgres>, transfer: &EquipmentTransfer, note: Option<&str>) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO equipment_custody_events (equipment_id, transfer_id, status, from_hospital_id, to_hospital_id, note)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        transfer.equipment_id,
+        transfer.id,
+        transfer.status,
+        transfer.from_hospital_id,
+        transfer.to_hospital_id,
+        note
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_transfer(pool: &PgPool, transfer_id: Uuid) -> Result<Option<EquipmentTransfer>, sqlx::Error> {
+    sqlx::query_as!(
+        EquipmentTransfer,
+        "SELECT * FROM equipment_transfers WHERE id = $1",
+        transfer_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Incoming and outgoing transfers for a hospital, newest first.
+pub async fn get_hospital_transfers(pool: &PgPool, hospital_id: Uuid) -> Result<Vec<EquipmentTransfer>, sqlx::Error> {
+    sqlx::query_as!(
+        EquipmentTransfer,
+        r#"
+        SELECT * FROM equipment_transfers
+        WHERE from_hospital_id = $1 OR to_hospital_id = $1
+        ORDER BY updated_at DESC
+        "#,
+        hospital_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_custody_history(pool: &PgPool, equipment_id: Uuid) -> Result<Vec<CustodyEvent>, sqlx::Error> {
+    sqlx::query_as!(
+        CustodyEvent,
+        "SELECT * FROM equipment_custody_events WHERE equipment_id = $1 ORDER BY occurred_at ASC",
+        equipment_id
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::forecast::{HourlyHistoryPoint, ForecastPoint, ForecastVsActual};
+
+/// Active hospitals that have beds to forecast, optionally just `hospital_id`.
+pub async fn get_forecastable_hospitals(pool: &PgPool, hospital_id: Option<Uuid>) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT id FROM hospitals
+        WHERE is_active = TRUE AND COALESCE(total_beds, 0) > 0
+          AND ($1::UUID IS NULL OR id = $1)
+        ORDER BY id
+        "#,
+        hospital_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Occupancy at each hour mark in `[from, to]` from the snapshot in effect at that moment,
+/// with the visits started in the following hour. Hours before the first snapshot are omitted.
+pub async fn get_hourly_history(
+    pool: &PgPool,
+    hospital_id: Uuid,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<HourlyHistoryPoint>, sqlx::Error> {
+    sqlx::query_as!(
+        HourlyHistoryPoint,
+        r#"
+        SELECT
+            g.hour AS "hour!",
+            s.occupied_beds AS "occupied_beds!",
+            s.total_beds AS "total_beds!",
+            (
+                SELECT COUNT(*) FROM visits v
+                WHERE v.hospital_id = $1 AND v.start_time >= g.hour AND v.start_time < g.hour + INTERVAL '1 hour'
+            ) AS "visits!"
+        FROM generate_series($2::TIMESTAMPTZ, $3::TIMESTAMPTZ, INTERVAL '1 hour') AS g(hour)
+        JOIN LATERAL (
+            SELECT occupied_beds, total_beds
+            FROM bed_occupancy_snapshots
+            WHERE hospital_id = $1 AND recorded_at <= g.hour
+            ORDER BY recorded_at DESC
+            LIMIT 1
+        ) s ON TRUE
+        ORDER BY g.hour ASC
+        "#,
+        hospital_id,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Stores one run's predictions; re-running with the same cutoff replaces them.
+pub async fn store_forecasts(
+    pool: &PgPool,
+    hospital_id: Uuid,
+    generated_at: DateTime<Utc>,
+    model: &str,
+    points: &[ForecastPoint],
+) -> Result<u64, sqlx::Error> {
+    let targets: Vec<DateTime<Utc>> = points.iter().map(|p| p.target_time).collect();
+    let horizons: Vec<i32> = points.iter().map(|p| p.horizon_hours).collect();
+    let predicted: Vec<f64> = points.iter().map(|p| p.predicted_occupied).collect();
+    let lower: Vec<f64> = points.iter().map(|p| p.lower_bound).collect();
+    let upper: Vec<f64> = points.iter().map(|p| p.upper_bound).collect();
+    let total_beds: Vec<i32> = points.iter().map(|p| p.total_beds).collect();
+    let visits: Vec<f64> = points.iter().map(|p| p.expected_visits).collect();
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO occupancy_forecasts (
+            hospital_id, generated_at, model, target_time, horizon_hours, predicted_occupied,
+            lower_bound, upper_bound, total_beds, expected_visits
+        )
+        SELECT $1, $2, $3, f.*
+        FROM UNNEST($4::TIMESTAMPTZ[], $5::INT[], $6::FLOAT8[], $7::FLOAT8[], $8::FLOAT8[], $9::INT[], $10::FLOAT8[])
+            AS f(target_time, horizon_hours, predicted_occupied, lower_bound, upper_bound, total_beds, expected_visits)
+        ON CONFLICT (hospital_id, generated_at, target_time) DO UPDATE
+        SET model = EXCLUDED.model,
+            horizon_hours = EXCLUDED.horizon_hours,
+            predicted_occupied = EXCLUDED.predicted_occupied,
+            lower_bound = EXCLUDED.lower_bound,
+            upper_bound = EXCLUDED.upper_bound,
+            total_beds = EXCLUDED.total_beds,
+            expected_visits = EXCLUDED.expected_visits,
+            created_at = NOW()
+        "#,
+        hospital_id,
+        generated_at,
+        model,
+        &targets,
+        &horizons,
+        &predicted,
+        &lower,
+        &upper,
+        &total_beds,
+        &visits
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// The most recent prediction for each hour in `[from, to)` (optionally only those made at most
+/// `max_horizon` hours ahead), alongside the occupancy recorded at that hour once it has passed.
+pub async fn get_forecast_vs_actual(
+    pool: &PgPool,
+    hospital_id: Uuid,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    max_horizon: Option<i32>,
+) -> Result<Vec<ForecastVsActual>, sqlx::Error> {
+    sqlx::query_as!(
+        ForecastVsActual,
+        r#"
+        SELECT DISTINCT ON (f.target_time)
+            f.target_time, f.generated_at, f.horizon_hours, f.predicted_occupied, f.lower_bound,
+            f.upper_bound, f.total_beds, f.expected_visits,
+            CASE WHEN f.target_time <= NOW() THEN a.occupied_beds END AS actual_occupied
+        FROM occupancy_forecasts f
+        LEFT JOIN LATERAL (
+            SELECT occupied_beds
+            FROM bed_occupancy_snapshots s
+            WHERE s.hospital_id = f.hospital_id AND s.recorded_at <= f.target_time
+            ORDER BY s.recorded_at DESC
+            LIMIT 1
+        ) a ON TRUE
+        WHERE f.hospital_id = $1
+          AND f.target_time >= $2 AND f.target_time < $3
+          AND ($4::INT IS NULL OR f.horizon_hours <= $4)
+        ORDER BY f.target_time ASC, f.generated_at DESC
+        "#,
+        hospital_id,
+        from,
+        to,
+        max_horizon
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -1,6 +1,8 @@
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use crate::models::Hospital;
 use crate::models::hospital::CreateHospitalRequest;
+use crate::models::{outbox::AGGREGATE_HOSPITAL, webhook::EVENT_HOSPITAL_UPDATED};
+use crate::db::outbox_repo;
 
 pub async fn fetch_all_hospitals(
     pool: &PgPool,
@@ -10,7 +12,7 @@ pub async fn fetch_all_hospitals(
         r#"
         SELECT 
             id, name, hospital_type, state, city, is_active, created_at, 
-            latitude, longitude, total_beds, occupied_beds, has_emergency, has_oxygen, has_ventilators, has_ambulance
+            latitude, longitude, total_beds, occupied_beds, has_emergency, has_oxygen, has_ventilators, has_ambulance, lga
         FROM hospitals
         ORDER BY created_at DESC
         "#
@@ -30,7 +32,7 @@ pub async fn fetch_hospital_by_id(
         r#"
         SELECT 
             id, name, hospital_type, state, city, is_active, created_at, 
-            latitude, longitude, total_beds, occupied_beds, has_emergency, has_oxygen, has_ventilators, has_ambulance
+            latitude, longitude, total_beds, occupied_beds, has_emergency, has_oxygen, has_ventilators, has_ambulance, lga
         FROM hospitals
         WHERE id = $1
         "#,
@@ -46,19 +48,21 @@ pub async fn create_hospital(
     pool: &PgPool,
     payload: CreateHospitalRequest,
 ) -> Result<Hospital, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
     let hospital = sqlx::query_as!(
         Hospital,
         r#"
         INSERT INTO hospitals (
             name, hospital_type, state, city, 
             latitude, longitude, total_beds, occupied_beds, has_emergency,
-            has_oxygen, has_ventilators, has_ambulance
+            has_oxygen, has_ventilators, has_ambulance, lga
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
         RETURNING 
             id, name, hospital_type, state, city, is_active, created_at, 
             latitude, longitude, total_beds, occupied_beds, has_emergency,
-            has_oxygen, has_ventilators, has_ambulance
+            has_oxygen, has_ventilators, has_ambulance, lga
         "#,
         payload.name,
         payload.hospital_type,
@@ -72,11 +76,15 @@ pub async fn create_hospital(
         // New Fields (Default to false if missing)
         payload.has_oxygen.unwrap_or(false),
         payload.has_ventilators.unwrap_or(false),
-        payload.has_ambulance.unwrap_or(false)
+        payload.has_ambulance.unwrap_or(false),
+        payload.lga
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    record_occupancy(&mut tx, &hospital).await?;
+    outbox_repo::append_capacity_changed(&mut tx, hospital.id).await?;
+    tx.commit().await?;
     Ok(hospital)
 }
 
@@ -85,6 +93,8 @@ pub async fn update_hospital(
     id: uuid::Uuid,
     payload: CreateHospitalRequest,
 ) -> Result<Hospital, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
     let hospital = sqlx::query_as!(
         Hospital,
         r#"
@@ -101,12 +111,13 @@ pub async fn update_hospital(
             has_emergency = $9,
             has_oxygen = $10,
             has_ventilators = $11,
-            has_ambulance = $12
-        WHERE id = $13
+            has_ambulance = $12,
+            lga = $13
+        WHERE id = $14
         RETURNING 
             id, name, hospital_type, state, city, is_active, created_at, 
             latitude, longitude, total_beds, occupied_beds, has_emergency,
-            has_oxygen, has_ventilators, has_ambulance
+            has_oxygen, has_ventilators, has_ambulance, lga
         "#,
         payload.name,
         payload.hospital_type,
@@ -121,14 +132,46 @@ pub async fn update_hospital(
         payload.has_oxygen.unwrap_or(false),
         payload.has_ventilators.unwrap_or(false),
         payload.has_ambulance.unwrap_or(false),
+        payload.lga,
         id
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    record_occupancy(&mut tx, &hospital).await?;
+    outbox_repo::append(&mut tx, AGGREGATE_HOSPITAL, hospital.id, EVENT_HOSPITAL_UPDATED, &hospital).await?;
+    outbox_repo::append_capacity_changed(&mut tx, hospital.id).await?;
+    tx.commit().await?;
     Ok(hospital)
 }
 
+/// Appends a bed occupancy snapshot unless the counts are unchanged since the last one.
+async fn record_occupancy(tx: &mut Transaction<'_, Postgres>, hospital: &Hospital) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO bed_occupancy_snapshots (hospital_id, total_beds, occupied_beds)
+        SELECT $1, $2, $3
+        WHERE NOT EXISTS (
+            SELECT 1 FROM (
+                SELECT total_beds, occupied_beds
+                FROM bed_occupancy_snapshots
+                WHERE hospital_id = $1
+                ORDER BY recorded_at DESC
+                LIMIT 1
+            ) latest
+            WHERE latest.total_beds = $2 AND latest.occupied_beds = $3
+        )
+        "#,
+        hospital.id,
+        hospital.total_beds.unwrap_or(0),
+        hospital.occupied_beds
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn delete_hospital(pool: &PgPool, hospital_id: uuid::Uuid) -> Result<u64, sqlx::Error> {
     let result = sqlx::query!(
         "DELETE FROM hospitals WHERE id = $1",
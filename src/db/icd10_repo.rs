@@ -0,0 +1,69 @@
+use sqlx::PgPool;
+use crate::models::icd10::Icd10Code;
+
+// Bundled code table; edit the file and restart to change the codes available for coding
+const BUNDLED_CODES: &str = include_str!("../../data/icd10_codes.tsv");
+
+/// Parses `code<TAB>description` lines, skipping blanks and `#` comments.
+pub fn parse_codes(source: &str) -> Vec<(String, String)> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (code, description) = line.split_once('\t')?;
+            Some((code.trim().to_uppercase(), description.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Upserts the bundled code table. Unchanged rows are left alone so repeated startups are cheap.
+pub async fn load_bundled_codes(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let (codes, descriptions): (Vec<String>, Vec<String>) = parse_codes(BUNDLED_CODES).into_iter().unzip();
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO icd10_codes (code, description)
+        SELECT * FROM UNNEST($1::VARCHAR[], $2::TEXT[])
+        ON CONFLICT (code) DO UPDATE SET description = EXCLUDED.description
+        WHERE icd10_codes.description IS DISTINCT FROM EXCLUDED.description
+        "#,
+        &codes,
+        &descriptions
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn get_code(pool: &PgPool, code: &str) -> Result<Option<Icd10Code>, sqlx::Error> {
+    sqlx::query_as!(
+        Icd10Code,
+        "SELECT code, description, category FROM icd10_codes WHERE code = UPPER($1)",
+        code
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Autocomplete over codes (prefix match) and descriptions (substring match); code matches rank first.
+pub async fn search_codes(pool: &PgPool, term: &str, limit: i64) -> Result<Vec<Icd10Code>, sqlx::Error> {
+    // Treat LIKE wildcards in the search term literally
+    let term = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+
+    sqlx::query_as!(
+        Icd10Code,
+        r#"
+        SELECT code, description, category
+        FROM icd10_codes
+        WHERE code ILIKE $1 || '%' OR description ILIKE '%' || $1 || '%'
+        ORDER BY (code ILIKE $1 || '%') DESC, code ASC
+        LIMIT $2
+        "#,
+        term,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,200 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::db::outbox_repo;
+use crate::models::inventory::{
+    InventoryItem, CreateInventoryItemRequest, InventoryTransaction, CreateInventoryTransactionRequest,
+    CapabilitySummary,
+};
+
+pub async fn create_item(pool: &PgPool, hospital_id: Uuid, payload: CreateInventoryItemRequest) -> Result<InventoryItem, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let item = sqlx::query_as!(
+        InventoryItem,
+        r#"
+        INSERT INTO inventory_items (hospital_id, category, name, unit, quantity_on_hand, reorder_level)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, hospital_id, category, name, unit, quantity_on_hand, reorder_level, stock_status, created_at, updated_at
+        "#,
+        hospital_id,
+        payload.category,
+        payload.name,
+        payload.unit,
+        payload.quantity_on_hand.unwrap_or(0),
+        payload.reorder_level.unwrap_or(0)
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if item.category == "OXYGEN" {
+        sync_oxygen_flag(&mut tx, hospital_id).await?;
+        outbox_repo::append_capacity_changed(&mut tx, hospital_id).await?;
+    }
+
+    tx.commit().await?;
+    Ok(item)
+}
+
+pub async fn get_hospital_items(pool: &PgPool, hospital_id: Uuid, category: Option<String>) -> Result<Vec<InventoryItem>, sqlx::Error> {
+    sqlx::query_as!(
+        InventoryItem,
+        r#"
+        SELECT * FROM inventory_items
+        WHERE hospital_id = $1 AND ($2::VARCHAR IS NULL OR category = $2)
+        ORDER BY category ASC, name ASC
+        "#,
+        hospital_id,
+        category
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_low_stock_items(pool: &PgPool, hospital_id: Uuid) -> Result<Vec<InventoryItem>, sqlx::Error> {
+    sqlx::query_as!(
+        InventoryItem,
+        r#"
+        SELECT * FROM inventory_items
+        WHERE hospital_id = $1 AND stock_status <> 'OK'
+        ORDER BY quantity_on_hand ASC, name ASC
+        "#,
+        hospital_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Applies a stock movement and records it with the resulting balance.
+/// Returns `Ok(None)` if a STOCK_OUT would take the balance below zero, or a STOCK_IN would take it
+/// past what the column can hold.
+pub async fn record_transaction(
+    pool: &PgPool,
+    item_id: Uuid,
+    payload: CreateInventoryTransactionRequest,
+) -> Result<Option<(InventoryItem, InventoryTransaction)>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let item = sqlx::query_as!(
+        InventoryItem,
+        r#"
+        UPDATE inventory_items
+        SET quantity_on_hand = CASE $2
+                WHEN 'STOCK_IN' THEN quantity_on_hand + $3
+                WHEN 'STOCK_OUT' THEN quantity_on_hand - $3
+                ELSE $3
+            END,
+            updated_at = NOW()
+        WHERE id = $1
+          AND ($2 <> 'STOCK_OUT' OR quantity_on_hand >= $3)
+          AND ($2 <> 'STOCK_IN' OR quantity_on_hand <= 2147483647 - $3)
+        RETURNING id, hospital_id, category, name, unit, quantity_on_hand, reorder_level, stock_status, created_at, updated_at
+        "#,
+        item_id,
+        payload.transaction_type,
+        payload.quantity
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(item) = item else {
+        // Distinguish "no such item" from a rejected movement
+        sqlx::query!("SELECT id FROM inventory_items WHERE id = $1", item_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        return Ok(None);
+    };
+
+    let transaction = sqlx::query_as!(
+        InventoryTransaction,
+        r#"
+        INSERT INTO inventory_transactions (item_id, transaction_type, quantity, balance_after, note)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, item_id, transaction_type, quantity, balance_after, note, created_at
+        "#,
+        item.id,
+        payload.transaction_type,
+        payload.quantity,
+        item.quantity_on_hand,
+        payload.note
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if item.category == "OXYGEN" {
+        sync_oxygen_flag(&mut tx, item.hospital_id).await?;
+        outbox_repo::append_capacity_changed(&mut tx, item.hospital_id).await?;
+    }
+
+    tx.commit().await?;
+    Ok(Some((item, transaction)))
+}
+
+pub async fn get_item_transactions(pool: &PgPool, item_id: Uuid) -> Result<Vec<InventoryTransaction>, sqlx::Error> {
+    sqlx::query_as!(
+        InventoryTransaction,
+        "SELECT * FROM inventory_transactions WHERE item_id = $1 ORDER BY created_at DESC",
+        item_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Once a hospital tracks oxygen stock, `has_oxygen` follows it: true while any cylinders
+/// remain or an operational concentrator is on site.
+async fn sync_oxygen_flag(tx: &mut Transaction<'_, Postgres>, hospital_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE hospitals
+        SET has_oxygen = EXISTS (
+                SELECT 1 FROM inventory_items
+                WHERE hospital_id = $1 AND category = 'OXYGEN' AND quantity_on_hand > 0
+            ) OR EXISTS (
+                SELECT 1 FROM equipment
+                WHERE COALESCE(custodian_hospital_id, hospital_id) = $1
+                  AND equipment_type = 'OXYGEN_CONCENTRATOR'
+                  AND is_operational
+            )
+        WHERE id = $1
+        "#,
+        hospital_id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_capability_summary(pool: &PgPool, hospital_id: Uuid) -> Result<Option<CapabilitySummary>, sqlx::Error> {
+    sqlx::query_as!(
+        CapabilitySummary,
+        r#"
+        SELECT
+            h.id AS hospital_id, h.total_beds, h.occupied_beds, h.has_emergency,
+            h.has_oxygen, h.has_ventilators, h.has_ambulance,
+            COALESCE(o.status, 'UNKNOWN') AS "oxygen_status!",
+            COALESCE(s.low, 0) AS "low_stock_items!",
+            COALESCE(s.out, 0) AS "out_of_stock_items!"
+        FROM hospitals h
+        LEFT JOIN LATERAL (
+            SELECT CASE
+                WHEN SUM(quantity_on_hand) = 0 THEN 'OUT'
+                WHEN SUM(quantity_on_hand) <= SUM(reorder_level) THEN 'LOW'
+                ELSE 'OK'
+            END AS status
+            FROM inventory_items
+            WHERE hospital_id = h.id AND category = 'OXYGEN'
+            HAVING COUNT(*) > 0
+        ) o ON TRUE
+        LEFT JOIN LATERAL (
+            SELECT
+                COUNT(*) FILTER (WHERE stock_status = 'LOW') AS low,
+                COUNT(*) FILTER (WHERE stock_status = 'OUT') AS out
+            FROM inventory_items
+            WHERE hospital_id = h.id
+        ) s ON TRUE
+        WHERE h.id = $1
+        "#,
+        hospital_id
+    )
+    .fetch_optional(pool)
+    .await
+}
@@ -0,0 +1,242 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::lab::{
+    LabTestType, CreateLabTestTypeRequest, LabOrder, CreateLabOrderRequest, EnterLabResultRequest,
+};
+
+pub async fn get_test_types(pool: &PgPool) -> Result<Vec<LabTestType>, sqlx::Error> {
+    sqlx::query_as!(LabTestType, "SELECT * FROM lab_test_types ORDER BY name ASC")
+        .fetch_all(pool)
+        .await
+}
+
+pub async fn get_test_type(pool: &PgPool, test_type_id: Uuid) -> Result<Option<LabTestType>, sqlx::Error> {
+    sqlx::query_as!(LabTestType, "SELECT * FROM lab_test_types WHERE id = $1", test_type_id)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn create_test_type(pool: &PgPool, payload: CreateLabTestTypeRequest) -> Result<LabTestType, sqlx::Error> {
+    sqlx::query_as!(
+        LabTestType,
+        r#"
+        INSERT INTO lab_test_types (code, name, specimen_type, result_type, unit, reference_low, reference_high, normal_text)
+        VALUES (UPPER($1), $2, $3, $4, $5, $6, $7, UPPER($8))
+        RETURNING id, code, name, specimen_type, result_type, unit, reference_low, reference_high, normal_text, created_at
+        "#,
+        payload.code,
+        payload.name,
+        payload.specimen_type,
+        payload.result_type,
+        payload.unit,
+        payload.reference_low,
+        payload.reference_high,
+        payload.normal_text
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Places an order on a visit; the patient and hospital come from the visit.
+pub async fn create_order(
+    pool: &PgPool,
+    visit_id: Uuid,
+    test: &LabTestType,
+    payload: CreateLabOrderRequest,
+) -> Result<LabOrder, sqlx::Error> {
+    sqlx::query_as!(
+        LabOrder,
+        r#"
+        WITH o AS (
+            INSERT INTO lab_orders (visit_id, patient_id, hospital_id, test_type_id, specimen_type, priority, clinical_notes, ordered_by)
+            SELECT v.id, v.patient_id, v.hospital_id, $2, $3, COALESCE($4, 'ROUTINE'), $5, $6
+            FROM visits v
+            WHERE v.id = $1
+            RETURNING *
+        )
+        SELECT
+            o.id, o.visit_id, o.patient_id, o.hospital_id, o.test_type_id, t.code AS test_code, t.name AS test_name,
+            o.specimen_type, o.priority, o.clinical_notes, o.ordered_by, o.status, o.collected_by, o.collected_at,
+            o.result_value, o.result_text, o.result_unit, o.reference_low, o.reference_high, o.abnormal_flag,
+            o.result_notes, o.resulted_by, o.resulted_at, o.verified_by, o.verified_at, o.created_at, o.updated_at
+        FROM o
+        JOIN lab_test_types t ON t.id = o.test_type_id
+        "#,
+        visit_id,
+        test.id,
+        payload.specimen_type.unwrap_or_else(|| test.specimen_type.clone()),
+        payload.priority,
+        payload.clinical_notes,
+        payload.ordered_by
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_order(pool: &PgPool, order_id: Uuid) -> Result<Option<LabOrder>, sqlx::Error> {
+    sqlx::query_as!(
+        LabOrder,
+        r#"
+        SELECT
+            o.id, o.visit_id, o.patient_id, o.hospital_id, o.test_type_id, t.code AS test_code, t.name AS test_name,
+            o.specimen_type, o.priority, o.clinical_notes, o.ordered_by, o.status, o.collected_by, o.collected_at,
+            o.result_value, o.result_text, o.result_unit, o.reference_low, o.reference_high, o.abnormal_flag,
+            o.result_notes, o.resulted_by, o.resulted_at, o.verified_by, o.verified_at, o.created_at, o.updated_at
+        FROM lab_orders o
+        JOIN lab_test_types t ON t.id = o.test_type_id
+        WHERE o.id = $1
+        "#,
+        order_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn get_visit_orders(pool: &PgPool, visit_id: Uuid) -> Result<Vec<LabOrder>, sqlx::Error> {
+    sqlx::query_as!(
+        LabOrder,
+        r#"
+        SELECT
+            o.id, o.visit_id, o.patient_id, o.hospital_id, o.test_type_id, t.code AS test_code, t.name AS test_name,
+            o.specimen_type, o.priority, o.clinical_notes, o.ordered_by, o.status, o.collected_by, o.collected_at,
+            o.result_value, o.result_text, o.result_unit, o.reference_low, o.reference_high, o.abnormal_flag,
+            o.result_notes, o.resulted_by, o.resulted_at, o.verified_by, o.verified_at, o.created_at, o.updated_at
+        FROM lab_orders o
+        JOIN lab_test_types t ON t.id = o.test_type_id
+        WHERE o.visit_id = $1
+        ORDER BY o.created_at ASC
+        "#,
+        visit_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Unverified orders for a hospital, most urgent first, then oldest first.
+pub async fn get_pending_queue(pool: &PgPool, hospital_id: Uuid, status: Option<String>) -> Result<Vec<LabOrder>, sqlx::Error> {
+    sqlx::query_as!(
+        LabOrder,
+        r#"
+        SELECT
+            o.id, o.visit_id, o.patient_id, o.hospital_id, o.test_type_id, t.code AS test_code, t.name AS test_name,
+            o.specimen_type, o.priority, o.clinical_notes, o.ordered_by, o.status, o.collected_by, o.collected_at,
+            o.result_value, o.result_text, o.result_unit, o.reference_low, o.reference_high, o.abnormal_flag,
+            o.result_notes, o.resulted_by, o.resulted_at, o.verified_by, o.verified_at, o.created_at, o.updated_at
+        FROM lab_orders o
+        JOIN lab_test_types t ON t.id = o.test_type_id
+        WHERE o.hospital_id = $1
+          AND o.status <> 'VERIFIED'
+          AND ($2::VARCHAR IS NULL OR o.status = $2)
+        ORDER BY
+            CASE o.priority WHEN 'STAT' THEN 0 WHEN 'URGENT' THEN 1 ELSE 2 END ASC,
+            o.created_at ASC
+        "#,
+        hospital_id,
+        status
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// ORDERED -> COLLECTED. Returns `Ok(None)` if the order is not awaiting collection.
+pub async fn collect_specimen(pool: &PgPool, order_id: Uuid, collected_by: Uuid) -> Result<Option<LabOrder>, sqlx::Error> {
+    sqlx::query_as!(
+        LabOrder,
+        r#"
+        WITH o AS (
+            UPDATE lab_orders
+            SET status = 'COLLECTED', collected_by = $2, collected_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status = 'ORDERED'
+            RETURNING *
+        )
+        SELECT
+            o.id, o.visit_id, o.patient_id, o.hospital_id, o.test_type_id, t.code AS test_code, t.name AS test_name,
+            o.specimen_type, o.priority, o.clinical_notes, o.ordered_by, o.status, o.collected_by, o.collected_at,
+            o.result_value, o.result_text, o.result_unit, o.reference_low, o.reference_high, o.abnormal_flag,
+            o.result_notes, o.resulted_by, o.resulted_at, o.verified_by, o.verified_at, o.created_at, o.updated_at
+        FROM o
+        JOIN lab_test_types t ON t.id = o.test_type_id
+        "#,
+        order_id,
+        collected_by
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// COLLECTED -> RESULTED, snapshotting the test's unit and reference range onto the order.
+/// Returns `Ok(None)` if no specimen has been collected yet (or it was already resulted).
+pub async fn enter_result(
+    pool: &PgPool,
+    order_id: Uuid,
+    test: &LabTestType,
+    payload: &EnterLabResultRequest,
+    flag: Option<&str>,
+) -> Result<Option<LabOrder>, sqlx::Error> {
+    sqlx::query_as!(
+        LabOrder,
+        r#"
+        WITH o AS (
+            UPDATE lab_orders
+            SET status = 'RESULTED',
+                result_value = $2,
+                result_text = UPPER($3),
+                result_unit = $4,
+                reference_low = $5,
+                reference_high = $6,
+                abnormal_flag = $7,
+                result_notes = $8,
+                resulted_by = $9,
+                resulted_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1 AND status = 'COLLECTED'
+            RETURNING *
+        )
+        SELECT
+            o.id, o.visit_id, o.patient_id, o.hospital_id, o.test_type_id, t.code AS test_code, t.name AS test_name,
+            o.specimen_type, o.priority, o.clinical_notes, o.ordered_by, o.status, o.collected_by, o.collected_at,
+            o.result_value, o.result_text, o.result_unit, o.reference_low, o.reference_high, o.abnormal_flag,
+            o.result_notes, o.resulted_by, o.resulted_at, o.verified_by, o.verified_at, o.created_at, o.updated_at
+        FROM o
+        JOIN lab_test_types t ON t.id = o.test_type_id
+        "#,
+        order_id,
+        payload.value,
+        payload.text,
+        test.unit,
+        test.reference_low,
+        test.reference_high,
+        flag,
+        payload.notes,
+        payload.resulted_by
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// RESULTED -> VERIFIED by a second person. Returns `Ok(None)` if the order is not awaiting
+/// verification or the verifier entered the result themselves.
+pub async fn verify_result(pool: &PgPool, order_id: Uuid, verified_by: Uuid) -> Result<Option<LabOrder>, sqlx::Error> {
+    sqlx::query_as!(
+        LabOrder,
+        r#"
+        WITH o AS (
+            UPDATE lab_orders
+            SET status = 'VERIFIED', verified_by = $2, verified_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status = 'RESULTED' AND resulted_by <> $2
+            RETURNING *
+        )
+        SELECT
+            o.id, o.visit_id, o.patient_id, o.hospital_id, o.test_type_id, t.code AS test_code, t.name AS test_name,
+            o.specimen_type, o.priority, o.clinical_notes, o.ordered_by, o.status, o.collected_by, o.collected_at,
+            o.result_value, o.result_text, o.result_unit, o.reference_low, o.reference_high, o.abnormal_flag,
+            o.result_notes, o.resulted_by, o.resulted_at, o.verified_by, o.verified_at, o.created_at, o.updated_at
+        FROM o
+        JOIN lab_test_types t ON t.id = o.test_type_id
+        "#,
+        order_id,
+        verified_by
+    )
+    .fetch_optional(pool)
+    .await
+}
@@ -0,0 +1,126 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::maintenance::{
+    MaintenancePlan, CreateMaintenancePlanRequest, WorkOrder, CreateWorkOrderRequest, OverdueMaintenance,
+};
+
+pub async fn create_plan(pool: &PgPool, equipment_id: Uuid, payload: CreateMaintenancePlanRequest) -> Result<MaintenancePlan, sqlx::Error> {
+    sqlx::query_as!(
+        MaintenancePlan,
+        r#"
+        INSERT INTO maintenance_plans (equipment_id, task, interval_days, last_performed_on, next_due_on)
+        VALUES ($1, $2, $3, $4, COALESCE($4::DATE + $3::INT, CURRENT_DATE))
+        RETURNING id, equipment_id, task, interval_days, last_performed_on, next_due_on, created_at
+        "#,
+        equipment_id,
+        payload.task,
+        payload.interval_days,
+        payload.last_performed_on
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_equipment_plans(pool: &PgPool, equipment_id: Uuid) -> Result<Vec<MaintenancePlan>, sqlx::Error> {
+    sqlx::query_as!(
+        MaintenancePlan,
+        "SELECT * FROM maintenance_plans WHERE equipment_id = $1 ORDER BY next_due_on ASC",
+        equipment_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Opens a work order. A `plan_id` belonging to different equipment matches no row (`RowNotFound`).
+pub async fn create_work_order(pool: &PgPool, equipment_id: Uuid, payload: CreateWorkOrderRequest) -> Result<WorkOrder, sqlx::Error> {
+    sqlx::query_as!(
+        WorkOrder,
+        r#"
+        INSERT INTO work_orders (equipment_id, plan_id, description)
+        SELECT $1, $2, $3
+        WHERE $2::UUID IS NULL
+           OR EXISTS (SELECT 1 FROM maintenance_plans WHERE id = $2 AND equipment_id = $1)
+        RETURNING id, equipment_id, plan_id, description, status, resolution_notes, opened_at, closed_at
+        "#,
+        equipment_id,
+        payload.plan_id,
+        payload.description
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Closes an OPEN work order; if it belongs to a plan, the plan's schedule is advanced from today.
+/// Returns `Ok(None)` if the order is already closed.
+pub async fn close_work_order(pool: &PgPool, work_order_id: Uuid, resolution_notes: &str) -> Result<Option<WorkOrder>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let order = sqlx::query_as!(
+        WorkOrder,
+        r#"
+        UPDATE work_orders
+        SET status = 'CLOSED', resolution_notes = $2, closed_at = NOW()
+        WHERE id = $1 AND status = 'OPEN'
+        RETURNING id, equipment_id, plan_id, description, status, resolution_notes, opened_at, closed_at
+        "#,
+        work_order_id,
+        resolution_notes
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(order) = order else {
+        return Ok(None);
+    };
+
+    if let Some(plan_id) = order.plan_id {
+        sqlx::query!(
+            r#"
+            UPDATE maintenance_plans
+            SET last_performed_on = CURRENT_DATE, next_due_on = CURRENT_DATE + interval_days
+            WHERE id = $1
+            "#,
+            plan_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(Some(order))
+}
+
+pub async fn get_hospital_work_orders(pool: &PgPool, hospital_id: Uuid, status: Option<String>) -> Result<Vec<WorkOrder>, sqlx::Error> {
+    sqlx::query_as!(
+        WorkOrder,
+        r#"
+        SELECT w.id, w.equipment_id, w.plan_id, w.description, w.status, w.resolution_notes, w.opened_at, w.closed_at
+        FROM work_orders w
+        JOIN equipment e ON e.id = w.equipment_id
+        WHERE e.hospital_id = $1 AND ($2::VARCHAR IS NULL OR w.status = $2)
+        ORDER BY w.opened_at DESC
+        "#,
+        hospital_id,
+        status
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_overdue_maintenance(pool: &PgPool, hospital_id: Uuid) -> Result<Vec<OverdueMaintenance>, sqlx::Error> {
+    sqlx::query_as!(
+        OverdueMaintenance,
+        r#"
+        SELECT
+            p.id AS plan_id, e.id AS equipment_id, e.name AS equipment_name, e.serial_number,
+            p.task, p.next_due_on, (CURRENT_DATE - p.next_due_on) AS "days_overdue!"
+        FROM maintenance_plans p
+        JOIN equipment e ON e.id = p.equipment_id
+        WHERE e.hospital_id = $1 AND p.next_due_on < CURRENT_DATE
+        ORDER BY p.next_due_on ASC
+        "#,
+        hospital_id
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -3,8 +3,34 @@ pub mod hospital_repo;
 pub mod user_repo;
 pub mod department_repo;
 pub mod staff_repo;
+pub mod staff_account_repo;
 pub mod patient_repo;
 pub mod visit_repo;
 pub mod equipment_repo;
+pub mod shift_repo;
+pub mod credential_repo;
+pub mod appointment_repo;
+pub mod maintenance_repo;
+pub mod equipment_transfer_repo;
+pub mod inventory_repo;
+pub mod blood_bank_repo;
+pub mod diagnosis_repo;
+pub mod icd10_repo;
+pub mod observation_repo;
+pub mod prescription_repo;
+pub mod lab_repo;
+pub mod allergy_repo;
+pub mod problem_repo;
+pub mod surveillance_repo;
+pub mod outbreak_repo;
+pub mod timeline_repo;
+pub mod consent_repo;
+pub mod public_repo;
+pub mod analytics_repo;
+pub mod forecast_repo;
 
 pub use pool::create_pool;
+pub mod capacity_alert_repo;
+pub mod webhook_repo;
+pub mod outbox_repo;
+pub mod notification_repo;
@@ -0,0 +1,284 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::notification::{
+    DueNotification, NewNotification, Notification, NotificationPreference, NotificationQuery, NotificationRecipient,
+    NotificationTemplate, UpdateNotificationPreferenceRequest, UpsertNotificationTemplateRequest,
+};
+
+pub async fn upsert_template(
+    pool: &PgPool,
+    payload: UpsertNotificationTemplateRequest,
+) -> Result<NotificationTemplate, sqlx::Error> {
+    sqlx::query_as!(
+        NotificationTemplate,
+        r#"
+        INSERT INTO notification_templates (template_key, channel, subject, body)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (template_key, channel) DO UPDATE
+        SET subject = EXCLUDED.subject, body = EXCLUDED.body, updated_at = NOW()
+        RETURNING *
+        "#,
+        payload.template_key,
+        payload.channel,
+        payload.subject,
+        payload.body
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_templates(pool: &PgPool) -> Result<Vec<NotificationTemplate>, sqlx::Error> {
+    sqlx::query_as!(
+        NotificationTemplate,
+        "SELECT * FROM notification_templates ORDER BY template_key, channel"
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_template(
+    pool: &PgPool,
+    template_key: &str,
+    channel: &str,
+) -> Result<Option<NotificationTemplate>, sqlx::Error> {
+    sqlx::query_as!(
+        NotificationTemplate,
+        "SELECT * FROM notification_templates WHERE template_key = $1 AND channel = $2",
+        template_key,
+        channel
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// A staff member's stored preferences, or the defaults (every channel on, nothing muted) if
+/// they have never set any. `Ok(None)` if the staff member does not exist.
+pub async fn get_preference(pool: &PgPool, staff_id: Uuid) -> Result<Option<NotificationPreference>, sqlx::Error> {
+    sqlx::query_as!(
+        NotificationPreference,
+        r#"
+        SELECT s.id AS "staff_id!",
+               COALESCE(p.email_enabled, TRUE) AS "email_enabled!",
+               COALESCE(p.sms_enabled, TRUE) AS "sms_enabled!",
+               COALESCE(p.muted_templates, '{}') AS "muted_templates!",
+               COALESCE(p.updated_at, s.created_at) AS "updated_at!"
+        FROM staff s
+        LEFT JOIN notification_preferences p ON p.staff_id = s.id
+        WHERE s.id = $1
+        "#,
+        staff_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Returns `Ok(None)` if the staff member does not exist
+pub async fn upsert_preference(
+    pool: &PgPool,
+    staff_id: Uuid,
+    payload: UpdateNotificationPreferenceRequest,
+) -> Result<Option<NotificationPreference>, sqlx::Error> {
+    sqlx::query_as!(
+        NotificationPreference,
+        r#"
+        INSERT INTO notification_preferences (staff_id, email_enabled, sms_enabled, muted_templates)
+        SELECT id, $2, $3, $4 FROM staff WHERE id = $1
+        ON CONFLICT (staff_id) DO UPDATE
+        SET email_enabled = EXCLUDED.email_enabled,
+            sms_enabled = EXCLUDED.sms_enabled,
+            muted_templates = EXCLUDED.muted_templates,
+            updated_at = NOW()
+        RETURNING *
+        "#,
+        staff_id,
+        payload.email_enabled,
+        payload.sms_enabled,
+        &payload.muted_templates
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn get_recipient(pool: &PgPool, staff_id: Uuid) -> Result<Option<NotificationRecipient>, sqlx::Error> {
+    sqlx::query_as!(
+        NotificationRecipient,
+        r#"
+        SELECT s.id AS staff_id, s.email, s.contact_phone, s.is_active,
+               COALESCE(p.email_enabled, TRUE) AS "email_enabled!",
+               COALESCE(p.sms_enabled, TRUE) AS "sms_enabled!",
+               COALESCE(p.muted_templates, '{}') AS "muted_templates!"
+        FROM staff s
+        LEFT JOIN notification_preferences p ON p.staff_id = s.id
+        WHERE s.id = $1
+        "#,
+        staff_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Queues a notification. Returns `Ok(None)` if one with the same dedupe key, channel and
+/// recipient is already queued or sent.
+pub async fn enqueue(pool: &PgPool, notification: NewNotification) -> Result<Option<Notification>, sqlx::Error> {
+    sqlx::query_as!(
+        Notification,
+        r#"
+        INSERT INTO notifications (staff_id, template_key, channel, recipient, subject, body, dedupe_key)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (dedupe_key, channel, recipient) WHERE dedupe_key IS NOT NULL DO NOTHING
+        RETURNING id, staff_id, template_key, channel, recipient, subject, body, status, attempts,
+                  next_attempt_at, provider, provider_message_id, last_error, created_at, sent_at, updated_at
+        "#,
+        notification.staff_id,
+        notification.template_key,
+        notification.channel,
+        notification.recipient,
+        notification.subject,
+        notification.body,
+        notification.dedupe_key
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Claims up to `limit` due notifications (optionally only those in `only`) by pushing their
+/// next attempt `lease_secs` out, so a concurrent dispatcher skips them while they are sent.
+pub async fn claim_due(
+    pool: &PgPool,
+    only: Option<&[Uuid]>,
+    limit: i64,
+    lease_secs: f64,
+) -> Result<Vec<DueNotification>, sqlx::Error> {
+    sqlx::query_as!(
+        DueNotification,
+        r#"
+        UPDATE notifications
+        SET next_attempt_at = NOW() + make_interval(secs => $3), updated_at = NOW()
+        WHERE id IN (
+            SELECT id FROM notifications
+            WHERE status = 'PENDING' AND next_attempt_at <= NOW()
+              AND ($1::UUID[] IS NULL OR id = ANY($1))
+            ORDER BY next_attempt_at ASC
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, channel, recipient, subject, body, attempts
+        "#,
+        only,
+        limit,
+        lease_secs
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn mark_sent(
+    pool: &PgPool,
+    notification_id: Uuid,
+    provider: &str,
+    provider_message_id: Option<String>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE notifications
+        SET status = 'SENT', attempts = attempts + 1, provider = $2, provider_message_id = $3,
+            last_error = NULL, sent_at = NOW(), updated_at = NOW()
+        WHERE id = $1
+        "#,
+        notification_id,
+        provider,
+        provider_message_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Records a failed attempt. With `retry_at` the notification stays PENDING until then;
+/// without, it is given up as FAILED.
+pub async fn mark_attempt_failed(
+    pool: &PgPool,
+    notification_id: Uuid,
+    provider: &str,
+    error: &str,
+    retry_at: Option<DateTime<Utc>>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE notifications
+        SET status = CASE WHEN $4::TIMESTAMPTZ IS NULL THEN 'FAILED' ELSE 'PENDING' END,
+            attempts = attempts + 1,
+            provider = $2,
+            last_error = $3,
+            next_attempt_at = COALESCE($4, next_attempt_at),
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+        notification_id,
+        provider,
+        error,
+        retry_at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_notification(pool: &PgPool, notification_id: Uuid) -> Result<Option<Notification>, sqlx::Error> {
+    sqlx::query_as!(
+        Notification,
+        r#"
+        SELECT id, staff_id, template_key, channel, recipient, subject, body, status, attempts,
+               next_attempt_at, provider, provider_message_id, last_error, created_at, sent_at, updated_at
+        FROM notifications WHERE id = $1
+        "#,
+        notification_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Delivery log, newest first
+pub async fn get_notifications(pool: &PgPool, query: NotificationQuery) -> Result<Vec<Notification>, sqlx::Error> {
+    let limit = query.limit.unwrap_or(100).clamp(1, 500);
+    sqlx::query_as!(
+        Notification,
+        r#"
+        SELECT id, staff_id, template_key, channel, recipient, subject, body, status, attempts,
+               next_attempt_at, provider, provider_message_id, last_error, created_at, sent_at, updated_at
+        FROM notifications
+        WHERE ($1::VARCHAR IS NULL OR status = $1)
+          AND ($2::UUID IS NULL OR staff_id = $2)
+          AND ($3::VARCHAR IS NULL OR template_key = $3)
+        ORDER BY created_at DESC
+        LIMIT $4
+        "#,
+        query.status,
+        query.staff_id,
+        query.template_key,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Puts a FAILED notification back in the queue with a fresh set of attempts. Returns
+/// `Ok(None)` if it does not exist or has not failed.
+pub async fn retry(pool: &PgPool, notification_id: Uuid) -> Result<Option<Notification>, sqlx::Error> {
+    sqlx::query_as!(
+        Notification,
+        r#"
+        UPDATE notifications
+        SET status = 'PENDING', attempts = 0, next_attempt_at = NOW(), updated_at = NOW()
+        WHERE id = $1 AND status = 'FAILED'
+        RETURNING id, staff_id, template_key, channel, recipient, subject, body, status, attempts,
+                  next_attempt_at, provider, provider_message_id, last_error, created_at, sent_at, updated_at
+        "#,
+        notification_id
+    )
+    .fetch_optional(pool)
+    .await
+}
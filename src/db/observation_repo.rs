@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::crypto::pii::PiiCipher;
+use crate::db::patient_repo;
+use crate::models::observation::{
+    ObservationSet, Observation, ObservationSetResponse, RecordObservationsRequest, TrendPoint, DeterioratingPatient,
+};
+
+/// Stores an observation set and its measurements (already converted to canonical units).
+/// The set inherits the patient and hospital from the visit; the recording staff member must work there.
+pub async fn record_observations(
+    pool: &PgPool,
+    visit_id: Uuid,
+    payload: &RecordObservationsRequest,
+    measurements: &[(String, f64, &str)],
+    news2: Option<(i32, &str)>,
+) -> Result<ObservationSetResponse, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let set = sqlx::query_as!(
+        ObservationSet,
+        r#"
+        INSERT INTO observation_sets (
+            visit_id, patient_id, hospital_id, recorded_by, recorded_at,
+            on_supplemental_oxygen, consciousness, news2_score, news2_risk
+        )
+        SELECT v.id, v.patient_id, v.hospital_id, s.id, COALESCE($3, NOW()), $4, $5, $6, $7
+        FROM visits v
+        JOIN staff s ON s.id = $2 AND s.hospital_id = v.hospital_id
+        WHERE v.id = $1
+        RETURNING id, visit_id, patient_id, hospital_id, recorded_by, recorded_at,
+                  on_supplemental_oxygen, consciousness, news2_score, news2_risk, created_at
+        "#,
+        visit_id,
+        payload.staff_id,
+        payload.recorded_at,
+        payload.on_supplemental_oxygen,
+        payload.consciousness,
+        news2.map(|(score, _)| score),
+        news2.map(|(_, risk)| risk)
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let types: Vec<String> = measurements.iter().map(|(t, _, _)| t.clone()).collect();
+    let values: Vec<f64> = measurements.iter().map(|(_, v, _)| *v).collect();
+    let units: Vec<String> = measurements.iter().map(|(_, _, u)| u.to_string()).collect();
+
+    let measurements = sqlx::query_as!(
+        Observation,
+        r#"
+        INSERT INTO observations (observation_set_id, patient_id, observation_type, value, unit, recorded_at)
+        SELECT $1, $2, m.observation_type, m.value, m.unit, $3
+        FROM UNNEST($4::VARCHAR[], $5::FLOAT8[], $6::VARCHAR[]) AS m(observation_type, value, unit)
+        RETURNING id, observation_set_id, observation_type, value, unit, recorded_at
+        "#,
+        set.id,
+        set.patient_id,
+        set.recorded_at,
+        &types,
+        &values,
+        &units
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(ObservationSetResponse { set, measurements })
+}
+
+pub async fn get_visit_observations(pool: &PgPool, visit_id: Uuid) -> Result<Vec<ObservationSetResponse>, sqlx::Error> {
+    let sets = sqlx::query_as!(
+        ObservationSet,
+        "SELECT * FROM observation_sets WHERE visit_id = $1 ORDER BY recorded_at ASC",
+        visit_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let measurements = sqlx::query_as!(
+        Observation,
+        r#"
+        SELECT o.id, o.observation_set_id, o.observation_type, o.value, o.unit, o.recorded_at
+        FROM observations o
+        JOIN observation_sets os ON os.id = o.observation_set_id
+        WHERE os.visit_id = $1
+        ORDER BY o.observation_type ASC
+        "#,
+        visit_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_set: HashMap<Uuid, Vec<Observation>> = HashMap::new();
+    for m in measurements {
+        by_set.entry(m.observation_set_id).or_default().push(m);
+    }
+
+    Ok(sets
+        .into_iter()
+        .map(|set| {
+            let measurements = by_set.remove(&set.id).unwrap_or_default();
+            ObservationSetResponse { set, measurements }
+        })
+        .collect())
+}
+
+/// Time series of one measurement type for a patient. `NEWS2` returns the aggregate score of each complete set.
+/// With `hospital_id`, only sets recorded at that hospital are included.
+pub async fn get_patient_trend(
+    pool: &PgPool,
+    patient_id: Uuid,
+    hospital_id: Option<Uuid>,
+    observation_type: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<TrendPoint>, sqlx::Error> {
+    if observation_type == "NEWS2" {
+        return sqlx::query_as!(
+            TrendPoint,
+            r#"
+            SELECT id AS observation_set_id, visit_id, recorded_at, news2_score::FLOAT8 AS "value!", 'score' AS "unit!"
+            FROM observation_sets
+            WHERE patient_id = $1 AND news2_score IS NOT NULL AND recorded_at >= $2 AND recorded_at < $3
+              AND ($4::UUID IS NULL OR hospital_id = $4)
+            ORDER BY recorded_at ASC
+            "#,
+            patient_id,
+            from,
+            to,
+            hospital_id
+        )
+        .fetch_all(pool)
+        .await;
+    }
+
+    sqlx::query_as!(
+        TrendPoint,
+        r#"
+        SELECT o.observation_set_id, os.visit_id, o.recorded_at, o.value, o.unit
+        FROM observations o
+        JOIN observation_sets os ON os.id = o.observation_set_id
+        WHERE o.patient_id = $1 AND o.observation_type = $2 AND o.recorded_at >= $3 AND o.recorded_at < $4
+          AND ($5::UUID IS NULL OR os.hospital_id = $5)
+        ORDER BY o.recorded_at ASC
+        "#,
+        patient_id,
+        observation_type,
+        from,
+        to,
+        hospital_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Patients whose latest scored set since `since` is above LOW risk, or whose score rose by 3 or more
+/// since their previous set at this hospital.
+pub async fn get_deteriorating_patients(
+    pool: &PgPool,
+    cipher: &PiiCipher,
+    hospital_id: Uuid,
+    since: DateTime<Utc>,
+) -> Result<Vec<DeterioratingPatient>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        WITH scored AS (
+            SELECT
+                os.*,
+                LAG(os.news2_score) OVER (PARTITION BY os.patient_id ORDER BY os.recorded_at) AS previous_score,
+                ROW_NUMBER() OVER (PARTITION BY os.patient_id ORDER BY os.recorded_at DESC) AS rn
+            FROM observation_sets os
+            WHERE os.hospital_id = $1 AND os.news2_score IS NOT NULL
+        )
+        SELECT
+            s.patient_id, p.first_name, p.last_name, p.pii_key_version, s.visit_id, s.id AS observation_set_id,
+            s.recorded_at, s.news2_score AS "news2_score!", s.news2_risk AS "news2_risk!",
+            s.news2_score - s.previous_score AS score_change
+        FROM scored s
+        JOIN patients p ON p.id = s.patient_id
+        WHERE s.rn = 1
+          AND s.recorded_at >= $2
+          AND (s.news2_risk <> 'LOW' OR s.news2_score - s.previous_score >= 3)
+        ORDER BY s.news2_score DESC, s.recorded_at DESC
+        "#,
+        hospital_id,
+        since
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|r| {
+            let (first_name, last_name) =
+                patient_repo::decrypt_name(cipher, r.patient_id, r.pii_key_version, &r.first_name, &r.last_name)?;
+            Ok(DeterioratingPatient {
+                patient_id: r.patient_id,
+                first_name,
+                last_name,
+                visit_id: r.visit_id,
+                observation_set_id: r.observation_set_id,
+                recorded_at: r.recorded_at,
+                news2_score: r.news2_score,
+                news2_risk: r.news2_risk,
+                score_change: r.score_change,
+            })
+        })
+        .collect()
+}
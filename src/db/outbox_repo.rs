@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::models::{
+    outbox::{CapacityChanged, OutboxEvent, OutboxStatus, ParkedOutboxEvent, AGGREGATE_HOSPITAL},
+    webhook::EVENT_CAPACITY_CHANGED,
+};
+
+// Key for the advisory lock that lets one dispatcher at a time relay events, across processes
+const DISPATCH_LOCK_KEY: i64 = 0x6f7574626f78; // "outbox"
+
+/// Records an event in the caller's transaction, so it is published if and only if the change
+/// that produced it commits.
+pub async fn append<T: Serialize>(
+    tx: &mut Transaction<'_, Postgres>,
+    aggregate_type: &str,
+    aggregate_id: Uuid,
+    event_type: &str,
+    data: &T,
+) -> Result<(), sqlx::Error> {
+    let payload = serde_json::to_string(data).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+    sqlx::query!(
+        r#"
+        INSERT INTO outbox_events (aggregate_type, aggregate_id, event_type, payload)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        aggregate_type,
+        aggregate_id,
+        event_type,
+        payload
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Records that beds, oxygen stock or equipment changed at a hospital.
+pub async fn append_capacity_changed(tx: &mut Transaction<'_, Postgres>, hospital_id: Uuid) -> Result<(), sqlx::Error> {
+    append(tx, AGGREGATE_HOSPITAL, hospital_id, EVENT_CAPACITY_CHANGED, &CapacityChanged { hospital_id }).await
+}
+
+/// Takes the dispatcher lock for the rest of the transaction, waiting for any other dispatcher.
+pub async fn lock_dispatch(tx: &mut Transaction<'_, Postgres>) -> Result<(), sqlx::Error> {
+    sqlx::query!("SELECT pg_advisory_xact_lock($1)", DISPATCH_LOCK_KEY)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// The oldest events due for relay, in commit order. An event waiting out a retry back-off
+/// holds back the later events for its aggregate, so those are skipped rather than filling the
+/// batch; parked events hold nothing back.
+pub async fn get_unpublished(tx: &mut Transaction<'_, Postgres>, limit: i64) -> Result<Vec<OutboxEvent>, sqlx::Error> {
+    sqlx::query_as!(
+        OutboxEvent,
+        r#"
+        SELECT e.id, e.event_id, e.aggregate_type, e.aggregate_id, e.event_type, e.payload, e.created_at, e.attempts
+        FROM outbox_events e
+        WHERE e.published_at IS NULL
+          AND e.parked_at IS NULL
+          AND e.next_attempt_at <= NOW()
+          AND NOT EXISTS (
+              SELECT 1 FROM outbox_events earlier
+              WHERE earlier.aggregate_id = e.aggregate_id
+                AND earlier.id < e.id
+                AND earlier.published_at IS NULL
+                AND earlier.parked_at IS NULL
+                AND earlier.next_attempt_at > NOW()
+          )
+        ORDER BY e.id ASC
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(&mut **tx)
+    .await
+}
+
+pub async fn mark_published(tx: &mut Transaction<'_, Postgres>, ids: &[i64]) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE outbox_events SET published_at = NOW(), attempts = attempts + 1, last_error = NULL WHERE id = ANY($1)",
+        ids
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Records a failed attempt: retried at `retry_at`, or parked when that is `None`.
+pub async fn mark_failed(
+    tx: &mut Transaction<'_, Postgres>,
+    id: i64,
+    error: &str,
+    retry_at: Option<DateTime<Utc>>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE outbox_events
+        SET attempts = attempts + 1,
+            last_error = $2,
+            next_attempt_at = COALESCE($3, next_attempt_at),
+            parked_at = CASE WHEN $3::TIMESTAMPTZ IS NULL THEN NOW() END
+        WHERE id = $1
+        "#,
+        id,
+        error,
+        retry_at
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_status(pool: &PgPool) -> Result<OutboxStatus, sqlx::Error> {
+    sqlx::query_as!(
+        OutboxStatus,
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE parked_at IS NULL) AS "pending!",
+            COUNT(*) FILTER (WHERE parked_at IS NULL AND attempts > 0) AS "failing!",
+            COUNT(*) FILTER (WHERE parked_at IS NOT NULL) AS "parked!",
+            MIN(created_at) FILTER (WHERE parked_at IS NULL) AS oldest_pending_at
+        FROM outbox_events
+        WHERE published_at IS NULL
+        "#
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Parked events, most recently parked first.
+pub async fn get_parked(pool: &PgPool) -> Result<Vec<ParkedOutboxEvent>, sqlx::Error> {
+    sqlx::query_as!(
+        ParkedOutboxEvent,
+        r#"
+        SELECT id, event_id, aggregate_type, aggregate_id, event_type, created_at, attempts, last_error,
+               parked_at AS "parked_at!"
+        FROM outbox_events
+        WHERE parked_at IS NOT NULL AND published_at IS NULL
+        ORDER BY parked_at DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Puts a parked event back in line with a fresh set of attempts. False if it is not parked.
+pub async fn requeue(pool: &PgPool, id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE outbox_events
+        SET parked_at = NULL, attempts = 0, next_attempt_at = NOW()
+        WHERE id = $1 AND parked_at IS NOT NULL AND published_at IS NULL
+        "#,
+        id
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn event_exists(pool: &PgPool, id: i64) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM outbox_events WHERE id = $1) AS "exists!""#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Drops published events older than `retain_days`.
+pub async fn purge_published(pool: &PgPool, retain_days: i64) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM outbox_events WHERE published_at < NOW() - make_interval(days => $1::INT)",
+        retain_days as i32
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,66 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::outbreak::{OutbreakAlert, OutbreakSignal};
+
+/// Stores a signal as an ACTIVE alert. Re-running the detector during the same week
+/// refreshes the counts of the existing alert instead of raising a duplicate.
+pub async fn upsert_alert(pool: &PgPool, signal: &OutbreakSignal) -> Result<Uuid, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO outbreak_alerts (condition_id, state, lga, week_start, observed_cases, baseline_mean, baseline_stddev, threshold)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (condition_id, state, lga, week_start) DO UPDATE
+        SET observed_cases = EXCLUDED.observed_cases,
+            baseline_mean = EXCLUDED.baseline_mean,
+            baseline_stddev = EXCLUDED.baseline_stddev,
+            threshold = EXCLUDED.threshold,
+            updated_at = NOW()
+        RETURNING id
+        "#,
+        signal.condition_id,
+        signal.state,
+        signal.lga,
+        signal.week_start,
+        signal.observed_cases as i32,
+        signal.baseline_mean,
+        signal.baseline_stddev,
+        signal.threshold
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_alerts(pool: &PgPool, status: Option<String>, state: Option<String>) -> Result<Vec<OutbreakAlert>, sqlx::Error> {
+    sqlx::query_as!(
+        OutbreakAlert,
+        r#"
+        SELECT
+            a.id, a.condition_id, c.name AS condition_name, a.state, a.lga, a.week_start, a.observed_cases,
+            a.baseline_mean, a.baseline_stddev, a.threshold, a.status, a.created_at, a.updated_at, a.resolved_at
+        FROM outbreak_alerts a
+        JOIN notifiable_conditions c ON c.id = a.condition_id
+        WHERE ($1::VARCHAR IS NULL OR a.status = $1)
+          AND ($2::TEXT IS NULL OR a.state = $2)
+        ORDER BY a.week_start DESC, a.observed_cases DESC
+        "#,
+        status,
+        state
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Returns `Ok(None)` if the alert does not exist or is already resolved.
+pub async fn resolve_alert(pool: &PgPool, alert_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE outbreak_alerts
+        SET status = 'RESOLVED', resolved_at = NOW(), updated_at = NOW()
+        WHERE id = $1 AND status = 'ACTIVE'
+        RETURNING id
+        "#,
+        alert_id
+    )
+    .fetch_optional(pool)
+    .await
+}
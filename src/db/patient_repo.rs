@@ -1,46 +1,334 @@
-use sqlx::PgPool;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
-use crate::models::patient::{Patient, CreatePatientRequest};
+use crate::crypto::pii::{PiiCipher, PiiError};
+use crate::models::patient::{Patient, CreatePatientRequest, PiiKeyVersionCount, ReencryptSummary};
 
-pub async fn create_patient(pool: &PgPool, payload: CreatePatientRequest) -> Result<Patient, sqlx::Error> {
-    sqlx::query_as!(
-        Patient,
+// Encrypted columns, also used as the associated data that binds each ciphertext to its column
+const FIRST_NAME: &str = "first_name";
+const LAST_NAME: &str = "last_name";
+const CONTACT_PHONE: &str = "contact_phone";
+const EMERGENCY_CONTACT: &str = "emergency_contact";
+const ADDRESS: &str = "address";
+const NATIONAL_ID: &str = "national_id";
+
+/// A `patients` row as stored, with PII still encrypted under `pii_key_version`.
+struct PatientRow {
+    id: Uuid,
+    hospital_id: Option<Uuid>,
+    first_name: String,
+    last_name: String,
+    date_of_birth: NaiveDate,
+    gender: String,
+    contact_phone: Option<String>,
+    emergency_contact: Option<String>,
+    address: Option<String>,
+    national_id: Option<String>,
+    pii_key_version: Option<i16>,
+    created_at: DateTime<Utc>,
+}
+
+impl PatientRow {
+    fn decrypt(self, cipher: &PiiCipher) -> Result<Patient, PiiError> {
+        let (id, version) = (self.id, self.pii_key_version);
+        Ok(Patient {
+            id,
+            hospital_id: self.hospital_id,
+            first_name: cipher.decrypt(version, id, FIRST_NAME, &self.first_name)?,
+            last_name: cipher.decrypt(version, id, LAST_NAME, &self.last_name)?,
+            date_of_birth: self.date_of_birth,
+            gender: self.gender,
+            contact_phone: cipher.decrypt_opt(version, id, CONTACT_PHONE, self.contact_phone.as_deref())?,
+            emergency_contact: cipher.decrypt_opt(version, id, EMERGENCY_CONTACT, self.emergency_contact.as_deref())?,
+            address: cipher.decrypt_opt(version, id, ADDRESS, self.address.as_deref())?,
+            national_id: cipher.decrypt_opt(version, id, NATIONAL_ID, self.national_id.as_deref())?,
+            created_at: self.created_at,
+        })
+    }
+}
+
+/// Decrypts the name columns of a patient row selected alongside other data.
+pub fn decrypt_name(
+    cipher: &PiiCipher,
+    patient_id: Uuid,
+    key_version: Option<i16>,
+    first_name: &str,
+    last_name: &str,
+) -> Result<(String, String), sqlx::Error> {
+    Ok((
+        cipher.decrypt(key_version, patient_id, FIRST_NAME, first_name)?,
+        cipher.decrypt(key_version, patient_id, LAST_NAME, last_name)?,
+    ))
+}
+
+fn decrypt_all(rows: Vec<PatientRow>, cipher: &PiiCipher) -> Result<Vec<Patient>, sqlx::Error> {
+    rows.into_iter()
+        .map(|row| row.decrypt(cipher).map_err(sqlx::Error::from))
+        .collect()
+}
+
+/// The PII columns of one patient encrypted under the active key, plus their blind indexes.
+struct EncryptedPii {
+    first_name: String,
+    last_name: String,
+    contact_phone: Option<String>,
+    emergency_contact: Option<String>,
+    address: Option<String>,
+    national_id: Option<String>,
+    contact_phone_index: Option<String>,
+    national_id_index: Option<String>,
+}
+
+impl EncryptedPii {
+    fn new(cipher: &PiiCipher, patient: &Patient) -> Self {
+        let id = patient.id;
+        Self {
+            first_name: cipher.encrypt(id, FIRST_NAME, &patient.first_name),
+            last_name: cipher.encrypt(id, LAST_NAME, &patient.last_name),
+            contact_phone: cipher.encrypt_opt(id, CONTACT_PHONE, patient.contact_phone.as_deref()),
+            emergency_contact: cipher.encrypt_opt(id, EMERGENCY_CONTACT, patient.emergency_contact.as_deref()),
+            address: cipher.encrypt_opt(id, ADDRESS, patient.address.as_deref()),
+            national_id: cipher.encrypt_opt(id, NATIONAL_ID, patient.national_id.as_deref()),
+            contact_phone_index: patient.contact_phone.as_deref().and_then(|p| cipher.phone_index(p)),
+            national_id_index: patient.national_id.as_deref().and_then(|n| cipher.national_id_index(n)),
+        }
+    }
+}
+
+/// Registers a patient. The id is generated here because it is bound into each ciphertext.
+pub async fn create_patient(pool: &PgPool, cipher: &PiiCipher, payload: CreatePatientRequest) -> Result<Patient, sqlx::Error> {
+    let mut patient = Patient {
+        id: Uuid::new_v4(),
+        hospital_id: payload.hospital_id,
+        first_name: payload.first_name,
+        last_name: payload.last_name,
+        date_of_birth: payload.date_of_birth,
+        gender: payload.gender,
+        contact_phone: payload.contact_phone,
+        emergency_contact: payload.emergency_contact,
+        address: payload.address,
+        national_id: payload.national_id,
+        created_at: Utc::now(),
+    };
+    let pii = EncryptedPii::new(cipher, &patient);
+
+    patient.created_at = sqlx::query_scalar!(
         r#"
-        INSERT INTO patients (hospital_id, first_name, last_name, date_of_birth, gender, contact_phone, emergency_contact, address)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-        RETURNING id, hospital_id, first_name, last_name, date_of_birth, gender, contact_phone, emergency_contact, address, created_at
+        INSERT INTO patients (
+            id, hospital_id, first_name, last_name, date_of_birth, gender, contact_phone, emergency_contact,
+            address, national_id, pii_key_version, contact_phone_index, national_id_index
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        RETURNING created_at
         "#,
-        payload.hospital_id,
-        payload.first_name,
-        payload.last_name,
-        payload.date_of_birth,
-        payload.gender,
-        payload.contact_phone,
-        payload.emergency_contact,
-        payload.address
+        patient.id,
+        patient.hospital_id,
+        pii.first_name,
+        pii.last_name,
+        patient.date_of_birth,
+        patient.gender,
+        pii.contact_phone,
+        pii.emergency_contact,
+        pii.address,
+        pii.national_id,
+        cipher.active_version(),
+        pii.contact_phone_index,
+        pii.national_id_index
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(patient)
+}
+
+/// Patients registered at, or seen at, `caller_hospital_id`, optionally narrowed to those registered
+/// at `hospital_id`. Unfiltered lists are capped at 100.
+pub async fn get_patients(
+    pool: &PgPool,
+    cipher: &PiiCipher,
+    caller_hospital_id: Uuid,
+    hospital_id: Option<Uuid>,
+) -> Result<Vec<Patient>, sqlx::Error> {
+    let rows = sqlx::query_as!(
+        PatientRow,
+        r#"
+        SELECT
+            p.id, p.hospital_id, p.first_name, p.last_name, p.date_of_birth, p.gender, p.contact_phone,
+            p.emergency_contact, p.address, p.national_id, p.pii_key_version, p.created_at
+        FROM patients p
+        WHERE (
+            p.hospital_id = $1
+            OR EXISTS (SELECT 1 FROM visits v WHERE v.patient_id = p.id AND v.hospital_id = $1)
+          )
+          AND ($2::UUID IS NULL OR p.hospital_id = $2)
+        ORDER BY p.created_at DESC
+        LIMIT CASE WHEN $2::UUID IS NULL THEN 100 END
+        "#,
+        caller_hospital_id,
+        hospital_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    decrypt_all(rows, cipher)
+}
+
+pub async fn get_patient_by_id(pool: &PgPool, cipher: &PiiCipher, patient_id: Uuid) -> Result<Option<Patient>, sqlx::Error> {
+    let row = sqlx::query_as!(
+        PatientRow,
+        r#"
+        SELECT
+            id, hospital_id, first_name, last_name, date_of_birth, gender, contact_phone,
+            emergency_contact, address, national_id, pii_key_version, created_at
+        FROM patients
+        WHERE id = $1
+        "#,
+        patient_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    row.map(|r| r.decrypt(cipher).map_err(sqlx::Error::from)).transpose()
+}
+
+pub async fn patient_exists(pool: &PgPool, patient_id: Uuid) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM patients WHERE id = $1) AS "exists!""#,
+        patient_id
     )
     .fetch_one(pool)
     .await
 }
 
-pub async fn get_patients(pool: &PgPool, hospital_id: Option<Uuid>) -> Result<Vec<Patient>, sqlx::Error> {
-    match hospital_id {
-        Some(id) => {
-            sqlx::query_as!(
-                Patient,
-                "SELECT * FROM patients WHERE hospital_id = $1 ORDER BY created_at DESC",
-                id
-            )
-            .fetch_all(pool)
-            .await
-        }
-        None => {
-            sqlx::query_as!(
-                Patient,
-                "SELECT * FROM patients ORDER BY created_at DESC LIMIT 100"
-            )
-            .fetch_all(pool)
-            .await
+/// Exact-match search on phone number and/or national ID through their blind indexes.
+/// Rows written before encryption have no index yet and are found once re-encrypted.
+pub async fn find_patients(
+    pool: &PgPool,
+    cipher: &PiiCipher,
+    phone: Option<&str>,
+    national_id: Option<&str>,
+) -> Result<Vec<Patient>, sqlx::Error> {
+    let phone_index = phone.and_then(|p| cipher.phone_index(p));
+    let national_id_index = national_id.and_then(|n| cipher.national_id_index(n));
+    if phone_index.is_none() && national_id_index.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query_as!(
+        PatientRow,
+        r#"
+        SELECT
+            id, hospital_id, first_name, last_name, date_of_birth, gender, contact_phone,
+            emergency_contact, address, national_id, pii_key_version, created_at
+        FROM patients
+        WHERE ($1::VARCHAR IS NULL OR contact_phone_index = $1)
+          AND ($2::VARCHAR IS NULL OR national_id_index = $2)
+        ORDER BY created_at DESC
+        LIMIT 50
+        "#,
+        phone_index,
+        national_id_index
+    )
+    .fetch_all(pool)
+    .await?;
+
+    decrypt_all(rows, cipher)
+}
+
+/// Re-encrypts up to `batch_size` patients that are not yet under the active key (including
+/// legacy plaintext rows) and refreshes their blind indexes. Rows locked by a concurrent run are
+/// skipped. A row that cannot be decrypted is parked rather than failing the batch, so it does not
+/// hold back the rows behind it; parked rows are left out of later batches.
+pub async fn reencrypt_batch(pool: &PgPool, cipher: &PiiCipher, batch_size: i64) -> Result<ReencryptSummary, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let rows = sqlx::query_as!(
+        PatientRow,
+        r#"
+        SELECT
+            id, hospital_id, first_name, last_name, date_of_birth, gender, contact_phone,
+            emergency_contact, address, national_id, pii_key_version, created_at
+        FROM patients
+        WHERE pii_key_version IS DISTINCT FROM $1 AND pii_reencrypt_failed_at IS NULL
+        ORDER BY created_at ASC
+        LIMIT $2
+        FOR UPDATE SKIP LOCKED
+        "#,
+        cipher.active_version(),
+        batch_size
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut summary = ReencryptSummary::default();
+    for row in rows {
+        let (id, version) = (row.id, row.pii_key_version);
+        match row.decrypt(cipher) {
+            Ok(patient) => {
+                write_pii(&mut tx, cipher, &patient).await?;
+                summary.rewritten += 1;
+            }
+            Err(e) => {
+                tracing::warn!("Parking patient {} (PII key version {:?}) from re-encryption: {}", id, version, e);
+                sqlx::query!("UPDATE patients SET pii_reencrypt_failed_at = NOW() WHERE id = $1", id)
+                    .execute(&mut *tx)
+                    .await?;
+                summary.parked += 1;
+            }
         }
     }
-}
\ No newline at end of file
+
+    tx.commit().await?;
+    Ok(summary)
+}
+
+/// Patients the re-encryption sweep has parked because it could not read them.
+pub async fn count_reencrypt_failures(pool: &PgPool) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM patients WHERE pii_reencrypt_failed_at IS NOT NULL"#
+    )
+    .fetch_one(pool)
+    .await
+}
+
+async fn write_pii(tx: &mut Transaction<'_, Postgres>, cipher: &PiiCipher, patient: &Patient) -> Result<(), sqlx::Error> {
+    let pii = EncryptedPii::new(cipher, patient);
+    sqlx::query!(
+        r#"
+        UPDATE patients
+        SET first_name = $2, last_name = $3, contact_phone = $4, emergency_contact = $5, address = $6,
+            national_id = $7, pii_key_version = $8, contact_phone_index = $9, national_id_index = $10,
+            pii_reencrypt_failed_at = NULL
+        WHERE id = $1
+        "#,
+        patient.id,
+        pii.first_name,
+        pii.last_name,
+        pii.contact_phone,
+        pii.emergency_contact,
+        pii.address,
+        pii.national_id,
+        cipher.active_version(),
+        pii.contact_phone_index,
+        pii.national_id_index
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Patient counts per key version (NULL = plaintext).
+pub async fn get_key_version_counts(pool: &PgPool) -> Result<Vec<PiiKeyVersionCount>, sqlx::Error> {
+    sqlx::query_as!(
+        PiiKeyVersionCount,
+        r#"
+        SELECT pii_key_version AS key_version, COUNT(*) AS "patients!"
+        FROM patients
+        GROUP BY pii_key_version
+        ORDER BY pii_key_version ASC NULLS FIRST
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,145 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::prescription::{Prescription, CreatePrescriptionRequest, DuplicateMedication};
+
+/// Creates a prescription on a visit. The patient and hospital come from the visit.
+pub async fn create_prescription(
+    pool: &PgPool,
+    visit_id: Uuid,
+    payload: CreatePrescriptionRequest,
+) -> Result<Prescription, sqlx::Error> {
+    sqlx::query_as!(
+        Prescription,
+        r#"
+        INSERT INTO prescriptions (
+            visit_id, patient_id, hospital_id, prescriber_id, drug_name, dose, route, frequency,
+            duration_days, instructions, start_date
+        )
+        SELECT v.id, v.patient_id, v.hospital_id, $2, TRIM($3), $4, $5, $6, $7, $8, COALESCE($9, CURRENT_DATE)
+        FROM visits v
+        WHERE v.id = $1
+        RETURNING id, visit_id, patient_id, hospital_id, prescriber_id, drug_name, dose, route, frequency,
+            duration_days, instructions, start_date, status, status_reason, dispensed_at, discontinued_at,
+            created_at, updated_at
+        "#,
+        visit_id,
+        payload.prescriber_id,
+        payload.drug_name,
+        payload.dose,
+        payload.route,
+        payload.frequency,
+        payload.duration_days,
+        payload.instructions,
+        payload.start_date
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_prescription(pool: &PgPool, prescription_id: Uuid) -> Result<Option<Prescription>, sqlx::Error> {
+    sqlx::query_as!(
+        Prescription,
+        "SELECT * FROM prescriptions WHERE id = $1",
+        prescription_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn get_visit_prescriptions(pool: &PgPool, visit_id: Uuid) -> Result<Vec<Prescription>, sqlx::Error> {
+    sqlx::query_as!(
+        Prescription,
+        "SELECT * FROM prescriptions WHERE visit_id = $1 ORDER BY created_at ASC",
+        visit_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// A patient's prescriptions, optionally only those written at `hospital_id`.
+pub async fn get_patient_prescriptions(
+    pool: &PgPool,
+    patient_id: Uuid,
+    hospital_id: Option<Uuid>,
+    status: Option<String>,
+) -> Result<Vec<Prescription>, sqlx::Error> {
+    sqlx::query_as!(
+        Prescription,
+        r#"
+        SELECT * FROM prescriptions
+        WHERE patient_id = $1
+          AND ($2::UUID IS NULL OR hospital_id = $2)
+          AND ($3::VARCHAR IS NULL OR status = $3)
+        ORDER BY start_date DESC, created_at DESC
+        "#,
+        patient_id,
+        hospital_id,
+        status
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Current prescriptions of the same drug for a patient: not discontinued and course not yet finished.
+/// Pass `drug_name` to check a single drug, or `None` to list every duplicated drug.
+pub async fn find_duplicate_medications(
+    pool: &PgPool,
+    patient_id: Uuid,
+    drug_name: Option<&str>,
+    min_count: i64,
+) -> Result<Vec<DuplicateMedication>, sqlx::Error> {
+    sqlx::query_as!(
+        DuplicateMedication,
+        r#"
+        SELECT
+            MIN(drug_name) AS "drug_name!",
+            ARRAY_AGG(id ORDER BY created_at) AS "prescription_ids!"
+        FROM prescriptions
+        WHERE patient_id = $1
+          AND status IN ('ACTIVE', 'DISPENSED')
+          AND (duration_days IS NULL OR start_date + duration_days > CURRENT_DATE)
+          AND ($2::TEXT IS NULL OR LOWER(drug_name) = LOWER(TRIM($2)))
+        GROUP BY LOWER(drug_name)
+        HAVING COUNT(*) >= $3
+        ORDER BY 1 ASC
+        "#,
+        patient_id,
+        drug_name,
+        min_count
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Moves a prescription to `next_status` if it is currently in one of `allowed_from`.
+/// Returns `Ok(None)` if the prescription is not in an allowed status.
+pub async fn advance_prescription(
+    pool: &PgPool,
+    prescription_id: Uuid,
+    allowed_from: &[&str],
+    next_status: &str,
+    reason: Option<&str>,
+) -> Result<Option<Prescription>, sqlx::Error> {
+    let allowed_from: Vec<String> = allowed_from.iter().map(|s| s.to_string()).collect();
+    sqlx::query_as!(
+        Prescription,
+        r#"
+        UPDATE prescriptions
+        SET status = $2::VARCHAR,
+            status_reason = COALESCE($4, status_reason),
+            dispensed_at = CASE WHEN $2 = 'DISPENSED' THEN NOW() ELSE dispensed_at END,
+            discontinued_at = CASE WHEN $2 = 'DISCONTINUED' THEN NOW() ELSE discontinued_at END,
+            updated_at = NOW()
+        WHERE id = $1 AND status = ANY($3)
+        RETURNING id, visit_id, patient_id, hospital_id, prescriber_id, drug_name, dose, route, frequency,
+            duration_days, instructions, start_date, status, status_reason, dispensed_at, discontinued_at,
+            created_at, updated_at
+        "#,
+        prescription_id,
+        next_status,
+        &allowed_from,
+        reason
+    )
+    .fetch_optional(pool)
+    .await
+}
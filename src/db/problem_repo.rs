@@ -0,0 +1,63 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::problem::{Problem, CreateProblemRequest, UpdateProblemRequest};
+
+/// `code` and `description` are resolved by the caller (the code is validated against the ICD-10 table).
+pub async fn create_problem(
+    pool: &PgPool,
+    patient_id: Uuid,
+    code: Option<&str>,
+    description: &str,
+    payload: &CreateProblemRequest,
+) -> Result<Problem, sqlx::Error> {
+    sqlx::query_as!(
+        Problem,
+        r#"
+        INSERT INTO patient_problems (patient_id, code, description, onset_date, notes)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, patient_id, code, description, status, onset_date, resolved_date, notes, created_at, updated_at
+        "#,
+        patient_id,
+        code,
+        description,
+        payload.onset_date,
+        payload.notes
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_patient_problems(pool: &PgPool, patient_id: Uuid, status: Option<String>) -> Result<Vec<Problem>, sqlx::Error> {
+    sqlx::query_as!(
+        Problem,
+        r#"
+        SELECT * FROM patient_problems
+        WHERE patient_id = $1 AND ($2::VARCHAR IS NULL OR status = $2)
+        ORDER BY status ASC, onset_date DESC NULLS LAST, created_at DESC
+        "#,
+        patient_id,
+        status
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Resolving stamps the resolved date (today by default); re-activating clears it.
+pub async fn update_problem_status(pool: &PgPool, problem_id: Uuid, payload: UpdateProblemRequest) -> Result<Problem, sqlx::Error> {
+    sqlx::query_as!(
+        Problem,
+        r#"
+        UPDATE patient_problems
+        SET status = $2::VARCHAR,
+            resolved_date = CASE WHEN $2 = 'RESOLVED' THEN COALESCE($3, CURRENT_DATE) ELSE NULL END,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING id, patient_id, code, description, status, onset_date, resolved_date, notes, created_at, updated_at
+        "#,
+        problem_id,
+        payload.status,
+        payload.resolved_date
+    )
+    .fetch_one(pool)
+    .await
+}
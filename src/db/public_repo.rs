@@ -0,0 +1,66 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use crate::models::public::{PublicHospital, AreaWeekCount};
+
+/// Active hospitals with their facilities, free beds and oxygen stock status.
+pub async fn get_hospitals(pool: &PgPool, state: Option<String>, lga: Option<String>) -> Result<Vec<PublicHospital>, sqlx::Error> {
+    sqlx::query_as!(
+        PublicHospital,
+        r#"
+        SELECT
+            h.id, h.name, h.hospital_type, h.state, h.city, h.lga, h.latitude, h.longitude,
+            h.total_beds, GREATEST(h.total_beds - h.occupied_beds, 0) AS available_beds,
+            h.has_emergency, h.has_oxygen, h.has_ventilators, h.has_ambulance,
+            COALESCE(o.status, 'UNKNOWN') AS "oxygen_status!"
+        FROM hospitals h
+        LEFT JOIN LATERAL (
+            SELECT CASE
+                WHEN SUM(quantity_on_hand) = 0 THEN 'OUT'
+                WHEN SUM(quantity_on_hand) <= SUM(reorder_level) THEN 'LOW'
+                ELSE 'OK'
+            END AS status
+            FROM inventory_items
+            WHERE hospital_id = h.id AND category = 'OXYGEN'
+            HAVING COUNT(*) > 0
+        ) o ON TRUE
+        WHERE h.is_active = TRUE
+          AND ($1::TEXT IS NULL OR h.state ILIKE $1)
+          AND ($2::TEXT IS NULL OR h.lga ILIKE $2)
+        ORDER BY h.state ASC, h.lga ASC NULLS LAST, h.name ASC
+        "#,
+        state,
+        lga
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Visits (excluding cancellations) per week and hospital area (state, LGA) in `[from, to)`.
+pub async fn get_weekly_visit_counts(
+    pool: &PgPool,
+    state: Option<String>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<AreaWeekCount>, sqlx::Error> {
+    sqlx::query_as!(
+        AreaWeekCount,
+        r#"
+        SELECT
+            DATE_TRUNC('week', v.start_time)::DATE AS "week_start!",
+            h.state, h.lga,
+            COUNT(*) AS "visits!"
+        FROM visits v
+        JOIN hospitals h ON h.id = v.hospital_id
+        WHERE v.start_time >= $2::DATE AND v.start_time < $3::DATE
+          AND v.status <> 'CANCELLED'
+          AND ($1::TEXT IS NULL OR h.state ILIKE $1)
+        GROUP BY 1, h.state, h.lga
+        ORDER BY 1 ASC, h.state ASC, h.lga ASC NULLS LAST
+        "#,
+        state,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await
+}
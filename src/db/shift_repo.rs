@@ -0,0 +1,181 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::shift::{
+    Shift, ShiftRejection, CreateShiftRequest, RotaTemplate, CreateRotaTemplateRequest,
+    CoverageRule, UpsertCoverageRuleRequest, OnDutyStaff, CoverageGap,
+};
+
+/// Inserts a shift unless the staff member already has one overlapping the same window.
+/// Refuses deactivated staff and overlaps. The staff row is locked so concurrent bookings serialize.
+pub async fn create_shift(
+    pool: &PgPool,
+    payload: &CreateShiftRequest,
+) -> Result<Result<Shift, ShiftRejection>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // Holding the staff row keeps a concurrent deactivation or overlapping shift out
+    let is_active = sqlx::query_scalar!("SELECT is_active FROM staff WHERE id = $1 FOR UPDATE", payload.staff_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    if !is_active {
+        return Ok(Err(ShiftRejection::StaffInactive));
+    }
+
+    let overlapping = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM shifts
+            WHERE staff_id = $1 AND start_time < $3 AND end_time > $2
+        ) AS "exists!"
+        "#,
+        payload.staff_id,
+        payload.start_time,
+        payload.end_time
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if overlapping {
+        return Ok(Err(ShiftRejection::Overlap));
+    }
+
+    let shift = sqlx::query_as!(
+        Shift,
+        r#"
+        INSERT INTO shifts (hospital_id, department_id, staff_id, role, shift_type, start_time, end_time)
+        SELECT s.hospital_id, COALESCE($2, s.department_id), s.id, s.role, $3, $4, $5
+        FROM staff s
+        WHERE s.id = $1
+        RETURNING id, hospital_id, department_id, staff_id, role, shift_type, start_time, end_time, created_at
+        "#,
+        payload.staff_id,
+        payload.department_id,
+        payload.shift_type,
+        payload.start_time,
+        payload.end_time
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Ok(shift))
+}
+
+pub async fn get_hospital_shifts(
+    pool: &PgPool,
+    hospital_id: Uuid,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Shift>, sqlx::Error> {
+    sqlx::query_as!(
+        Shift,
+        r#"
+        SELECT id, hospital_id, department_id, staff_id, role, shift_type, start_time, end_time, created_at
+        FROM shifts
+        WHERE hospital_id = $1 AND start_time < $3 AND end_time > $2
+        ORDER BY start_time ASC
+        "#,
+        hospital_id,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn create_rota_template(pool: &PgPool, payload: CreateRotaTemplateRequest) -> Result<RotaTemplate, sqlx::Error> {
+    sqlx::query_as!(
+        RotaTemplate,
+        r#"
+        INSERT INTO rota_templates (hospital_id, department_id, staff_id, shift_type, day_of_week, start_time, duration_minutes)
+        SELECT s.hospital_id, COALESCE($2, s.department_id), s.id, $3, $4, $5, $6
+        FROM staff s
+        WHERE s.id = $1
+        RETURNING id, hospital_id, department_id, staff_id, shift_type, day_of_week, start_time, duration_minutes, created_at
+        "#,
+        payload.staff_id,
+        payload.department_id,
+        payload.shift_type,
+        payload.day_of_week,
+        payload.start_time,
+        payload.duration_minutes
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_hospital_rota_templates(pool: &PgPool, hospital_id: Uuid) -> Result<Vec<RotaTemplate>, sqlx::Error> {
+    sqlx::query_as!(
+        RotaTemplate,
+        "SELECT * FROM rota_templates WHERE hospital_id = $1 ORDER BY day_of_week ASC, start_time ASC",
+        hospital_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn upsert_coverage_rule(pool: &PgPool, payload: UpsertCoverageRuleRequest) -> Result<CoverageRule, sqlx::Error> {
+    sqlx::query_as!(
+        CoverageRule,
+        r#"
+        INSERT INTO coverage_rules (department_id, role, min_staff)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (department_id, role) DO UPDATE SET min_staff = EXCLUDED.min_staff
+        RETURNING id, department_id, role, min_staff, created_at
+        "#,
+        payload.department_id,
+        payload.role,
+        payload.min_staff
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_on_duty_staff(pool: &PgPool, hospital_id: Uuid, at: DateTime<Utc>) -> Result<Vec<OnDutyStaff>, sqlx::Error> {
+    sqlx::query_as!(
+        OnDutyStaff,
+        r#"
+        SELECT
+            sh.id AS shift_id, st.id AS staff_id, st.first_name, st.last_name, sh.role,
+            d.id AS department_id, d.name AS department_name, sh.shift_type, sh.start_time, sh.end_time
+        FROM shifts sh
+        JOIN staff st ON st.id = sh.staff_id
+        JOIN departments d ON d.id = sh.department_id
+        WHERE sh.hospital_id = $1 AND st.is_active AND sh.start_time <= $2 AND sh.end_time > $2
+        ORDER BY d.name ASC, sh.role ASC, st.last_name ASC
+        "#,
+        hospital_id,
+        at
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Departments whose on-duty headcount for a role is below the configured minimum at `at`.
+/// Shifts held by deactivated staff do not count towards the headcount.
+pub async fn get_coverage_gaps(pool: &PgPool, hospital_id: Uuid, at: DateTime<Utc>) -> Result<Vec<CoverageGap>, sqlx::Error> {
+    sqlx::query_as!(
+        CoverageGap,
+        r#"
+        SELECT
+            r.department_id, d.name AS department_name, r.role, r.min_staff,
+            COUNT(sh.id) AS "on_duty!"
+        FROM coverage_rules r
+        JOIN departments d ON d.id = r.department_id
+        LEFT JOIN shifts sh
+            ON sh.department_id = r.department_id
+            AND sh.role = r.role
+            AND sh.start_time <= $2 AND sh.end_time > $2
+            AND EXISTS (SELECT 1 FROM staff st WHERE st.id = sh.staff_id AND st.is_active)
+        WHERE d.hospital_id = $1
+        GROUP BY r.id, d.name
+        HAVING COUNT(sh.id) < r.min_staff
+        ORDER BY d.name ASC, r.role ASC
+        "#,
+        hospital_id,
+        at
+    )
+    .fetch_all(pool)
+    .await
+}
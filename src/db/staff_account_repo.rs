@@ -0,0 +1,60 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::staff_account::{StaffAccount, StaffLogin, StaffSession};
+
+/// Creates the staff member's sign-in, or replaces its email and password. Replacing the
+/// password ends the sessions issued before it.
+pub async fn set_account(
+    pool: &PgPool,
+    staff_id: Uuid,
+    email: &str,
+    password_hash: &str,
+) -> Result<StaffAccount, sqlx::Error> {
+    sqlx::query_as!(
+        StaffAccount,
+        r#"
+        INSERT INTO staff_accounts (staff_id, email, password_hash)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (staff_id) DO UPDATE
+        SET email = EXCLUDED.email,
+            password_hash = EXCLUDED.password_hash,
+            password_changed_at = NOW()
+        RETURNING staff_id, email, created_at, password_changed_at
+        "#,
+        staff_id,
+        email,
+        password_hash
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn find_login_by_email(pool: &PgPool, email: &str) -> Result<Option<StaffLogin>, sqlx::Error> {
+    sqlx::query_as!(
+        StaffLogin,
+        r#"
+        SELECT a.staff_id, a.password_hash, s.is_active
+        FROM staff_accounts a
+        JOIN staff s ON s.id = a.staff_id
+        WHERE a.email = $1
+        "#,
+        email
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn get_session(pool: &PgPool, staff_id: Uuid) -> Result<Option<StaffSession>, sqlx::Error> {
+    sqlx::query_as!(
+        StaffSession,
+        r#"
+        SELECT a.staff_id, s.hospital_id, s.is_active, a.password_changed_at
+        FROM staff_accounts a
+        JOIN staff s ON s.id = a.staff_id
+        WHERE a.staff_id = $1
+        "#,
+        staff_id
+    )
+    .fetch_optional(pool)
+    .await
+}
@@ -30,4 +30,14 @@ pub async fn get_staff_by_hospital(pool: &PgPool, hospital_id: Uuid) -> Result<V
     )
     .fetch_all(pool)
     .await
-}
\ No newline at end of file
+}
+
+pub async fn get_staff_by_id(pool: &PgPool, staff_id: Uuid) -> Result<Option<Staff>, sqlx::Error> {
+    sqlx::query_as!(
+        Staff,
+        "SELECT * FROM staff WHERE id = $1",
+        staff_id
+    )
+    .fetch_optional(pool)
+    .await
+}
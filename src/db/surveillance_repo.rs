@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::models::surveillance::{
+    NotifiableCondition, CreateNotifiableConditionRequest, SurveillanceCase, WeeklyCaseCount,
+};
+
+/// Raises a case report for every notifiable condition matched by the diagnoses of a
+/// COMPLETED visit. Idempotent, so it is safe to call whenever a visit or its diagnoses change.
+pub async fn record_cases_for_visit(tx: &mut Transaction<'_, Postgres>, visit_id: Uuid) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO surveillance_cases (visit_id, patient_id, hospital_id, condition_id, diagnosis_code, state, lga, presented_at)
+        SELECT v.id, v.patient_id, v.hospital_id, c.id, d.code, h.state, COALESCE(h.lga, h.city), v.start_time
+        FROM visits v
+        JOIN hospitals h ON h.id = v.hospital_id
+        JOIN visit_diagnoses d ON d.visit_id = v.id
+        JOIN notifiable_conditions c ON c.is_active AND d.code LIKE c.code_prefix || '%'
+        WHERE v.id = $1 AND v.status = 'COMPLETED'
+        ON CONFLICT (visit_id, condition_id) DO NOTHING
+        "#,
+        visit_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn get_conditions(pool: &PgPool) -> Result<Vec<NotifiableCondition>, sqlx::Error> {
+    sqlx::query_as!(
+        NotifiableCondition,
+        "SELECT * FROM notifiable_conditions ORDER BY name ASC"
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn create_condition(pool: &PgPool, payload: CreateNotifiableConditionRequest) -> Result<NotifiableCondition, sqlx::Error> {
+    sqlx::query_as!(
+        NotifiableCondition,
+        r#"
+        INSERT INTO notifiable_conditions (name, code_prefix)
+        VALUES ($1, UPPER($2))
+        RETURNING id, name, code_prefix, is_active, created_at
+        "#,
+        payload.name,
+        payload.code_prefix
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn set_condition_active(pool: &PgPool, condition_id: Uuid, is_active: bool) -> Result<NotifiableCondition, sqlx::Error> {
+    sqlx::query_as!(
+        NotifiableCondition,
+        r#"
+        UPDATE notifiable_conditions SET is_active = $2 WHERE id = $1
+        RETURNING id, name, code_prefix, is_active, created_at
+        "#,
+        condition_id,
+        is_active
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_cases(
+    pool: &PgPool,
+    state: Option<String>,
+    condition_id: Option<Uuid>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<SurveillanceCase>, sqlx::Error> {
+    sqlx::query_as!(
+        SurveillanceCase,
+        r#"
+        SELECT
+            s.id, s.visit_id, s.hospital_id, s.condition_id, c.name AS condition_name,
+            s.diagnosis_code, s.state, s.lga, s.presented_at, s.reported_at
+        FROM surveillance_cases s
+        JOIN notifiable_conditions c ON c.id = s.condition_id
+        WHERE s.presented_at >= $3 AND s.presented_at < $4
+          AND ($1::TEXT IS NULL OR s.state = $1)
+          AND ($2::UUID IS NULL OR s.condition_id = $2)
+        ORDER BY s.presented_at DESC
+        "#,
+        state,
+        condition_id,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Case counts per ISO week, state, LGA and condition.
+pub async fn get_weekly_counts(
+    pool: &PgPool,
+    state: Option<String>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<WeeklyCaseCount>, sqlx::Error> {
+    sqlx::query_as!(
+        WeeklyCaseCount,
+        r#"
+        SELECT
+            DATE_TRUNC('week', s.presented_at)::DATE AS "week_start!",
+            s.state, s.lga, s.condition_id, c.name AS condition_name,
+            COUNT(*) AS "cases!"
+        FROM surveillance_cases s
+        JOIN notifiable_conditions c ON c.id = s.condition_id
+        WHERE s.presented_at >= $2 AND s.presented_at < $3
+          AND ($1::TEXT IS NULL OR s.state = $1)
+        GROUP BY 1, s.state, s.lga, s.condition_id, c.name
+        ORDER BY 1 ASC, s.state ASC, s.lga ASC, c.name ASC
+        "#,
+        state,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,109 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::timeline::TimelineEvent;
+
+/// One page of a patient's clinical history across every hospital, merged from the visit,
+/// status history, appointment, diagnosis, observation, prescription, lab and problem-list tables.
+/// With `hospital_id`, only that hospital's events and patient-level entries are included.
+/// Fetches one row more than `limit` so the caller can tell whether another page follows.
+pub async fn get_patient_timeline(
+    pool: &PgPool,
+    patient_id: Uuid,
+    hospital_id: Option<Uuid>,
+    event_types: Option<Vec<String>>,
+    newest_first: bool,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<TimelineEvent>, sqlx::Error> {
+    sqlx::query_as!(
+        TimelineEvent,
+        r#"
+        WITH patient_visits AS (
+            SELECT * FROM visits WHERE patient_id = $1
+        ),
+        events AS (
+            SELECT 'VISIT' AS event_type, v.start_time AS occurred_at, v.id AS reference_id,
+                   v.id AS visit_id, v.hospital_id, v.reason AS summary, v.status
+            FROM patient_visits v
+
+            UNION ALL
+            SELECT 'STATUS_CHANGE', c.changed_at, c.id, v.id, v.hospital_id,
+                   'Visit ' || c.from_status || ' -> ' || c.to_status, c.to_status
+            FROM visit_status_changes c
+            JOIN patient_visits v ON v.id = c.visit_id
+
+            UNION ALL
+            SELECT 'APPOINTMENT', a.created_at, a.id, v.id, v.hospital_id,
+                   'Appointment ' || LOWER(a.change_type) || COALESCE(': ' || a.reason, ''), a.change_type
+            FROM appointment_changes a
+            JOIN patient_visits v ON v.id = a.visit_id
+
+            UNION ALL
+            SELECT 'DIAGNOSIS', d.created_at, d.id, v.id, v.hospital_id,
+                   d.code || COALESCE(' ' || d.description, ''), d.diagnosis_type
+            FROM visit_diagnoses d
+            JOIN patient_visits v ON v.id = d.visit_id
+
+            UNION ALL
+            SELECT 'OBSERVATION', o.recorded_at, o.id, o.visit_id, o.hospital_id,
+                   COALESCE('NEWS2 score ' || o.news2_score, 'Observations recorded'), o.news2_risk
+            FROM observation_sets o
+            WHERE o.patient_id = $1
+
+            UNION ALL
+            SELECT 'PRESCRIPTION', p.created_at, p.id, p.visit_id, p.hospital_id,
+                   p.drug_name || ' ' || p.dose || ' ' || p.route || ' ' || p.frequency, p.status
+            FROM prescriptions p
+            WHERE p.patient_id = $1
+
+            UNION ALL
+            SELECT 'LAB_ORDER', l.created_at, l.id, l.visit_id, l.hospital_id,
+                   t.name || ' ordered (' || l.priority || ')', l.status
+            FROM lab_orders l
+            JOIN lab_test_types t ON t.id = l.test_type_id
+            WHERE l.patient_id = $1
+
+            UNION ALL
+            SELECT 'LAB_RESULT', l.resulted_at, l.id, l.visit_id, l.hospital_id,
+                   t.name || ': ' || COALESCE(l.result_value::TEXT || COALESCE(' ' || l.result_unit, ''), l.result_text, ''),
+                   l.abnormal_flag
+            FROM lab_orders l
+            JOIN lab_test_types t ON t.id = l.test_type_id
+            WHERE l.patient_id = $1 AND l.resulted_at IS NOT NULL
+
+            UNION ALL
+            SELECT 'ALLERGY', a.created_at, a.id, NULL::UUID, NULL::UUID,
+                   'Allergy to ' || a.substance || ' (' || a.severity || ')',
+                   CASE WHEN a.is_active THEN 'ACTIVE' ELSE 'INACTIVE' END
+            FROM patient_allergies a
+            WHERE a.patient_id = $1
+
+            UNION ALL
+            SELECT 'PROBLEM', pr.created_at, pr.id, NULL::UUID, NULL::UUID,
+                   COALESCE(pr.code || ' ', '') || pr.description, pr.status
+            FROM patient_problems pr
+            WHERE pr.patient_id = $1
+        )
+        SELECT
+            e.event_type AS "event_type!", e.occurred_at AS "occurred_at!", e.reference_id AS "reference_id!",
+            e.visit_id, e.hospital_id, h.name AS "hospital_name?", e.summary AS "summary!", e.status
+        FROM events e
+        LEFT JOIN hospitals h ON h.id = e.hospital_id
+        WHERE ($2::UUID IS NULL OR e.hospital_id IS NULL OR e.hospital_id = $2)
+          AND ($3::TEXT[] IS NULL OR e.event_type = ANY($3))
+        ORDER BY
+            CASE WHEN $4 THEN e.occurred_at END DESC,
+            CASE WHEN NOT $4 THEN e.occurred_at END ASC,
+            e.reference_id ASC
+        LIMIT $5 OFFSET $6
+        "#,
+        patient_id,
+        hospital_id,
+        event_types.as_deref(),
+        newest_first,
+        limit + 1,
+        offset
+    )
+    .fetch_all(pool)
+    .await
+}
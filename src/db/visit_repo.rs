@@ -1,31 +1,98 @@
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
-use crate::models::visit::{Visit, CreateVisitRequest};
+use crate::db::{outbox_repo, surveillance_repo};
+use crate::models::{
+    outbox::AGGREGATE_VISIT,
+    visit::Visit,
+    webhook::{VisitCompletedEvent, EVENT_VISIT_COMPLETED},
+};
 
-pub async fn create_visit(pool: &PgPool, payload: CreateVisitRequest) -> Result<Visit, sqlx::Error> {
+pub async fn get_hospital_visits(pool: &PgPool, hospital_id: Uuid) -> Result<Vec<Visit>, sqlx::Error> {
     sqlx::query_as!(
         Visit,
-        r#"
-        INSERT INTO visits (hospital_id, patient_id, staff_id, reason, status, start_time)
-        VALUES ($1, $2, $3, $4, 'PENDING', COALESCE($5, NOW()))
-        RETURNING id, hospital_id, patient_id, staff_id, reason, status, start_time, end_time, created_at
-        "#,
-        payload.hospital_id,
-        payload.patient_id,
-        payload.staff_id,
-        payload.reason,
-        payload.start_time
+        "SELECT * FROM visits WHERE hospital_id = $1 ORDER BY start_time DESC",
+        hospital_id
     )
-    .fetch_one(pool)
+    .fetch_all(pool)
     .await
 }
 
-pub async fn get_hospital_visits(pool: &PgPool, hospital_id: Uuid) -> Result<Vec<Visit>, sqlx::Error> {
+pub async fn get_visit_by_id(pool: &PgPool, visit_id: Uuid) -> Result<Option<Visit>, sqlx::Error> {
     sqlx::query_as!(
         Visit,
-        "SELECT * FROM visits WHERE hospital_id = $1 ORDER BY start_time DESC",
-        hospital_id
+        "SELECT * FROM visits WHERE id = $1",
+        visit_id
     )
-    .fetch_all(pool)
+    .fetch_optional(pool)
     .await
-}
\ No newline at end of file
+}
+
+/// Moves a visit to `status` if the transition is allowed
+/// (PENDING -> IN_PROGRESS, PENDING/IN_PROGRESS -> COMPLETED). Cancelling is
+/// `appointment_repo::cancel_visit`, which also frees the visit's slot.
+/// Returns `Ok(None)` otherwise. Every transition is kept in `visit_status_changes`;
+/// completion raises surveillance case reports.
+pub async fn update_visit_status(pool: &PgPool, visit_id: Uuid, status: &str) -> Result<Option<Visit>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let from_status = sqlx::query_scalar!("SELECT status FROM visits WHERE id = $1 FOR UPDATE", visit_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    let visit = sqlx::query_as!(
+        Visit,
+        r#"
+        UPDATE visits
+        SET status = $2,
+            end_time = CASE WHEN $2 = 'COMPLETED' THEN NOW() ELSE end_time END
+        WHERE id = $1
+          AND (
+            ($2 = 'IN_PROGRESS' AND status = 'PENDING')
+            OR ($2 = 'COMPLETED' AND status IN ('PENDING', 'IN_PROGRESS'))
+          )
+        RETURNING id, hospital_id, patient_id, staff_id, reason, status, start_time, end_time, created_at
+        "#,
+        visit_id,
+        status
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let (Some(visit), Some(from_status)) = (visit, from_status) else {
+        return Ok(None);
+    };
+
+    log_status_change(&mut tx, visit.id, &from_status, &visit.status).await?;
+
+    if visit.status == "COMPLETED" {
+        surveillance_repo::record_cases_for_visit(&mut tx, visit.id).await?;
+        let event = VisitCompletedEvent {
+            visit_id: visit.id,
+            hospital_id: visit.hospital_id,
+            start_time: visit.start_time,
+            end_time: visit.end_time,
+        };
+        outbox_repo::append(&mut tx, AGGREGATE_VISIT, visit.id, EVENT_VISIT_COMPLETED, &event).await?;
+    }
+
+    tx.commit().await?;
+    Ok(Some(visit))
+}
+
+/// Appends a row to the visit's status history; callers hold the visit row lock.
+pub async fn log_status_change(
+    tx: &mut Transaction<'_, Postgres>,
+    visit_id: Uuid,
+    from_status: &str,
+    to_status: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO visit_status_changes (visit_id, from_status, to_status) VALUES ($1, $2, $3)",
+        visit_id,
+        from_status,
+        to_status
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::webhook::{CreateWebhookEndpointRequest, WebhookEndpoint, WebhookDelivery, DueWebhookDelivery};
+
+pub async fn create_endpoint(
+    pool: &PgPool,
+    payload: CreateWebhookEndpointRequest,
+    secret: &str,
+) -> Result<WebhookEndpoint, sqlx::Error> {
+    sqlx::query_as!(
+        WebhookEndpoint,
+        r#"
+        INSERT INTO webhook_endpoints (name, url, secret, event_types)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, name, url, event_types, is_active, created_at
+        "#,
+        payload.name,
+        payload.url,
+        secret,
+        &payload.event_types
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_endpoints(pool: &PgPool) -> Result<Vec<WebhookEndpoint>, sqlx::Error> {
+    sqlx::query_as!(
+        WebhookEndpoint,
+        r#"
+        SELECT id, name, url, event_types, is_active, created_at
+        FROM webhook_endpoints
+        ORDER BY is_active DESC, created_at DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_endpoint(pool: &PgPool, endpoint_id: Uuid) -> Result<Option<WebhookEndpoint>, sqlx::Error> {
+    sqlx::query_as!(
+        WebhookEndpoint,
+        "SELECT id, name, url, event_types, is_active, created_at FROM webhook_endpoints WHERE id = $1",
+        endpoint_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Deactivates an endpoint and abandons its pending deliveries. Returns `Ok(None)` if the
+/// endpoint does not exist or is already inactive.
+pub async fn deactivate_endpoint(pool: &PgPool, endpoint_id: Uuid) -> Result<Option<WebhookEndpoint>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let endpoint = sqlx::query_as!(
+        WebhookEndpoint,
+        r#"
+        UPDATE webhook_endpoints SET is_active = FALSE
+        WHERE id = $1 AND is_active = TRUE
+        RETURNING id, name, url, event_types, is_active, created_at
+        "#,
+        endpoint_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if endpoint.is_some() {
+        sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'FAILED', last_error = 'Endpoint deactivated', updated_at = NOW()
+            WHERE endpoint_id = $1 AND status = 'PENDING'
+            "#,
+            endpoint_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(endpoint)
+}
+
+/// Queues an event for every active endpoint subscribed to its type that does not have it yet.
+/// Returns the new delivery ids.
+pub async fn enqueue_event(
+    pool: &PgPool,
+    event_id: Uuid,
+    event_type: &str,
+    payload: &str,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO webhook_deliveries (endpoint_id, event_id, event_type, payload)
+        SELECT e.id, $1, $2::TEXT, $3 FROM webhook_endpoints e
+        WHERE e.is_active = TRUE AND $2::TEXT = ANY(e.event_types)
+          AND NOT EXISTS (SELECT 1 FROM webhook_deliveries d WHERE d.endpoint_id = e.id AND d.event_id = $1)
+        RETURNING id
+        "#,
+        event_id,
+        event_type,
+        payload
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Claims up to `limit` due deliveries (optionally only those in `only`) by pushing their next
+/// attempt `lease_secs` out, so a concurrent dispatcher skips them while they are being sent.
+/// A dispatcher that dies mid-send leaves them to be retried once the lease runs out.
+pub async fn claim_due_deliveries(
+    pool: &PgPool,
+    only: Option<&[Uuid]>,
+    limit: i64,
+    lease_secs: f64,
+) -> Result<Vec<DueWebhookDelivery>, sqlx::Error> {
+    sqlx::query_as!(
+        DueWebhookDelivery,
+        r#"
+        UPDATE webhook_deliveries d
+        SET next_attempt_at = NOW() + make_interval(secs => $3), updated_at = NOW()
+        FROM webhook_endpoints e
+        WHERE e.id = d.endpoint_id
+          AND d.id IN (
+              SELECT id FROM webhook_deliveries
+              WHERE status = 'PENDING' AND next_attempt_at <= NOW()
+                AND ($1::UUID[] IS NULL OR id = ANY($1))
+              ORDER BY next_attempt_at ASC
+              LIMIT $2
+              FOR UPDATE SKIP LOCKED
+          )
+        RETURNING d.id, e.url, e.secret, d.event_id, d.event_type, d.payload, d.attempts
+        "#,
+        only,
+        limit,
+        lease_secs
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn mark_delivered(pool: &PgPool, delivery_id: Uuid, status_code: i32) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = 'DELIVERED', attempts = attempts + 1, last_status_code = $2, last_error = NULL,
+            delivered_at = NOW(), updated_at = NOW()
+        WHERE id = $1
+        "#,
+        delivery_id,
+        status_code
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Records a failed attempt. With `retry_at` the delivery stays PENDING until then; without,
+/// it is given up as FAILED.
+pub async fn mark_attempt_failed(
+    pool: &PgPool,
+    delivery_id: Uuid,
+    status_code: Option<i32>,
+    error: &str,
+    retry_at: Option<DateTime<Utc>>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = CASE WHEN $4::TIMESTAMPTZ IS NULL THEN 'FAILED' ELSE 'PENDING' END,
+            attempts = attempts + 1,
+            last_status_code = $2,
+            last_error = $3,
+            next_attempt_at = COALESCE($4, next_attempt_at),
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+        delivery_id,
+        status_code,
+        error,
+        retry_at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_delivery(pool: &PgPool, delivery_id: Uuid) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+    sqlx::query_as!(WebhookDelivery, "SELECT * FROM webhook_deliveries WHERE id = $1", delivery_id)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn get_endpoint_deliveries(pool: &PgPool, endpoint_id: Uuid, status: Option<String>) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+    sqlx::query_as!(
+        WebhookDelivery,
+        r#"
+        SELECT * FROM webhook_deliveries
+        WHERE endpoint_id = $1 AND ($2::VARCHAR IS NULL OR status = $2)
+        ORDER BY created_at DESC
+        LIMIT 200
+        "#,
+        endpoint_id,
+        status
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Queues a fresh copy of a delivery (same event id and payload) to its endpoint, leaving the
+/// original in the log. Returns `Ok(None)` if the endpoint has been deactivated.
+pub async fn redeliver(pool: &PgPool, delivery_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO webhook_deliveries (endpoint_id, event_id, event_type, payload)
+        SELECT d.endpoint_id, d.event_id, d.event_type, d.payload
+        FROM webhook_deliveries d
+        JOIN webhook_endpoints e ON e.id = d.endpoint_id
+        WHERE d.id = $1 AND e.is_active = TRUE
+        RETURNING id
+        "#,
+        delivery_id
+    )
+    .fetch_optional(pool)
+    .await
+}
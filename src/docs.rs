@@ -1,13 +1,75 @@
-use utoipa::OpenApi;
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
 use crate::models::{
     hospital::{CreateHospitalRequest, Hospital},
     hospital_response::HospitalsResponse,
     single_hospital_response::SingleHospitalResponse,
     department::{Department, CreateDepartmentRequest},
     staff::{Staff, CreateStaffRequest},
-    patient::{Patient, CreatePatientRequest},
-    visit::{Visit, CreateVisitRequest},
+    staff_account::{StaffAccount, SetStaffAccountRequest, StaffLoginResponse},
+    patient::{Patient, CreatePatientRequest, PatientRecord, PiiKeyVersionCount, PiiKeyStatus},
+    timeline::{TimelineEvent, PatientTimeline},
+    consent::{Consent, GrantConsentRequest, RevokeConsentRequest, AccessLogEntry},
+    public::{PublicHospital, PublicVisitCount, PublicVisitCounts},
+    analytics::{OccupancyGroup, HospitalOccupancy, HighOccupancySummary, StateCoverage, DailyVisitVolume},
+    forecast::{ForecastVsActual, ForecastComparison, ForecastRunSummary},
+    capacity_alert::{
+        AlertRule, CreateAlertRuleRequest, CapacityAlert, AcknowledgeAlertRequest, ResolveCapacityAlertRequest,
+        AlertSubscription, CreateAlertSubscriptionRequest, AlertDelivery,
+    },
+    webhook::{
+        WebhookEndpoint, CreateWebhookEndpointRequest, RegisteredWebhookEndpoint, WebhookDelivery,
+        WebhookDispatchSummary,
+    },
+    outbox::{OutboxDispatchSummary, OutboxStatus, ParkedOutboxEvent},
+    notification::{
+        NotificationTemplate, UpsertNotificationTemplateRequest, NotificationPreference,
+        UpdateNotificationPreferenceRequest, SendNotificationRequest, Notification, NotificationDispatchSummary,
+    },
+    visit::{Visit, CreateVisitRequest, UpdateVisitStatusRequest},
     equipment::{Equipment, CreateEquipmentRequest},
+    shift::{
+        Shift, CreateShiftRequest, RotaTemplate, CreateRotaTemplateRequest, ApplyRotaRequest,
+        ApplyRotaResponse, CoverageRule, UpsertCoverageRuleRequest, OnDutyStaff, CoverageGap,
+    },
+    credential::{Credential, CreateCredentialRequest, ExpiringCredential},
+    appointment::{
+        AvailabilitySlot, CreateSlotsRequest, BookSlotRequest, RescheduleAppointmentRequest,
+        CancelAppointmentRequest, AppointmentChange,
+    },
+    maintenance::{
+        MaintenancePlan, CreateMaintenancePlanRequest, WorkOrder, CreateWorkOrderRequest,
+        CloseWorkOrderRequest, ConditionLogEntry, UpdateConditionRequest, OverdueMaintenance,
+    },
+    equipment_transfer::{EquipmentTransfer, CreateTransferRequest, TransferActionRequest, CustodyEvent},
+    inventory::{
+        InventoryItem, CreateInventoryItemRequest, InventoryTransaction, CreateInventoryTransactionRequest,
+        StockMovementResponse, CapabilitySummary,
+    },
+    blood_bank::{BloodUnitBatch, ReportBloodUnitsRequest, IssueBloodUnitsRequest, BloodStockLevel, BloodSearchResult},
+    diagnosis::{VisitDiagnosis, CreateDiagnosisRequest},
+    icd10::Icd10Code,
+    surveillance::{
+        NotifiableCondition, CreateNotifiableConditionRequest, UpdateNotifiableConditionRequest,
+        SurveillanceCase, WeeklyCaseCount,
+    },
+    outbreak::OutbreakAlert,
+    observation::{
+        ObservationSet, Observation, ObservationSetResponse, MeasurementInput, RecordObservationsRequest,
+        TrendPoint, DeterioratingPatient,
+    },
+    prescription::{
+        Prescription, CreatePrescriptionRequest, PrescriptionResponse, PrescriptionActionRequest,
+        DuplicateMedication, MedicationHistory,
+    },
+    lab::{
+        LabTestType, CreateLabTestTypeRequest, LabOrder, LabOrderResponse, CreateLabOrderRequest, CollectSpecimenRequest,
+        EnterLabResultRequest, VerifyLabResultRequest,
+    },
+    allergy::{Allergy, CreateAllergyRequest, UpdateAllergyRequest},
+    problem::{Problem, CreateProblemRequest, UpdateProblemRequest},
     api_response::{Meta, HospitalListResponse, HospitalSingleResponse},
 };
 use crate::routes::hospitals;
@@ -32,16 +94,157 @@ use crate::routes::hospitals;
             HospitalSingleResponse,
             Staff,
             CreateStaffRequest,
+            StaffAccount,
+            SetStaffAccountRequest,
+            StaffLoginResponse,
             Patient,
             CreatePatientRequest,
             Visit,
             CreateVisitRequest,
+            UpdateVisitStatusRequest,
             Equipment,
             CreateEquipmentRequest,
+            Shift,
+            CreateShiftRequest,
+            RotaTemplate,
+            CreateRotaTemplateRequest,
+            ApplyRotaRequest,
+            ApplyRotaResponse,
+            CoverageRule,
+            UpsertCoverageRuleRequest,
+            OnDutyStaff,
+            CoverageGap,
+            Credential,
+            CreateCredentialRequest,
+            ExpiringCredential,
+            AvailabilitySlot,
+            CreateSlotsRequest,
+            BookSlotRequest,
+            RescheduleAppointmentRequest,
+            CancelAppointmentRequest,
+            AppointmentChange,
+            MaintenancePlan,
+            CreateMaintenancePlanRequest,
+            WorkOrder,
+            CreateWorkOrderRequest,
+            CloseWorkOrderRequest,
+            ConditionLogEntry,
+            UpdateConditionRequest,
+            OverdueMaintenance,
+            EquipmentTransfer,
+            CreateTransferRequest,
+            TransferActionRequest,
+            CustodyEvent,
+            InventoryItem,
+            CreateInventoryItemRequest,
+            InventoryTransaction,
+            CreateInventoryTransactionRequest,
+            StockMovementResponse,
+            CapabilitySummary,
+            BloodUnitBatch,
+            ReportBloodUnitsRequest,
+            IssueBloodUnitsRequest,
+            BloodStockLevel,
+            BloodSearchResult,
+            VisitDiagnosis,
+            CreateDiagnosisRequest,
+            Icd10Code,
+            NotifiableCondition,
+            CreateNotifiableConditionRequest,
+            UpdateNotifiableConditionRequest,
+            SurveillanceCase,
+            WeeklyCaseCount,
+            OutbreakAlert,
+            ObservationSet,
+            Observation,
+            ObservationSetResponse,
+            MeasurementInput,
+            RecordObservationsRequest,
+            TrendPoint,
+            DeterioratingPatient,
+            Prescription,
+            CreatePrescriptionRequest,
+            PrescriptionResponse,
+            PrescriptionActionRequest,
+            DuplicateMedication,
+            MedicationHistory,
+            LabTestType,
+            CreateLabTestTypeRequest,
+            LabOrder,
+            LabOrderResponse,
+            CreateLabOrderRequest,
+            CollectSpecimenRequest,
+            EnterLabResultRequest,
+            VerifyLabResultRequest,
+            Allergy,
+            CreateAllergyRequest,
+            UpdateAllergyRequest,
+            Problem,
+            CreateProblemRequest,
+            UpdateProblemRequest,
+            PatientRecord,
+            TimelineEvent,
+            PatientTimeline,
+            Consent,
+            GrantConsentRequest,
+            RevokeConsentRequest,
+            AccessLogEntry,
+            PiiKeyVersionCount,
+            PiiKeyStatus,
+            PublicHospital,
+            PublicVisitCount,
+            PublicVisitCounts,
+            OccupancyGroup,
+            HospitalOccupancy,
+            HighOccupancySummary,
+            StateCoverage,
+            DailyVisitVolume,
+            ForecastVsActual,
+            ForecastComparison,
+            ForecastRunSummary,
+            AlertRule,
+            CreateAlertRuleRequest,
+            CapacityAlert,
+            AcknowledgeAlertRequest,
+            ResolveCapacityAlertRequest,
+            AlertSubscription,
+            CreateAlertSubscriptionRequest,
+            AlertDelivery,
+            WebhookEndpoint,
+            CreateWebhookEndpointRequest,
+            RegisteredWebhookEndpoint,
+            WebhookDelivery,
+            WebhookDispatchSummary,
+            OutboxDispatchSummary,
+            OutboxStatus,
+            ParkedOutboxEvent,
+            NotificationTemplate,
+            UpsertNotificationTemplateRequest,
+            NotificationPreference,
+            UpdateNotificationPreferenceRequest,
+            SendNotificationRequest,
+            Notification,
+            NotificationDispatchSummary,
         )
     ),
+    modifiers(&SessionSchemes),
     tags(
         (name = "Hospitals", description = "Manage hospital records")
     )
 )]
-pub struct ApiDoc;
\ No newline at end of file
+pub struct ApiDoc;
+
+/// Bearer tokens from `POST /api/v1/login` (admin) and `POST /api/v1/staff/login` (staff).
+struct SessionSchemes;
+
+impl Modify for SessionSchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        for name in ["admin_session", "staff_session"] {
+            components.add_security_scheme(
+                name,
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+            );
+        }
+    }
+}
\ No newline at end of file
@@ -1,12 +1,23 @@
 use crate::errors::app::AppError;
 
+/// Maps database errors for every handler: a missing row is a 404, while duplicate, dangling
+/// and out-of-range values are client errors. Anything else is a 500.
 impl From<sqlx::Error> for AppError {
     fn from(error: sqlx::Error) -> Self {
+        // A `fetch_one` that matched nothing (e.g. an INSERT ... SELECT from a missing parent row)
+        if let sqlx::Error::RowNotFound = &error {
+            return AppError::NotFound;
+        }
+
         if let sqlx::Error::Database(db_err) = &error {
             // PostgreSQL error code for Unique Violation is "23505"
             if db_err.code().as_deref() == Some("23505") {
                 return AppError::Conflict("This record already exists.".to_string());
             }
+            // PostgreSQL error code for Foreign Key Violation is "23503"
+            if db_err.code().as_deref() == Some("23503") {
+                return AppError::BadRequest("Referenced record does not exist.".to_string());
+            }
             // PostgreSQL error code for Check Violation is "23514"
             if db_err.code().as_deref() == Some("23514") {
                  return AppError::BadRequest("Invalid data format (check constraint failed).".to_string());
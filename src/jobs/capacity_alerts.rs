@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    db::capacity_alert_repo,
+    models::{
+        capacity_alert::{AlertRule, AlertSubscription, CapacityAlert, HospitalCapacityMetrics},
+        notification::TEMPLATE_CAPACITY_ALERT_RAISED,
+    },
+    notify::Notifier,
+};
+
+/// Checks one rule against a hospital's current figures. Returns the observed value and a
+/// human-readable message when the condition holds, `None` when it does not.
+pub fn evaluate_rule(rule: &AlertRule, metrics: &HospitalCapacityMetrics) -> Option<(Option<f64>, String)> {
+    match rule.metric.as_str() {
+        "OCCUPANCY_ABOVE" => {
+            let threshold = rule.threshold?;
+            if metrics.total_beds <= 0 {
+                return None;
+            }
+            let occupancy = 100.0 * metrics.occupied_beds as f64 / metrics.total_beds as f64;
+            (occupancy >= threshold).then(|| {
+                (
+                    Some(occupancy),
+                    format!(
+                        "{}: bed occupancy {:.1}% ({}/{}) is at or above {:.1}%",
+                        metrics.name, occupancy, metrics.occupied_beds, metrics.total_beds, threshold
+                    ),
+                )
+            })
+        }
+        "OXYGEN_LOW" => matches!(metrics.oxygen_status.as_str(), "LOW" | "OUT").then(|| {
+            (None, format!("{}: oxygen stock is {}", metrics.name, metrics.oxygen_status))
+        }),
+        "EMERGENCY_CLOSED" => (!metrics.has_emergency).then(|| {
+            (None, format!("{}: emergency department is not available", metrics.name))
+        }),
+        "BROKEN_EQUIPMENT_ABOVE" => {
+            let threshold = rule.threshold?;
+            let broken = metrics.broken_equipment as f64;
+            (broken > threshold).then(|| {
+                (
+                    Some(broken),
+                    format!("{}: {} broken equipment item(s), above {}", metrics.name, metrics.broken_equipment, threshold),
+                )
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Evaluates every rule covering a hospital: raises alerts whose condition now holds, refreshes
+/// the reading on ones already open, and clears those whose condition has stopped holding.
+/// Returns the newly raised alerts; their `capacity_alert.raised` outbox events deliver them
+/// to subscribers here and to partner webhook endpoints subscribed to that event.
+/// Evaluating twice in a row changes nothing, so repeated `capacity.changed` relays are safe.
+pub async fn evaluate_hospital(pool: &PgPool, hospital_id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+    let Some(metrics) = capacity_alert_repo::get_capacity_metrics(pool, hospital_id).await? else {
+        return Ok(Vec::new());
+    };
+
+    let rules = capacity_alert_repo::get_rules_for_hospital(pool, hospital_id, &metrics.state).await?;
+    let rule_ids: Vec<Uuid> = rules.iter().map(|r| r.id).collect();
+    capacity_alert_repo::clear_orphaned_alerts(pool, hospital_id, &rule_ids).await?;
+
+    let mut raised = Vec::new();
+    for rule in &rules {
+        match evaluate_rule(rule, &metrics) {
+            Some((observed, message)) => {
+                let (alert_id, inserted) =
+                    capacity_alert_repo::raise_alert(pool, rule, hospital_id, observed, &message).await?;
+                if inserted {
+                    raised.push(alert_id);
+                }
+            }
+            None => {
+                capacity_alert_repo::clear_alert(pool, rule.id, hospital_id).await?;
+            }
+        }
+    }
+
+    Ok(raised)
+}
+
+/// Sends a newly raised alert to its subscribers without holding up the caller. Subscribers
+/// that already have it are skipped, so a repeated relay does not notify them twice.
+pub fn deliver_in_background(pool: &PgPool, notifier: &Notifier, alert_id: Uuid) {
+    let pool = pool.clone();
+    let notifier = notifier.clone();
+    tokio::spawn(async move {
+        if let Err(e) = deliver(&pool, &notifier, alert_id).await {
+            tracing::error!("Delivering capacity alert {} failed: {:?}", alert_id, e);
+        }
+    });
+}
+
+async fn deliver(pool: &PgPool, notifier: &Notifier, alert_id: Uuid) -> Result<(), sqlx::Error> {
+    let Some(alert) = capacity_alert_repo::get_alert(pool, alert_id).await? else {
+        return Ok(());
+    };
+
+    let subscriptions = capacity_alert_repo::get_subscriptions_for_alert(pool, &alert).await?;
+    for subscription in &subscriptions {
+        let error = send(notifier, subscription, &alert).await.err();
+        capacity_alert_repo::record_delivery(pool, alert.id, subscription, error).await?;
+    }
+
+    Ok(())
+}
+
+async fn send(notifier: &Notifier, subscription: &AlertSubscription, alert: &CapacityAlert) -> Result<(), String> {
+    match subscription.channel.as_str() {
+        "LOG" => {
+            tracing::warn!(
+                alert_id = %alert.id,
+                hospital_id = %alert.hospital_id,
+                severity = %alert.severity,
+                target = subscription.target.as_deref().unwrap_or(""),
+                "Capacity alert: {}",
+                alert.message
+            );
+            Ok(())
+        }
+        "STAFF" => {
+            // Queued on the staff member's channels; the notification log tracks actual sending
+            let staff_id = subscription
+                .target
+                .as_deref()
+                .and_then(|t| Uuid::parse_str(t).ok())
+                .ok_or("Staff subscription target is not a staff id")?;
+            let variables = HashMap::from([
+                ("hospital_name".to_string(), alert.hospital_name.clone()),
+                ("severity".to_string(), alert.severity.clone()),
+                ("message".to_string(), alert.message.clone()),
+                ("rule_name".to_string(), alert.rule_name.clone()),
+                ("triggered_at".to_string(), alert.triggered_at.to_rfc3339()),
+            ]);
+            let dedupe_key = format!("capacity_alert:{}", alert.id);
+            notifier
+                .send_to_staff(staff_id, TEMPLATE_CAPACITY_ALERT_RAISED, None, &variables, Some(&dedupe_key))
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+        other => Err(format!("Unsupported channel {}", other)),
+    }
+}
+
+/// Re-evaluates every active hospital. Catches changes that bypass the handler hooks (direct
+/// database edits, rules created after the last change). Returns the number of alerts raised.
+pub async fn run_once(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let mut raised = 0;
+    for hospital_id in capacity_alert_repo::get_active_hospital_ids(pool).await? {
+        raised += evaluate_hospital(pool, hospital_id).await?.len();
+    }
+    Ok(raised)
+}
+
+/// Runs the sweep on a fixed interval.
+pub fn spawn(pool: PgPool, interval_secs: u64) {
+    super::spawn_every(interval_secs, move || {
+        let pool = pool.clone();
+        async move {
+            match run_once(&pool).await {
+                Ok(count) if count > 0 => tracing::info!("Capacity alert sweep raised {} alert(s)", count),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Capacity alert sweep failed: {:?}", e),
+            }
+        }
+    });
+}
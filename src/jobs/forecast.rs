@@ -0,0 +1,183 @@
+use chrono::{DateTime, Datelike, Duration, DurationRound, RoundingError, Timelike, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    config::Settings,
+    db::forecast_repo,
+    errors::app::AppError,
+    models::forecast::{ForecastPoint, ForecastRunSummary, HourlyHistoryPoint},
+};
+
+pub const MODEL: &str = "SES_HOUR_OF_DAY";
+
+// Two-sided 95% interval under normally distributed errors
+const Z_95: f64 = 1.96;
+
+/// Why a forecast run failed.
+#[derive(Debug)]
+pub enum ForecastError {
+    /// The data cutoff cannot be rounded down to the hour (it is outside chrono's range)
+    InvalidCutoff(RoundingError),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for ForecastError {
+    fn from(e: sqlx::Error) -> Self {
+        ForecastError::Database(e)
+    }
+}
+
+impl From<ForecastError> for AppError {
+    fn from(e: ForecastError) -> Self {
+        match e {
+            ForecastError::InvalidCutoff(e) => AppError::BadRequest(format!("Invalid forecast cutoff: {}", e)),
+            ForecastError::Database(e) => e.into(),
+        }
+    }
+}
+
+/// Tuning for the occupancy forecaster: an hour-of-day profile plus a simple exponentially
+/// smoothed level, fitted on the last `lookback_days` of hourly occupancy.
+#[derive(Debug, Clone)]
+pub struct ForecastConfig {
+    pub lookback_days: i64,
+    // Weight of the newest observation in the level, between 0 and 1
+    pub alpha: f64,
+    pub horizon_hours: i64,
+    pub interval_secs: u64,
+}
+
+impl ForecastConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            lookback_days: settings.forecast_lookback_days.max(1),
+            alpha: settings.forecast_smoothing_alpha.clamp(0.01, 1.0),
+            horizon_hours: settings.forecast_horizon_hours.clamp(1, 24 * 14),
+            interval_secs: settings.forecast_interval_secs,
+        }
+    }
+}
+
+fn hour_of_week(t: DateTime<Utc>) -> usize {
+    t.weekday().num_days_from_monday() as usize * 24 + t.hour() as usize
+}
+
+fn round2(x: f64) -> f64 {
+    (x * 100.0).round() / 100.0
+}
+
+/// Predicts occupancy for each of the `horizon_hours` hours after the last history point.
+///
+/// The model is deliberately simple so it can be explained to planners:
+/// 1. Seasonality: how far each hour of the day sits above or below the overall mean.
+/// 2. Level: simple exponential smoothing of the series with that profile removed.
+/// 3. Prediction: level + the target hour's offset, clamped to the bed count.
+/// 4. Interval: ±1.96 one-step RMS errors, widened with the horizon as for SES.
+///
+/// Expected visits are the average for the same hour of the week in the history.
+pub fn forecast(history: &[HourlyHistoryPoint], alpha: f64, horizon_hours: i64) -> Vec<ForecastPoint> {
+    let Some(last) = history.last() else {
+        return Vec::new();
+    };
+
+    let values: Vec<f64> = history.iter().map(|p| p.occupied_beds as f64).collect();
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+
+    let mut hour_sums = [0.0; 24];
+    let mut hour_counts = [0usize; 24];
+    let mut visit_sums = [0.0; 168];
+    let mut visit_counts = [0usize; 168];
+    for (point, value) in history.iter().zip(&values) {
+        let hour = point.hour.hour() as usize;
+        hour_sums[hour] += value;
+        hour_counts[hour] += 1;
+
+        let how = hour_of_week(point.hour);
+        visit_sums[how] += point.visits as f64;
+        visit_counts[how] += 1;
+    }
+    let offsets: Vec<f64> = (0..24)
+        .map(|h| if hour_counts[h] > 0 { hour_sums[h] / hour_counts[h] as f64 - mean } else { 0.0 })
+        .collect();
+
+    let deseasonalised: Vec<f64> = history
+        .iter()
+        .zip(&values)
+        .map(|(p, v)| v - offsets[p.hour.hour() as usize])
+        .collect();
+    let mut level = deseasonalised[0];
+    let mut squared_error = 0.0;
+    for d in &deseasonalised[1..] {
+        let error = d - level;
+        squared_error += error * error;
+        level += alpha * error;
+    }
+    let sigma = if deseasonalised.len() > 1 {
+        (squared_error / (deseasonalised.len() - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+
+    let capacity = last.total_beds as f64;
+    (1..=horizon_hours)
+        .map(|k| {
+            let target_time = last.hour + Duration::hours(k);
+            let predicted = (level + offsets[target_time.hour() as usize]).clamp(0.0, capacity);
+            let spread = Z_95 * sigma * (1.0 + (k - 1) as f64 * alpha * alpha).sqrt();
+            let how = hour_of_week(target_time);
+            let expected_visits = if visit_counts[how] > 0 { visit_sums[how] / visit_counts[how] as f64 } else { 0.0 };
+
+            ForecastPoint {
+                target_time,
+                horizon_hours: k as i32,
+                predicted_occupied: round2(predicted),
+                lower_bound: round2((predicted - spread).max(0.0)),
+                upper_bound: round2((predicted + spread).min(capacity)),
+                total_beds: last.total_beds,
+                expected_visits: round2(expected_visits),
+            }
+        })
+        .collect()
+}
+
+/// Forecasts every active hospital with beds (or just `hospital_id`) from history up to `as_of`
+/// (now, unless backtesting) and stores the predictions.
+pub async fn run_once(
+    pool: &PgPool,
+    config: &ForecastConfig,
+    as_of: Option<DateTime<Utc>>,
+    hospital_id: Option<Uuid>,
+) -> Result<ForecastRunSummary, ForecastError> {
+    let generated_at = as_of.unwrap_or_else(Utc::now);
+    let last_hour = generated_at
+        .duration_trunc(Duration::hours(1))
+        .map_err(ForecastError::InvalidCutoff)?;
+    let first_hour = last_hour - Duration::days(config.lookback_days);
+
+    let mut hospitals = 0;
+    let mut predictions = 0;
+    for hospital_id in forecast_repo::get_forecastable_hospitals(pool, hospital_id).await? {
+        let history = forecast_repo::get_hourly_history(pool, hospital_id, first_hour, last_hour).await?;
+        let points = forecast(&history, config.alpha, config.horizon_hours);
+        if points.is_empty() {
+            continue;
+        }
+
+        predictions += forecast_repo::store_forecasts(pool, hospital_id, generated_at, MODEL, &points).await? as i64;
+        hospitals += 1;
+    }
+
+    Ok(ForecastRunSummary { generated_at, hospitals, predictions })
+}
+
+/// Refreshes forecasts on a fixed interval.
+pub fn spawn(pool: PgPool, config: ForecastConfig) {
+    super::spawn_every(config.interval_secs, move || {
+        let (pool, config) = (pool.clone(), config.clone());
+        async move {
+            if let Err(e) = run_once(&pool, &config, None, None).await {
+                tracing::error!("Occupancy forecasting failed: {:?}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,41 @@
+use std::{future::Future, time::Duration as StdDuration};
+use chrono::Duration;
+
+pub mod outbreak;
+pub mod pii_rotation;
+pub mod forecast;
+pub mod capacity_alerts;
+pub mod webhooks;
+pub mod outbox;
+pub mod notifications;
+
+// Longest back-off, whatever the configuration, so retry times stay representable
+const MAX_BACKOFF_SECS: i64 = 365 * 24 * 3600;
+
+/// Wait before the next attempt, after `attempts` failed ones: base, 2x base, 4x base, ...
+/// capped at `max_secs`.
+pub fn backoff(attempts: i32, base_secs: i64, max_secs: i64) -> Duration {
+    let exponent = (attempts.max(1) - 1).min(30) as u32;
+    let secs = base_secs.saturating_mul(2_i64.saturating_pow(exponent));
+    Duration::seconds(secs.min(max_secs).clamp(0, MAX_BACKOFF_SECS))
+}
+
+/// Runs `run` in the background every `interval_secs` seconds, starting now. A zero interval
+/// disables the job.
+pub fn spawn_every<F, Fut>(interval_secs: u64, mut run: F)
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    if interval_secs == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(StdDuration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            run().await;
+        }
+    });
+}
@@ -0,0 +1,20 @@
+use crate::notify::Notifier;
+
+/// Sends due notification retries on a fixed interval.
+pub fn spawn(notifier: Notifier) {
+    super::spawn_every(notifier.interval_secs, move || {
+        let notifier = notifier.clone();
+        async move {
+            match notifier.dispatch(None).await {
+                Ok(summary) if summary.attempted > 0 => tracing::info!(
+                    "Notification dispatch: {} sent, {} retrying, {} failed",
+                    summary.sent,
+                    summary.retrying,
+                    summary.failed
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Notification dispatch failed: {:?}", e),
+            }
+        }
+    });
+}
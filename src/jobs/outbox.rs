@@ -0,0 +1,267 @@
+use std::{collections::HashSet, fmt, sync::Arc};
+use chrono::Utc;
+use sqlx::PgPool;
+use tokio::sync::{Mutex, Notify};
+use uuid::Uuid;
+use crate::{
+    config::Settings,
+    db::{capacity_alert_repo, outbox_repo},
+    jobs::{capacity_alerts, webhooks::{self, WebhookConfig}},
+    models::{
+        outbox::{CapacityAlertChanged, CapacityChanged, OutboxDispatchSummary, OutboxEvent},
+        webhook::{EVENT_CAPACITY_ALERT_RAISED, EVENT_CAPACITY_CHANGED, WEBHOOK_EVENT_TYPES},
+    },
+    notify::Notifier,
+    ws::hub::{EventHub, HubEvent},
+};
+
+const PURGE_EVERY_SECS: u64 = 3600;
+
+/// Why a subscriber could not handle an outbox event. Recorded on the event as its last error.
+#[derive(Debug)]
+pub enum OutboxError {
+    /// The stored payload does not have the shape its event type promises
+    Payload(serde_json::Error),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for OutboxError {
+    fn from(e: sqlx::Error) -> Self {
+        OutboxError::Database(e)
+    }
+}
+
+impl From<serde_json::Error> for OutboxError {
+    fn from(e: serde_json::Error) -> Self {
+        OutboxError::Payload(e)
+    }
+}
+
+impl fmt::Display for OutboxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutboxError::Payload(e) => write!(f, "invalid event payload: {}", e),
+            OutboxError::Database(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Relays outbox events to the in-process subscribers: capacity alerting (and its notifications),
+/// outbound webhooks and the live event hub. Delivery is at-least-once: an event is marked published only after every
+/// subscriber has handled it, so subscribers must tolerate seeing an event twice. Events for the
+/// same aggregate are relayed in commit order; one that fails holds back the later ones until it
+/// succeeds on a retry or runs out of attempts and is parked.
+#[derive(Clone)]
+pub struct OutboxDispatcher {
+    pool: PgPool,
+    hub: EventHub,
+    webhooks: WebhookConfig,
+    notifier: Notifier,
+    batch_size: i64,
+    max_attempts: i32,
+    retry_base_secs: i64,
+    retry_max_secs: i64,
+    // Queues relays within this process; the advisory lock covers other processes
+    running: Arc<Mutex<()>>,
+    // Wakes the relay worker when handlers commit new events
+    pending: Arc<Notify>,
+}
+
+impl OutboxDispatcher {
+    pub fn new(pool: PgPool, hub: EventHub, webhooks: WebhookConfig, notifier: Notifier, settings: &Settings) -> Self {
+        Self {
+            pool,
+            hub,
+            webhooks,
+            notifier,
+            batch_size: settings.outbox_batch_size.max(1),
+            max_attempts: settings.outbox_max_attempts.max(1),
+            retry_base_secs: settings.outbox_retry_base_secs.max(1),
+            retry_max_secs: settings.outbox_retry_max_secs.max(1),
+            running: Arc::new(Mutex::new(())),
+            pending: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Relays everything currently in the outbox, one batch (and transaction) at a time.
+    pub async fn dispatch(&self) -> Result<OutboxDispatchSummary, sqlx::Error> {
+        let _running = self.running.lock().await;
+        let mut summary = OutboxDispatchSummary::default();
+        loop {
+            let batch = self.dispatch_batch().await?;
+            let handled = batch.published + batch.failed + batch.parked;
+            summary.published += batch.published;
+            summary.failed += batch.failed;
+            summary.parked += batch.parked;
+            summary.deferred += batch.deferred;
+            // Stop when nothing is due: failed events (and those behind them) wait for their retry
+            if handled == 0 {
+                return Ok(summary);
+            }
+        }
+    }
+
+    /// Called by handlers right after their change commits, so subscribers see it without
+    /// waiting for the next poll. Only wakes the relay worker: the request does not wait for
+    /// subscribers, and wakes that arrive while a relay is running are folded into one more.
+    pub fn wake(&self) {
+        self.pending.notify_one();
+    }
+
+    async fn dispatch_batch(&self) -> Result<OutboxDispatchSummary, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        outbox_repo::lock_dispatch(&mut tx).await?;
+        let events = outbox_repo::get_unpublished(&mut tx, self.batch_size).await?;
+
+        let mut summary = OutboxDispatchSummary::default();
+        let mut published = Vec::new();
+        let mut blocked: HashSet<Uuid> = HashSet::new();
+        for event in &events {
+            if blocked.contains(&event.aggregate_id) {
+                summary.deferred += 1;
+                continue;
+            }
+            match self.handle(event).await {
+                Ok(()) => published.push(event.id),
+                Err(e) => {
+                    let attempts = event.attempts + 1;
+                    let retry_at = (attempts < self.max_attempts).then(|| Utc::now() + super::backoff(attempts, self.retry_base_secs, self.retry_max_secs));
+                    if retry_at.is_some() {
+                        tracing::warn!("Relaying outbox event {} ({}) failed: {:?}", event.id, event.event_type, e);
+                        blocked.insert(event.aggregate_id);
+                        summary.failed += 1;
+                    } else {
+                        tracing::error!(
+                            "Parking outbox event {} ({}) after {} attempts: {:?}",
+                            event.id,
+                            event.event_type,
+                            attempts,
+                            e
+                        );
+                        summary.parked += 1;
+                    }
+                    outbox_repo::mark_failed(&mut tx, event.id, &e.to_string(), retry_at).await?;
+                }
+            }
+        }
+
+        outbox_repo::mark_published(&mut tx, &published).await?;
+        tx.commit().await?;
+        summary.published = published.len();
+        Ok(summary)
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), OutboxError> {
+        let data: serde_json::Value = serde_json::from_str(&event.payload)?;
+
+        // Alerting
+        match event.event_type.as_str() {
+            EVENT_CAPACITY_CHANGED => {
+                let changed: CapacityChanged = decode(&data)?;
+                capacity_alerts::evaluate_hospital(&self.pool, changed.hospital_id).await?;
+            }
+            EVENT_CAPACITY_ALERT_RAISED => {
+                let raised: CapacityAlertChanged = decode(&data)?;
+                capacity_alerts::deliver_in_background(&self.pool, &self.notifier, raised.alert_id);
+            }
+            _ => {}
+        }
+
+        // Outbound webhooks
+        if WEBHOOK_EVENT_TYPES.contains(&event.event_type.as_str()) {
+            let webhook_data = match event.event_type.as_str() {
+                EVENT_CAPACITY_CHANGED => {
+                    // Partners get the figures as they are now, not just which hospital changed
+                    let changed: CapacityChanged = decode(&data)?;
+                    match capacity_alert_repo::get_capacity_metrics(&self.pool, changed.hospital_id).await? {
+                        Some(metrics) => Some(serde_json::to_value(metrics)?),
+                        None => None,
+                    }
+                }
+                EVENT_CAPACITY_ALERT_RAISED => {
+                    // The whole alert, so partners need not call back for the message and severity
+                    let raised: CapacityAlertChanged = decode(&data)?;
+                    match capacity_alert_repo::get_alert(&self.pool, raised.alert_id).await? {
+                        Some(alert) => Some(serde_json::to_value(alert)?),
+                        None => None,
+                    }
+                }
+                _ => Some(data.clone()),
+            };
+            if let Some(webhook_data) = webhook_data {
+                webhooks::publish(
+                    &self.pool,
+                    &self.webhooks,
+                    event.event_id,
+                    &event.event_type,
+                    event.created_at,
+                    webhook_data,
+                )
+                .await?;
+            }
+        }
+
+        // Live clients, once the durable subscribers have succeeded
+        self.hub.publish(HubEvent {
+            sequence: event.id,
+            event_id: event.event_id,
+            event_type: event.event_type.clone(),
+            aggregate_type: event.aggregate_type.clone(),
+            aggregate_id: event.aggregate_id,
+            occurred_at: event.created_at,
+            data,
+        });
+
+        Ok(())
+    }
+}
+
+fn decode<T: serde::de::DeserializeOwned>(data: &serde_json::Value) -> Result<T, OutboxError> {
+    Ok(T::deserialize(data)?)
+}
+
+/// Relays whenever a handler calls `wake`. Runs wherever the router does, since handlers rely on
+/// it to get their events to subscribers promptly.
+pub fn spawn_relay(dispatcher: OutboxDispatcher) {
+    tokio::spawn(async move {
+        loop {
+            dispatcher.pending.notified().await;
+            if let Err(e) = dispatcher.dispatch().await {
+                tracing::error!("Outbox relay failed: {:?}", e);
+            }
+        }
+    });
+}
+
+/// Relays on a fixed interval (picking up events a crashed or failed relay left behind) and
+/// hourly purges published events past the retention period. A zero interval disables both.
+pub fn spawn(dispatcher: OutboxDispatcher, interval_secs: u64, retain_days: i64) {
+    if retain_days > 0 && interval_secs > 0 {
+        let pool = dispatcher.pool.clone();
+        super::spawn_every(PURGE_EVERY_SECS, move || {
+            let pool = pool.clone();
+            async move {
+                if let Err(e) = outbox_repo::purge_published(&pool, retain_days).await {
+                    tracing::error!("Outbox purge failed: {:?}", e);
+                }
+            }
+        });
+    }
+
+    super::spawn_every(interval_secs, move || {
+        let dispatcher = dispatcher.clone();
+        async move {
+            match dispatcher.dispatch().await {
+                Ok(summary) if summary.failed + summary.parked > 0 => tracing::warn!(
+                    "Outbox relay: {} published, {} failed, {} parked, {} deferred",
+                    summary.published,
+                    summary.failed,
+                    summary.parked,
+                    summary.deferred
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Outbox relay failed: {:?}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    config::Settings,
+    db::{outbreak_repo, surveillance_repo},
+    models::outbreak::OutbreakSignal,
+};
+
+/// Tuning for the weekly outbreak detector: an area is flagged when this week's cases
+/// exceed the mean of the previous `baseline_weeks` plus `threshold_sd` standard deviations.
+#[derive(Debug, Clone)]
+pub struct OutbreakConfig {
+    pub baseline_weeks: u32,
+    pub threshold_sd: f64,
+    // Floor that stops a single case in a previously silent area from raising an alert
+    pub min_cases: i64,
+    pub check_interval_secs: u64,
+}
+
+impl OutbreakConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            baseline_weeks: settings.outbreak_baseline_weeks,
+            threshold_sd: settings.outbreak_threshold_sd,
+            min_cases: settings.outbreak_min_cases,
+            check_interval_secs: settings.outbreak_check_interval_secs,
+        }
+    }
+}
+
+/// Baseline statistics for one series. `alert` is set when `current` crosses the threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    pub mean: f64,
+    pub stddev: f64,
+    pub threshold: f64,
+    pub alert: bool,
+}
+
+/// Moving average + k standard deviations over the baseline weeks (population SD).
+pub fn detect(current: i64, baseline: &[i64], threshold_sd: f64, min_cases: i64) -> Detection {
+    let n = baseline.len().max(1) as f64;
+    let mean = baseline.iter().sum::<i64>() as f64 / n;
+    let variance = baseline.iter().map(|&c| (c as f64 - mean).powi(2)).sum::<f64>() / n;
+    let stddev = variance.sqrt();
+    let threshold = mean + threshold_sd * stddev;
+
+    Detection {
+        mean,
+        stddev,
+        threshold,
+        alert: current >= min_cases && current as f64 > threshold,
+    }
+}
+
+/// Monday of the week containing `date`, matching Postgres `DATE_TRUNC('week', ...)`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Scans the current week against the baseline window and stores an alert for every
+/// condition/area that crosses the threshold. Returns the alerts raised or refreshed.
+pub async fn run_once(pool: &PgPool, config: &OutbreakConfig) -> Result<Vec<OutbreakSignal>, sqlx::Error> {
+    let current_week = week_start(Utc::now().date_naive());
+    let window_start = current_week - Duration::weeks(config.baseline_weeks as i64);
+    let window_end = current_week + Duration::weeks(1);
+
+    let counts = surveillance_repo::get_weekly_counts(
+        pool,
+        None,
+        window_start.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+        window_end.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+    )
+    .await?;
+
+    // (condition, state, lga) -> week -> cases; weeks with no cases count as zero
+    let mut series: HashMap<(Uuid, String, String), HashMap<NaiveDate, i64>> = HashMap::new();
+    for row in counts {
+        series
+            .entry((row.condition_id, row.state, row.lga))
+            .or_default()
+            .insert(row.week_start, row.cases);
+    }
+
+    let mut signals = Vec::new();
+    for ((condition_id, state, lga), weeks) in series {
+        let current = weeks.get(&current_week).copied().unwrap_or(0);
+        let baseline: Vec<i64> = (1..=config.baseline_weeks as i64)
+            .map(|i| weeks.get(&(current_week - Duration::weeks(i))).copied().unwrap_or(0))
+            .collect();
+
+        let detection = detect(current, &baseline, config.threshold_sd, config.min_cases);
+        if !detection.alert {
+            continue;
+        }
+
+        let signal = OutbreakSignal {
+            condition_id,
+            state,
+            lga,
+            week_start: current_week,
+            observed_cases: current,
+            baseline_mean: detection.mean,
+            baseline_stddev: detection.stddev,
+            threshold: detection.threshold,
+        };
+        outbreak_repo::upsert_alert(pool, &signal).await?;
+        signals.push(signal);
+    }
+
+    Ok(signals)
+}
+
+/// Runs the detector on a fixed interval.
+pub fn spawn(pool: PgPool, config: OutbreakConfig) {
+    super::spawn_every(config.check_interval_secs, move || {
+        let (pool, config) = (pool.clone(), config.clone());
+        async move {
+            match run_once(&pool, &config).await {
+                Ok(signals) if !signals.is_empty() => {
+                    tracing::warn!("Outbreak detection raised {} alert(s)", signals.len());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Outbreak detection failed: {:?}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,56 @@
+use sqlx::PgPool;
+use crate::{config::Settings, crypto::pii::PiiCipher, db::patient_repo, models::patient::ReencryptSummary};
+
+/// Background re-encryption of patient PII onto the active key after a rotation.
+#[derive(Debug, Clone)]
+pub struct PiiRotationConfig {
+    pub batch_size: i64,
+    pub interval_secs: u64,
+}
+
+impl PiiRotationConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            batch_size: settings.pii_reencrypt_batch_size.max(1),
+            interval_secs: settings.pii_reencrypt_interval_secs,
+        }
+    }
+}
+
+/// Re-encrypts every patient not yet under the active key, one batch (and transaction) at a time.
+/// Returns how many patients were rewritten and how many were parked as unreadable.
+pub async fn run_once(pool: &PgPool, cipher: &PiiCipher, config: &PiiRotationConfig) -> Result<ReencryptSummary, sqlx::Error> {
+    let mut total = ReencryptSummary::default();
+    loop {
+        let batch = patient_repo::reencrypt_batch(pool, cipher, config.batch_size).await?;
+        total.rewritten += batch.rewritten;
+        total.parked += batch.parked;
+        if batch.rewritten + batch.parked < config.batch_size as u64 {
+            return Ok(total);
+        }
+    }
+}
+
+/// Runs the re-encryption sweep on a fixed interval.
+pub fn spawn(pool: PgPool, cipher: PiiCipher, config: PiiRotationConfig) {
+    super::spawn_every(config.interval_secs, move || {
+        let (pool, cipher, config) = (pool.clone(), cipher.clone(), config.clone());
+        async move {
+            match run_once(&pool, &cipher, &config).await {
+                Ok(summary) => {
+                    if summary.rewritten > 0 {
+                        tracing::info!(
+                            "Re-encrypted PII of {} patient(s) under key version {}",
+                            summary.rewritten,
+                            cipher.active_version()
+                        );
+                    }
+                    if summary.parked > 0 {
+                        tracing::warn!("Parked {} patient(s) whose PII could not be decrypted", summary.parked);
+                    }
+                }
+                Err(e) => tracing::error!("PII re-encryption failed: {:?}", e),
+            }
+        }
+    });
+}
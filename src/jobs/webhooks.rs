@@ -0,0 +1,170 @@
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    config::Settings,
+    db::webhook_repo,
+    models::webhook::{DueWebhookDelivery, WebhookDispatchSummary},
+};
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+
+/// Retry policy and pacing for outbound webhook deliveries.
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    pub max_attempts: i32,
+    pub retry_base_secs: i64,
+    pub retry_max_secs: i64,
+    pub timeout_secs: u64,
+    pub batch_size: i64,
+    pub interval_secs: u64,
+}
+
+impl WebhookConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            max_attempts: settings.webhook_max_attempts.max(1),
+            retry_base_secs: settings.webhook_retry_base_secs.max(1),
+            retry_max_secs: settings.webhook_retry_max_secs.max(1),
+            timeout_secs: settings.webhook_timeout_secs.max(1),
+            batch_size: settings.webhook_batch_size.max(1),
+            interval_secs: settings.webhook_dispatch_interval_secs,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EventEnvelope<'a, T: Serialize> {
+    id: Uuid,
+    #[serde(rename = "type")]
+    event_type: &'a str,
+    occurred_at: DateTime<Utc>,
+    data: T,
+}
+
+/// Hex HMAC-SHA256 of `"{timestamp}.{body}"`, sent as `sha256=<hex>`. Including the timestamp
+/// lets receivers reject replayed requests.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Queues an event for every subscribed endpoint and makes the first attempt in the background.
+/// Queueing the same event again (a repeated outbox relay) does not duplicate deliveries.
+pub async fn publish<T: Serialize>(
+    pool: &PgPool,
+    config: &WebhookConfig,
+    event_id: Uuid,
+    event_type: &str,
+    occurred_at: DateTime<Utc>,
+    data: T,
+) -> Result<(), sqlx::Error> {
+    let envelope = EventEnvelope { id: event_id, event_type, occurred_at, data };
+    let payload = serde_json::to_string(&envelope).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+
+    let ids = webhook_repo::enqueue_event(pool, event_id, event_type, &payload).await?;
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let pool = pool.clone();
+    let config = config.clone();
+    tokio::spawn(async move {
+        if let Err(e) = dispatch(&pool, &config, Some(&ids)).await {
+            tracing::error!("Webhook dispatch failed: {:?}", e);
+        }
+    });
+    Ok(())
+}
+
+/// Sends one batch of due deliveries (or just `only`, if they are due), recording each outcome
+/// and scheduling retries with exponential back-off.
+pub async fn dispatch(
+    pool: &PgPool,
+    config: &WebhookConfig,
+    only: Option<&[Uuid]>,
+) -> Result<WebhookDispatchSummary, sqlx::Error> {
+    // Long enough that a claimed delivery is not picked up again while its request is in flight
+    let lease_secs = (config.timeout_secs + 30) as f64;
+    let due = webhook_repo::claim_due_deliveries(pool, only, config.batch_size, lease_secs).await?;
+
+    let client = reqwest::Client::new();
+    let mut summary = WebhookDispatchSummary::default();
+    for delivery in &due {
+        summary.attempted += 1;
+        match send(&client, config, delivery).await {
+            Ok(status_code) => {
+                webhook_repo::mark_delivered(pool, delivery.id, status_code).await?;
+                summary.delivered += 1;
+            }
+            Err((status_code, error)) => {
+                let attempts = delivery.attempts + 1;
+                let retry_at = (attempts < config.max_attempts).then(|| Utc::now() + super::backoff(attempts, config.retry_base_secs, config.retry_max_secs));
+                if retry_at.is_some() {
+                    summary.retrying += 1;
+                } else {
+                    summary.failed += 1;
+                    tracing::warn!("Giving up on webhook delivery {} after {} attempts: {}", delivery.id, attempts, error);
+                }
+                webhook_repo::mark_attempt_failed(pool, delivery.id, status_code, &error, retry_at).await?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+async fn send(
+    client: &reqwest::Client,
+    config: &WebhookConfig,
+    delivery: &DueWebhookDelivery,
+) -> Result<i32, (Option<i32>, String)> {
+    let timestamp = Utc::now().timestamp();
+    let response = client
+        .post(&delivery.url)
+        .timeout(StdDuration::from_secs(config.timeout_secs))
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-Webhook-Id", delivery.id.to_string())
+        .header("X-Webhook-Event", &delivery.event_type)
+        .header("X-Webhook-Event-Id", delivery.event_id.to_string())
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(SIGNATURE_HEADER, sign(&delivery.secret, timestamp, &delivery.payload))
+        .body(delivery.payload.clone())
+        .send()
+        .await
+        .map_err(|e| (None, e.to_string()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16() as i32)
+    } else {
+        Err((Some(status.as_u16() as i32), format!("Endpoint responded with {}", status)))
+    }
+}
+
+/// Sends due retries on a fixed interval.
+pub fn spawn(pool: PgPool, config: WebhookConfig) {
+    super::spawn_every(config.interval_secs, move || {
+        let (pool, config) = (pool.clone(), config.clone());
+        async move {
+            match dispatch(&pool, &config, None).await {
+                Ok(summary) if summary.attempted > 0 => tracing::info!(
+                    "Webhook dispatch: {} delivered, {} retrying, {} failed",
+                    summary.delivered,
+                    summary.retrying,
+                    summary.failed
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Webhook dispatch failed: {:?}", e),
+            }
+        }
+    });
+}
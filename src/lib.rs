@@ -5,16 +5,33 @@ pub mod routes;
 pub mod middleware;
 pub mod ws;
 pub mod errors;
+pub mod jobs;
+pub mod crypto;
+pub mod notify;
 pub mod docs; 
 
 use sqlx::PgPool;
 use axum::Router;
 use tower_http::trace::TraceLayer;
 use config::Settings;
-use db::create_pool;
+use db::{create_pool, icd10_repo};
+use crypto::pii::PiiCipher;
+use jobs::{
+    forecast::ForecastConfig, outbreak::OutbreakConfig, outbox::{self, OutboxDispatcher}, pii_rotation::PiiRotationConfig,
+    webhooks::WebhookConfig,
+};
+use notify::Notifier;
+use ws::hub::EventHub;
 use routes::{create_router, AppState};
 
 pub async fn setup_app() -> (Router, PgPool) {
+    let (app, state) = build_app().await;
+    (app, state.db)
+}
+
+/// Like `setup_app`, but hands back the whole state so the server binary can run background
+/// jobs against the same event hub, outbox dispatcher and notifier as the router.
+pub async fn build_app() -> (Router, AppState) {
     dotenvy::dotenv().ok();
 
     // 1. Load Config
@@ -22,17 +39,35 @@ pub async fn setup_app() -> (Router, PgPool) {
     
     // 2. Connect to DB
     let db_pool = create_pool(&settings.database_url).await;
+
+    // 2b. Load the bundled ICD-10 code table
+    icd10_repo::load_bundled_codes(&db_pool).await.expect("Failed to load ICD-10 codes");
     
     // 3. Create AppState (With JWT Secret!)
+    let webhooks = WebhookConfig::from_settings(&settings);
+    let events = EventHub::with_replay_capacity(settings.event_replay_buffer_size);
+    let notifier = Notifier::from_settings(db_pool.clone(), &settings).expect("Invalid notification settings");
     let app_state = AppState { 
         db: db_pool.clone(),
-        jwt_secret: settings.jwt_secret.clone(), // <--- Added this line
+        jwt_secret: settings.jwt_secret.clone(),
+        outbreak: OutbreakConfig::from_settings(&settings),
+        pii: PiiCipher::from_settings(&settings).expect("Invalid PII encryption settings"),
+        pii_rotation: PiiRotationConfig::from_settings(&settings),
+        public_min_cell_size: settings.public_min_cell_size.max(1),
+        forecast: ForecastConfig::from_settings(&settings),
+        webhooks: webhooks.clone(),
+        events: events.clone(),
+        outbox: OutboxDispatcher::new(db_pool.clone(), events, webhooks, notifier.clone(), &settings),
+        notifier,
     };
 
+    // Handlers wake the outbox relay after they commit, so it runs alongside the router
+    outbox::spawn_relay(app_state.outbox.clone());
+
     // 4. Build Router
     let app = create_router()
         .layer(TraceLayer::new_for_http())
-        .with_state(app_state);
+        .with_state(app_state.clone());
         
-    (app, db_pool)
-}
\ No newline at end of file
+    (app, app_state)
+}
@@ -1,5 +1,5 @@
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use health_intel_backend::{config::Settings, setup_app}; // Import setup_app from lib
+use health_intel_backend::{config::Settings, jobs::{capacity_alerts, forecast, notifications, outbox, outbreak, pii_rotation, webhooks}, build_app}; // Import build_app from lib
 use tokio::net::TcpListener;
 
 #[tokio::main]
@@ -16,10 +16,20 @@ async fn main() {
         .init();
 
     // Use our new helper function from lib.rs
-    let (app, _) = setup_app().await;
+    let (app, state) = build_app().await;
+    let db_pool = state.db.clone();
 
-    // Load settings just for the port info
+    // Load settings for the port info and background jobs
     let settings = Settings::from_env().expect("Failed to load settings");
+
+    // Background jobs run only in the server binary, not in tests that reuse setup_app
+    outbreak::spawn(db_pool.clone(), outbreak::OutbreakConfig::from_settings(&settings));
+    forecast::spawn(db_pool.clone(), forecast::ForecastConfig::from_settings(&settings));
+    capacity_alerts::spawn(db_pool.clone(), settings.capacity_alert_interval_secs);
+    webhooks::spawn(db_pool.clone(), state.webhooks.clone());
+    outbox::spawn(state.outbox.clone(), settings.outbox_dispatch_interval_secs, settings.outbox_retain_days);
+    notifications::spawn(state.notifier.clone());
+    pii_rotation::spawn(db_pool, state.pii.clone(), state.pii_rotation.clone());
     let addr = format!("{}:{}", settings.host, settings.port);
 
     let listener = TcpListener::bind(&addr)
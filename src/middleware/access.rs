@@ -0,0 +1,153 @@
+// Callers read patient data with a staff session (`Authorization: Bearer <staff token>`, see
+// `middleware::auth`). The calling hospital is the signed-in staff member's own. Per request:
+// - `X-Access-Purpose`: TREATMENT (default), REFERRAL, PUBLIC_HEALTH, RESEARCH or ADMINISTRATION
+// - `X-Break-Glass-Reason`: overrides a missing consent; always logged against the staff member
+//
+// A hospital may read a patient it has registered or seen, but only its own visits' data.
+// Anything held by another hospital needs the patient's consent (for that hospital or purpose)
+// or a break-glass override. Every read, allowed or not, lands in `patient_access_log`.
+// Consent itself is recorded and revoked only by a hospital with a care relationship.
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    db::{consent_repo, patient_repo, visit_repo},
+    errors::app::AppError,
+    middleware::auth::staff_session,
+    models::consent::{validate_purpose, AccessBasis, AccessContext},
+    routes::state::AppState,
+};
+
+const PURPOSE_HEADER: &str = "x-access-purpose";
+const BREAK_GLASS_HEADER: &str = "x-break-glass-reason";
+
+#[async_trait]
+impl FromRequestParts<AppState> for AccessContext {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let session = staff_session(parts, state).await?;
+
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+
+        let purpose = header(PURPOSE_HEADER).map(str::to_uppercase).unwrap_or_else(|| "TREATMENT".to_string());
+        if validate_purpose(&purpose).is_err() {
+            return Err(AppError::BadRequest(format!("Invalid access purpose '{}'", purpose)));
+        }
+
+        Ok(AccessContext {
+            hospital_id: session.hospital_id,
+            staff_id: session.staff_id,
+            purpose,
+            break_glass_reason: header(BREAK_GLASS_HEADER).map(str::to_string),
+        })
+    }
+}
+
+/// What an allowed caller may see of a patient's record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessScope {
+    /// Everything, at every hospital
+    Full,
+    /// Patient-level data plus whatever this hospital recorded itself
+    Hospital(Uuid),
+}
+
+impl AccessScope {
+    /// Hospital filter for cross-hospital queries; `None` means no filter.
+    pub fn hospital_filter(&self) -> Option<Uuid> {
+        match self {
+            AccessScope::Full => None,
+            AccessScope::Hospital(id) => Some(*id),
+        }
+    }
+}
+
+/// Checks and logs a read of one patient's data. `data_hospital` is the hospital that holds the
+/// data (e.g. the visit's hospital), or `None` for patient-level data and cross-hospital views.
+/// Returns `NotFound` for an unknown patient and `Forbidden` when there is no care relationship,
+/// consent or break-glass override.
+pub async fn authorize_patient(
+    pool: &PgPool,
+    ctx: &AccessContext,
+    patient_id: Uuid,
+    resource: &str,
+    data_hospital: Option<Uuid>,
+) -> Result<AccessScope, AppError> {
+    if !patient_repo::patient_exists(pool, patient_id).await? {
+        return Err(AppError::NotFound);
+    }
+
+    let mut consent_id = None;
+    let (basis, scope) = if data_hospital == Some(ctx.hospital_id) {
+        (AccessBasis::OwnHospital, AccessScope::Full)
+    } else if let Some(id) = consent_repo::find_active_consent(pool, patient_id, ctx.hospital_id, &ctx.purpose).await? {
+        consent_id = Some(id);
+        (AccessBasis::Consent, AccessScope::Full)
+    } else if ctx.break_glass_reason.is_some() {
+        (AccessBasis::BreakGlass, AccessScope::Full)
+    } else if data_hospital.is_none() && consent_repo::has_care_relationship(pool, patient_id, ctx.hospital_id).await? {
+        (AccessBasis::OwnHospital, AccessScope::Hospital(ctx.hospital_id))
+    } else {
+        consent_repo::log_access(pool, ctx, &[patient_id], resource, AccessBasis::Denied, None).await?;
+        return Err(AppError::Forbidden);
+    };
+
+    consent_repo::log_access(pool, ctx, &[patient_id], resource, basis, consent_id).await?;
+    Ok(scope)
+}
+
+/// Checks and logs a read of one visit's data. Returns `NotFound` for an unknown visit.
+pub async fn authorize_visit(pool: &PgPool, ctx: &AccessContext, visit_id: Uuid, resource: &str) -> Result<(), AppError> {
+    let visit = visit_repo::get_visit_by_id(pool, visit_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    authorize_patient(pool, ctx, visit.patient_id, resource, Some(visit.hospital_id)).await?;
+    Ok(())
+}
+
+/// Hospital worklists (visits, lab queue, ...) are only for the hospital itself.
+pub fn require_own_hospital(ctx: &AccessContext, hospital_id: Uuid) -> Result<(), AppError> {
+    if ctx.hospital_id == hospital_id {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+/// Consents may only be granted or revoked by a hospital that has registered or seen the patient.
+/// Returns `NotFound` for an unknown patient and `Forbidden` otherwise.
+pub async fn require_care_relationship(pool: &PgPool, ctx: &AccessContext, patient_id: Uuid) -> Result<(), AppError> {
+    if !patient_repo::patient_exists(pool, patient_id).await? {
+        return Err(AppError::NotFound);
+    }
+    if consent_repo::has_care_relationship(pool, patient_id, ctx.hospital_id).await? {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+/// Logs a hospital's read of a list of its own patients.
+pub async fn log_list_access(
+    pool: &PgPool,
+    ctx: &AccessContext,
+    patient_ids: impl IntoIterator<Item = Uuid>,
+    resource: &str,
+) -> Result<(), AppError> {
+    let mut patient_ids: Vec<Uuid> = patient_ids.into_iter().collect();
+    patient_ids.sort();
+    patient_ids.dedup();
+    if !patient_ids.is_empty() {
+        consent_repo::log_access(pool, ctx, &patient_ids, resource, AccessBasis::OwnHospital, None).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+// Sessions are bearer JWTs signed with `JWT_SECRET`:
+// - admin tokens come from `POST /api/v1/login` and guard account administration
+// - staff tokens come from `POST /api/v1/staff/login`, carry the `staff` audience, and are
+//   re-checked against the staff record on every request (see `middleware::access`)
+
+use axum::{async_trait, extract::FromRequestParts, http::{header::AUTHORIZATION, request::Parts}};
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use uuid::Uuid;
+use crate::{
+    db::staff_account_repo,
+    errors::app::AppError,
+    models::{
+        staff_account::{StaffClaims, StaffSession, STAFF_AUDIENCE},
+        user::Claims,
+    },
+    routes::state::AppState,
+};
+
+/// A request made with a valid admin token.
+#[derive(Debug, Clone)]
+pub struct AdminSession {
+    pub admin_id: Uuid,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AdminSession {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(parts).ok_or(AppError::Unauthorized)?;
+        // Staff tokens carry an audience, which the default validation refuses
+        let claims = decode::<Claims>(token, &DecodingKey::from_secret(state.jwt_secret.as_bytes()), &Validation::default())
+            .map_err(|_| AppError::Unauthorized)?
+            .claims;
+        let admin_id = claims.sub.parse().map_err(|_| AppError::Unauthorized)?;
+        Ok(AdminSession { admin_id })
+    }
+}
+
+/// The staff member behind the request's staff token: signed by us, unexpired, issued after
+/// their last password change, and still active.
+pub async fn staff_session(parts: &Parts, state: &AppState) -> Result<StaffSession, AppError> {
+    let token = bearer_token(parts).ok_or(AppError::Unauthorized)?;
+
+    let mut validation = Validation::default();
+    validation.set_audience(&[STAFF_AUDIENCE]);
+    validation.set_required_spec_claims(&["exp", "aud"]);
+    let claims = decode::<StaffClaims>(token, &DecodingKey::from_secret(state.jwt_secret.as_bytes()), &validation)
+        .map_err(|_| AppError::Unauthorized)?
+        .claims;
+    let staff_id: Uuid = claims.sub.parse().map_err(|_| AppError::Unauthorized)?;
+
+    let session = staff_account_repo::get_session(&state.db, staff_id)
+        .await?
+        .ok_or(AppError::Unauthorized)?;
+    if !session.is_active || (claims.iat as i64) < session.password_changed_at.timestamp() {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(session)
+}
+
+fn bearer_token(parts: &Parts) -> Option<&str> {
+    parts
+        .headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Allergy {
+    pub id: Uuid,
+    pub patient_id: Uuid,
+    pub substance: String,
+    pub reaction: Option<String>,
+    pub severity: String,
+    pub is_active: bool,
+    pub recorded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateAllergyRequest {
+    #[validate(length(min = 2, max = 255, message = "Substance must be between 2 and 255 characters"))]
+    pub substance: String, // Drug or other allergen, e.g. "Penicillin"
+    pub reaction: Option<String>, // e.g. "Urticaria"
+    #[validate(custom(function = "validate_severity"))]
+    pub severity: String,
+    pub recorded_by: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateAllergyRequest {
+    pub is_active: bool,
+}
+
+fn validate_severity(severity: &str) -> Result<(), validator::ValidationError> {
+    match severity {
+        "MILD" | "MODERATE" | "SEVERE" | "LIFE_THREATENING" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid allergy severity")),
+    }
+}
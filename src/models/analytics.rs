@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AnalyticsQuery {
+    // Optional: defaults to 30 days before `to`
+    pub from: Option<DateTime<Utc>>,
+    // Optional: defaults to now
+    pub to: Option<DateTime<Utc>>,
+    pub state: Option<String>,
+    // Occupancy grouping: state (default), city or hospital_type
+    #[validate(custom(function = "validate_group_by"))]
+    pub group_by: Option<String>,
+    // Occupancy rate (0-1) for /occupancy/high; defaults to 0.85
+    #[validate(range(min = 0.0, max = 1.0, message = "Threshold must be between 0 and 1"))]
+    pub threshold: Option<f64>,
+    pub hospital_id: Option<Uuid>,
+}
+
+/// Time-weighted bed occupancy over the requested range for one group of hospitals.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct OccupancyGroup {
+    // State, "city, state" or hospital type, depending on `group_by`
+    pub group: String,
+    pub hospitals: i64,
+    pub average_total_beds: f64,
+    pub average_occupied_beds: f64,
+    // average_occupied_beds / average_total_beds; null when the group has no beds
+    pub occupancy_rate: Option<f64>,
+    // Highest occupancy any single hospital in the group reached
+    pub peak_hospital_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct HospitalOccupancy {
+    pub hospital_id: Uuid,
+    pub name: String,
+    pub state: String,
+    pub average_rate: Option<f64>,
+    pub peak_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HighOccupancySummary {
+    pub threshold: f64,
+    pub hospitals_with_beds: i64,
+    // Hospitals whose average occupancy over the range was at or above the threshold
+    pub average_at_or_above: i64,
+    // Hospitals that reached the threshold at any point in the range
+    pub peak_at_or_above: i64,
+    // The latter, highest average first
+    pub hospitals: Vec<HospitalOccupancy>,
+}
+
+/// Share of active hospitals in a state offering each service (current configuration).
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct StateCoverage {
+    pub state: String,
+    pub hospitals: i64,
+    pub with_emergency: i64,
+    pub with_oxygen: i64,
+    pub with_ventilators: i64,
+    pub with_ambulance: i64,
+    pub emergency_coverage: f64,
+    pub oxygen_coverage: f64,
+    pub ventilator_coverage: f64,
+    pub ambulance_coverage: f64,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct DailyVisitVolume {
+    pub date: NaiveDate,
+    pub visits: i64,
+    pub completed: i64,
+    pub cancelled: i64,
+}
+
+fn validate_group_by(group_by: &str) -> Result<(), validator::ValidationError> {
+    match group_by {
+        "state" | "city" | "hospital_type" => Ok(()),
+        _ => Err(validator::ValidationError::new("group_by must be state, city or hospital_type")),
+    }
+}
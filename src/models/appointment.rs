@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct AvailabilitySlot {
+    pub id: Uuid,
+    pub hospital_id: Uuid,
+    pub department_id: Uuid,
+    pub staff_id: Uuid,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub status: String,
+    pub visit_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateSlotsRequest {
+    pub staff_id: Uuid,
+    // Optional: defaults to the staff member's own department
+    pub department_id: Option<Uuid>,
+    pub start_time: DateTime<Utc>,
+    #[validate(range(min = 5, max = 480, message = "Slot duration must be between 5 and 480 minutes"))]
+    pub duration_minutes: i32,
+    // Optional: publish several back-to-back slots in one call (defaults to 1)
+    #[validate(range(min = 1, max = 48, message = "Count must be between 1 and 48"))]
+    pub count: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct BookSlotRequest {
+    pub patient_id: Uuid,
+    #[validate(length(min = 3, message = "Reason must be at least 3 characters"))]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RescheduleAppointmentRequest {
+    pub new_slot_id: Uuid,
+    #[validate(length(min = 3, message = "Reason must be at least 3 characters"))]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CancelAppointmentRequest {
+    #[validate(length(min = 3, message = "Reason must be at least 3 characters"))]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct AppointmentChange {
+    pub id: Uuid,
+    pub visit_id: Uuid,
+    pub change_type: String,
+    pub from_slot_id: Option<Uuid>,
+    pub to_slot_id: Option<Uuid>,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
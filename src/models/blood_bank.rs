@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct BloodUnitBatch {
+    pub id: Uuid,
+    pub hospital_id: Uuid,
+    pub blood_group: String,
+    pub component: String,
+    pub units: i32,
+    pub expires_on: NaiveDate,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReportBloodUnitsRequest {
+    #[validate(custom(function = "validate_blood_group"))]
+    pub blood_group: String,
+    #[validate(custom(function = "validate_component"))]
+    pub component: String,
+    #[validate(range(min = 1, message = "Units must be at least 1"))]
+    pub units: i32,
+    pub expires_on: NaiveDate, // Format: YYYY-MM-DD
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct IssueBloodUnitsRequest {
+    #[validate(range(min = 1, message = "Units must be at least 1"))]
+    pub units: i32,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct BloodStockLevel {
+    pub blood_group: String,
+    pub component: String,
+    pub units: i64,
+    pub earliest_expiry: NaiveDate,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct BloodSearchResult {
+    pub hospital_id: Uuid,
+    pub hospital_name: String,
+    pub state: String,
+    pub city: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub distance_km: f64,
+    pub units_available: i64,
+    pub earliest_expiry: NaiveDate,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BloodSearchQuery {
+    #[validate(custom(function = "validate_blood_group"))]
+    pub blood_group: String,
+    #[validate(custom(function = "validate_component"))]
+    pub component: Option<String>,
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub lat: f64,
+    #[validate(range(min = -180.0, max = 180.0, message = "Longitude must be between -180 and 180"))]
+    pub lng: f64,
+    pub min_units: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+fn validate_blood_group(blood_group: &str) -> Result<(), validator::ValidationError> {
+    match blood_group {
+        "A+" | "A-" | "B+" | "B-" | "AB+" | "AB-" | "O+" | "O-" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid blood group")),
+    }
+}
+
+fn validate_component(component: &str) -> Result<(), validator::ValidationError> {
+    match component {
+        "WHOLE_BLOOD" | "RED_CELLS" | "PLASMA" | "PLATELETS" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid blood component")),
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AlertRule {
+    pub id: Uuid,
+    pub name: String,
+    pub hospital_id: Option<Uuid>,
+    pub state: Option<String>,
+    pub metric: String,
+    pub threshold: Option<f64>,
+    pub severity: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateAlertRuleRequest {
+    #[validate(length(min = 3, max = 255, message = "Name must be between 3 and 255 characters"))]
+    pub name: String,
+    // Scope: set hospital_id for one hospital, state for a whole state, neither for everywhere
+    pub hospital_id: Option<Uuid>,
+    pub state: Option<String>,
+    #[validate(custom(function = "validate_metric"))]
+    pub metric: String,
+    // OCCUPANCY_ABOVE: percent (0-100); BROKEN_EQUIPMENT_ABOVE: item count
+    #[validate(range(min = 0.0, message = "Threshold cannot be negative"))]
+    pub threshold: Option<f64>,
+    // Optional: defaults to WARNING
+    #[validate(custom(function = "validate_severity"))]
+    pub severity: Option<String>,
+}
+
+impl CreateAlertRuleRequest {
+    /// Checks the threshold is present (and a sensible size) for the metrics that use one.
+    pub fn check_threshold(&self) -> Result<(), String> {
+        match (self.metric.as_str(), self.threshold) {
+            ("OCCUPANCY_ABOVE", Some(t)) if t <= 100.0 => Ok(()),
+            ("OCCUPANCY_ABOVE", _) => Err("OCCUPANCY_ABOVE needs a threshold between 0 and 100 (percent)".to_string()),
+            ("BROKEN_EQUIPMENT_ABOVE", Some(_)) => Ok(()),
+            ("BROKEN_EQUIPMENT_ABOVE", None) => Err("BROKEN_EQUIPMENT_ABOVE needs a threshold (item count)".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Current capacity figures for a hospital, as the alert rules see them.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct HospitalCapacityMetrics {
+    pub hospital_id: Uuid,
+    pub name: String,
+    pub state: String,
+    pub total_beds: i32,
+    pub occupied_beds: i32,
+    pub has_emergency: bool,
+    // OK, LOW, OUT, or UNKNOWN when the hospital does not track oxygen stock
+    pub oxygen_status: String,
+    pub broken_equipment: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CapacityAlert {
+    pub id: Uuid,
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub hospital_id: Uuid,
+    pub hospital_name: String,
+    pub state: String,
+    pub metric: String,
+    pub severity: String,
+    pub observed_value: Option<f64>,
+    pub threshold: Option<f64>,
+    pub message: String,
+    pub status: String,
+    pub triggered_at: DateTime<Utc>,
+    pub acknowledged_by: Option<Uuid>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolution_note: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CapacityAlertQuery {
+    // ACTIVE, ACKNOWLEDGED or RESOLVED; defaults to open (ACTIVE and ACKNOWLEDGED)
+    pub status: Option<String>,
+    pub hospital_id: Option<Uuid>,
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AcknowledgeAlertRequest {
+    pub staff_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResolveCapacityAlertRequest {
+    pub staff_id: Uuid,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AlertSubscription {
+    pub id: Uuid,
+    pub hospital_id: Option<Uuid>,
+    pub state: Option<String>,
+    pub channel: String,
+    pub target: Option<String>,
+    pub min_severity: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateAlertSubscriptionRequest {
+    // Scope, as for rules
+    pub hospital_id: Option<Uuid>,
+    pub state: Option<String>,
+    #[validate(custom(function = "validate_channel"))]
+    pub channel: String,
+    // STAFF: staff id to notify by email/SMS; LOG: optional label. Partner systems subscribe a
+    // webhook endpoint to capacity_alert.raised instead
+    #[validate(length(min = 1, max = 2048, message = "Target must be between 1 and 2048 characters"))]
+    pub target: Option<String>,
+    // Optional: defaults to INFO (everything)
+    #[validate(custom(function = "validate_severity"))]
+    pub min_severity: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct AlertDelivery {
+    pub id: Uuid,
+    pub alert_id: Uuid,
+    pub subscription_id: Uuid,
+    pub channel: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub delivered_at: DateTime<Utc>,
+}
+
+fn validate_metric(metric: &str) -> Result<(), validator::ValidationError> {
+    match metric {
+        "OCCUPANCY_ABOVE" | "OXYGEN_LOW" | "EMERGENCY_CLOSED" | "BROKEN_EQUIPMENT_ABOVE" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid alert metric")),
+    }
+}
+
+fn validate_severity(severity: &str) -> Result<(), validator::ValidationError> {
+    match severity {
+        "INFO" | "WARNING" | "CRITICAL" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid severity")),
+    }
+}
+
+fn validate_channel(channel: &str) -> Result<(), validator::ValidationError> {
+    match channel {
+        "LOG" | "STAFF" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid channel")),
+    }
+}
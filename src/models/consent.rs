@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Consent {
+    pub id: Uuid,
+    pub patient_id: Uuid,
+    // None = any hospital, for `purpose`
+    pub hospital_id: Option<Uuid>,
+    // None = any purpose, for `hospital_id`
+    pub purpose: Option<String>,
+    pub recorded_by: Option<Uuid>,
+    pub notes: Option<String>,
+    pub granted_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoke_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct GrantConsentRequest {
+    // At least one of hospital_id and purpose is required
+    pub hospital_id: Option<Uuid>,
+    #[validate(custom(function = "validate_purpose"))]
+    pub purpose: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RevokeConsentRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct AccessLogEntry {
+    pub id: Uuid,
+    pub patient_id: Uuid,
+    pub hospital_id: Uuid,
+    pub hospital_name: String,
+    pub staff_id: Option<Uuid>,
+    pub staff_name: Option<String>,
+    pub purpose: String,
+    pub resource: String,
+    pub basis: String,
+    pub consent_id: Option<Uuid>,
+    pub break_glass_reason: Option<String>,
+    pub accessed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AccessLogQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[validate(custom(function = "validate_basis"))]
+    pub basis: Option<String>,
+}
+
+/// Who is reading patient data and why: the signed-in staff member, their hospital, and the
+/// purpose and break-glass headers (see `middleware::access`).
+#[derive(Debug, Clone)]
+pub struct AccessContext {
+    pub hospital_id: Uuid,
+    pub staff_id: Uuid,
+    pub purpose: String,
+    pub break_glass_reason: Option<String>,
+}
+
+/// How a read of patient data was allowed (or not).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessBasis {
+    OwnHospital,
+    Consent,
+    BreakGlass,
+    Denied,
+}
+
+impl AccessBasis {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessBasis::OwnHospital => "OWN_HOSPITAL",
+            AccessBasis::Consent => "CONSENT",
+            AccessBasis::BreakGlass => "BREAK_GLASS",
+            AccessBasis::Denied => "DENIED",
+        }
+    }
+}
+
+pub fn validate_purpose(purpose: &str) -> Result<(), validator::ValidationError> {
+    match purpose {
+        "TREATMENT" | "REFERRAL" | "PUBLIC_HEALTH" | "RESEARCH" | "ADMINISTRATION" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid access purpose")),
+    }
+}
+
+fn validate_basis(basis: &str) -> Result<(), validator::ValidationError> {
+    match basis {
+        "OWN_HOSPITAL" | "CONSENT" | "BREAK_GLASS" | "DENIED" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid access basis")),
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Credential {
+    pub id: Uuid,
+    pub staff_id: Uuid,
+    pub licence_body: String,
+    pub licence_number: String,
+    pub specialty: Option<String>,
+    pub issued_on: NaiveDate,
+    pub expires_on: NaiveDate,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateCredentialRequest {
+    #[validate(length(min = 2, message = "Licence body is required"))]
+    pub licence_body: String,
+    #[validate(length(min = 3, message = "Licence number must be at least 3 characters"))]
+    pub licence_number: String,
+    pub specialty: Option<String>,
+    pub issued_on: NaiveDate,  // Format: YYYY-MM-DD
+    pub expires_on: NaiveDate, // Format: YYYY-MM-DD
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ExpiringCredential {
+    pub credential_id: Uuid,
+    pub staff_id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub role: String,
+    pub licence_body: String,
+    pub licence_number: String,
+    pub expires_on: NaiveDate,
+    pub days_remaining: i32,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct VisitDiagnosis {
+    pub id: Uuid,
+    pub visit_id: Uuid,
+    pub code: String,
+    pub description: Option<String>,
+    pub diagnosis_type: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateDiagnosisRequest {
+    #[validate(length(min = 3, max = 10, message = "Code must be between 3 and 10 characters"))]
+    pub code: String, // e.g. "A00.9", must exist in the ICD-10 code table
+    // Optional: defaults to the code table description
+    pub description: Option<String>,
+    // PRIMARY or SECONDARY (default). A visit has at most one primary diagnosis.
+    #[validate(custom(function = "validate_diagnosis_type"))]
+    pub diagnosis_type: Option<String>,
+}
+
+fn validate_diagnosis_type(diagnosis_type: &str) -> Result<(), validator::ValidationError> {
+    match diagnosis_type {
+        "PRIMARY" | "SECONDARY" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid diagnosis type")),
+    }
+}
@@ -15,6 +15,9 @@ pub struct Equipment {
     pub condition: String,
     pub is_operational: bool,
     pub created_at: DateTime<Utc>,
+    pub equipment_type: Option<String>,
+    // Set while another hospital holds the equipment on loan
+    pub custodian_hospital_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
@@ -27,6 +30,8 @@ pub struct CreateEquipmentRequest {
     #[validate(custom(function = "validate_condition"))]
     pub condition: String,
     pub is_operational: bool,
+    #[validate(custom(function = "validate_equipment_type"))]
+    pub equipment_type: Option<String>,
 }
 
 fn validate_condition(condition: &str) -> Result<(), validator::ValidationError> {
@@ -34,4 +39,11 @@ fn validate_condition(condition: &str) -> Result<(), validator::ValidationError>
         "NEW" | "GOOD" | "FAIR" | "POOR" | "BROKEN" => Ok(()),
         _ => Err(validator::ValidationError::new("Invalid condition")),
     }
+}
+
+fn validate_equipment_type(equipment_type: &str) -> Result<(), validator::ValidationError> {
+    match equipment_type {
+        "VENTILATOR" | "OXYGEN_CONCENTRATOR" | "AMBULANCE" | "OTHER" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid equipment type")),
+    }
 }
\ No newline at end of file
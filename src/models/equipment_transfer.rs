@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct EquipmentTransfer {
+    pub id: Uuid,
+    pub equipment_id: Uuid,
+    pub from_hospital_id: Uuid,
+    pub to_hospital_id: Uuid,
+    pub transfer_type: String,
+    pub status: String,
+    pub reason: String,
+    pub expected_return_on: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateTransferRequest {
+    pub equipment_id: Uuid,
+    pub to_hospital_id: Uuid,
+    // LOAN moves custody only; TRANSFER moves ownership on receipt
+    #[validate(custom(function = "validate_transfer_type"))]
+    pub transfer_type: String,
+    #[validate(length(min = 3, message = "Reason must be at least 3 characters"))]
+    pub reason: String,
+    pub expected_return_on: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransferActionRequest {
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct CustodyEvent {
+    pub id: Uuid,
+    pub equipment_id: Uuid,
+    pub transfer_id: Uuid,
+    pub status: String,
+    pub from_hospital_id: Uuid,
+    pub to_hospital_id: Uuid,
+    pub note: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Workflow step names accepted on `/equipment-transfers/{id}/{action}`,
+/// mapped to the statuses they may start from and the status they lead to.
+pub fn transfer_transition(action: &str) -> Option<(&'static [&'static str], &'static str)> {
+    match action {
+        "approve" => Some((&["REQUESTED"], "APPROVED")),
+        "reject" => Some((&["REQUESTED", "APPROVED"], "REJECTED")),
+        "dispatch" => Some((&["APPROVED"], "DISPATCHED")),
+        "receive" => Some((&["DISPATCHED"], "RECEIVED")),
+        "return" => Some((&["RECEIVED"], "RETURNED")),
+        _ => None,
+    }
+}
+
+fn validate_transfer_type(transfer_type: &str) -> Result<(), validator::ValidationError> {
+    match transfer_type {
+        "LOAN" | "TRANSFER" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid transfer type")),
+    }
+}
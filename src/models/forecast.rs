@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+/// Bed occupancy in effect at an hour mark, with the visits started during that hour.
+#[derive(Debug, Clone, FromRow)]
+pub struct HourlyHistoryPoint {
+    pub hour: DateTime<Utc>,
+    pub occupied_beds: i32,
+    pub total_beds: i32,
+    pub visits: i64,
+}
+
+/// One hourly prediction from the model, before it is stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastPoint {
+    pub target_time: DateTime<Utc>,
+    pub horizon_hours: i32,
+    pub predicted_occupied: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub total_beds: i32,
+    pub expected_visits: f64,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ForecastVsActual {
+    pub target_time: DateTime<Utc>,
+    // Data cutoff of the run that produced this prediction
+    pub generated_at: DateTime<Utc>,
+    pub horizon_hours: i32,
+    pub predicted_occupied: f64,
+    // 95% prediction interval
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub total_beds: i32,
+    pub expected_visits: f64,
+    // Occupancy recorded at target_time; null until then
+    pub actual_occupied: Option<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ForecastComparison {
+    pub hospital_id: Uuid,
+    pub model: String,
+    pub points: Vec<ForecastVsActual>,
+    // Hours in range that already have an actual value
+    pub compared_hours: i64,
+    pub mean_absolute_error: Option<f64>,
+    // Share of actuals that fell inside the prediction interval
+    pub interval_coverage: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ForecastQuery {
+    // Optional: defaults to 24 hours ago
+    pub from: Option<DateTime<Utc>>,
+    // Optional: defaults to 7 days ahead
+    pub to: Option<DateTime<Utc>>,
+    // 24h or 7d: only use predictions made at most this far ahead
+    pub horizon: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunForecastQuery {
+    // Optional: forecast as if it were this time, using only earlier history (backtesting)
+    pub as_of: Option<DateTime<Utc>>,
+    // Optional: defaults to every active hospital with beds
+    pub hospital_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ForecastRunSummary {
+    pub generated_at: DateTime<Utc>,
+    pub hospitals: i64,
+    pub predictions: i64,
+}
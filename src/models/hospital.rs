@@ -22,6 +22,7 @@ pub struct Hospital {
     pub has_oxygen: bool,
     pub has_ventilators: bool,
     pub has_ambulance: bool,
+    pub lga: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate, ToSchema)] // Add ToSchema
@@ -46,4 +47,5 @@ pub struct CreateHospitalRequest {
     pub has_oxygen: Option<bool>,
     pub has_ventilators: Option<bool>,
     pub has_ambulance: Option<bool>,
+    pub lga: Option<String>,
 }
\ No newline at end of file
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Icd10Code {
+    pub code: String,
+    pub description: String,
+    // Three-character category, e.g. "A00" for "A00.9"
+    pub category: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct Icd10SearchQuery {
+    #[validate(length(min = 1, max = 100, message = "Search term must be between 1 and 100 characters"))]
+    pub q: String,
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct InventoryItem {
+    pub id: Uuid,
+    pub hospital_id: Uuid,
+    pub category: String,
+    pub name: String,
+    pub unit: String,
+    pub quantity_on_hand: i32,
+    pub reorder_level: i32,
+    // Derived by the database: OK, LOW (at or below reorder level) or OUT
+    pub stock_status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateInventoryItemRequest {
+    #[validate(custom(function = "validate_category"))]
+    pub category: String,
+    #[validate(length(min = 2, message = "Name must be at least 2 characters"))]
+    pub name: String,
+    #[validate(length(min = 1, message = "Unit is required"))]
+    pub unit: String, // e.g. "cylinder", "unit", "vial"
+    #[validate(range(min = 0, max = 1000000, message = "Opening stock must be between 0 and 1,000,000"))]
+    pub quantity_on_hand: Option<i32>,
+    #[validate(range(min = 0, max = 1000000, message = "Reorder level must be between 0 and 1,000,000"))]
+    pub reorder_level: Option<i32>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct InventoryTransaction {
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub transaction_type: String,
+    pub quantity: i32,
+    pub balance_after: i32,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateInventoryTransactionRequest {
+    // STOCK_IN / STOCK_OUT move stock by `quantity`; ADJUSTMENT sets the counted balance
+    #[validate(custom(function = "validate_transaction_type"))]
+    pub transaction_type: String,
+    #[validate(range(min = 0, max = 1000000, message = "Quantity must be between 0 and 1,000,000"))]
+    pub quantity: i32,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StockMovementResponse {
+    pub item: InventoryItem,
+    pub transaction: InventoryTransaction,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct CapabilitySummary {
+    pub hospital_id: Uuid,
+    pub total_beds: Option<i32>,
+    pub occupied_beds: i32,
+    pub has_emergency: Option<bool>,
+    pub has_oxygen: bool,
+    pub has_ventilators: bool,
+    pub has_ambulance: bool,
+    // OK, LOW, OUT, or UNKNOWN when the hospital does not track oxygen stock
+    pub oxygen_status: String,
+    pub low_stock_items: i64,
+    pub out_of_stock_items: i64,
+}
+
+fn validate_category(category: &str) -> Result<(), validator::ValidationError> {
+    match category {
+        "OXYGEN" | "MEDICINE" | "OTHER" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid inventory category")),
+    }
+}
+
+fn validate_transaction_type(transaction_type: &str) -> Result<(), validator::ValidationError> {
+    match transaction_type {
+        "STOCK_IN" | "STOCK_OUT" | "ADJUSTMENT" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid transaction type")),
+    }
+}
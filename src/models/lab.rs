@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct LabTestType {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub specimen_type: String,
+    pub result_type: String,
+    pub unit: Option<String>,
+    pub reference_low: Option<f64>,
+    pub reference_high: Option<f64>,
+    pub normal_text: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateLabTestTypeRequest {
+    #[validate(length(min = 1, max = 20, message = "Code must be between 1 and 20 characters"))]
+    pub code: String,
+    #[validate(length(min = 2, message = "Name must be at least 2 characters"))]
+    pub name: String,
+    #[validate(custom(function = "validate_specimen_type"))]
+    pub specimen_type: String,
+    #[validate(custom(function = "validate_result_type"))]
+    pub result_type: String,
+    pub unit: Option<String>,
+    pub reference_low: Option<f64>,
+    pub reference_high: Option<f64>,
+    pub normal_text: Option<String>, // e.g. NEGATIVE
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct LabOrder {
+    pub id: Uuid,
+    pub visit_id: Uuid,
+    pub patient_id: Uuid,
+    pub hospital_id: Uuid,
+    pub test_type_id: Uuid,
+    pub test_code: String,
+    pub test_name: String,
+    pub specimen_type: String,
+    pub priority: String,
+    pub clinical_notes: Option<String>,
+    pub ordered_by: Uuid,
+    pub status: String,
+    pub collected_by: Option<Uuid>,
+    pub collected_at: Option<DateTime<Utc>>,
+    pub result_value: Option<f64>,
+    pub result_text: Option<String>,
+    pub result_unit: Option<String>,
+    pub reference_low: Option<f64>,
+    pub reference_high: Option<f64>,
+    pub abnormal_flag: Option<String>,
+    pub result_notes: Option<String>,
+    pub resulted_by: Option<Uuid>,
+    pub resulted_at: Option<DateTime<Utc>>,
+    pub verified_by: Option<Uuid>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A new lab order plus anything the orderer should double-check.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LabOrderResponse {
+    pub order: LabOrder,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateLabOrderRequest {
+    pub test_type_id: Uuid,
+    pub ordered_by: Uuid, // Ordering clinician (DOCTOR or NURSE at the visit's hospital)
+    // Optional: defaults to ROUTINE
+    #[validate(custom(function = "validate_priority"))]
+    pub priority: Option<String>,
+    // Optional: defaults to the test's usual specimen
+    #[validate(custom(function = "validate_specimen_type"))]
+    pub specimen_type: Option<String>,
+    pub clinical_notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CollectSpecimenRequest {
+    pub collected_by: Uuid,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct EnterLabResultRequest {
+    pub resulted_by: Uuid,
+    // NUMERIC tests take `value`; QUALITATIVE tests take `text` (e.g. POSITIVE)
+    pub value: Option<f64>,
+    #[validate(length(min = 1, max = 255, message = "Result text must be between 1 and 255 characters"))]
+    pub text: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerifyLabResultRequest {
+    pub verified_by: Uuid, // Must differ from the person who entered the result
+}
+
+/// Flags a result against its reference range (numeric) or expected text (qualitative).
+/// Returns `None` when there is nothing to compare against.
+pub fn abnormal_flag(test: &LabTestType, value: Option<f64>, text: Option<&str>) -> Option<&'static str> {
+    match test.result_type.as_str() {
+        "NUMERIC" => {
+            let value = value?;
+            match (test.reference_low, test.reference_high) {
+                (Some(low), _) if value < low => Some("LOW"),
+                (_, Some(high)) if value > high => Some("HIGH"),
+                (None, None) => None,
+                _ => Some("NORMAL"),
+            }
+        }
+        _ => {
+            let normal = test.normal_text.as_deref()?;
+            if text?.eq_ignore_ascii_case(normal) {
+                Some("NORMAL")
+            } else {
+                Some("ABNORMAL")
+            }
+        }
+    }
+}
+
+fn validate_specimen_type(specimen_type: &str) -> Result<(), validator::ValidationError> {
+    match specimen_type {
+        "BLOOD" | "URINE" | "STOOL" | "SPUTUM" | "CSF" | "SWAB" | "OTHER" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid specimen type")),
+    }
+}
+
+fn validate_result_type(result_type: &str) -> Result<(), validator::ValidationError> {
+    match result_type {
+        "NUMERIC" | "QUALITATIVE" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid result type")),
+    }
+}
+
+fn validate_priority(priority: &str) -> Result<(), validator::ValidationError> {
+    match priority {
+        "ROUTINE" | "URGENT" | "STAT" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid priority")),
+    }
+}
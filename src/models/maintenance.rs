@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct MaintenancePlan {
+    pub id: Uuid,
+    pub equipment_id: Uuid,
+    pub task: String,
+    pub interval_days: i32,
+    pub last_performed_on: Option<NaiveDate>,
+    pub next_due_on: NaiveDate,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateMaintenancePlanRequest {
+    #[validate(length(min = 3, message = "Task must be at least 3 characters"))]
+    pub task: String,
+    #[validate(range(min = 1, max = 3650, message = "Interval must be between 1 and 3650 days"))]
+    pub interval_days: i32,
+    // Optional: if omitted the first maintenance is due today
+    pub last_performed_on: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct WorkOrder {
+    pub id: Uuid,
+    pub equipment_id: Uuid,
+    pub plan_id: Option<Uuid>,
+    pub description: String,
+    pub status: String,
+    pub resolution_notes: Option<String>,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateWorkOrderRequest {
+    // Optional: links preventive work to its plan so closing it advances the schedule
+    pub plan_id: Option<Uuid>,
+    #[validate(length(min = 3, message = "Description must be at least 3 characters"))]
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CloseWorkOrderRequest {
+    #[validate(length(min = 3, message = "Resolution notes must be at least 3 characters"))]
+    pub resolution_notes: String,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ConditionLogEntry {
+    pub id: Uuid,
+    pub equipment_id: Uuid,
+    pub old_condition: Option<String>,
+    pub new_condition: String,
+    pub old_is_operational: Option<bool>,
+    pub new_is_operational: bool,
+    pub note: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateConditionRequest {
+    #[validate(custom(function = "validate_condition"))]
+    pub condition: String,
+    pub is_operational: bool,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct OverdueMaintenance {
+    pub plan_id: Uuid,
+    pub equipment_id: Uuid,
+    pub equipment_name: String,
+    pub serial_number: Option<String>,
+    pub task: String,
+    pub next_due_on: NaiveDate,
+    pub days_overdue: i32,
+}
+
+fn validate_condition(condition: &str) -> Result<(), validator::ValidationError> {
+    match condition {
+        "NEW" | "GOOD" | "FAIR" | "POOR" | "BROKEN" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid condition")),
+    }
+}
@@ -5,9 +5,35 @@ pub mod single_hospital_response;
 pub mod user;
 pub mod department;
 pub mod staff;
+pub mod staff_account;
 pub mod patient;
 pub mod visit;
 pub mod equipment;
+pub mod shift;
+pub mod credential;
+pub mod appointment;
+pub mod maintenance;
+pub mod equipment_transfer;
+pub mod inventory;
+pub mod blood_bank;
+pub mod diagnosis;
+pub mod icd10;
+pub mod observation;
+pub mod prescription;
+pub mod lab;
+pub mod allergy;
+pub mod problem;
+pub mod surveillance;
+pub mod outbreak;
+pub mod timeline;
+pub mod consent;
+pub mod public;
+pub mod analytics;
+pub mod forecast;
 
 pub use hospital::Hospital;
 pub use api_response::ApiResponse;
+pub mod capacity_alert;
+pub mod webhook;
+pub mod outbox;
+pub mod notification;
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use utoipa::ToSchema;
+use validator::Validate;
+
+// Template keys the application sends itself. The referral template is seeded for the flow that
+// will use it; until then it can be sent through the API.
+pub const TEMPLATE_CAPACITY_ALERT_RAISED: &str = "capacity_alert.raised";
+pub const TEMPLATE_REFERRAL_ACCEPTED: &str = "referral.accepted";
+pub const TEMPLATE_PASSWORD_RESET: &str = "password_reset";
+
+// Templates carrying codes or links that only the application may send. The API can neither
+// send nor edit them.
+pub const APPLICATION_ONLY_TEMPLATES: &[&str] = &[TEMPLATE_PASSWORD_RESET];
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct NotificationTemplate {
+    pub id: Uuid,
+    pub template_key: String,
+    pub channel: String,
+    pub subject: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Creates the template for a key and channel, or replaces it
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpsertNotificationTemplateRequest {
+    #[validate(length(min = 1, max = 100, message = "Template key must be between 1 and 100 characters"))]
+    pub template_key: String,
+    #[validate(custom(function = "validate_channel"))]
+    pub channel: String,
+    // Required for EMAIL, ignored for SMS
+    #[validate(length(min = 1, max = 500, message = "Subject must be between 1 and 500 characters"))]
+    pub subject: Option<String>,
+    #[validate(length(min = 1, max = 10000, message = "Body must be between 1 and 10000 characters"))]
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct NotificationPreference {
+    pub staff_id: Uuid,
+    pub email_enabled: bool,
+    pub sms_enabled: bool,
+    pub muted_templates: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateNotificationPreferenceRequest {
+    pub email_enabled: bool,
+    pub sms_enabled: bool,
+    // Template keys to opt out of, e.g. "capacity_alert.raised"
+    #[serde(default)]
+    pub muted_templates: Vec<String>,
+}
+
+/// Send a template to a staff member, through their preferences and contact details
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SendNotificationRequest {
+    #[validate(length(min = 1, max = 100, message = "Template key must be between 1 and 100 characters"))]
+    pub template_key: String,
+    pub staff_id: Uuid,
+    // Defaults to every channel the staff member has enabled
+    #[validate(custom(function = "validate_channel"))]
+    pub channel: Option<String>,
+    // Values for the template's {{placeholders}}
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Notification {
+    pub id: Uuid,
+    pub staff_id: Option<Uuid>,
+    pub template_key: String,
+    pub channel: String,
+    pub recipient: String,
+    pub subject: Option<String>,
+    #[serde(skip_serializing)] // May carry reset links or codes; kept out of the delivery log
+    pub body: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub provider: Option<String>,
+    pub provider_message_id: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationQuery {
+    pub status: Option<String>,
+    pub staff_id: Option<Uuid>,
+    pub template_key: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// A rendered notification ready to queue
+#[derive(Debug)]
+pub struct NewNotification {
+    pub staff_id: Option<Uuid>,
+    pub template_key: String,
+    pub channel: String,
+    pub recipient: String,
+    pub subject: Option<String>,
+    pub body: String,
+    pub dedupe_key: Option<String>,
+}
+
+/// A pending notification claimed for sending
+#[derive(Debug, FromRow)]
+pub struct DueNotification {
+    pub id: Uuid,
+    pub channel: String,
+    pub recipient: String,
+    pub subject: Option<String>,
+    pub body: String,
+    pub attempts: i32,
+}
+
+/// Where and how to reach a staff member
+#[derive(Debug, FromRow)]
+pub struct NotificationRecipient {
+    pub staff_id: Uuid,
+    pub email: Option<String>,
+    pub contact_phone: Option<String>,
+    pub is_active: bool,
+    pub email_enabled: bool,
+    pub sms_enabled: bool,
+    pub muted_templates: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct NotificationDispatchSummary {
+    pub attempted: usize,
+    pub sent: usize,
+    // Failed attempts that will be retried
+    pub retrying: usize,
+    // Failed attempts that used up the last retry
+    pub failed: usize,
+}
+
+fn validate_channel(channel: &str) -> Result<(), validator::ValidationError> {
+    match channel {
+        "EMAIL" | "SMS" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid channel")),
+    }
+}
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ObservationSet {
+    pub id: Uuid,
+    pub visit_id: Uuid,
+    pub patient_id: Uuid,
+    pub hospital_id: Uuid,
+    pub recorded_by: Uuid,
+    pub recorded_at: DateTime<Utc>,
+    pub on_supplemental_oxygen: bool,
+    pub consciousness: Option<String>,
+    pub news2_score: Option<i32>,
+    pub news2_risk: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Observation {
+    pub id: Uuid,
+    pub observation_set_id: Uuid,
+    pub observation_type: String,
+    pub value: f64,
+    pub unit: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ObservationSetResponse {
+    pub set: ObservationSet,
+    pub measurements: Vec<Observation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct MeasurementInput {
+    #[validate(custom(function = "validate_observation_type"))]
+    pub observation_type: String,
+    pub value: f64,
+    // Must be an accepted unit for the type; see `normalize_measurement`
+    pub unit: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RecordObservationsRequest {
+    pub staff_id: Uuid, // Who took the observations; must work at the visit's hospital
+    // Optional: defaults to NOW
+    pub recorded_at: Option<DateTime<Utc>>,
+    #[validate(length(min = 1, message = "At least one measurement is required"), nested)]
+    pub measurements: Vec<MeasurementInput>,
+    #[serde(default)]
+    pub on_supplemental_oxygen: bool,
+    // ACVPU scale
+    #[validate(custom(function = "validate_consciousness"))]
+    pub consciousness: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TrendPoint {
+    pub observation_set_id: Uuid,
+    pub visit_id: Uuid,
+    pub recorded_at: DateTime<Utc>,
+    pub value: f64,
+    pub unit: String,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct DeterioratingPatient {
+    pub patient_id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub visit_id: Uuid,
+    pub observation_set_id: Uuid,
+    pub recorded_at: DateTime<Utc>,
+    pub news2_score: i32,
+    pub news2_risk: String,
+    // Score change since the patient's previous complete set, if any
+    pub score_change: Option<i32>,
+}
+
+/// Converts a measurement to the canonical unit for its type and checks it is physiologically plausible.
+/// Returns the value and canonical unit, or a message describing what was wrong.
+pub fn normalize_measurement(observation_type: &str, value: f64, unit: &str) -> Result<(f64, &'static str), String> {
+    let (value, canonical, min, max) = match (observation_type, unit) {
+        ("RESPIRATORY_RATE", "/min") => (value, "/min", 0.0, 80.0),
+        ("SPO2", "%") => (value, "%", 0.0, 100.0),
+        ("SYSTOLIC_BP", "mmHg") => (value, "mmHg", 0.0, 300.0),
+        ("DIASTOLIC_BP", "mmHg") => (value, "mmHg", 0.0, 200.0),
+        ("HEART_RATE", "/min" | "bpm") => (value, "/min", 0.0, 300.0),
+        ("TEMPERATURE", "C") => (value, "C", 25.0, 45.0),
+        ("TEMPERATURE", "F") => ((value - 32.0) * 5.0 / 9.0, "C", 25.0, 45.0),
+        _ => return Err(format!("Unit '{}' is not valid for {}", unit, observation_type)),
+    };
+
+    if !value.is_finite() || value < min || value > max {
+        return Err(format!("{} must be between {} and {} {}", observation_type, min, max, canonical));
+    }
+
+    Ok(((value * 10.0).round() / 10.0, canonical))
+}
+
+/// Parameters needed for a NEWS2 score, in canonical units.
+#[derive(Debug, Clone, Default)]
+pub struct News2Input {
+    pub respiratory_rate: Option<f64>,
+    pub spo2: Option<f64>,
+    pub on_supplemental_oxygen: bool,
+    pub systolic_bp: Option<f64>,
+    pub heart_rate: Option<f64>,
+    pub consciousness: Option<String>,
+    pub temperature: Option<f64>,
+}
+
+/// NEWS2 aggregate score (SpO2 scale 1) and clinical risk band.
+/// Returns `None` unless every parameter is present.
+pub fn news2_score(input: &News2Input) -> Option<(i32, &'static str)> {
+    let rr = input.respiratory_rate?;
+    let spo2 = input.spo2?;
+    let sbp = input.systolic_bp?;
+    let hr = input.heart_rate?;
+    let temp = input.temperature?;
+    let consciousness = input.consciousness.as_deref()?;
+
+    let scores = [
+        match rr {
+            r if r <= 8.0 => 3,
+            r if r <= 11.0 => 1,
+            r if r <= 20.0 => 0,
+            r if r <= 24.0 => 2,
+            _ => 3,
+        },
+        match spo2 {
+            s if s <= 91.0 => 3,
+            s if s <= 93.0 => 2,
+            s if s <= 95.0 => 1,
+            _ => 0,
+        },
+        if input.on_supplemental_oxygen { 2 } else { 0 },
+        match sbp {
+            b if b <= 90.0 => 3,
+            b if b <= 100.0 => 2,
+            b if b <= 110.0 => 1,
+            b if b < 220.0 => 0,
+            _ => 3,
+        },
+        match hr {
+            h if h <= 40.0 => 3,
+            h if h <= 50.0 => 1,
+            h if h <= 90.0 => 0,
+            h if h <= 110.0 => 1,
+            h if h <= 130.0 => 2,
+            _ => 3,
+        },
+        if consciousness == "ALERT" { 0 } else { 3 },
+        match temp {
+            t if t <= 35.0 => 3,
+            t if t <= 36.0 => 1,
+            t if t <= 38.0 => 0,
+            t if t <= 39.0 => 1,
+            _ => 2,
+        },
+    ];
+
+    let total: i32 = scores.iter().sum();
+    let risk = match total {
+        t if t >= 7 => "HIGH",
+        t if t >= 5 => "MEDIUM",
+        // A red score (3) in any single parameter needs an urgent ward-based review
+        _ if scores.contains(&3) => "LOW_MEDIUM",
+        _ => "LOW",
+    };
+
+    Some((total, risk))
+}
+
+fn validate_observation_type(observation_type: &str) -> Result<(), validator::ValidationError> {
+    match observation_type {
+        "RESPIRATORY_RATE" | "SPO2" | "SYSTOLIC_BP" | "DIASTOLIC_BP" | "HEART_RATE" | "TEMPERATURE" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid observation type")),
+    }
+}
+
+fn validate_consciousness(consciousness: &str) -> Result<(), validator::ValidationError> {
+    match consciousness {
+        "ALERT" | "CONFUSION" | "VOICE" | "PAIN" | "UNRESPONSIVE" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid consciousness level")),
+    }
+}
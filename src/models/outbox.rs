@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+pub const AGGREGATE_HOSPITAL: &str = "hospital";
+pub const AGGREGATE_VISIT: &str = "visit";
+pub const AGGREGATE_CAPACITY_ALERT: &str = "capacity_alert";
+
+// hospital.updated, capacity.changed, visit.completed and capacity_alert.raised are in models::webhook
+pub const EVENT_CAPACITY_ALERT_UPDATED: &str = "capacity_alert.updated";
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct OutboxEvent {
+    pub id: i64,
+    pub event_id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    // JSON event data
+    pub payload: String,
+    pub created_at: DateTime<Utc>,
+    pub attempts: i32,
+}
+
+/// `capacity.changed` data: which hospital to re-read. Subscribers load the current figures
+/// themselves, so a late relay never publishes stale numbers.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CapacityChanged {
+    pub hospital_id: Uuid,
+}
+
+/// `capacity_alert.*` data.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CapacityAlertChanged {
+    pub alert_id: Uuid,
+    pub hospital_id: Uuid,
+    pub status: String,
+}
+
+/// An event the relay gave up on, without its payload.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ParkedOutboxEvent {
+    pub id: i64,
+    pub event_id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub parked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct OutboxDispatchSummary {
+    pub published: usize,
+    // Failed and scheduled for another attempt
+    pub failed: usize,
+    // Failed for the last time and parked
+    pub parked: usize,
+    // Held back behind an earlier failed event for the same aggregate
+    pub deferred: usize,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct OutboxStatus {
+    pub pending: i64,
+    pub failing: i64,
+    pub parked: i64,
+    pub oldest_pending_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,36 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct OutbreakAlert {
+    pub id: Uuid,
+    pub condition_id: Uuid,
+    pub condition_name: String,
+    pub state: String,
+    pub lga: String,
+    pub week_start: NaiveDate,
+    pub observed_cases: i32,
+    pub baseline_mean: f64,
+    pub baseline_stddev: f64,
+    pub threshold: f64,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// A threshold crossing found by the detector, before it is stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutbreakSignal {
+    pub condition_id: Uuid,
+    pub state: String,
+    pub lga: String,
+    pub week_start: NaiveDate,
+    pub observed_cases: i64,
+    pub baseline_mean: f64,
+    pub baseline_stddev: f64,
+    pub threshold: f64,
+}
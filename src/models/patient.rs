@@ -4,6 +4,7 @@ use uuid::Uuid;
 use chrono::{NaiveDate, DateTime, Utc};
 use utoipa::ToSchema;
 use validator::Validate;
+use crate::models::{allergy::Allergy, problem::Problem};
 
 #[derive(Debug, Serialize, FromRow, ToSchema)]
 pub struct Patient {
@@ -16,6 +17,7 @@ pub struct Patient {
     pub contact_phone: Option<String>,
     pub emergency_contact: Option<String>,
     pub address: Option<String>,
+    pub national_id: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -32,6 +34,49 @@ pub struct CreatePatientRequest {
     pub contact_phone: Option<String>,
     pub emergency_contact: Option<String>,
     pub address: Option<String>,
+    // NIN or other government ID; searchable through /patients/lookup
+    #[validate(length(min = 4, max = 50, message = "National ID must be between 4 and 50 characters"))]
+    pub national_id: Option<String>,
+}
+
+/// A patient with their active allergies and problem list.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PatientRecord {
+    pub patient: Patient,
+    pub allergies: Vec<Allergy>,
+    pub problems: Vec<Problem>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PatientLookupQuery {
+    // Any format; compared on digits, with +234 treated as a leading 0
+    pub phone: Option<String>,
+    pub national_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct PiiKeyVersionCount {
+    // NULL: written before encryption was enabled, still plaintext
+    pub key_version: Option<i16>,
+    pub patients: i64,
+}
+
+/// How far the patient table is from being fully encrypted under the active key.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PiiKeyStatus {
+    pub active_key_version: i16,
+    pub versions: Vec<PiiKeyVersionCount>,
+    // Still to be moved onto the active key
+    pub pending: i64,
+    // Not under the active key and unreadable by the re-encryption sweep, which now skips them
+    pub parked: i64,
+}
+
+/// What one or more re-encryption batches did.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReencryptSummary {
+    pub rewritten: u64,
+    pub parked: u64,
 }
 
 fn validate_gender(gender: &str) -> Result<(), validator::ValidationError> {
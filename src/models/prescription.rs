@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Prescription {
+    pub id: Uuid,
+    pub visit_id: Uuid,
+    pub patient_id: Uuid,
+    pub hospital_id: Uuid,
+    pub prescriber_id: Uuid,
+    pub drug_name: String,
+    pub dose: String,
+    pub route: String,
+    pub frequency: String,
+    pub duration_days: Option<i32>,
+    pub instructions: Option<String>,
+    pub start_date: NaiveDate,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub dispensed_at: Option<DateTime<Utc>>,
+    pub discontinued_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreatePrescriptionRequest {
+    pub prescriber_id: Uuid, // Must be an active DOCTOR with a valid licence
+    #[validate(length(min = 2, max = 255, message = "Drug name must be between 2 and 255 characters"))]
+    pub drug_name: String,
+    #[validate(length(min = 1, max = 50, message = "Dose must be between 1 and 50 characters"))]
+    pub dose: String, // e.g. "500 mg"
+    #[validate(custom(function = "validate_route"))]
+    pub route: String,
+    #[validate(custom(function = "validate_frequency"))]
+    pub frequency: String,
+    // Optional: omit for ongoing medication
+    #[validate(range(min = 1, max = 365, message = "Duration must be between 1 and 365 days"))]
+    pub duration_days: Option<i32>,
+    pub instructions: Option<String>,
+    // Optional: defaults to today
+    pub start_date: Option<NaiveDate>,
+}
+
+/// A new prescription plus anything the prescriber should double-check.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PrescriptionResponse {
+    pub prescription: Prescription,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PrescriptionActionRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct DuplicateMedication {
+    pub drug_name: String,
+    pub prescription_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MedicationHistory {
+    pub prescriptions: Vec<Prescription>,
+    // Drugs with more than one current (active or dispensed, course not finished) prescription
+    pub duplicate_warnings: Vec<DuplicateMedication>,
+}
+
+/// Actions accepted on `/prescriptions/{id}/{action}`,
+/// mapped to the statuses they may start from and the status they lead to.
+pub fn prescription_transition(action: &str) -> Option<(&'static [&'static str], &'static str)> {
+    match action {
+        "dispense" => Some((&["ACTIVE"], "DISPENSED")),
+        "discontinue" => Some((&["ACTIVE", "DISPENSED"], "DISCONTINUED")),
+        _ => None,
+    }
+}
+
+fn validate_route(route: &str) -> Result<(), validator::ValidationError> {
+    match route {
+        "ORAL" | "IV" | "IM" | "SC" | "TOPICAL" | "INHALED" | "RECTAL" | "SUBLINGUAL" | "OTHER" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid route")),
+    }
+}
+
+fn validate_frequency(frequency: &str) -> Result<(), validator::ValidationError> {
+    match frequency {
+        "STAT" | "OD" | "BD" | "TDS" | "QDS" | "NOCTE" | "PRN" | "WEEKLY" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid frequency")),
+    }
+}
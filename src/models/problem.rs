@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Problem {
+    pub id: Uuid,
+    pub patient_id: Uuid,
+    pub code: Option<String>,
+    pub description: String,
+    pub status: String,
+    pub onset_date: Option<NaiveDate>,
+    pub resolved_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateProblemRequest {
+    // ICD-10 code; optional, but one of code or description is required
+    pub code: Option<String>,
+    // Optional when a code is given: defaults to the code table description
+    #[validate(length(min = 2, message = "Description must be at least 2 characters"))]
+    pub description: Option<String>,
+    pub onset_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateProblemRequest {
+    #[validate(custom(function = "validate_problem_status"))]
+    pub status: String,
+    // Optional: defaults to today when resolving
+    pub resolved_date: Option<NaiveDate>,
+}
+
+fn validate_problem_status(status: &str) -> Result<(), validator::ValidationError> {
+    match status {
+        "ACTIVE" | "RESOLVED" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid problem status")),
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{NaiveDate, DateTime, Utc};
+use utoipa::ToSchema;
+
+/// A hospital's published facilities and bed availability. Facility-level data only.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct PublicHospital {
+    pub id: Uuid,
+    pub name: String,
+    pub hospital_type: String,
+    pub state: String,
+    pub city: String,
+    pub lga: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub total_beds: Option<i32>,
+    pub available_beds: Option<i32>,
+    pub has_emergency: Option<bool>,
+    pub has_oxygen: bool,
+    pub has_ventilators: bool,
+    pub has_ambulance: bool,
+    // OK, LOW, OUT, or UNKNOWN when the hospital does not track oxygen stock
+    pub oxygen_status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublicHospitalQuery {
+    pub state: Option<String>,
+    pub lga: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublicVisitQuery {
+    pub state: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Raw weekly visit count for one area, before suppression. Never returned as is.
+#[derive(Debug, FromRow)]
+pub struct AreaWeekCount {
+    pub week_start: NaiveDate,
+    pub state: String,
+    pub lga: Option<String>,
+    pub visits: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicVisitCount {
+    // Monday of the ISO week
+    pub week_start: NaiveDate,
+    pub state: String,
+    pub lga: Option<String>,
+    // Null when suppressed
+    pub visits: Option<i64>,
+    pub suppressed: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicVisitCounts {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    // Counts below this are withheld
+    pub min_cell_size: i64,
+    pub counts: Vec<PublicVisitCount>,
+}
+
+impl PublicVisitCount {
+    /// Withholds counts between 1 and `min_cell_size - 1`, which could identify individuals.
+    pub fn from_count(count: AreaWeekCount, min_cell_size: i64) -> Self {
+        let suppressed = count.visits < min_cell_size;
+        Self {
+            week_start: count.week_start,
+            state: count.state,
+            lga: count.lga,
+            visits: (!suppressed).then_some(count.visits),
+            suppressed,
+        }
+    }
+}
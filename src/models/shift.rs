@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Shift {
+    pub id: Uuid,
+    pub hospital_id: Uuid,
+    pub department_id: Uuid,
+    pub staff_id: Uuid,
+    pub role: String,
+    pub shift_type: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Why a shift could not be rostered.
+#[derive(Debug, PartialEq)]
+pub enum ShiftRejection {
+    StaffInactive,
+    Overlap,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateShiftRequest {
+    pub staff_id: Uuid,
+    // Optional: defaults to the staff member's own department
+    pub department_id: Option<Uuid>,
+    #[validate(custom(function = "validate_shift_type"))]
+    pub shift_type: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct RotaTemplate {
+    pub id: Uuid,
+    pub hospital_id: Uuid,
+    pub department_id: Uuid,
+    pub staff_id: Uuid,
+    pub shift_type: String,
+    pub day_of_week: i16,
+    pub start_time: NaiveTime,
+    pub duration_minutes: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateRotaTemplateRequest {
+    pub staff_id: Uuid,
+    pub department_id: Option<Uuid>,
+    #[validate(custom(function = "validate_shift_type"))]
+    pub shift_type: String,
+    // 0 = Monday ... 6 = Sunday
+    #[validate(range(min = 0, max = 6, message = "Day of week must be between 0 (Monday) and 6 (Sunday)"))]
+    pub day_of_week: i16,
+    pub start_time: NaiveTime, // Format: HH:MM:SS (UTC)
+    #[validate(range(min = 1, max = 1440, message = "Duration must be between 1 and 1440 minutes"))]
+    pub duration_minutes: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ApplyRotaRequest {
+    // First day of the 7-day window the templates are expanded into
+    pub week_start: NaiveDate,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApplyRotaResponse {
+    pub created: Vec<Shift>,
+    // Templates that were not applied because the staff member already had an overlapping shift
+    pub skipped: u32,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct CoverageRule {
+    pub id: Uuid,
+    pub department_id: Uuid,
+    pub role: String,
+    pub min_staff: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpsertCoverageRuleRequest {
+    pub department_id: Uuid,
+    #[validate(custom(function = "validate_staff_role"))]
+    pub role: String,
+    #[validate(range(min = 0, message = "Minimum staff cannot be negative"))]
+    pub min_staff: i32,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct OnDutyStaff {
+    pub shift_id: Uuid,
+    pub staff_id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub role: String,
+    pub department_id: Uuid,
+    pub department_name: String,
+    pub shift_type: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct CoverageGap {
+    pub department_id: Uuid,
+    pub department_name: String,
+    pub role: String,
+    pub min_staff: i32,
+    pub on_duty: i64,
+}
+
+fn validate_shift_type(shift_type: &str) -> Result<(), validator::ValidationError> {
+    match shift_type {
+        "REGULAR" | "ON_CALL" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid shift type")),
+    }
+}
+
+fn validate_staff_role(role: &str) -> Result<(), validator::ValidationError> {
+    match role {
+        "DOCTOR" | "NURSE" | "ADMIN" | "SUPPORT" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid staff role")),
+    }
+}
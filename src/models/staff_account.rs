@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+use crate::models::staff::Staff;
+
+// Audience of staff session tokens, so they cannot stand in for admin tokens (or the reverse)
+pub const STAFF_AUDIENCE: &str = "staff";
+
+/// A staff member's sign-in. The password hash is never listed.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct StaffAccount {
+    pub staff_id: Uuid,
+    pub email: String,
+    pub created_at: DateTime<Utc>,
+    pub password_changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SetStaffAccountRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+    #[validate(length(min = 8, max = 128, message = "Password must be between 8 and 128 characters"))]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StaffLoginResponse {
+    pub token: String,
+    pub staff: Staff,
+}
+
+/// What a staff login needs to check a password.
+#[derive(Debug, FromRow)]
+pub struct StaffLogin {
+    pub staff_id: Uuid,
+    pub password_hash: String,
+    pub is_active: bool,
+}
+
+/// The signed-in staff member behind a session token, as of now.
+#[derive(Debug, FromRow)]
+pub struct StaffSession {
+    pub staff_id: Uuid,
+    pub hospital_id: Uuid,
+    pub is_active: bool,
+    pub password_changed_at: DateTime<Utc>,
+}
+
+// JWT claims of a staff session
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StaffClaims {
+    pub sub: String, // Staff ID
+    pub aud: String,
+    pub exp: usize,
+    pub iat: usize,
+}
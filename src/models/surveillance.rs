@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct NotifiableCondition {
+    pub id: Uuid,
+    pub name: String,
+    pub code_prefix: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateNotifiableConditionRequest {
+    #[validate(length(min = 2, message = "Name must be at least 2 characters"))]
+    pub name: String,
+    #[validate(length(min = 1, max = 10, message = "Code prefix must be between 1 and 10 characters"))]
+    pub code_prefix: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateNotifiableConditionRequest {
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct SurveillanceCase {
+    // No patient id: case reports leave the hospital, identities do not
+    pub id: Uuid,
+    pub visit_id: Uuid,
+    pub hospital_id: Uuid,
+    pub condition_id: Uuid,
+    pub condition_name: String,
+    pub diagnosis_code: String,
+    pub state: String,
+    pub lga: String,
+    pub presented_at: DateTime<Utc>,
+    pub reported_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct WeeklyCaseCount {
+    // Monday of the ISO week the cases presented in
+    pub week_start: NaiveDate,
+    pub state: String,
+    pub lga: String,
+    pub condition_id: Uuid,
+    pub condition_name: String,
+    pub cases: i64,
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Event types that can appear on a patient timeline.
+pub const TIMELINE_EVENT_TYPES: [&str; 10] = [
+    "VISIT",
+    "STATUS_CHANGE",
+    "APPOINTMENT",
+    "DIAGNOSIS",
+    "OBSERVATION",
+    "PRESCRIPTION",
+    "LAB_ORDER",
+    "LAB_RESULT",
+    "ALLERGY",
+    "PROBLEM",
+];
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TimelineEvent {
+    pub event_type: String,
+    pub occurred_at: DateTime<Utc>,
+    // The row behind the event (visit, diagnosis, prescription, ...)
+    pub reference_id: Uuid,
+    // Allergies and problems belong to the patient rather than a visit
+    pub visit_id: Option<Uuid>,
+    pub hospital_id: Option<Uuid>,
+    pub hospital_name: Option<String>,
+    pub summary: String,
+    // Status, type or flag of the underlying row, e.g. COMPLETED, PRIMARY, HIGH
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PatientTimeline {
+    pub patient_id: Uuid,
+    pub page: i64,
+    pub per_page: i64,
+    pub has_more: bool,
+    pub events: Vec<TimelineEvent>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct TimelineQuery {
+    // Optional: defaults to 1
+    #[validate(range(min = 1, max = 1000000, message = "Page must be between 1 and 1,000,000"))]
+    pub page: Option<i64>,
+    // Optional: defaults to 50
+    #[validate(range(min = 1, max = 200, message = "Per page must be between 1 and 200"))]
+    pub per_page: Option<i64>,
+    // Comma-separated event types, e.g. DIAGNOSIS,LAB_RESULT
+    #[validate(custom(function = "validate_event_types"))]
+    pub types: Option<String>,
+    // Optional: "desc" for newest first; defaults to oldest first
+    #[validate(custom(function = "validate_order"))]
+    pub order: Option<String>,
+}
+
+impl TimelineQuery {
+    pub fn event_types(&self) -> Option<Vec<String>> {
+        self.types.as_ref().map(|types| types.split(',').map(|t| t.trim().to_uppercase()).collect())
+    }
+}
+
+fn validate_event_types(types: &str) -> Result<(), validator::ValidationError> {
+    if types.split(',').all(|t| TIMELINE_EVENT_TYPES.contains(&t.trim().to_uppercase().as_str())) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("Invalid timeline event type"))
+    }
+}
+
+fn validate_order(order: &str) -> Result<(), validator::ValidationError> {
+    match order {
+        "asc" | "desc" => Ok(()),
+        _ => Err(validator::ValidationError::new("Order must be asc or desc")),
+    }
+}
@@ -5,6 +5,9 @@ use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use validator::Validate;
 
+/// How long a visit booked outside any availability slot is assumed to take, for clash checks.
+pub const DEFAULT_VISIT_MINUTES: i32 = 30;
+
 #[derive(Debug, Serialize, FromRow, ToSchema)]
 pub struct Visit {
     pub id: Uuid,
@@ -27,4 +30,17 @@ pub struct CreateVisitRequest {
     pub reason: String,
     // Optional: User can specify a start time (for appointments), otherwise defaults to NOW
     pub start_time: Option<DateTime<Utc>>,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateVisitStatusRequest {
+    #[validate(custom(function = "validate_visit_status"))]
+    pub status: String,
+}
+
+fn validate_visit_status(status: &str) -> Result<(), validator::ValidationError> {
+    match status {
+        "IN_PROGRESS" | "COMPLETED" => Ok(()),
+        _ => Err(validator::ValidationError::new("Invalid visit status")),
+    }
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+pub const EVENT_HOSPITAL_UPDATED: &str = "hospital.updated";
+pub const EVENT_CAPACITY_CHANGED: &str = "capacity.changed";
+pub const EVENT_VISIT_COMPLETED: &str = "visit.completed";
+pub const EVENT_CAPACITY_ALERT_RAISED: &str = "capacity_alert.raised";
+// Accepted for subscriptions so partners can register ahead of the referrals module, which will emit it
+pub const EVENT_REFERRAL_CREATED: &str = "referral.created";
+
+pub const WEBHOOK_EVENT_TYPES: [&str; 5] = [
+    EVENT_HOSPITAL_UPDATED,
+    EVENT_CAPACITY_CHANGED,
+    EVENT_VISIT_COMPLETED,
+    EVENT_CAPACITY_ALERT_RAISED,
+    EVENT_REFERRAL_CREATED,
+];
+
+/// A registered partner endpoint. The signing secret is never listed.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateWebhookEndpointRequest {
+    #[validate(length(min = 3, max = 255, message = "Name must be between 3 and 255 characters"))]
+    pub name: String,
+    #[validate(custom(function = "validate_url"))]
+    pub url: String,
+    #[validate(custom(function = "validate_event_types"))]
+    pub event_types: Vec<String>,
+    // Optional: a random secret is generated if omitted
+    #[validate(length(min = 16, max = 255, message = "Secret must be between 16 and 255 characters"))]
+    pub secret: Option<String>,
+}
+
+/// Returned once, on registration: the partner needs the secret to verify signatures.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RegisteredWebhookEndpoint {
+    pub endpoint: WebhookEndpoint,
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    // The exact JSON body that was (or will be) signed and sent
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A delivery claimed for an attempt, with what is needed to send it.
+#[derive(Debug, FromRow)]
+pub struct DueWebhookDelivery {
+    pub id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: String,
+    pub attempts: i32,
+}
+
+/// `visit.completed` data. Partners get timing and place only: no patient, staff or reason.
+#[derive(Debug, Serialize)]
+pub struct VisitCompletedEvent {
+    pub visit_id: Uuid,
+    pub hospital_id: Uuid,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct WebhookDispatchSummary {
+    pub attempted: usize,
+    pub delivered: usize,
+    // Failed attempts that will be retried
+    pub retrying: usize,
+    // Failed attempts that used up the last retry
+    pub failed: usize,
+}
+
+fn validate_url(url: &str) -> Result<(), validator::ValidationError> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("URL must start with http:// or https://"))
+    }
+}
+
+fn validate_event_types(event_types: &[String]) -> Result<(), validator::ValidationError> {
+    if event_types.is_empty() {
+        return Err(validator::ValidationError::new("Subscribe to at least one event type"));
+    }
+    if event_types.iter().all(|t| WEBHOOK_EVENT_TYPES.contains(&t.as_str())) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("Unknown event type"))
+    }
+}
@@ -0,0 +1,301 @@
+pub mod providers;
+pub mod template;
+
+use std::{collections::HashMap, sync::Arc};
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    config::Settings,
+    db::notification_repo,
+    errors::app::AppError,
+    jobs,
+    models::notification::{
+        DueNotification, NewNotification, Notification, NotificationDispatchSummary, NotificationTemplate,
+        TEMPLATE_PASSWORD_RESET,
+    },
+};
+use providers::{FileSink, HttpSmsProvider, LogSink, SmtpProvider};
+
+/// Sends one rendered message on one channel.
+#[async_trait]
+pub trait NotificationProvider: Send + Sync {
+    /// Recorded against each attempt in the delivery log
+    fn name(&self) -> &'static str;
+
+    /// Returns the provider's message id when it hands one back. Errors are retried.
+    async fn send(&self, notification: &DueNotification) -> Result<Option<String>, String>;
+}
+
+#[derive(Debug)]
+pub enum NotifyError {
+    Database(sqlx::Error),
+    UnknownRecipient,
+    UnknownTemplate(String),
+    MissingVariable(String),
+    InvalidRequest(String),
+}
+
+impl From<sqlx::Error> for NotifyError {
+    fn from(e: sqlx::Error) -> Self {
+        NotifyError::Database(e)
+    }
+}
+
+impl std::fmt::Display for NotifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotifyError::Database(e) => write!(f, "{}", e),
+            NotifyError::UnknownRecipient => write!(f, "Recipient not found"),
+            NotifyError::UnknownTemplate(key) => write!(f, "No notification template {:?} for this channel", key),
+            NotifyError::MissingVariable(name) => write!(f, "Template variable {:?} has no value", name),
+            NotifyError::InvalidRequest(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl From<NotifyError> for AppError {
+    fn from(e: NotifyError) -> Self {
+        match e {
+            NotifyError::Database(e) => e.into(),
+            NotifyError::UnknownRecipient => AppError::NotFound,
+            other => AppError::BadRequest(other.to_string()),
+        }
+    }
+}
+
+/// Renders templates into the notification queue and sends the queue through the configured
+/// email and SMS providers, retrying failures with exponential back-off.
+#[derive(Clone)]
+pub struct Notifier {
+    pool: PgPool,
+    email: Arc<dyn NotificationProvider>,
+    sms: Arc<dyn NotificationProvider>,
+    max_attempts: i32,
+    retry_base_secs: i64,
+    retry_max_secs: i64,
+    timeout_secs: u64,
+    batch_size: i64,
+    pub interval_secs: u64,
+}
+
+impl Notifier {
+    /// Picks providers from `NOTIFY_EMAIL_PROVIDER` (`smtp`, `file` or `log`) and
+    /// `NOTIFY_SMS_PROVIDER` (`http`, `file` or `log`). Both default to `log`.
+    pub fn from_settings(pool: PgPool, settings: &Settings) -> Result<Self, String> {
+        let timeout_secs = settings.notify_timeout_secs.max(1);
+        let sink = || Arc::new(FileSink::new(&settings.notify_sink_path));
+
+        let email: Arc<dyn NotificationProvider> = match settings.notify_email_provider.as_str() {
+            "smtp" => {
+                let host = settings.smtp_host.as_deref().ok_or("SMTP_HOST is required for the smtp provider")?;
+                let from = settings.smtp_from.as_deref().ok_or("SMTP_FROM is required for the smtp provider")?;
+                let credentials = settings.smtp_username.clone().zip(settings.smtp_password.clone());
+                Arc::new(SmtpProvider::new(host, settings.smtp_port, &settings.smtp_security, credentials, from, timeout_secs)?)
+            }
+            "file" => sink(),
+            "log" => Arc::new(LogSink),
+            other => return Err(format!("Unknown email provider {:?}", other)),
+        };
+
+        let sms: Arc<dyn NotificationProvider> = match settings.notify_sms_provider.as_str() {
+            "http" => {
+                let url = settings.sms_gateway_url.as_deref().ok_or("SMS_GATEWAY_URL is required for the http provider")?;
+                Arc::new(HttpSmsProvider::new(url, settings.sms_gateway_token.clone(), settings.sms_sender.clone(), timeout_secs)?)
+            }
+            "file" => sink(),
+            "log" => Arc::new(LogSink),
+            other => return Err(format!("Unknown SMS provider {:?}", other)),
+        };
+
+        Ok(Self::new(pool, email, sms, settings))
+    }
+
+    /// Uses the given providers; retry and pacing settings still come from `settings`
+    pub fn new(
+        pool: PgPool,
+        email: Arc<dyn NotificationProvider>,
+        sms: Arc<dyn NotificationProvider>,
+        settings: &Settings,
+    ) -> Self {
+        Self {
+            pool,
+            email,
+            sms,
+            max_attempts: settings.notify_max_attempts.max(1),
+            retry_base_secs: settings.notify_retry_base_secs.max(1),
+            retry_max_secs: settings.notify_retry_max_secs.max(1),
+            timeout_secs: settings.notify_timeout_secs.max(1),
+            batch_size: settings.notify_batch_size.max(1),
+            interval_secs: settings.notify_dispatch_interval_secs,
+        }
+    }
+
+    /// Queues a template for a staff member on `channel`, or on every channel they can be
+    /// reached on when `None`. Disabled channels, muted templates, missing contact details and
+    /// inactive staff are skipped silently, so the result may be empty. `dedupe_key` makes a
+    /// repeated call for the same cause a no-op.
+    pub async fn send_to_staff(
+        &self,
+        staff_id: Uuid,
+        template_key: &str,
+        channel: Option<&str>,
+        variables: &HashMap<String, String>,
+        dedupe_key: Option<&str>,
+    ) -> Result<Vec<Notification>, NotifyError> {
+        let recipient = notification_repo::get_recipient(&self.pool, staff_id)
+            .await?
+            .ok_or(NotifyError::UnknownRecipient)?;
+
+        // Password resets are asked for by the person receiving them, so they ignore opt-outs
+        let transactional = template_key == TEMPLATE_PASSWORD_RESET;
+        if !recipient.is_active
+            || (!transactional && recipient.muted_templates.iter().any(|t| t == template_key))
+        {
+            return Ok(Vec::new());
+        }
+
+        let channels: &[&str] = match channel {
+            Some(channel) => &[channel][..],
+            None => &["EMAIL", "SMS"],
+        };
+        let mut templates = Vec::new();
+        for &channel in channels {
+            if !matches!(channel, "EMAIL" | "SMS") {
+                return Err(NotifyError::InvalidRequest(format!("Invalid channel {}", channel)));
+            }
+            if let Some(template) = notification_repo::get_template(&self.pool, template_key, channel).await? {
+                templates.push(template);
+            }
+        }
+        // With every channel requested, one without a template is simply not used
+        if templates.is_empty() {
+            return Err(NotifyError::UnknownTemplate(template_key.to_string()));
+        }
+
+        let mut rendered = Vec::new();
+        for template in &templates {
+            let (enabled, address) = match template.channel.as_str() {
+                "EMAIL" => (recipient.email_enabled, recipient.email.as_deref()),
+                _ => (recipient.sms_enabled, recipient.contact_phone.as_deref()),
+            };
+            let Some(address) = address.map(str::trim).filter(|a| !a.is_empty()) else {
+                continue;
+            };
+            if !enabled && !transactional {
+                continue;
+            }
+
+            let (subject, body) = render(template, variables)?;
+            rendered.push(NewNotification {
+                staff_id: Some(staff_id),
+                template_key: template_key.to_string(),
+                channel: template.channel.clone(),
+                recipient: address.to_string(),
+                subject,
+                body,
+                dedupe_key: dedupe_key.map(str::to_string),
+            });
+        }
+
+        self.enqueue_all(rendered).await
+    }
+
+    /// Queues a template for an address that is not a staff member's (an admin's email for a
+    /// password reset, say). No preferences apply.
+    pub async fn send_to_address(
+        &self,
+        channel: &str,
+        to: &str,
+        template_key: &str,
+        variables: &HashMap<String, String>,
+        dedupe_key: Option<&str>,
+    ) -> Result<Vec<Notification>, NotifyError> {
+        let template = notification_repo::get_template(&self.pool, template_key, channel)
+            .await?
+            .ok_or_else(|| NotifyError::UnknownTemplate(template_key.to_string()))?;
+        let (subject, body) = render(&template, variables)?;
+        self.enqueue_all(vec![NewNotification {
+            staff_id: None,
+            template_key: template_key.to_string(),
+            channel: channel.to_string(),
+            recipient: to.trim().to_string(),
+            subject,
+            body,
+            dedupe_key: dedupe_key.map(str::to_string),
+        }])
+        .await
+    }
+
+    /// Queues the messages and makes their first attempt in the background
+    async fn enqueue_all(&self, messages: Vec<NewNotification>) -> Result<Vec<Notification>, NotifyError> {
+        let mut queued = Vec::new();
+        for message in messages {
+            if let Some(notification) = notification_repo::enqueue(&self.pool, message).await? {
+                queued.push(notification);
+            }
+        }
+
+        if !queued.is_empty() {
+            let ids: Vec<Uuid> = queued.iter().map(|n| n.id).collect();
+            let notifier = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = notifier.dispatch(Some(&ids)).await {
+                    tracing::error!("Notification dispatch failed: {:?}", e);
+                }
+            });
+        }
+        Ok(queued)
+    }
+
+    /// Sends one batch of due notifications (or just `only`, if they are due), recording each
+    /// outcome and scheduling retries.
+    pub async fn dispatch(&self, only: Option<&[Uuid]>) -> Result<NotificationDispatchSummary, sqlx::Error> {
+        // Long enough that a claimed notification is not picked up again while it is being sent
+        let lease_secs = (self.timeout_secs + 30) as f64;
+        let due = notification_repo::claim_due(&self.pool, only, self.batch_size, lease_secs).await?;
+
+        let mut summary = NotificationDispatchSummary::default();
+        for notification in &due {
+            let provider = match notification.channel.as_str() {
+                "SMS" => &self.sms,
+                _ => &self.email,
+            };
+            summary.attempted += 1;
+            match provider.send(notification).await {
+                Ok(message_id) => {
+                    notification_repo::mark_sent(&self.pool, notification.id, provider.name(), message_id).await?;
+                    summary.sent += 1;
+                }
+                Err(error) => {
+                    let attempts = notification.attempts + 1;
+                    let retry_at = (attempts < self.max_attempts).then(|| Utc::now() + jobs::backoff(attempts, self.retry_base_secs, self.retry_max_secs));
+                    if retry_at.is_some() {
+                        summary.retrying += 1;
+                    } else {
+                        summary.failed += 1;
+                        tracing::warn!("Giving up on notification {} after {} attempts: {}", notification.id, attempts, error);
+                    }
+                    notification_repo::mark_attempt_failed(&self.pool, notification.id, provider.name(), &error, retry_at)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+/// Fills a template's subject (email only) and body
+fn render(
+    template: &NotificationTemplate,
+    variables: &HashMap<String, String>,
+) -> Result<(Option<String>, String), NotifyError> {
+    let subject = match (template.channel.as_str(), template.subject.as_deref()) {
+        ("EMAIL", Some(subject)) => Some(template::render(subject, variables).map_err(NotifyError::MissingVariable)?),
+        _ => None,
+    };
+    let body = template::render(&template.body, variables).map_err(NotifyError::MissingVariable)?;
+    Ok((subject, body))
+}
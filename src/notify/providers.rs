@@ -0,0 +1,213 @@
+use std::{path::PathBuf, time::Duration as StdDuration};
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use crate::models::notification::DueNotification;
+use super::NotificationProvider;
+
+/// Email over SMTP. `security` is `starttls` (upgrade a plain connection, usually port 587),
+/// `tls` (TLS from the start, usually 465) or `none` (local relays and test servers only).
+pub struct SmtpProvider {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpProvider {
+    pub fn new(
+        host: &str,
+        port: u16,
+        security: &str,
+        credentials: Option<(String, String)>,
+        from: &str,
+        timeout_secs: u64,
+    ) -> Result<Self, String> {
+        let builder = match security {
+            "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host).map_err(|e| e.to_string())?,
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(host).map_err(|e| e.to_string())?,
+            "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+            other => return Err(format!("Unknown SMTP security mode {:?}", other)),
+        };
+        let mut builder = builder.port(port).timeout(Some(StdDuration::from_secs(timeout_secs)));
+        if let Some((username, password)) = credentials {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: from.parse().map_err(|e| format!("Invalid sender address {:?}: {}", from, e))?,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for SmtpProvider {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    async fn send(&self, notification: &DueNotification) -> Result<Option<String>, String> {
+        let to: Mailbox = notification.recipient.parse().map_err(|e| format!("Invalid recipient address: {}", e))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(notification.subject.clone().unwrap_or_default())
+            .header(ContentType::TEXT_PLAIN)
+            .body(notification.body.clone())
+            .map_err(|e| e.to_string())?;
+
+        let response = self.transport.send(message).await.map_err(|e| e.to_string())?;
+        // Usually carries the relay's queue id, e.g. "2.0.0 Ok: queued as 4XyZ"
+        Ok(response.first_line().map(str::to_string))
+    }
+}
+
+#[derive(Serialize)]
+struct SmsRequest<'a> {
+    to: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<&'a str>,
+    message: &'a str,
+}
+
+#[derive(Deserialize)]
+struct SmsResponse {
+    id: Option<serde_json::Value>,
+    message_id: Option<serde_json::Value>,
+}
+
+/// SMS through a gateway that takes `{"to", "from", "message"}` as a JSON POST with an optional
+/// bearer token. Any 2xx counts as accepted; an `id` or `message_id` in the response is kept.
+pub struct HttpSmsProvider {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+    sender: Option<String>,
+    timeout_secs: u64,
+}
+
+impl HttpSmsProvider {
+    pub fn new(url: &str, token: Option<String>, sender: Option<String>, timeout_secs: u64) -> Result<Self, String> {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(format!("SMS gateway URL must be http(s): {:?}", url));
+        }
+        Ok(Self { client: reqwest::Client::new(), url: url.to_string(), token, sender, timeout_secs })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for HttpSmsProvider {
+    fn name(&self) -> &'static str {
+        "http_sms"
+    }
+
+    async fn send(&self, notification: &DueNotification) -> Result<Option<String>, String> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .timeout(StdDuration::from_secs(self.timeout_secs))
+            .json(&SmsRequest { to: &notification.recipient, from: self.sender.as_deref(), message: &notification.body });
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Gateway responded with {}", status));
+        }
+
+        let id = response
+            .json::<SmsResponse>()
+            .await
+            .ok()
+            .and_then(|r| r.id.or(r.message_id))
+            .map(|id| match id {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            });
+        Ok(id)
+    }
+}
+
+#[derive(Serialize)]
+struct SinkRecord<'a> {
+    id: uuid::Uuid,
+    channel: &'a str,
+    to: &'a str,
+    subject: Option<&'a str>,
+    body: &'a str,
+    sent_at: chrono::DateTime<Utc>,
+}
+
+/// Appends each message as a JSON line to a file instead of sending it. For local development
+/// and tests; one file can serve both channels.
+pub struct FileSink {
+    path: PathBuf,
+    // Keeps lines from concurrent sends whole
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl FileSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), lock: tokio::sync::Mutex::new(()) }
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for FileSink {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&self, notification: &DueNotification) -> Result<Option<String>, String> {
+        let record = SinkRecord {
+            id: notification.id,
+            channel: &notification.channel,
+            to: &notification.recipient,
+            subject: notification.subject.as_deref(),
+            body: &notification.body,
+            sent_at: Utc::now(),
+        };
+        let mut line = serde_json::to_string(&record).map_err(|e| e.to_string())?;
+        line.push('\n');
+
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| format!("Opening {}: {}", self.path.display(), e))?;
+        file.write_all(line.as_bytes()).await.map_err(|e| e.to_string())?;
+        Ok(None)
+    }
+}
+
+/// Writes each message to the application log instead of sending it. The default, so a fresh
+/// checkout never emails or texts anyone.
+pub struct LogSink;
+
+#[async_trait]
+impl NotificationProvider for LogSink {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn send(&self, notification: &DueNotification) -> Result<Option<String>, String> {
+        tracing::info!(
+            notification_id = %notification.id,
+            channel = %notification.channel,
+            to = %notification.recipient,
+            subject = notification.subject.as_deref().unwrap_or(""),
+            "Notification (log sink): {}",
+            notification.body
+        );
+        Ok(None)
+    }
+}
@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+/// Fills `{{name}}` placeholders (spaces inside the braces are allowed). Fails with the name of
+/// the first placeholder that has no value, so a half-filled message is never queued. An
+/// unclosed `{{` is left as it is.
+pub fn render(template: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = rest[start + 2..start + 2 + len].trim();
+        let value = variables.get(name).ok_or_else(|| name.to_string())?;
+        out.push_str(value);
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
@@ -0,0 +1,101 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        allergy::{Allergy, CreateAllergyRequest, UpdateAllergyRequest},
+        consent::AccessContext,
+        api_response::ApiResponse,
+    },
+    db::allergy_repo,
+    errors::app::AppError,
+    middleware::access::authorize_patient,
+};
+
+#[derive(Deserialize)]
+pub struct AllergyQuery {
+    pub include_inactive: Option<bool>,
+}
+
+/// Record an allergy for a patient
+#[utoipa::path(
+    post,
+    path = "/api/v1/patients/{id}/allergies",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID")
+    ),
+    request_body = CreateAllergyRequest,
+    responses(
+        (status = 200, description = "Allergy recorded", body = ApiResponse<Allergy>),
+        (status = 409, description = "Substance already recorded as an active allergy")
+    )
+)]
+pub async fn create_allergy_handler(
+    State(state): State<AppState>,
+    Path(patient_id): Path<Uuid>,
+    Json(payload): Json<CreateAllergyRequest>,
+) -> Result<Json<ApiResponse<Allergy>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let allergy = allergy_repo::create_allergy(&state.db, patient_id, payload).await?;
+    Ok(Json(ApiResponse::success(allergy, Some("Allergy recorded".to_string()))))
+}
+
+/// Get a patient's allergies (active only by default)
+#[utoipa::path(
+    get,
+    path = "/api/v1/patients/{id}/allergies",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID"),
+        ("include_inactive" = Option<bool>, Query, description = "Include refuted/inactive allergies")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "List of allergies", body = ApiResponse<Vec<Allergy>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override"),
+        (status = 404, description = "Patient not found")
+    )
+)]
+pub async fn get_patient_allergies(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(patient_id): Path<Uuid>,
+    Query(params): Query<AllergyQuery>,
+) -> Result<Json<ApiResponse<Vec<Allergy>>>, AppError> {
+    authorize_patient(&state.db, &ctx, patient_id, "ALLERGIES", None).await?;
+    let allergies = allergy_repo::get_patient_allergies(&state.db, patient_id, params.include_inactive.unwrap_or(false)).await?;
+    Ok(Json(ApiResponse::success(allergies, None)))
+}
+
+/// Activate or deactivate an allergy (e.g. refuted, or entered in error)
+#[utoipa::path(
+    put,
+    path = "/api/v1/allergies/{id}",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Allergy UUID")
+    ),
+    request_body = UpdateAllergyRequest,
+    responses(
+        (status = 200, description = "Allergy updated", body = ApiResponse<Allergy>),
+        (status = 404, description = "Allergy not found")
+    )
+)]
+pub async fn update_allergy_handler(
+    State(state): State<AppState>,
+    Path(allergy_id): Path<Uuid>,
+    Json(payload): Json<UpdateAllergyRequest>,
+) -> Result<Json<ApiResponse<Allergy>>, AppError> {
+    let allergy = allergy_repo::set_allergy_active(&state.db, allergy_id, payload.is_active).await?;
+    Ok(Json(ApiResponse::success(allergy, Some("Allergy updated".to_string()))))
+}
@@ -0,0 +1,150 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        analytics::{AnalyticsQuery, OccupancyGroup, HighOccupancySummary, StateCoverage, DailyVisitVolume},
+        api_response::ApiResponse,
+    },
+    db::analytics_repo,
+    errors::app::AppError,
+};
+
+impl AnalyticsQuery {
+    /// Defaults to the last 30 days; rejects empty ranges and ranges over a year.
+    fn range(&self) -> Result<(DateTime<Utc>, DateTime<Utc>), AppError> {
+        let to = self.to.unwrap_or_else(Utc::now);
+        let from = self.from.unwrap_or(to - Duration::days(30));
+        if from >= to {
+            return Err(AppError::BadRequest("'from' must be before 'to'".to_string()));
+        }
+        if to - from > Duration::days(366) {
+            return Err(AppError::BadRequest("Range cannot exceed 366 days".to_string()));
+        }
+        Ok((from, to))
+    }
+}
+
+/// Time-weighted bed occupancy grouped by state, city or hospital type
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/occupancy",
+    tag = "Analytics",
+    params(
+        ("group_by" = Option<String>, Query, description = "state (default), city or hospital_type"),
+        ("state" = Option<String>, Query, description = "Filter by state"),
+        ("from" = Option<String>, Query, description = "Start (RFC 3339), defaults to 30 days ago"),
+        ("to" = Option<String>, Query, description = "End (RFC 3339), defaults to now")
+    ),
+    responses(
+        (status = 200, description = "Occupancy per group, fullest first", body = ApiResponse<Vec<OccupancyGroup>>),
+        (status = 400, description = "Invalid grouping or range")
+    )
+)]
+pub async fn get_occupancy_analytics(
+    State(state): State<AppState>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<ApiResponse<Vec<OccupancyGroup>>>, AppError> {
+    if let Err(e) = params.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    let (from, to) = params.range()?;
+    let group_by = params.group_by.as_deref().unwrap_or("state");
+
+    let groups = analytics_repo::get_occupancy_by_group(&state.db, from, to, group_by, params.state).await?;
+    Ok(Json(ApiResponse::success(groups, None)))
+}
+
+/// Count hospitals at or above an occupancy threshold
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/occupancy/high",
+    tag = "Analytics",
+    params(
+        ("threshold" = Option<f64>, Query, description = "Occupancy rate between 0 and 1, defaults to 0.85"),
+        ("state" = Option<String>, Query, description = "Filter by state"),
+        ("from" = Option<String>, Query, description = "Start (RFC 3339), defaults to 30 days ago"),
+        ("to" = Option<String>, Query, description = "End (RFC 3339), defaults to now")
+    ),
+    responses(
+        (status = 200, description = "Hospitals at or above the threshold", body = ApiResponse<HighOccupancySummary>),
+        (status = 400, description = "Invalid threshold or range")
+    )
+)]
+pub async fn get_high_occupancy(
+    State(state): State<AppState>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<ApiResponse<HighOccupancySummary>>, AppError> {
+    if let Err(e) = params.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    let (from, to) = params.range()?;
+    let threshold = params.threshold.unwrap_or(0.85);
+
+    let all = analytics_repo::get_hospital_occupancy(&state.db, from, to, params.state).await?;
+    let hospitals_with_beds = all.len() as i64;
+    let average_at_or_above = all.iter().filter(|h| h.average_rate.is_some_and(|r| r >= threshold)).count() as i64;
+    let hospitals: Vec<_> = all
+        .into_iter()
+        .filter(|h| h.peak_rate.is_some_and(|r| r >= threshold))
+        .collect();
+
+    let summary = HighOccupancySummary {
+        threshold,
+        hospitals_with_beds,
+        average_at_or_above,
+        peak_at_or_above: hospitals.len() as i64,
+        hospitals,
+    };
+    Ok(Json(ApiResponse::success(summary, None)))
+}
+
+/// Emergency, oxygen, ventilator and ambulance coverage per state. Capabilities are not
+/// versioned, so this reflects the current configuration regardless of range.
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/coverage",
+    tag = "Analytics",
+    params(
+        ("state" = Option<String>, Query, description = "Filter by state")
+    ),
+    responses(
+        (status = 200, description = "Service coverage per state", body = ApiResponse<Vec<StateCoverage>>)
+    )
+)]
+pub async fn get_coverage_analytics(
+    State(state): State<AppState>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<ApiResponse<Vec<StateCoverage>>>, AppError> {
+    let coverage = analytics_repo::get_state_coverage(&state.db, params.state).await?;
+    Ok(Json(ApiResponse::success(coverage, None)))
+}
+
+/// Visits per day, including days with none
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/visits/daily",
+    tag = "Analytics",
+    params(
+        ("state" = Option<String>, Query, description = "Filter by state"),
+        ("hospital_id" = Option<Uuid>, Query, description = "Filter by hospital"),
+        ("from" = Option<String>, Query, description = "Start (RFC 3339), defaults to 30 days ago"),
+        ("to" = Option<String>, Query, description = "End (RFC 3339), defaults to now")
+    ),
+    responses(
+        (status = 200, description = "Daily visit volumes", body = ApiResponse<Vec<DailyVisitVolume>>),
+        (status = 400, description = "Invalid range")
+    )
+)]
+pub async fn get_daily_visit_analytics(
+    State(state): State<AppState>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<ApiResponse<Vec<DailyVisitVolume>>>, AppError> {
+    let (from, to) = params.range()?;
+    let days = analytics_repo::get_daily_visit_volumes(&state.db, from, to, params.state, params.hospital_id).await?;
+    Ok(Json(ApiResponse::success(days, None)))
+}
@@ -0,0 +1,230 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::{departments::check_department, state::AppState},
+    models::{
+        appointment::{
+            AvailabilitySlot, CreateSlotsRequest, BookSlotRequest, RescheduleAppointmentRequest,
+            CancelAppointmentRequest, AppointmentChange,
+        },
+        consent::AccessContext,
+        visit::Visit,
+        api_response::ApiResponse,
+    },
+    db::{appointment_repo, credential_repo, visit_repo},
+    errors::app::AppError,
+    middleware::access::authorize_visit,
+};
+
+#[derive(Deserialize)]
+pub struct AvailableSlotsQuery {
+    pub department_id: Option<Uuid>,
+    pub staff_id: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+/// Looks up an open slot and checks its clinician is licensed on the slot date.
+async fn bookable_slot(state: &AppState, slot_id: Uuid) -> Result<AvailabilitySlot, AppError> {
+    let slot = appointment_repo::get_slot(&state.db, slot_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !credential_repo::has_valid_credential(&state.db, slot.staff_id, slot.start_time.date_naive()).await? {
+        return Err(AppError::BadRequest("Staff member does not hold a valid licence for this date".to_string()));
+    }
+
+    Ok(slot)
+}
+
+/// Looks up a visit that can still be moved or cancelled.
+async fn pending_visit(state: &AppState, visit_id: Uuid) -> Result<Visit, AppError> {
+    let visit = visit_repo::get_visit_by_id(&state.db, visit_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if visit.status != "PENDING" {
+        return Err(AppError::Conflict(format!("Appointment is already {}", visit.status)));
+    }
+
+    Ok(visit)
+}
+
+/// Publish availability slots for a clinician
+#[utoipa::path(
+    post,
+    path = "/api/v1/slots",
+    tag = "Appointments",
+    request_body = CreateSlotsRequest,
+    responses(
+        (status = 200, description = "Slots published", body = ApiResponse<Vec<AvailabilitySlot>>),
+        (status = 400, description = "Department is not in the staff member's hospital"),
+        (status = 404, description = "Staff member not found"),
+        (status = 409, description = "Overlaps an existing slot or booked visit")
+    )
+)]
+pub async fn create_slots_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateSlotsRequest>,
+) -> Result<Json<ApiResponse<Vec<AvailabilitySlot>>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    check_department(&state, payload.staff_id, payload.department_id).await?;
+
+    let slots = appointment_repo::create_slots(&state.db, &payload)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Overlaps an existing slot or booked visit for this clinician".to_string()))?;
+
+    Ok(Json(ApiResponse::success(slots, Some("Slots published".to_string()))))
+}
+
+/// Next free slots, by department and/or clinician
+#[utoipa::path(
+    get,
+    path = "/api/v1/slots/available",
+    tag = "Appointments",
+    params(
+        ("department_id" = Option<Uuid>, Query, description = "Filter by Department ID"),
+        ("staff_id" = Option<Uuid>, Query, description = "Filter by clinician"),
+        ("limit" = Option<i64>, Query, description = "Maximum slots to return (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Open slots", body = ApiResponse<Vec<AvailabilitySlot>>)
+    )
+)]
+pub async fn get_available_slots(
+    State(state): State<AppState>,
+    Query(params): Query<AvailableSlotsQuery>,
+) -> Result<Json<ApiResponse<Vec<AvailabilitySlot>>>, AppError> {
+    let limit = params.limit.unwrap_or(10).clamp(1, 100);
+    let slots = appointment_repo::get_available_slots(&state.db, params.department_id, params.staff_id, limit).await?;
+    Ok(Json(ApiResponse::success(slots, None)))
+}
+
+/// Book a slot (creates a PENDING visit)
+#[utoipa::path(
+    post,
+    path = "/api/v1/slots/{id}/book",
+    tag = "Appointments",
+    params(
+        ("id" = Uuid, Path, description = "Slot UUID")
+    ),
+    request_body = BookSlotRequest,
+    responses(
+        (status = 200, description = "Appointment booked", body = ApiResponse<Visit>),
+        (status = 409, description = "Slot is no longer available")
+    )
+)]
+pub async fn book_slot_handler(
+    State(state): State<AppState>,
+    Path(slot_id): Path<Uuid>,
+    Json(payload): Json<BookSlotRequest>,
+) -> Result<Json<ApiResponse<Visit>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    bookable_slot(&state, slot_id).await?;
+
+    let visit = appointment_repo::book_slot(&state.db, slot_id, payload.patient_id, &payload.reason)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Slot is no longer available".to_string()))?;
+
+    Ok(Json(ApiResponse::success(visit, Some("Appointment booked".to_string()))))
+}
+
+/// Move an appointment to another slot
+#[utoipa::path(
+    post,
+    path = "/api/v1/appointments/{id}/reschedule",
+    tag = "Appointments",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    request_body = RescheduleAppointmentRequest,
+    responses(
+        (status = 200, description = "Appointment rescheduled", body = ApiResponse<Visit>),
+        (status = 400, description = "Slot is at a different hospital"),
+        (status = 409, description = "Slot unavailable or appointment no longer pending")
+    )
+)]
+pub async fn reschedule_appointment_handler(
+    State(state): State<AppState>,
+    Path(visit_id): Path<Uuid>,
+    Json(payload): Json<RescheduleAppointmentRequest>,
+) -> Result<Json<ApiResponse<Visit>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    let visit = pending_visit(&state, visit_id).await?;
+    let slot = bookable_slot(&state, payload.new_slot_id).await?;
+    if slot.hospital_id != visit.hospital_id {
+        return Err(AppError::BadRequest("Appointments can only move to a slot at the same hospital".to_string()));
+    }
+
+    let visit = appointment_repo::reschedule_visit(&state.db, visit_id, payload.new_slot_id, &payload.reason)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Slot is no longer available or appointment is no longer pending".to_string()))?;
+
+    Ok(Json(ApiResponse::success(visit, Some("Appointment rescheduled".to_string()))))
+}
+
+/// Cancel an appointment and free its slot
+#[utoipa::path(
+    post,
+    path = "/api/v1/appointments/{id}/cancel",
+    tag = "Appointments",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    request_body = CancelAppointmentRequest,
+    responses(
+        (status = 200, description = "Appointment cancelled", body = ApiResponse<Visit>),
+        (status = 409, description = "Appointment no longer pending")
+    )
+)]
+pub async fn cancel_appointment_handler(
+    State(state): State<AppState>,
+    Path(visit_id): Path<Uuid>,
+    Json(payload): Json<CancelAppointmentRequest>,
+) -> Result<Json<ApiResponse<Visit>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    pending_visit(&state, visit_id).await?;
+
+    let visit = appointment_repo::cancel_visit(&state.db, visit_id, &payload.reason)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Appointment is no longer pending".to_string()))?;
+    Ok(Json(ApiResponse::success(visit, Some("Appointment cancelled".to_string()))))
+}
+
+/// Booking, rescheduling and cancellation history of an appointment
+#[utoipa::path(
+    get,
+    path = "/api/v1/appointments/{id}/history",
+    tag = "Appointments",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Appointment changes", body = ApiResponse<Vec<AppointmentChange>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No consent or break-glass override for another hospital's visit"),
+        (status = 404, description = "Visit not found")
+    )
+)]
+pub async fn get_appointment_history(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(visit_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<AppointmentChange>>>, AppError> {
+    authorize_visit(&state.db, &ctx, visit_id, "APPOINTMENT_HISTORY").await?;
+    let changes = appointment_repo::get_appointment_changes(&state.db, visit_id).await?;
+    Ok(Json(ApiResponse::success(changes, None)))
+}
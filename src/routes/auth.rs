@@ -8,12 +8,16 @@ use crate::{
     routes::state::AppState,
     models::{
         user::{LoginRequest, LoginResponse, Claims},
+        staff_account::{StaffClaims, StaffLoginResponse, STAFF_AUDIENCE},
         api_response::ApiResponse,
     },
     errors::app::AppError,
-    db::user_repo,
+    db::{staff_account_repo, staff_repo, user_repo},
 };
 
+// Staff sessions last about a shift
+const STAFF_SESSION_SECS: usize = 12 * 3600;
+
 #[utoipa::path(
     post,
     path = "/api/v1/login",
@@ -67,4 +71,53 @@ pub async fn login_handler(
         LoginResponse { token, user },
         Some("Login successful".to_string()),
     )))
-}
\ No newline at end of file
+}
+
+/// Sign in as a staff member. The token is the staff session that patient-data endpoints require
+/// (`Authorization: Bearer <token>`); it names the staff member, and through them the hospital.
+#[utoipa::path(
+    post,
+    path = "/api/v1/staff/login",
+    tag = "Auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Login successful", body = StaffLoginResponse),
+        (status = 401, description = "Invalid credentials, or the staff member is not active")
+    )
+)]
+pub async fn staff_login_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<ApiResponse<StaffLoginResponse>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let login = staff_account_repo::find_login_by_email(&state.db, &payload.email)
+        .await?
+        .ok_or(AppError::Unauthorized)?;
+    if !verify(&payload.password, &login.password_hash).unwrap_or(false) || !login.is_active {
+        return Err(AppError::Unauthorized);
+    }
+    let staff = staff_repo::get_staff_by_id(&state.db, login.staff_id)
+        .await?
+        .ok_or(AppError::Unauthorized)?;
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as usize;
+    let claims = StaffClaims {
+        sub: staff.id.to_string(),
+        aud: STAFF_AUDIENCE.to_string(),
+        exp: now + STAFF_SESSION_SECS,
+        iat: now,
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
+    ).map_err(|_| AppError::Internal)?;
+
+    Ok(Json(ApiResponse::success(
+        StaffLoginResponse { token, staff },
+        Some("Login successful".to_string()),
+    )))
+}
@@ -0,0 +1,131 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::Utc;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        blood_bank::{
+            BloodUnitBatch, ReportBloodUnitsRequest, IssueBloodUnitsRequest, BloodStockLevel,
+            BloodSearchResult, BloodSearchQuery,
+        },
+        api_response::ApiResponse,
+    },
+    db::blood_bank_repo,
+    errors::app::AppError,
+};
+
+/// Report a batch of blood units held by a hospital
+#[utoipa::path(
+    post,
+    path = "/api/v1/hospitals/{id}/blood-units",
+    tag = "Blood Bank",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID")
+    ),
+    request_body = ReportBloodUnitsRequest,
+    responses(
+        (status = 200, description = "Blood units reported", body = ApiResponse<BloodUnitBatch>)
+    )
+)]
+pub async fn report_blood_units_handler(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Json(payload): Json<ReportBloodUnitsRequest>,
+) -> Result<Json<ApiResponse<BloodUnitBatch>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let batch = blood_bank_repo::report_units(&state.db, hospital_id, payload).await?;
+    Ok(Json(ApiResponse::success(batch, Some("Blood units reported".to_string()))))
+}
+
+/// Unexpired blood stock at a hospital by group and component
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/blood-units",
+    tag = "Blood Bank",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID")
+    ),
+    responses(
+        (status = 200, description = "Blood stock levels", body = ApiResponse<Vec<BloodStockLevel>>)
+    )
+)]
+pub async fn get_hospital_blood_stock(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<BloodStockLevel>>>, AppError> {
+    let stock = blood_bank_repo::get_hospital_stock(&state.db, hospital_id).await?;
+    Ok(Json(ApiResponse::success(stock, None)))
+}
+
+/// Issue (use or dispatch) units from a batch
+#[utoipa::path(
+    post,
+    path = "/api/v1/blood-units/{id}/issue",
+    tag = "Blood Bank",
+    params(
+        ("id" = Uuid, Path, description = "Blood unit batch UUID")
+    ),
+    request_body = IssueBloodUnitsRequest,
+    responses(
+        (status = 200, description = "Units issued", body = ApiResponse<BloodUnitBatch>),
+        (status = 404, description = "Batch not found"),
+        (status = 409, description = "Batch has expired or holds too few units")
+    )
+)]
+pub async fn issue_blood_units_handler(
+    State(state): State<AppState>,
+    Path(batch_id): Path<Uuid>,
+    Json(payload): Json<IssueBloodUnitsRequest>,
+) -> Result<Json<ApiResponse<BloodUnitBatch>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let batch = blood_bank_repo::get_batch(&state.db, batch_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if batch.expires_on < Utc::now().date_naive() {
+        return Err(AppError::Conflict("Batch has expired".to_string()));
+    }
+
+    let batch = blood_bank_repo::issue_units(&state.db, batch_id, payload.units)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Not enough unexpired units in batch".to_string()))?;
+    Ok(Json(ApiResponse::success(batch, Some("Units issued".to_string()))))
+}
+
+/// Find the nearest hospitals holding a blood group (URL-encode `+` as `%2B`)
+#[utoipa::path(
+    get,
+    path = "/api/v1/blood-bank/search",
+    tag = "Blood Bank",
+    params(
+        ("blood_group" = String, Query, description = "ABO/Rh group, e.g. O-"),
+        ("component" = Option<String>, Query, description = "WHOLE_BLOOD, RED_CELLS, PLASMA or PLATELETS"),
+        ("lat" = f64, Query, description = "Caller latitude"),
+        ("lng" = f64, Query, description = "Caller longitude"),
+        ("min_units" = Option<i64>, Query, description = "Minimum units required (default 1)"),
+        ("limit" = Option<i64>, Query, description = "Maximum hospitals to return (default 10, max 50)")
+    ),
+    responses(
+        (status = 200, description = "Hospitals ordered by distance", body = ApiResponse<Vec<BloodSearchResult>>)
+    )
+)]
+pub async fn search_blood_handler(
+    State(state): State<AppState>,
+    Query(params): Query<BloodSearchQuery>,
+) -> Result<Json<ApiResponse<Vec<BloodSearchResult>>>, AppError> {
+    if let Err(e) = params.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let results = blood_bank_repo::search_nearest(&state.db, &params).await?;
+    Ok(Json(ApiResponse::success(results, None)))
+}
@@ -0,0 +1,334 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        capacity_alert::{
+            AlertRule, CreateAlertRuleRequest, CapacityAlert, CapacityAlertQuery, AcknowledgeAlertRequest,
+            ResolveCapacityAlertRequest, AlertSubscription, CreateAlertSubscriptionRequest, AlertDelivery,
+        },
+        api_response::ApiResponse,
+    },
+    db::{capacity_alert_repo, hospital_repo, staff_repo},
+    errors::app::AppError,
+    jobs::capacity_alerts,
+};
+
+#[derive(Deserialize)]
+pub struct AlertScopeQuery {
+    pub hospital_id: Option<Uuid>,
+    pub state: Option<String>,
+}
+
+async fn check_scope(state: &AppState, hospital_id: Option<Uuid>) -> Result<(), AppError> {
+    if let Some(hospital_id) = hospital_id {
+        hospital_repo::fetch_hospital_by_id(&state.db, hospital_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Hospital does not exist".to_string()))?;
+    }
+    Ok(())
+}
+
+/// Create a capacity alert rule for a hospital, a state, or every hospital
+#[utoipa::path(
+    post,
+    path = "/api/v1/alert-rules",
+    tag = "Capacity Alerts",
+    request_body = CreateAlertRuleRequest,
+    responses(
+        (status = 200, description = "Rule created", body = ApiResponse<AlertRule>),
+        (status = 400, description = "Invalid metric, threshold or scope")
+    )
+)]
+pub async fn create_alert_rule_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateAlertRuleRequest>,
+) -> Result<Json<ApiResponse<AlertRule>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    payload.check_threshold().map_err(AppError::BadRequest)?;
+    check_scope(&state, payload.hospital_id).await?;
+
+    let rule = capacity_alert_repo::create_rule(&state.db, payload).await?;
+    Ok(Json(ApiResponse::success(rule, Some("Alert rule created".to_string()))))
+}
+
+/// List alert rules
+#[utoipa::path(
+    get,
+    path = "/api/v1/alert-rules",
+    tag = "Capacity Alerts",
+    params(
+        ("hospital_id" = Option<Uuid>, Query, description = "Rules scoped to this hospital"),
+        ("state" = Option<String>, Query, description = "Rules scoped to this state")
+    ),
+    responses(
+        (status = 200, description = "Alert rules", body = ApiResponse<Vec<AlertRule>>)
+    )
+)]
+pub async fn get_alert_rules_handler(
+    State(state): State<AppState>,
+    Query(params): Query<AlertScopeQuery>,
+) -> Result<Json<ApiResponse<Vec<AlertRule>>>, AppError> {
+    let rules = capacity_alert_repo::get_rules(&state.db, params.hospital_id, params.state).await?;
+    Ok(Json(ApiResponse::success(rules, None)))
+}
+
+/// Deactivate an alert rule; its open alerts clear on the next evaluation
+#[utoipa::path(
+    put,
+    path = "/api/v1/alert-rules/{id}/deactivate",
+    tag = "Capacity Alerts",
+    params(("id" = Uuid, Path, description = "Rule UUID")),
+    responses(
+        (status = 200, description = "Rule deactivated", body = ApiResponse<AlertRule>),
+        (status = 404, description = "Rule not found or already inactive")
+    )
+)]
+pub async fn deactivate_alert_rule_handler(
+    State(state): State<AppState>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<AlertRule>>, AppError> {
+    let rule = capacity_alert_repo::deactivate_rule(&state.db, rule_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(rule, Some("Alert rule deactivated".to_string()))))
+}
+
+/// Evaluate every rule against every active hospital now instead of waiting for the background sweep
+#[utoipa::path(
+    post,
+    path = "/api/v1/alert-rules/evaluate",
+    tag = "Capacity Alerts",
+    responses(
+        (status = 200, description = "Evaluation completed; returns all open alerts", body = ApiResponse<Vec<CapacityAlert>>)
+    )
+)]
+pub async fn evaluate_alert_rules_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<CapacityAlert>>>, AppError> {
+    let raised = capacity_alerts::run_once(&state.db).await?;
+    state.outbox.wake();
+    let alerts = capacity_alert_repo::get_alerts(&state.db, None, None, None).await?;
+    let message = format!("{} new alert(s) raised", raised);
+    Ok(Json(ApiResponse::success(alerts, Some(message))))
+}
+
+/// List capacity alerts (defaults to open alerts), most severe first
+#[utoipa::path(
+    get,
+    path = "/api/v1/capacity-alerts",
+    tag = "Capacity Alerts",
+    params(
+        ("status" = Option<String>, Query, description = "ACTIVE, ACKNOWLEDGED or RESOLVED; defaults to all open alerts"),
+        ("hospital_id" = Option<Uuid>, Query, description = "Filter by hospital"),
+        ("state" = Option<String>, Query, description = "Filter by state")
+    ),
+    responses(
+        (status = 200, description = "Capacity alerts", body = ApiResponse<Vec<CapacityAlert>>),
+        (status = 400, description = "Invalid status")
+    )
+)]
+pub async fn get_capacity_alerts_handler(
+    State(state): State<AppState>,
+    Query(params): Query<CapacityAlertQuery>,
+) -> Result<Json<ApiResponse<Vec<CapacityAlert>>>, AppError> {
+    if let Some(status) = params.status.as_deref() {
+        if !matches!(status, "ACTIVE" | "ACKNOWLEDGED" | "RESOLVED") {
+            return Err(AppError::BadRequest("Status must be ACTIVE, ACKNOWLEDGED or RESOLVED".to_string()));
+        }
+    }
+
+    let alerts = capacity_alert_repo::get_alerts(&state.db, params.status, params.hospital_id, params.state).await?;
+    Ok(Json(ApiResponse::success(alerts, None)))
+}
+
+/// Acknowledge an active alert
+#[utoipa::path(
+    put,
+    path = "/api/v1/capacity-alerts/{id}/acknowledge",
+    tag = "Capacity Alerts",
+    params(("id" = Uuid, Path, description = "Alert UUID")),
+    request_body = AcknowledgeAlertRequest,
+    responses(
+        (status = 200, description = "Alert acknowledged", body = ApiResponse<CapacityAlert>),
+        (status = 400, description = "Staff member does not exist or is inactive"),
+        (status = 404, description = "Alert not found"),
+        (status = 409, description = "Alert is not active")
+    )
+)]
+pub async fn acknowledge_capacity_alert_handler(
+    State(state): State<AppState>,
+    Path(alert_id): Path<Uuid>,
+    Json(payload): Json<AcknowledgeAlertRequest>,
+) -> Result<Json<ApiResponse<CapacityAlert>>, AppError> {
+    capacity_alert_repo::get_alert(&state.db, alert_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    check_staff(&state, payload.staff_id).await?;
+
+    capacity_alert_repo::acknowledge_alert(&state.db, alert_id, payload.staff_id)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Only active alerts can be acknowledged".to_string()))?;
+    state.outbox.wake();
+
+    let alert = capacity_alert_repo::get_alert(&state.db, alert_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(alert, Some("Alert acknowledged".to_string()))))
+}
+
+/// Resolve an open alert. If the condition still holds, the next evaluation raises a new one.
+#[utoipa::path(
+    put,
+    path = "/api/v1/capacity-alerts/{id}/resolve",
+    tag = "Capacity Alerts",
+    params(("id" = Uuid, Path, description = "Alert UUID")),
+    request_body = ResolveCapacityAlertRequest,
+    responses(
+        (status = 200, description = "Alert resolved", body = ApiResponse<CapacityAlert>),
+        (status = 400, description = "Staff member does not exist or is inactive"),
+        (status = 404, description = "Alert not found"),
+        (status = 409, description = "Alert is already resolved")
+    )
+)]
+pub async fn resolve_capacity_alert_handler(
+    State(state): State<AppState>,
+    Path(alert_id): Path<Uuid>,
+    Json(payload): Json<ResolveCapacityAlertRequest>,
+) -> Result<Json<ApiResponse<CapacityAlert>>, AppError> {
+    capacity_alert_repo::get_alert(&state.db, alert_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    check_staff(&state, payload.staff_id).await?;
+
+    capacity_alert_repo::resolve_alert(&state.db, alert_id, payload.staff_id, payload.note)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Alert is already resolved".to_string()))?;
+    state.outbox.wake();
+
+    let alert = capacity_alert_repo::get_alert(&state.db, alert_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(alert, Some("Alert resolved".to_string()))))
+}
+
+async fn check_staff(state: &AppState, staff_id: Uuid) -> Result<(), AppError> {
+    let staff = staff_repo::get_staff_by_id(&state.db, staff_id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Staff member does not exist".to_string()))?;
+    if !staff.is_active {
+        return Err(AppError::BadRequest("Staff member is inactive".to_string()));
+    }
+    Ok(())
+}
+
+/// Delivery attempts for an alert
+#[utoipa::path(
+    get,
+    path = "/api/v1/capacity-alerts/{id}/deliveries",
+    tag = "Capacity Alerts",
+    params(("id" = Uuid, Path, description = "Alert UUID")),
+    responses(
+        (status = 200, description = "Deliveries", body = ApiResponse<Vec<AlertDelivery>>),
+        (status = 404, description = "Alert not found")
+    )
+)]
+pub async fn get_alert_deliveries_handler(
+    State(state): State<AppState>,
+    Path(alert_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<AlertDelivery>>>, AppError> {
+    capacity_alert_repo::get_alert(&state.db, alert_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let deliveries = capacity_alert_repo::get_deliveries(&state.db, alert_id).await?;
+    Ok(Json(ApiResponse::success(deliveries, None)))
+}
+
+/// Subscribe a channel to capacity alerts for a hospital, a state, or every hospital
+#[utoipa::path(
+    post,
+    path = "/api/v1/alert-subscriptions",
+    tag = "Capacity Alerts",
+    request_body = CreateAlertSubscriptionRequest,
+    responses(
+        (status = 200, description = "Subscription created", body = ApiResponse<AlertSubscription>),
+        (status = 400, description = "Invalid channel, target or scope")
+    )
+)]
+pub async fn create_alert_subscription_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateAlertSubscriptionRequest>,
+) -> Result<Json<ApiResponse<AlertSubscription>>, AppError> {
+    if payload.channel == "WEBHOOK" {
+        return Err(AppError::BadRequest(
+            "Register a webhook endpoint for capacity_alert.raised with POST /api/v1/webhooks".to_string(),
+        ));
+    }
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    if payload.channel == "STAFF" {
+        let staff_id = payload
+            .target
+            .as_deref()
+            .and_then(|t| Uuid::parse_str(t).ok())
+            .ok_or_else(|| AppError::BadRequest("Staff subscriptions need a staff id as the target".to_string()))?;
+        staff_repo::get_staff_by_id(&state.db, staff_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Staff member not found".to_string()))?;
+    }
+    check_scope(&state, payload.hospital_id).await?;
+
+    let subscription = capacity_alert_repo::create_subscription(&state.db, payload).await?;
+    Ok(Json(ApiResponse::success(subscription, Some("Subscription created".to_string()))))
+}
+
+/// List active alert subscriptions
+#[utoipa::path(
+    get,
+    path = "/api/v1/alert-subscriptions",
+    tag = "Capacity Alerts",
+    params(
+        ("hospital_id" = Option<Uuid>, Query, description = "Subscriptions scoped to this hospital"),
+        ("state" = Option<String>, Query, description = "Subscriptions scoped to this state")
+    ),
+    responses(
+        (status = 200, description = "Subscriptions", body = ApiResponse<Vec<AlertSubscription>>)
+    )
+)]
+pub async fn get_alert_subscriptions_handler(
+    State(state): State<AppState>,
+    Query(params): Query<AlertScopeQuery>,
+) -> Result<Json<ApiResponse<Vec<AlertSubscription>>>, AppError> {
+    let subscriptions = capacity_alert_repo::get_subscriptions(&state.db, params.hospital_id, params.state).await?;
+    Ok(Json(ApiResponse::success(subscriptions, None)))
+}
+
+/// Stop delivering alerts to a subscription
+#[utoipa::path(
+    put,
+    path = "/api/v1/alert-subscriptions/{id}/deactivate",
+    tag = "Capacity Alerts",
+    params(("id" = Uuid, Path, description = "Subscription UUID")),
+    responses(
+        (status = 200, description = "Subscription deactivated"),
+        (status = 404, description = "Subscription not found or already inactive")
+    )
+)]
+pub async fn deactivate_alert_subscription_handler(
+    State(state): State<AppState>,
+    Path(subscription_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Uuid>>, AppError> {
+    let id = capacity_alert_repo::deactivate_subscription(&state.db, subscription_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(id, Some("Subscription deactivated".to_string()))))
+}
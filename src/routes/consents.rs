@@ -0,0 +1,159 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        consent::{AccessContext, Consent, GrantConsentRequest, RevokeConsentRequest, AccessLogEntry, AccessLogQuery},
+        api_response::ApiResponse,
+    },
+    db::consent_repo,
+    errors::app::AppError,
+    middleware::access::{authorize_patient, require_care_relationship},
+};
+
+#[derive(Deserialize)]
+pub struct ConsentQuery {
+    pub include_revoked: Option<bool>,
+}
+
+/// Record a patient's consent to share their record with a hospital and/or for a purpose.
+/// Only a hospital that has registered or seen the patient can record it, and the signed-in
+/// staff member is recorded against it.
+#[utoipa::path(
+    post,
+    path = "/api/v1/patients/{id}/consents",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID")
+    ),
+    request_body = GrantConsentRequest,
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Consent granted", body = ApiResponse<Consent>),
+        (status = 400, description = "Neither hospital nor purpose given"),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "Calling hospital has no care relationship with the patient"),
+        (status = 404, description = "Patient not found")
+    )
+)]
+pub async fn grant_consent_handler(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(patient_id): Path<Uuid>,
+    Json(payload): Json<GrantConsentRequest>,
+) -> Result<Json<ApiResponse<Consent>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    if payload.hospital_id.is_none() && payload.purpose.is_none() {
+        return Err(AppError::BadRequest("Consent needs a hospital_id, a purpose, or both".to_string()));
+    }
+
+    require_care_relationship(&state.db, &ctx, patient_id).await?;
+    let consent = consent_repo::grant_consent(&state.db, patient_id, ctx.staff_id, payload).await?;
+    Ok(Json(ApiResponse::success(consent, Some("Consent granted".to_string()))))
+}
+
+/// Get a patient's consents (current only by default)
+#[utoipa::path(
+    get,
+    path = "/api/v1/patients/{id}/consents",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID"),
+        ("include_revoked" = Option<bool>, Query, description = "Include revoked consents")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Consents, newest first", body = ApiResponse<Vec<Consent>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override"),
+        (status = 404, description = "Patient not found")
+    )
+)]
+pub async fn get_patient_consents(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(patient_id): Path<Uuid>,
+    Query(params): Query<ConsentQuery>,
+) -> Result<Json<ApiResponse<Vec<Consent>>>, AppError> {
+    authorize_patient(&state.db, &ctx, patient_id, "CONSENTS", None).await?;
+    let consents = consent_repo::get_patient_consents(&state.db, patient_id, params.include_revoked.unwrap_or(false)).await?;
+    Ok(Json(ApiResponse::success(consents, None)))
+}
+
+/// Revoke a consent; access it allowed stops immediately. Only a hospital with a care
+/// relationship to the patient can revoke it.
+#[utoipa::path(
+    put,
+    path = "/api/v1/consents/{id}/revoke",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Consent UUID")
+    ),
+    request_body = RevokeConsentRequest,
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Consent revoked", body = ApiResponse<Consent>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "Calling hospital has no care relationship with the patient"),
+        (status = 404, description = "Consent not found"),
+        (status = 409, description = "Consent already revoked")
+    )
+)]
+pub async fn revoke_consent_handler(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(consent_id): Path<Uuid>,
+    Json(payload): Json<RevokeConsentRequest>,
+) -> Result<Json<ApiResponse<Consent>>, AppError> {
+    let consent = consent_repo::get_consent(&state.db, consent_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    require_care_relationship(&state.db, &ctx, consent.patient_id).await?;
+
+    let consent = consent_repo::revoke_consent(&state.db, consent_id, payload.reason)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Consent is already revoked".to_string()))?;
+    Ok(Json(ApiResponse::success(consent, Some("Consent revoked".to_string()))))
+}
+
+/// Every read of a patient's data: who, from which hospital, why, and on what basis
+#[utoipa::path(
+    get,
+    path = "/api/v1/patients/{id}/access-log",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID"),
+        ("from" = Option<DateTime<Utc>>, Query, description = "Only accesses at or after this time"),
+        ("to" = Option<DateTime<Utc>>, Query, description = "Only accesses before this time"),
+        ("basis" = Option<String>, Query, description = "OWN_HOSPITAL, CONSENT, BREAK_GLASS or DENIED")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Accesses, newest first", body = ApiResponse<Vec<AccessLogEntry>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override"),
+        (status = 404, description = "Patient not found")
+    )
+)]
+pub async fn get_patient_access_log(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(patient_id): Path<Uuid>,
+    Query(params): Query<AccessLogQuery>,
+) -> Result<Json<ApiResponse<Vec<AccessLogEntry>>>, AppError> {
+    if let Err(e) = params.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    authorize_patient(&state.db, &ctx, patient_id, "ACCESS_LOG", None).await?;
+
+    let entries = consent_repo::get_access_log(&state.db, patient_id, params.from, params.to, params.basis).await?;
+    Ok(Json(ApiResponse::success(entries, None)))
+}
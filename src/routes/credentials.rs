@@ -0,0 +1,98 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        credential::{Credential, CreateCredentialRequest, ExpiringCredential},
+        api_response::ApiResponse,
+    },
+    db::credential_repo,
+    errors::app::AppError,
+};
+
+#[derive(Deserialize)]
+pub struct ExpiringQuery {
+    pub days: Option<i32>,
+}
+
+/// Attach a licence to a staff member
+#[utoipa::path(
+    post,
+    path = "/api/v1/staff/{id}/credentials",
+    tag = "Credentials",
+    params(
+        ("id" = Uuid, Path, description = "Staff UUID")
+    ),
+    request_body = CreateCredentialRequest,
+    responses(
+        (status = 200, description = "Credential added", body = ApiResponse<Credential>),
+        (status = 409, description = "Licence number already registered")
+    )
+)]
+pub async fn create_credential_handler(
+    State(state): State<AppState>,
+    Path(staff_id): Path<Uuid>,
+    Json(payload): Json<CreateCredentialRequest>,
+) -> Result<Json<ApiResponse<Credential>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    if payload.expires_on <= payload.issued_on {
+        return Err(AppError::BadRequest("Expiry date must be after issue date".to_string()));
+    }
+
+    let credential = credential_repo::create_credential(&state.db, staff_id, payload).await?;
+    Ok(Json(ApiResponse::success(credential, Some("Credential added".to_string()))))
+}
+
+/// Get all credentials held by a staff member
+#[utoipa::path(
+    get,
+    path = "/api/v1/staff/{id}/credentials",
+    tag = "Credentials",
+    params(
+        ("id" = Uuid, Path, description = "Staff UUID")
+    ),
+    responses(
+        (status = 200, description = "List of credentials", body = ApiResponse<Vec<Credential>>)
+    )
+)]
+pub async fn get_staff_credentials(
+    State(state): State<AppState>,
+    Path(staff_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<Credential>>>, AppError> {
+    let credentials = credential_repo::get_staff_credentials(&state.db, staff_id).await?;
+    Ok(Json(ApiResponse::success(credentials, None)))
+}
+
+/// Credentials at a hospital expiring within N days (default 30)
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/credentials/expiring",
+    tag = "Credentials",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID"),
+        ("days" = Option<i32>, Query, description = "Look-ahead window in days (default 30)")
+    ),
+    responses(
+        (status = 200, description = "Expiring credentials", body = ApiResponse<Vec<ExpiringCredential>>)
+    )
+)]
+pub async fn get_expiring_credentials(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Query(params): Query<ExpiringQuery>,
+) -> Result<Json<ApiResponse<Vec<ExpiringCredential>>>, AppError> {
+    let days = params.days.unwrap_or(30);
+    if days < 0 {
+        return Err(AppError::BadRequest("days cannot be negative".to_string()));
+    }
+
+    let credentials = credential_repo::get_expiring_credentials(&state.db, hospital_id, days).await?;
+    Ok(Json(ApiResponse::success(credentials, None)))
+}
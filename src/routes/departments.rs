@@ -14,12 +14,24 @@ use crate::{
     errors::app::AppError,
 };
 
+/// Rejects a department override (for a shift, slot, ...) that is not in the staff member's own
+/// hospital.
+pub async fn check_department(state: &AppState, staff_id: Uuid, department_id: Option<Uuid>) -> Result<(), AppError> {
+    if let Some(department_id) = department_id {
+        if !department_repo::is_in_staff_hospital(&state.db, department_id, staff_id).await? {
+            return Err(AppError::BadRequest("Department is not in the staff member's hospital".to_string()));
+        }
+    }
+    Ok(())
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/departments",
     request_body = CreateDepartmentRequest,
     responses(
-        (status = 200, description = "Department created", body = ApiResponse<Department>)
+        (status = 200, description = "Department created", body = ApiResponse<Department>),
+        (status = 400, description = "Hospital does not exist")
     )
 )]
 pub async fn create_department_handler(
@@ -0,0 +1,126 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        diagnosis::{VisitDiagnosis, CreateDiagnosisRequest},
+        icd10::{Icd10Code, Icd10SearchQuery},
+        consent::AccessContext,
+        api_response::ApiResponse,
+    },
+    db::{diagnosis_repo, icd10_repo},
+    errors::app::AppError,
+    middleware::access::authorize_visit,
+};
+
+/// Attach a coded diagnosis to a visit
+#[utoipa::path(
+    post,
+    path = "/api/v1/visits/{id}/diagnoses",
+    tag = "Visits",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    request_body = CreateDiagnosisRequest,
+    responses(
+        (status = 200, description = "Diagnosis added", body = ApiResponse<VisitDiagnosis>),
+        (status = 400, description = "Unknown ICD-10 code"),
+        (status = 409, description = "Code already recorded, or visit already has a primary diagnosis")
+    )
+)]
+pub async fn add_diagnosis_handler(
+    State(state): State<AppState>,
+    Path(visit_id): Path<Uuid>,
+    Json(payload): Json<CreateDiagnosisRequest>,
+) -> Result<Json<ApiResponse<VisitDiagnosis>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let code = icd10_repo::get_code(&state.db, payload.code.trim())
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown ICD-10 code: {}", payload.code)))?;
+
+    let diagnosis = diagnosis_repo::add_diagnosis(&state.db, visit_id, &code, payload)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Visit already has a primary diagnosis".to_string()))?;
+    Ok(Json(ApiResponse::success(diagnosis, Some("Diagnosis added".to_string()))))
+}
+
+/// Get the diagnoses recorded for a visit
+#[utoipa::path(
+    get,
+    path = "/api/v1/visits/{id}/diagnoses",
+    tag = "Visits",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "List of diagnoses", body = ApiResponse<Vec<VisitDiagnosis>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override")
+    )
+)]
+pub async fn get_visit_diagnoses(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(visit_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<VisitDiagnosis>>>, AppError> {
+    authorize_visit(&state.db, &ctx, visit_id, "VISIT_DIAGNOSES").await?;
+    let diagnoses = diagnosis_repo::get_visit_diagnoses(&state.db, visit_id).await?;
+    Ok(Json(ApiResponse::success(diagnoses, None)))
+}
+
+/// Search ICD-10 codes by code prefix or description (autocomplete)
+#[utoipa::path(
+    get,
+    path = "/api/v1/icd10/codes",
+    tag = "Visits",
+    params(
+        ("q" = String, Query, description = "Code prefix or part of the description, e.g. A00 or malaria"),
+        ("limit" = Option<i64>, Query, description = "Maximum results (default 20, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Matching codes, code matches first", body = ApiResponse<Vec<Icd10Code>>)
+    )
+)]
+pub async fn search_icd10_codes(
+    State(state): State<AppState>,
+    Query(params): Query<Icd10SearchQuery>,
+) -> Result<Json<ApiResponse<Vec<Icd10Code>>>, AppError> {
+    if let Err(e) = params.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let codes = icd10_repo::search_codes(&state.db, params.q.trim(), limit).await?;
+    Ok(Json(ApiResponse::success(codes, None)))
+}
+
+/// Look up a single ICD-10 code
+#[utoipa::path(
+    get,
+    path = "/api/v1/icd10/codes/{code}",
+    tag = "Visits",
+    params(
+        ("code" = String, Path, description = "ICD-10 code, e.g. B50.9")
+    ),
+    responses(
+        (status = 200, description = "Code found", body = ApiResponse<Icd10Code>),
+        (status = 404, description = "Code not in the table")
+    )
+)]
+pub async fn get_icd10_code(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<Json<ApiResponse<Icd10Code>>, AppError> {
+    let code = icd10_repo::get_code(&state.db, &code)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(code, None)))
+}
@@ -8,6 +8,7 @@ use crate::{
     routes::state::AppState,
     models::{
         equipment::{Equipment, CreateEquipmentRequest},
+        maintenance::{ConditionLogEntry, UpdateConditionRequest},
         api_response::ApiResponse,
     },
     db::equipment_repo,
@@ -32,6 +33,7 @@ pub async fn create_equipment_handler(
     }
 
     let item = equipment_repo::create_equipment(&state.db, payload).await?;
+    state.outbox.wake();
     Ok(Json(ApiResponse::success(item, Some("Equipment registered".to_string()))))
 }
 
@@ -52,4 +54,52 @@ pub async fn get_hospital_equipment(
 ) -> Result<Json<ApiResponse<Vec<Equipment>>>, AppError> {
     let items = equipment_repo::get_hospital_equipment(&state.db, hospital_id).await?;
     Ok(Json(ApiResponse::success(items, None)))
-}
\ No newline at end of file
+}
+
+/// Record a change in an equipment item's condition
+#[utoipa::path(
+    put,
+    path = "/api/v1/equipment/{id}/condition",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Equipment UUID")
+    ),
+    request_body = UpdateConditionRequest,
+    responses(
+        (status = 200, description = "Condition updated", body = ApiResponse<Equipment>),
+        (status = 404, description = "Equipment not found")
+    )
+)]
+pub async fn update_equipment_condition_handler(
+    State(state): State<AppState>,
+    Path(equipment_id): Path<Uuid>,
+    Json(payload): Json<UpdateConditionRequest>,
+) -> Result<Json<ApiResponse<Equipment>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let item = equipment_repo::update_equipment_condition(&state.db, equipment_id, payload).await?;
+    state.outbox.wake();
+    Ok(Json(ApiResponse::success(item, Some("Condition updated".to_string()))))
+}
+
+/// Get the condition history of an equipment item
+#[utoipa::path(
+    get,
+    path = "/api/v1/equipment/{id}/condition-history",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Equipment UUID")
+    ),
+    responses(
+        (status = 200, description = "Condition changes, oldest first", body = ApiResponse<Vec<ConditionLogEntry>>)
+    )
+)]
+pub async fn get_equipment_condition_history(
+    State(state): State<AppState>,
+    Path(equipment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<ConditionLogEntry>>>, AppError> {
+    let history = equipment_repo::get_condition_history(&state.db, equipment_id).await?;
+    Ok(Json(ApiResponse::success(history, None)))
+}
@@ -0,0 +1,147 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        equipment_transfer::{
+            EquipmentTransfer, CreateTransferRequest, TransferActionRequest, CustodyEvent, transfer_transition,
+        },
+        api_response::ApiResponse,
+    },
+    db::equipment_transfer_repo,
+    errors::app::AppError,
+};
+
+/// Request a loan or transfer of equipment to another hospital
+#[utoipa::path(
+    post,
+    path = "/api/v1/equipment-transfers",
+    tag = "Equipment",
+    request_body = CreateTransferRequest,
+    responses(
+        (status = 200, description = "Transfer requested", body = ApiResponse<EquipmentTransfer>),
+        (status = 409, description = "Equipment already has an open transfer")
+    )
+)]
+pub async fn create_transfer_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateTransferRequest>,
+) -> Result<Json<ApiResponse<EquipmentTransfer>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    if equipment_transfer_repo::has_active_transfer(&state.db, payload.equipment_id).await? {
+        return Err(AppError::Conflict("Equipment already has an open transfer".to_string()));
+    }
+
+    let transfer = equipment_transfer_repo::create_transfer(&state.db, payload).await?;
+    Ok(Json(ApiResponse::success(transfer, Some("Transfer requested".to_string()))))
+}
+
+/// Get a transfer by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/equipment-transfers/{id}",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Transfer UUID")
+    ),
+    responses(
+        (status = 200, description = "Transfer details", body = ApiResponse<EquipmentTransfer>),
+        (status = 404, description = "Transfer not found")
+    )
+)]
+pub async fn get_transfer(
+    State(state): State<AppState>,
+    Path(transfer_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<EquipmentTransfer>>, AppError> {
+    let transfer = equipment_transfer_repo::get_transfer(&state.db, transfer_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(transfer, None)))
+}
+
+/// Advance a transfer: approve, reject, dispatch, receive or return
+#[utoipa::path(
+    post,
+    path = "/api/v1/equipment-transfers/{id}/{action}",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Transfer UUID"),
+        ("action" = String, Path, description = "One of approve, reject, dispatch, receive, return")
+    ),
+    request_body = TransferActionRequest,
+    responses(
+        (status = 200, description = "Transfer updated", body = ApiResponse<EquipmentTransfer>),
+        (status = 409, description = "Step not allowed from the current status")
+    )
+)]
+pub async fn advance_transfer_handler(
+    State(state): State<AppState>,
+    Path((transfer_id, action)): Path<(Uuid, String)>,
+    Json(payload): Json<TransferActionRequest>,
+) -> Result<Json<ApiResponse<EquipmentTransfer>>, AppError> {
+    let (allowed_from, next_status) = transfer_transition(&action)
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown transfer action '{}'", action)))?;
+
+    let current = equipment_transfer_repo::get_transfer(&state.db, transfer_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let transfer = equipment_transfer_repo::advance_transfer(
+        &state.db,
+        transfer_id,
+        allowed_from,
+        next_status,
+        payload.note.as_deref(),
+    )
+    .await?
+    .ok_or_else(|| AppError::Conflict(format!("Cannot {} a {} {}", action, current.status, current.transfer_type)))?;
+
+    state.outbox.wake();
+    Ok(Json(ApiResponse::success(transfer, Some(format!("Transfer {}", next_status)))))
+}
+
+/// Incoming and outgoing transfers for a hospital
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/equipment-transfers",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID")
+    ),
+    responses(
+        (status = 200, description = "List of transfers", body = ApiResponse<Vec<EquipmentTransfer>>)
+    )
+)]
+pub async fn get_hospital_transfers(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<EquipmentTransfer>>>, AppError> {
+    let transfers = equipment_transfer_repo::get_hospital_transfers(&state.db, hospital_id).await?;
+    Ok(Json(ApiResponse::success(transfers, None)))
+}
+
+/// Chain of custody for a piece of equipment
+#[utoipa::path(
+    get,
+    path = "/api/v1/equipment/{id}/custody-history",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Equipment UUID")
+    ),
+    responses(
+        (status = 200, description = "Custody events, oldest first", body = ApiResponse<Vec<CustodyEvent>>)
+    )
+)]
+pub async fn get_custody_history(
+    State(state): State<AppState>,
+    Path(equipment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<CustodyEvent>>>, AppError> {
+    let events = equipment_transfer_repo::get_custody_history(&state.db, equipment_id).await?;
+    Ok(Json(ApiResponse::success(events, None)))
+}
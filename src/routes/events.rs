@@ -0,0 +1,134 @@
+use std::{collections::VecDeque, convert::Infallible};
+use axum::{
+    extract::{ws::{Message, WebSocket, WebSocketUpgrade}, Query, State},
+    http::HeaderMap,
+    response::{sse::{Event, KeepAlive, Sse}, IntoResponse, Response},
+};
+use serde::Deserialize;
+use tokio::sync::broadcast::{self, error::RecvError};
+use crate::{
+    routes::state::AppState,
+    ws::hub::{EventHub, Resume, StreamEvent},
+};
+
+/// Live feed of hospital, capacity, visit and capacity alert events as JSON text frames
+pub async fn events_websocket(State(state): State<AppState>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| stream_events(socket, state))
+}
+
+async fn stream_events(mut socket: WebSocket, state: AppState) {
+    let mut events = state.events.subscribe();
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(streamed) => {
+                    let Ok(text) = serde_json::to_string(&*streamed.event) else { continue };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("WebSocket client fell behind; skipped {} event(s)", skipped);
+                }
+                Err(RecvError::Closed) => return,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EventStreamQuery {
+    // For clients that cannot set the Last-Event-ID header on their first connection
+    pub last_event_id: Option<String>,
+}
+
+/// The WebSocket feed as Server-Sent Events, for clients behind proxies that break WebSockets.
+/// Events carry the same JSON and an id; sending the last id back as `Last-Event-ID` (browsers
+/// do this when they reconnect) replays what was missed from a bounded buffer. When that is not
+/// possible (a restart, or the client was away too long) a `resync` event comes first, and the
+/// client should refetch current state.
+pub async fn events_sse(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<EventStreamQuery>,
+) -> impl IntoResponse {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+        .or(params.last_event_id);
+
+    let (receiver, resume) = state.events.subscribe_after(last_event_id.as_deref());
+    let feed = match resume {
+        Resume::Replay(missed) => Feed::new(state.events, receiver, missed.into(), None),
+        Resume::Gap => Feed::new(state.events, receiver, VecDeque::new(), Some("unknown_last_event_id")),
+    };
+
+    (
+        // Stops nginx-style proxies from buffering the stream
+        [("x-accel-buffering", "no")],
+        Sse::new(futures_util::stream::unfold(feed, Feed::next)).keep_alive(KeepAlive::default()),
+    )
+}
+
+/// One SSE client's position in the stream
+struct Feed {
+    hub: EventHub,
+    receiver: broadcast::Receiver<StreamEvent>,
+    pending: VecDeque<StreamEvent>,
+    last_position: Option<u64>,
+    resync: Option<&'static str>,
+}
+
+impl Feed {
+    fn new(
+        hub: EventHub,
+        receiver: broadcast::Receiver<StreamEvent>,
+        pending: VecDeque<StreamEvent>,
+        resync: Option<&'static str>,
+    ) -> Self {
+        Self { hub, receiver, pending, last_position: None, resync }
+    }
+
+    async fn next(mut self) -> Option<(Result<Event, Infallible>, Self)> {
+        loop {
+            if let Some(reason) = self.resync.take() {
+                let event = Event::default().event("resync").data(serde_json::json!({ "reason": reason }).to_string());
+                return Some((Ok(event), self));
+            }
+
+            if let Some(streamed) = self.pending.pop_front() {
+                // Catching up from the buffer can overlap with what the channel still holds
+                if self.last_position.is_some_and(|last| streamed.position <= last) {
+                    continue;
+                }
+                self.last_position = Some(streamed.position);
+                let Ok(event) = Event::default()
+                    .id(self.hub.event_id(streamed.position))
+                    .json_data(&*streamed.event)
+                else {
+                    continue;
+                };
+                return Some((Ok(event), self));
+            }
+
+            match self.receiver.recv().await {
+                Ok(streamed) => self.pending.push_back(streamed),
+                Err(RecvError::Lagged(skipped)) => {
+                    match self.last_position.and_then(|last| self.hub.replay_after(last)) {
+                        Some(missed) => self.pending.extend(missed),
+                        None => {
+                            tracing::warn!("SSE client fell behind the replay buffer; skipped {} event(s)", skipped);
+                            self.resync = Some("lagged");
+                        }
+                    }
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use crate::{
+    routes::state::AppState,
+    models::{
+        forecast::{ForecastComparison, ForecastQuery, ForecastRunSummary, RunForecastQuery},
+        api_response::ApiResponse,
+    },
+    db::{forecast_repo, hospital_repo},
+    errors::app::AppError,
+    jobs::forecast,
+};
+
+// How far back `as_of` may go when backtesting
+const MAX_BACKTEST_DAYS: i64 = 366;
+
+/// Bed occupancy forecast for a hospital, next to the actual occupancy for hours that have passed
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/forecast",
+    tag = "Forecasting",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID"),
+        ("from" = Option<String>, Query, description = "Start (RFC 3339), defaults to 24 hours ago"),
+        ("to" = Option<String>, Query, description = "End (RFC 3339), defaults to 7 days ahead"),
+        ("horizon" = Option<String>, Query, description = "24h or 7d: only predictions made at most this far ahead")
+    ),
+    responses(
+        (status = 200, description = "Hourly forecast vs actual", body = ApiResponse<ForecastComparison>),
+        (status = 400, description = "Invalid range or horizon"),
+        (status = 404, description = "Hospital not found")
+    )
+)]
+pub async fn get_hospital_forecast(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Query(params): Query<ForecastQuery>,
+) -> Result<Json<ApiResponse<ForecastComparison>>, AppError> {
+    let max_horizon = match params.horizon.as_deref() {
+        None => None,
+        Some("24h") => Some(24),
+        Some("7d") => Some(24 * 7),
+        Some(_) => return Err(AppError::BadRequest("Horizon must be 24h or 7d".to_string())),
+    };
+    let now = Utc::now();
+    let from = params.from.unwrap_or(now - Duration::hours(24));
+    let to = params.to.unwrap_or(now + Duration::days(7));
+    if from >= to {
+        return Err(AppError::BadRequest("'from' must be before 'to'".to_string()));
+    }
+
+    hospital_repo::fetch_hospital_by_id(&state.db, hospital_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let points = forecast_repo::get_forecast_vs_actual(&state.db, hospital_id, from, to, max_horizon).await?;
+
+    let compared: Vec<_> = points
+        .iter()
+        .filter_map(|p| p.actual_occupied.map(|actual| (p, actual as f64)))
+        .collect();
+    let compared_hours = compared.len() as i64;
+    let (mean_absolute_error, interval_coverage) = if compared.is_empty() {
+        (None, None)
+    } else {
+        let n = compared.len() as f64;
+        let mae = compared.iter().map(|(p, a)| (p.predicted_occupied - a).abs()).sum::<f64>() / n;
+        let inside = compared.iter().filter(|(p, a)| *a >= p.lower_bound && *a <= p.upper_bound).count() as f64;
+        (Some(mae), Some(inside / n))
+    };
+
+    let comparison = ForecastComparison {
+        hospital_id,
+        model: forecast::MODEL.to_string(),
+        points,
+        compared_hours,
+        mean_absolute_error,
+        interval_coverage,
+    };
+    Ok(Json(ApiResponse::success(comparison, None)))
+}
+
+/// Run the forecaster now instead of waiting for the background job. With `as_of`, forecasts
+/// from history before that time, so the result can be checked against what happened.
+#[utoipa::path(
+    post,
+    path = "/api/v1/forecasts/run",
+    tag = "Forecasting",
+    params(
+        ("as_of" = Option<String>, Query, description = "Data cutoff (RFC 3339), defaults to now"),
+        ("hospital_id" = Option<Uuid>, Query, description = "Only forecast this hospital")
+    ),
+    responses(
+        (status = 200, description = "Forecasts stored", body = ApiResponse<ForecastRunSummary>),
+        (status = 400, description = "as_of is in the future or more than a year ago")
+    )
+)]
+pub async fn run_forecast_handler(
+    State(state): State<AppState>,
+    Query(params): Query<RunForecastQuery>,
+) -> Result<Json<ApiResponse<ForecastRunSummary>>, AppError> {
+    if let Some(as_of) = params.as_of {
+        let now = Utc::now();
+        if as_of > now {
+            return Err(AppError::BadRequest("as_of cannot be in the future".to_string()));
+        }
+        if as_of < now - Duration::days(MAX_BACKTEST_DAYS) {
+            return Err(AppError::BadRequest(format!("as_of cannot be more than {} days ago", MAX_BACKTEST_DAYS)));
+        }
+    }
+
+    let summary = forecast::run_once(&state.db, &state.forecast, params.as_of, params.hospital_id).await?;
+    let message = format!("Forecast {} hospital(s)", summary.hospitals);
+    Ok(Json(ApiResponse::success(summary, Some(message))))
+}
@@ -33,6 +33,7 @@ pub async fn create_hospital_handler(
             )
         })?;
 
+    state.outbox.wake();
     Ok(Json(ApiResponse::success(hospital, Some("Hospital created successfully".to_string()))))
 }
 
@@ -131,6 +132,7 @@ pub async fn update_hospital_handler(
             }
         })?;
 
+    state.outbox.wake();
     Ok(Json(ApiResponse::success(hospital, Some("Hospital updated successfully".to_string()))))
 }
 
@@ -0,0 +1,175 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        inventory::{
+            InventoryItem, CreateInventoryItemRequest, InventoryTransaction, CreateInventoryTransactionRequest,
+            StockMovementResponse, CapabilitySummary,
+        },
+        api_response::ApiResponse,
+    },
+    db::inventory_repo,
+    errors::app::AppError,
+};
+
+#[derive(Deserialize)]
+pub struct InventoryQuery {
+    pub category: Option<String>,
+}
+
+/// Add a consumable to a hospital's inventory
+#[utoipa::path(
+    post,
+    path = "/api/v1/hospitals/{id}/inventory",
+    tag = "Inventory",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID")
+    ),
+    request_body = CreateInventoryItemRequest,
+    responses(
+        (status = 200, description = "Inventory item created", body = ApiResponse<InventoryItem>)
+    )
+)]
+pub async fn create_item_handler(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Json(payload): Json<CreateInventoryItemRequest>,
+) -> Result<Json<ApiResponse<InventoryItem>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let item = inventory_repo::create_item(&state.db, hospital_id, payload).await?;
+    state.outbox.wake();
+    Ok(Json(ApiResponse::success(item, Some("Inventory item created".to_string()))))
+}
+
+/// Current stock levels for a hospital
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/inventory",
+    tag = "Inventory",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID"),
+        ("category" = Option<String>, Query, description = "Filter by category (OXYGEN, MEDICINE, OTHER)")
+    ),
+    responses(
+        (status = 200, description = "List of inventory items", body = ApiResponse<Vec<InventoryItem>>)
+    )
+)]
+pub async fn get_hospital_inventory(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Query(params): Query<InventoryQuery>,
+) -> Result<Json<ApiResponse<Vec<InventoryItem>>>, AppError> {
+    let items = inventory_repo::get_hospital_items(&state.db, hospital_id, params.category).await?;
+    Ok(Json(ApiResponse::success(items, None)))
+}
+
+/// Items at or below their reorder level
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/inventory/low-stock",
+    tag = "Inventory",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID")
+    ),
+    responses(
+        (status = 200, description = "Low and out-of-stock items", body = ApiResponse<Vec<InventoryItem>>)
+    )
+)]
+pub async fn get_low_stock(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<InventoryItem>>>, AppError> {
+    let items = inventory_repo::get_low_stock_items(&state.db, hospital_id).await?;
+    Ok(Json(ApiResponse::success(items, None)))
+}
+
+/// Record a stock-in, stock-out or stock-take adjustment
+#[utoipa::path(
+    post,
+    path = "/api/v1/inventory/{id}/transactions",
+    tag = "Inventory",
+    params(
+        ("id" = Uuid, Path, description = "Inventory item UUID")
+    ),
+    request_body = CreateInventoryTransactionRequest,
+    responses(
+        (status = 200, description = "Stock updated", body = ApiResponse<StockMovementResponse>),
+        (status = 409, description = "Insufficient stock, or stock level would exceed the maximum")
+    )
+)]
+pub async fn create_transaction_handler(
+    State(state): State<AppState>,
+    Path(item_id): Path<Uuid>,
+    Json(payload): Json<CreateInventoryTransactionRequest>,
+) -> Result<Json<ApiResponse<StockMovementResponse>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let conflict = if payload.transaction_type == "STOCK_IN" {
+        "Stock level would exceed the maximum"
+    } else {
+        "Insufficient stock"
+    };
+    let (item, transaction) = inventory_repo::record_transaction(&state.db, item_id, payload)
+        .await?
+        .ok_or_else(|| AppError::Conflict(conflict.to_string()))?;
+    state.outbox.wake();
+
+    Ok(Json(ApiResponse::success(
+        StockMovementResponse { item, transaction },
+        Some("Stock updated".to_string()),
+    )))
+}
+
+/// Stock movements for an item, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/{id}/transactions",
+    tag = "Inventory",
+    params(
+        ("id" = Uuid, Path, description = "Inventory item UUID")
+    ),
+    responses(
+        (status = 200, description = "List of stock movements", body = ApiResponse<Vec<InventoryTransaction>>)
+    )
+)]
+pub async fn get_item_transactions(
+    State(state): State<AppState>,
+    Path(item_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<InventoryTransaction>>>, AppError> {
+    let transactions = inventory_repo::get_item_transactions(&state.db, item_id).await?;
+    Ok(Json(ApiResponse::success(transactions, None)))
+}
+
+/// Capability flags combined with derived supply status
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/capabilities",
+    tag = "Inventory",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID")
+    ),
+    responses(
+        (status = 200, description = "Capability summary", body = ApiResponse<CapabilitySummary>),
+        (status = 404, description = "Hospital not found")
+    )
+)]
+pub async fn get_capability_summary(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<CapabilitySummary>>, AppError> {
+    let summary = inventory_repo::get_capability_summary(&state.db, hospital_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(summary, None)))
+}
@@ -0,0 +1,326 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        lab::{
+            LabTestType, CreateLabTestTypeRequest, LabOrder, LabOrderResponse, CreateLabOrderRequest, CollectSpecimenRequest,
+            EnterLabResultRequest, VerifyLabResultRequest, abnormal_flag,
+        },
+        consent::AccessContext,
+        api_response::ApiResponse,
+    },
+    db::{allergy_repo, lab_repo, staff_repo, visit_repo},
+    errors::app::AppError,
+    middleware::access::{authorize_visit, authorize_patient, require_own_hospital, log_list_access},
+};
+
+#[derive(Deserialize)]
+pub struct LabQueueQuery {
+    pub status: Option<String>,
+}
+
+/// List the laboratory test catalogue
+#[utoipa::path(
+    get,
+    path = "/api/v1/lab-tests",
+    tag = "Laboratory",
+    responses(
+        (status = 200, description = "Orderable tests", body = ApiResponse<Vec<LabTestType>>)
+    )
+)]
+pub async fn get_lab_tests(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<LabTestType>>>, AppError> {
+    let tests = lab_repo::get_test_types(&state.db).await?;
+    Ok(Json(ApiResponse::success(tests, None)))
+}
+
+/// Add a test to the catalogue
+#[utoipa::path(
+    post,
+    path = "/api/v1/lab-tests",
+    tag = "Laboratory",
+    request_body = CreateLabTestTypeRequest,
+    responses(
+        (status = 200, description = "Test added", body = ApiResponse<LabTestType>),
+        (status = 409, description = "Code already exists")
+    )
+)]
+pub async fn create_lab_test_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateLabTestTypeRequest>,
+) -> Result<Json<ApiResponse<LabTestType>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    if let (Some(low), Some(high)) = (payload.reference_low, payload.reference_high) {
+        if low > high {
+            return Err(AppError::BadRequest("Reference low must not exceed reference high".to_string()));
+        }
+    }
+
+    let test = lab_repo::create_test_type(&state.db, payload).await?;
+    Ok(Json(ApiResponse::success(test, Some("Lab test added".to_string()))))
+}
+
+/// Order a lab test during a visit
+#[utoipa::path(
+    post,
+    path = "/api/v1/visits/{id}/lab-orders",
+    tag = "Laboratory",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    request_body = CreateLabOrderRequest,
+    responses(
+        (status = 200, description = "Lab test ordered, with any allergy warnings", body = ApiResponse<LabOrderResponse>),
+        (status = 400, description = "Unknown test, or orderer is not a clinician at the visit's hospital"),
+        (status = 404, description = "Visit not found")
+    )
+)]
+pub async fn create_lab_order_handler(
+    State(state): State<AppState>,
+    Path(visit_id): Path<Uuid>,
+    Json(payload): Json<CreateLabOrderRequest>,
+) -> Result<Json<ApiResponse<LabOrderResponse>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let visit = visit_repo::get_visit_by_id(&state.db, visit_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let test = lab_repo::get_test_type(&state.db, payload.test_type_id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Unknown lab test".to_string()))?;
+    let clinician = staff_repo::get_staff_by_id(&state.db, payload.ordered_by)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Ordering clinician does not exist".to_string()))?;
+
+    if !matches!(clinician.role.as_str(), "DOCTOR" | "NURSE") || clinician.hospital_id != visit.hospital_id {
+        return Err(AppError::BadRequest("Lab tests must be ordered by a clinician at the visit's hospital".to_string()));
+    }
+
+    let warnings: Vec<String> = allergy_repo::find_matching_allergies(&state.db, visit.patient_id, &test.name)
+        .await?
+        .into_iter()
+        .map(|a| match a.reaction {
+            Some(reaction) => format!("Known {} allergy to {} ({})", a.severity, a.substance, reaction),
+            None => format!("Known {} allergy to {}", a.severity, a.substance),
+        })
+        .collect();
+
+    let order = lab_repo::create_order(&state.db, visit_id, &test, payload).await?;
+
+    let message = if warnings.is_empty() {
+        "Lab test ordered".to_string()
+    } else {
+        "Lab test ordered with warnings".to_string()
+    };
+    Ok(Json(ApiResponse::success(LabOrderResponse { order, warnings }, Some(message))))
+}
+
+/// Get the lab orders for a visit
+#[utoipa::path(
+    get,
+    path = "/api/v1/visits/{id}/lab-orders",
+    tag = "Laboratory",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "List of lab orders", body = ApiResponse<Vec<LabOrder>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override")
+    )
+)]
+pub async fn get_visit_lab_orders(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(visit_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<LabOrder>>>, AppError> {
+    authorize_visit(&state.db, &ctx, visit_id, "VISIT_LAB_ORDERS").await?;
+    let orders = lab_repo::get_visit_orders(&state.db, visit_id).await?;
+    Ok(Json(ApiResponse::success(orders, None)))
+}
+
+/// Get a lab order
+#[utoipa::path(
+    get,
+    path = "/api/v1/lab-orders/{id}",
+    tag = "Laboratory",
+    params(
+        ("id" = Uuid, Path, description = "Lab order UUID")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Lab order", body = ApiResponse<LabOrder>),
+        (status = 401, description = "No valid staff session"),
+        (status = 404, description = "Lab order not found"),
+        (status = 403, description = "No care relationship, consent or break-glass override")
+    )
+)]
+pub async fn get_lab_order(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(order_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<LabOrder>>, AppError> {
+    let order = lab_repo::get_order(&state.db, order_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    authorize_patient(&state.db, &ctx, order.patient_id, "LAB_ORDER", Some(order.hospital_id)).await?;
+    Ok(Json(ApiResponse::success(order, None)))
+}
+
+/// Record specimen collection
+#[utoipa::path(
+    post,
+    path = "/api/v1/lab-orders/{id}/collect",
+    tag = "Laboratory",
+    params(
+        ("id" = Uuid, Path, description = "Lab order UUID")
+    ),
+    request_body = CollectSpecimenRequest,
+    responses(
+        (status = 200, description = "Specimen collected", body = ApiResponse<LabOrder>),
+        (status = 409, description = "Order is not awaiting collection")
+    )
+)]
+pub async fn collect_specimen_handler(
+    State(state): State<AppState>,
+    Path(order_id): Path<Uuid>,
+    Json(payload): Json<CollectSpecimenRequest>,
+) -> Result<Json<ApiResponse<LabOrder>>, AppError> {
+    let current = lab_repo::get_order(&state.db, order_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let order = lab_repo::collect_specimen(&state.db, order_id, payload.collected_by)
+        .await?
+        .ok_or_else(|| AppError::Conflict(format!("Cannot collect a specimen for a {} order", current.status)))?;
+
+    Ok(Json(ApiResponse::success(order, Some("Specimen collected".to_string()))))
+}
+
+/// Enter the result for a collected specimen; it is flagged against the reference range
+#[utoipa::path(
+    post,
+    path = "/api/v1/lab-orders/{id}/result",
+    tag = "Laboratory",
+    params(
+        ("id" = Uuid, Path, description = "Lab order UUID")
+    ),
+    request_body = EnterLabResultRequest,
+    responses(
+        (status = 200, description = "Result entered", body = ApiResponse<LabOrder>),
+        (status = 400, description = "Result does not match the test's result type"),
+        (status = 409, description = "Order has no collected specimen awaiting a result")
+    )
+)]
+pub async fn enter_lab_result_handler(
+    State(state): State<AppState>,
+    Path(order_id): Path<Uuid>,
+    Json(payload): Json<EnterLabResultRequest>,
+) -> Result<Json<ApiResponse<LabOrder>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let current = lab_repo::get_order(&state.db, order_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let test = lab_repo::get_test_type(&state.db, current.test_type_id)
+        .await?
+        .ok_or(AppError::Internal)?;
+
+    match test.result_type.as_str() {
+        "NUMERIC" if payload.value.is_none() => {
+            return Err(AppError::BadRequest(format!("{} requires a numeric value", test.name)));
+        }
+        "QUALITATIVE" if payload.text.is_none() => {
+            return Err(AppError::BadRequest(format!("{} requires a text result", test.name)));
+        }
+        _ => {}
+    }
+
+    let flag = abnormal_flag(&test, payload.value, payload.text.as_deref());
+    let order = lab_repo::enter_result(&state.db, order_id, &test, &payload, flag)
+        .await?
+        .ok_or_else(|| AppError::Conflict(format!("Cannot enter a result for a {} order", current.status)))?;
+
+    let message = match flag {
+        Some("NORMAL") | None => "Result entered".to_string(),
+        Some(flag) => format!("Result entered: {}", flag),
+    };
+    Ok(Json(ApiResponse::success(order, Some(message))))
+}
+
+/// Verify a result (must be a different person from whoever entered it)
+#[utoipa::path(
+    post,
+    path = "/api/v1/lab-orders/{id}/verify",
+    tag = "Laboratory",
+    params(
+        ("id" = Uuid, Path, description = "Lab order UUID")
+    ),
+    request_body = VerifyLabResultRequest,
+    responses(
+        (status = 200, description = "Result verified", body = ApiResponse<LabOrder>),
+        (status = 400, description = "Verifier entered the result"),
+        (status = 409, description = "Order is not awaiting verification")
+    )
+)]
+pub async fn verify_lab_result_handler(
+    State(state): State<AppState>,
+    Path(order_id): Path<Uuid>,
+    Json(payload): Json<VerifyLabResultRequest>,
+) -> Result<Json<ApiResponse<LabOrder>>, AppError> {
+    let current = lab_repo::get_order(&state.db, order_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if current.resulted_by == Some(payload.verified_by) {
+        return Err(AppError::BadRequest("Results must be verified by a second person".to_string()));
+    }
+
+    let order = lab_repo::verify_result(&state.db, order_id, payload.verified_by)
+        .await?
+        .ok_or_else(|| AppError::Conflict(format!("Cannot verify a {} order", current.status)))?;
+
+    Ok(Json(ApiResponse::success(order, Some("Result verified".to_string()))))
+}
+
+/// Pending lab work for a hospital (everything not yet verified), most urgent first
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/lab-queue",
+    tag = "Laboratory",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID"),
+        ("status" = Option<String>, Query, description = "Only ORDERED, COLLECTED or RESULTED orders")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Pending lab orders", body = ApiResponse<Vec<LabOrder>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "Calling hospital is not this hospital")
+    )
+)]
+pub async fn get_lab_queue(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(hospital_id): Path<Uuid>,
+    Query(params): Query<LabQueueQuery>,
+) -> Result<Json<ApiResponse<Vec<LabOrder>>>, AppError> {
+    require_own_hospital(&ctx, hospital_id)?;
+    let orders = lab_repo::get_pending_queue(&state.db, hospital_id, params.status).await?;
+    log_list_access(&state.db, &ctx, orders.iter().map(|o| o.patient_id), "LAB_QUEUE").await?;
+    Ok(Json(ApiResponse::success(orders, None)))
+}
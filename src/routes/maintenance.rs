@@ -0,0 +1,169 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        maintenance::{
+            MaintenancePlan, CreateMaintenancePlanRequest, WorkOrder, CreateWorkOrderRequest,
+            CloseWorkOrderRequest, OverdueMaintenance,
+        },
+        api_response::ApiResponse,
+    },
+    db::maintenance_repo,
+    errors::app::AppError,
+};
+
+#[derive(Deserialize)]
+pub struct WorkOrderQuery {
+    pub status: Option<String>,
+}
+
+/// Create a preventive maintenance plan for a piece of equipment
+#[utoipa::path(
+    post,
+    path = "/api/v1/equipment/{id}/maintenance-plans",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Equipment UUID")
+    ),
+    request_body = CreateMaintenancePlanRequest,
+    responses(
+        (status = 200, description = "Maintenance plan created", body = ApiResponse<MaintenancePlan>)
+    )
+)]
+pub async fn create_plan_handler(
+    State(state): State<AppState>,
+    Path(equipment_id): Path<Uuid>,
+    Json(payload): Json<CreateMaintenancePlanRequest>,
+) -> Result<Json<ApiResponse<MaintenancePlan>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let plan = maintenance_repo::create_plan(&state.db, equipment_id, payload).await?;
+    Ok(Json(ApiResponse::success(plan, Some("Maintenance plan created".to_string()))))
+}
+
+/// Get maintenance plans for a piece of equipment
+#[utoipa::path(
+    get,
+    path = "/api/v1/equipment/{id}/maintenance-plans",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Equipment UUID")
+    ),
+    responses(
+        (status = 200, description = "List of maintenance plans", body = ApiResponse<Vec<MaintenancePlan>>)
+    )
+)]
+pub async fn get_equipment_plans(
+    State(state): State<AppState>,
+    Path(equipment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<MaintenancePlan>>>, AppError> {
+    let plans = maintenance_repo::get_equipment_plans(&state.db, equipment_id).await?;
+    Ok(Json(ApiResponse::success(plans, None)))
+}
+
+/// Open a work order against a piece of equipment
+#[utoipa::path(
+    post,
+    path = "/api/v1/equipment/{id}/work-orders",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Equipment UUID")
+    ),
+    request_body = CreateWorkOrderRequest,
+    responses(
+        (status = 200, description = "Work order opened", body = ApiResponse<WorkOrder>),
+        (status = 404, description = "Plan does not belong to this equipment")
+    )
+)]
+pub async fn create_work_order_handler(
+    State(state): State<AppState>,
+    Path(equipment_id): Path<Uuid>,
+    Json(payload): Json<CreateWorkOrderRequest>,
+) -> Result<Json<ApiResponse<WorkOrder>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let order = maintenance_repo::create_work_order(&state.db, equipment_id, payload).await?;
+    Ok(Json(ApiResponse::success(order, Some("Work order opened".to_string()))))
+}
+
+/// Close a work order
+#[utoipa::path(
+    post,
+    path = "/api/v1/work-orders/{id}/close",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Work order UUID")
+    ),
+    request_body = CloseWorkOrderRequest,
+    responses(
+        (status = 200, description = "Work order closed", body = ApiResponse<WorkOrder>),
+        (status = 409, description = "Work order already closed")
+    )
+)]
+pub async fn close_work_order_handler(
+    State(state): State<AppState>,
+    Path(work_order_id): Path<Uuid>,
+    Json(payload): Json<CloseWorkOrderRequest>,
+) -> Result<Json<ApiResponse<WorkOrder>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let order = maintenance_repo::close_work_order(&state.db, work_order_id, &payload.resolution_notes)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Work order is not open".to_string()))?;
+
+    Ok(Json(ApiResponse::success(order, Some("Work order closed".to_string()))))
+}
+
+/// Get work orders for a hospital
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/work-orders",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID"),
+        ("status" = Option<String>, Query, description = "Filter by status (OPEN or CLOSED)")
+    ),
+    responses(
+        (status = 200, description = "List of work orders", body = ApiResponse<Vec<WorkOrder>>)
+    )
+)]
+pub async fn get_hospital_work_orders(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Query(params): Query<WorkOrderQuery>,
+) -> Result<Json<ApiResponse<Vec<WorkOrder>>>, AppError> {
+    let orders = maintenance_repo::get_hospital_work_orders(&state.db, hospital_id, params.status).await?;
+    Ok(Json(ApiResponse::success(orders, None)))
+}
+
+/// Maintenance plans past their due date at a hospital
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/maintenance/overdue",
+    tag = "Equipment",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID")
+    ),
+    responses(
+        (status = 200, description = "Overdue maintenance", body = ApiResponse<Vec<OverdueMaintenance>>)
+    )
+)]
+pub async fn get_overdue_maintenance(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<OverdueMaintenance>>>, AppError> {
+    let overdue = maintenance_repo::get_overdue_maintenance(&state.db, hospital_id).await?;
+    Ok(Json(ApiResponse::success(overdue, None)))
+}
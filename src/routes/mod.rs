@@ -8,6 +8,31 @@ pub mod staff;
 pub mod patients;
 pub mod visits;
 pub mod equipment;
+pub mod shifts;
+pub mod credentials;
+pub mod appointments;
+pub mod maintenance;
+pub mod equipment_transfers;
+pub mod inventory;
+pub mod blood_bank;
+pub mod diagnoses;
+pub mod surveillance;
+pub mod outbreaks;
+pub mod observations;
+pub mod prescriptions;
+pub mod lab;
+pub mod allergies;
+pub mod problems;
+pub mod consents;
+pub mod pii;
+pub mod public;
+pub mod analytics;
+pub mod forecasts;
+pub mod capacity_alerts;
+pub mod webhooks;
+pub mod outbox;
+pub mod events;
+pub mod notifications;
 
 pub use router::create_router;
 pub use state::AppState;
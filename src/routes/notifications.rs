@@ -0,0 +1,257 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        notification::{
+            Notification, NotificationDispatchSummary, NotificationPreference, NotificationQuery,
+            NotificationTemplate, SendNotificationRequest, UpdateNotificationPreferenceRequest,
+            UpsertNotificationTemplateRequest, APPLICATION_ONLY_TEMPLATES,
+        },
+        api_response::ApiResponse,
+    },
+    db::notification_repo,
+    errors::app::AppError,
+};
+
+/// Password resets and the like carry codes and links, so callers may not send or reword them.
+fn reject_application_only(template_key: &str) -> Result<(), AppError> {
+    if APPLICATION_ONLY_TEMPLATES.contains(&template_key) {
+        return Err(AppError::BadRequest(format!("Template {:?} is only sent by the application", template_key)));
+    }
+    Ok(())
+}
+
+/// List notification templates
+#[utoipa::path(
+    get,
+    path = "/api/v1/notification-templates",
+    tag = "Notifications",
+    responses(
+        (status = 200, description = "Templates", body = ApiResponse<Vec<NotificationTemplate>>)
+    )
+)]
+pub async fn get_notification_templates_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<NotificationTemplate>>>, AppError> {
+    let templates = notification_repo::get_templates(&state.db).await?;
+    Ok(Json(ApiResponse::success(templates, None)))
+}
+
+/// Create or replace the template for a key and channel. Subject and body take {{name}}
+/// placeholders, filled from the variables given when sending. Application-only templates such
+/// as password resets cannot be changed here.
+#[utoipa::path(
+    put,
+    path = "/api/v1/notification-templates",
+    tag = "Notifications",
+    request_body = UpsertNotificationTemplateRequest,
+    responses(
+        (status = 200, description = "Template saved", body = ApiResponse<NotificationTemplate>),
+        (status = 400, description = "Invalid channel, an email template without a subject, or an application-only template")
+    )
+)]
+pub async fn upsert_notification_template_handler(
+    State(state): State<AppState>,
+    Json(mut payload): Json<UpsertNotificationTemplateRequest>,
+) -> Result<Json<ApiResponse<NotificationTemplate>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    reject_application_only(&payload.template_key)?;
+    match payload.channel.as_str() {
+        "EMAIL" if payload.subject.is_none() => {
+            return Err(AppError::BadRequest("Email templates need a subject".to_string()));
+        }
+        "SMS" => payload.subject = None,
+        _ => {}
+    }
+
+    let template = notification_repo::upsert_template(&state.db, payload).await?;
+    Ok(Json(ApiResponse::success(template, Some("Template saved".to_string()))))
+}
+
+/// A staff member's notification preferences (the defaults if they have never set any)
+#[utoipa::path(
+    get,
+    path = "/api/v1/staff/{id}/notification-preferences",
+    tag = "Notifications",
+    params(("id" = Uuid, Path, description = "Staff UUID")),
+    responses(
+        (status = 200, description = "Preferences", body = ApiResponse<NotificationPreference>),
+        (status = 404, description = "Staff member not found")
+    )
+)]
+pub async fn get_notification_preferences_handler(
+    State(state): State<AppState>,
+    Path(staff_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<NotificationPreference>>, AppError> {
+    let preference = notification_repo::get_preference(&state.db, staff_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(preference, None)))
+}
+
+/// Switch channels on or off and mute templates for a staff member. Password resets ignore
+/// these, since the recipient asks for them.
+#[utoipa::path(
+    put,
+    path = "/api/v1/staff/{id}/notification-preferences",
+    tag = "Notifications",
+    params(("id" = Uuid, Path, description = "Staff UUID")),
+    request_body = UpdateNotificationPreferenceRequest,
+    responses(
+        (status = 200, description = "Preferences saved", body = ApiResponse<NotificationPreference>),
+        (status = 404, description = "Staff member not found")
+    )
+)]
+pub async fn update_notification_preferences_handler(
+    State(state): State<AppState>,
+    Path(staff_id): Path<Uuid>,
+    Json(mut payload): Json<UpdateNotificationPreferenceRequest>,
+) -> Result<Json<ApiResponse<NotificationPreference>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    payload.muted_templates.sort();
+    payload.muted_templates.dedup();
+
+    let preference = notification_repo::upsert_preference(&state.db, staff_id, payload)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(preference, Some("Preferences saved".to_string()))))
+}
+
+/// Queue a templated notification for a staff member. The first attempt is made straight away in
+/// the background; the returned rows show what was queued, which may be nothing if the staff
+/// member has opted out or cannot be reached. Application-only templates cannot be sent here.
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications",
+    tag = "Notifications",
+    request_body = SendNotificationRequest,
+    responses(
+        (status = 200, description = "Notifications queued", body = ApiResponse<Vec<Notification>>),
+        (status = 400, description = "Unknown or application-only template, missing variable, or invalid channel"),
+        (status = 404, description = "Staff member not found")
+    )
+)]
+pub async fn send_notification_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<SendNotificationRequest>,
+) -> Result<Json<ApiResponse<Vec<Notification>>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    reject_application_only(&payload.template_key)?;
+
+    let queued = state
+        .notifier
+        .send_to_staff(payload.staff_id, &payload.template_key, payload.channel.as_deref(), &payload.variables, None)
+        .await?;
+
+    let message = format!("{} notification(s) queued", queued.len());
+    Ok(Json(ApiResponse::success(queued, Some(message))))
+}
+
+/// Delivery log, newest first. Message bodies are not included.
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications",
+    tag = "Notifications",
+    params(
+        ("status" = Option<String>, Query, description = "PENDING, SENT or FAILED"),
+        ("staff_id" = Option<Uuid>, Query, description = "Notifications for this staff member"),
+        ("template_key" = Option<String>, Query, description = "Notifications from this template"),
+        ("limit" = Option<i64>, Query, description = "Maximum rows (default 100, at most 500)")
+    ),
+    responses(
+        (status = 200, description = "Notifications", body = ApiResponse<Vec<Notification>>),
+        (status = 400, description = "Invalid status")
+    )
+)]
+pub async fn get_notifications_handler(
+    State(state): State<AppState>,
+    Query(params): Query<NotificationQuery>,
+) -> Result<Json<ApiResponse<Vec<Notification>>>, AppError> {
+    if let Some(status) = params.status.as_deref() {
+        if !matches!(status, "PENDING" | "SENT" | "FAILED") {
+            return Err(AppError::BadRequest("Status must be PENDING, SENT or FAILED".to_string()));
+        }
+    }
+
+    let notifications = notification_repo::get_notifications(&state.db, params).await?;
+    Ok(Json(ApiResponse::success(notifications, None)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications/{id}",
+    tag = "Notifications",
+    params(("id" = Uuid, Path, description = "Notification UUID")),
+    responses(
+        (status = 200, description = "Notification", body = ApiResponse<Notification>),
+        (status = 404, description = "Notification not found")
+    )
+)]
+pub async fn get_notification_handler(
+    State(state): State<AppState>,
+    Path(notification_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Notification>>, AppError> {
+    let notification = notification_repo::get_notification(&state.db, notification_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(notification, None)))
+}
+
+/// Put a failed notification back in the queue with a fresh set of attempts, and try it now
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/{id}/retry",
+    tag = "Notifications",
+    params(("id" = Uuid, Path, description = "Notification UUID")),
+    responses(
+        (status = 200, description = "Retry attempted", body = ApiResponse<Notification>),
+        (status = 404, description = "Notification not found"),
+        (status = 409, description = "Notification has not failed")
+    )
+)]
+pub async fn retry_notification_handler(
+    State(state): State<AppState>,
+    Path(notification_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Notification>>, AppError> {
+    notification_repo::get_notification(&state.db, notification_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    notification_repo::retry(&state.db, notification_id)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Only failed notifications can be retried".to_string()))?;
+    state.notifier.dispatch(Some(&[notification_id])).await?;
+
+    let notification = notification_repo::get_notification(&state.db, notification_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let message = format!("Retry {}", notification.status);
+    Ok(Json(ApiResponse::success(notification, Some(message))))
+}
+
+/// Send due notifications and retries now instead of waiting for the background job
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/dispatch",
+    tag = "Notifications",
+    responses(
+        (status = 200, description = "Dispatch completed", body = ApiResponse<NotificationDispatchSummary>)
+    )
+)]
+pub async fn dispatch_notifications_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<NotificationDispatchSummary>>, AppError> {
+    let summary = state.notifier.dispatch(None).await?;
+    Ok(Json(ApiResponse::success(summary, None)))
+}
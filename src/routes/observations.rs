@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        observation::{
+            ObservationSetResponse, RecordObservationsRequest, TrendPoint, DeterioratingPatient,
+            News2Input, normalize_measurement, news2_score,
+        },
+        consent::AccessContext,
+        api_response::ApiResponse,
+    },
+    db::observation_repo,
+    errors::app::AppError,
+    middleware::access::{authorize_visit, authorize_patient, require_own_hospital, log_list_access},
+};
+
+#[derive(Deserialize)]
+pub struct TrendQuery {
+    #[serde(rename = "type")]
+    pub observation_type: String,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct DeterioratingQuery {
+    pub hours: Option<i64>,
+}
+
+/// Record a set of vital signs for a visit; the set is NEWS2-scored when complete
+#[utoipa::path(
+    post,
+    path = "/api/v1/visits/{id}/observations",
+    tag = "Visits",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    request_body = RecordObservationsRequest,
+    responses(
+        (status = 200, description = "Observations recorded", body = ApiResponse<ObservationSetResponse>),
+        (status = 400, description = "Invalid unit, implausible value or repeated measurement type"),
+        (status = 404, description = "Visit not found, or staff member does not work at its hospital")
+    )
+)]
+pub async fn record_observations_handler(
+    State(state): State<AppState>,
+    Path(visit_id): Path<Uuid>,
+    Json(payload): Json<RecordObservationsRequest>,
+) -> Result<Json<ApiResponse<ObservationSetResponse>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let mut seen = HashSet::new();
+    let mut measurements = Vec::new();
+    let mut news2 = News2Input {
+        on_supplemental_oxygen: payload.on_supplemental_oxygen,
+        consciousness: payload.consciousness.clone(),
+        ..Default::default()
+    };
+
+    for m in &payload.measurements {
+        if !seen.insert(m.observation_type.as_str()) {
+            return Err(AppError::BadRequest(format!("{} recorded more than once", m.observation_type)));
+        }
+        let (value, unit) = normalize_measurement(&m.observation_type, m.value, &m.unit)
+            .map_err(AppError::BadRequest)?;
+
+        match m.observation_type.as_str() {
+            "RESPIRATORY_RATE" => news2.respiratory_rate = Some(value),
+            "SPO2" => news2.spo2 = Some(value),
+            "SYSTOLIC_BP" => news2.systolic_bp = Some(value),
+            "HEART_RATE" => news2.heart_rate = Some(value),
+            "TEMPERATURE" => news2.temperature = Some(value),
+            _ => {}
+        }
+        measurements.push((m.observation_type.clone(), value, unit));
+    }
+
+    let score = news2_score(&news2);
+    let recorded = observation_repo::record_observations(&state.db, visit_id, &payload, &measurements, score).await?;
+
+    let message = match score {
+        Some((total, "HIGH")) => format!("Observations recorded. NEWS2 {} (HIGH): emergency assessment required", total),
+        Some((total, "MEDIUM")) => format!("Observations recorded. NEWS2 {} (MEDIUM): urgent clinical review required", total),
+        Some((total, "LOW_MEDIUM")) => format!("Observations recorded. NEWS2 {} (LOW_MEDIUM): urgent ward-based review required", total),
+        Some((total, _)) => format!("Observations recorded. NEWS2 {} (LOW)", total),
+        None => "Observations recorded. NEWS2 not scored: incomplete set".to_string(),
+    };
+    Ok(Json(ApiResponse::success(recorded, Some(message))))
+}
+
+/// Get the observation sets recorded for a visit
+#[utoipa::path(
+    get,
+    path = "/api/v1/visits/{id}/observations",
+    tag = "Visits",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Observation sets, oldest first", body = ApiResponse<Vec<ObservationSetResponse>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override")
+    )
+)]
+pub async fn get_visit_observations(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(visit_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<ObservationSetResponse>>>, AppError> {
+    authorize_visit(&state.db, &ctx, visit_id, "VISIT_OBSERVATIONS").await?;
+    let sets = observation_repo::get_visit_observations(&state.db, visit_id).await?;
+    Ok(Json(ApiResponse::success(sets, None)))
+}
+
+/// Trend of one measurement type (or NEWS2) for a patient (defaults to the last 7 days).
+/// Other hospitals' observations need consent or a break-glass override.
+#[utoipa::path(
+    get,
+    path = "/api/v1/patients/{id}/observations/trend",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID"),
+        ("type" = String, Query, description = "RESPIRATORY_RATE, SPO2, SYSTOLIC_BP, DIASTOLIC_BP, HEART_RATE, TEMPERATURE or NEWS2"),
+        ("from" = Option<DateTime<Utc>>, Query, description = "Range start (defaults to 7 days before end)"),
+        ("to" = Option<DateTime<Utc>>, Query, description = "Range end (defaults to now)")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Measurements, oldest first", body = ApiResponse<Vec<TrendPoint>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override")
+    )
+)]
+pub async fn get_patient_trend(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(patient_id): Path<Uuid>,
+    Query(params): Query<TrendQuery>,
+) -> Result<Json<ApiResponse<Vec<TrendPoint>>>, AppError> {
+    if !matches!(
+        params.observation_type.as_str(),
+        "RESPIRATORY_RATE" | "SPO2" | "SYSTOLIC_BP" | "DIASTOLIC_BP" | "HEART_RATE" | "TEMPERATURE" | "NEWS2"
+    ) {
+        return Err(AppError::BadRequest("Invalid observation type".to_string()));
+    }
+
+    let scope = authorize_patient(&state.db, &ctx, patient_id, "OBSERVATION_TREND", None).await?;
+
+    let to = params.to.unwrap_or_else(Utc::now);
+    let from = params.from.unwrap_or(to - Duration::days(7));
+
+    let points = observation_repo::get_patient_trend(
+        &state.db,
+        patient_id,
+        scope.hospital_filter(),
+        &params.observation_type,
+        from,
+        to,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(points, None)))
+}
+
+/// Patients at a hospital with an elevated or rising NEWS2 score
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/deteriorating",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID"),
+        ("hours" = Option<i64>, Query, description = "Only consider observations from the last N hours (default 24)")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Patients ordered by score, highest first", body = ApiResponse<Vec<DeterioratingPatient>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "Calling hospital is not this hospital")
+    )
+)]
+pub async fn get_deteriorating_patients(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(hospital_id): Path<Uuid>,
+    Query(params): Query<DeterioratingQuery>,
+) -> Result<Json<ApiResponse<Vec<DeterioratingPatient>>>, AppError> {
+    require_own_hospital(&ctx, hospital_id)?;
+    let hours = params.hours.unwrap_or(24).clamp(1, 24 * 7);
+    let since = Utc::now() - Duration::hours(hours);
+
+    let patients = observation_repo::get_deteriorating_patients(&state.db, &state.pii, hospital_id, since).await?;
+    log_list_access(&state.db, &ctx, patients.iter().map(|p| p.patient_id), "DETERIORATING_LIST").await?;
+    Ok(Json(ApiResponse::success(patients, None)))
+}
@@ -0,0 +1,91 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use crate::{
+    routes::state::AppState,
+    models::{
+        outbox::{OutboxDispatchSummary, OutboxStatus, ParkedOutboxEvent},
+        api_response::ApiResponse,
+    },
+    db::outbox_repo,
+    errors::app::AppError,
+};
+
+/// Events waiting to be relayed, how many of those have failed at least once, and how many the
+/// relay has given up on
+#[utoipa::path(
+    get,
+    path = "/api/v1/outbox/status",
+    tag = "Events",
+    responses(
+        (status = 200, description = "Outbox backlog", body = ApiResponse<OutboxStatus>)
+    )
+)]
+pub async fn get_outbox_status(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<OutboxStatus>>, AppError> {
+    let status = outbox_repo::get_status(&state.db).await?;
+    Ok(Json(ApiResponse::success(status, None)))
+}
+
+/// Relay pending events now instead of waiting for the background job
+#[utoipa::path(
+    post,
+    path = "/api/v1/outbox/dispatch",
+    tag = "Events",
+    responses(
+        (status = 200, description = "Relay completed", body = ApiResponse<OutboxDispatchSummary>)
+    )
+)]
+pub async fn dispatch_outbox_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<OutboxDispatchSummary>>, AppError> {
+    let summary = state.outbox.dispatch().await?;
+    Ok(Json(ApiResponse::success(summary, None)))
+}
+
+/// Events the relay gave up on after repeated failures. Later events for the same aggregate are
+/// no longer held back by them.
+#[utoipa::path(
+    get,
+    path = "/api/v1/outbox/parked",
+    tag = "Events",
+    responses(
+        (status = 200, description = "Parked events, most recently parked first", body = ApiResponse<Vec<ParkedOutboxEvent>>)
+    )
+)]
+pub async fn get_parked_outbox_events(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<ParkedOutboxEvent>>>, AppError> {
+    let events = outbox_repo::get_parked(&state.db).await?;
+    Ok(Json(ApiResponse::success(events, None)))
+}
+
+/// Put a parked event back in line with a fresh set of attempts once its cause is fixed, and
+/// relay now
+#[utoipa::path(
+    post,
+    path = "/api/v1/outbox/events/{id}/requeue",
+    tag = "Events",
+    params(("id" = i64, Path, description = "Outbox event id")),
+    responses(
+        (status = 200, description = "Event requeued and relay attempted", body = ApiResponse<OutboxDispatchSummary>),
+        (status = 404, description = "Event not found"),
+        (status = 409, description = "Event is not parked")
+    )
+)]
+pub async fn requeue_outbox_event_handler(
+    State(state): State<AppState>,
+    Path(event_id): Path<i64>,
+) -> Result<Json<ApiResponse<OutboxDispatchSummary>>, AppError> {
+    if !outbox_repo::event_exists(&state.db, event_id).await? {
+        return Err(AppError::NotFound);
+    }
+    if !outbox_repo::requeue(&state.db, event_id).await? {
+        return Err(AppError::Conflict("Only parked events can be requeued".to_string()));
+    }
+
+    let summary = state.outbox.dispatch().await?;
+    Ok(Json(ApiResponse::success(summary, Some("Event requeued".to_string()))))
+}
@@ -0,0 +1,91 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::{
+    routes::state::AppState,
+    models::{
+        outbreak::OutbreakAlert,
+        api_response::ApiResponse,
+    },
+    db::outbreak_repo,
+    errors::app::AppError,
+    jobs::outbreak,
+};
+
+#[derive(Deserialize)]
+pub struct OutbreakAlertQuery {
+    pub status: Option<String>,
+    pub state: Option<String>,
+}
+
+/// List outbreak alerts (defaults to active alerts)
+#[utoipa::path(
+    get,
+    path = "/api/v1/surveillance/alerts",
+    tag = "Surveillance",
+    params(
+        ("status" = Option<String>, Query, description = "ACTIVE (default) or RESOLVED"),
+        ("state" = Option<String>, Query, description = "Filter by state")
+    ),
+    responses(
+        (status = 200, description = "Outbreak alerts", body = ApiResponse<Vec<OutbreakAlert>>)
+    )
+)]
+pub async fn get_outbreak_alerts(
+    State(state): State<AppState>,
+    Query(params): Query<OutbreakAlertQuery>,
+) -> Result<Json<ApiResponse<Vec<OutbreakAlert>>>, AppError> {
+    let status = params.status.unwrap_or_else(|| "ACTIVE".to_string());
+    if !matches!(status.as_str(), "ACTIVE" | "RESOLVED") {
+        return Err(AppError::BadRequest("Status must be ACTIVE or RESOLVED".to_string()));
+    }
+
+    let alerts = outbreak_repo::get_alerts(&state.db, Some(status), params.state).await?;
+    Ok(Json(ApiResponse::success(alerts, None)))
+}
+
+/// Run outbreak detection now instead of waiting for the background job
+#[utoipa::path(
+    post,
+    path = "/api/v1/surveillance/alerts/run",
+    tag = "Surveillance",
+    responses(
+        (status = 200, description = "Detection completed; returns all active alerts", body = ApiResponse<Vec<OutbreakAlert>>)
+    )
+)]
+pub async fn run_outbreak_detection_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<OutbreakAlert>>>, AppError> {
+    let signals = outbreak::run_once(&state.db, &state.outbreak).await?;
+    let alerts = outbreak_repo::get_alerts(&state.db, Some("ACTIVE".to_string()), None).await?;
+
+    let message = format!("{} alert(s) raised this week", signals.len());
+    Ok(Json(ApiResponse::success(alerts, Some(message))))
+}
+
+/// Mark an outbreak alert as resolved
+#[utoipa::path(
+    put,
+    path = "/api/v1/surveillance/alerts/{id}/resolve",
+    tag = "Surveillance",
+    params(
+        ("id" = Uuid, Path, description = "Alert UUID")
+    ),
+    responses(
+        (status = 200, description = "Alert resolved", body = ApiResponse<Uuid>),
+        (status = 409, description = "Alert does not exist or is already resolved")
+    )
+)]
+pub async fn resolve_outbreak_alert_handler(
+    State(state): State<AppState>,
+    Path(alert_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Uuid>>, AppError> {
+    let id = outbreak_repo::resolve_alert(&state.db, alert_id)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Alert does not exist or is already resolved".to_string()))?;
+
+    Ok(Json(ApiResponse::success(id, Some("Alert resolved".to_string()))))
+}
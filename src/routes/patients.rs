@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     Json,
 };
 use serde::Deserialize;
@@ -8,11 +8,14 @@ use validator::Validate;
 use crate::{
     routes::state::AppState,
     models::{
-        patient::{Patient, CreatePatientRequest},
+        patient::{Patient, CreatePatientRequest, PatientRecord, PatientLookupQuery},
+        timeline::{PatientTimeline, TimelineQuery},
+        consent::AccessContext,
         api_response::ApiResponse,
     },
-    db::patient_repo,
+    db::{allergy_repo, patient_repo, problem_repo, timeline_repo},
     errors::app::AppError,
+    middleware::access::{authorize_patient, log_list_access},
 };
 
 #[derive(Deserialize)]
@@ -37,10 +40,11 @@ pub async fn create_patient_handler(
         return Err(AppError::BadRequest(e.to_string()));
     }
 
-    let patient = patient_repo::create_patient(&state.db, payload).await?;
+    let patient = patient_repo::create_patient(&state.db, &state.pii, payload).await?;
     Ok(Json(ApiResponse::success(patient, Some("Patient created successfully".to_string()))))
 }
 
+/// List patients registered at or seen by the signed-in staff member's hospital
 #[utoipa::path(
     get,
     path = "/api/v1/patients",
@@ -48,14 +52,153 @@ pub async fn create_patient_handler(
     params(
         ("hospital_id" = Option<Uuid>, Query, description = "Filter by Hospital ID")
     ),
+    security(("staff_session" = [])),
     responses(
-        (status = 200, description = "List of patients", body = ApiResponse<Vec<Patient>>)
+        (status = 200, description = "List of patients", body = ApiResponse<Vec<Patient>>),
+        (status = 401, description = "No valid staff session")
     )
 )]
 pub async fn get_patients_handler(
     State(state): State<AppState>,
+    ctx: AccessContext,
     Query(params): Query<PatientQuery>,
 ) -> Result<Json<ApiResponse<Vec<Patient>>>, AppError> {
-    let patients = patient_repo::get_patients(&state.db, params.hospital_id).await?;
+    let patients = patient_repo::get_patients(&state.db, &state.pii, ctx.hospital_id, params.hospital_id).await?;
+    log_list_access(&state.db, &ctx, patients.iter().map(|p| p.id), "PATIENT_LIST").await?;
     Ok(Json(ApiResponse::success(patients, None)))
-}
\ No newline at end of file
+}
+
+/// Find patients by phone number and/or national ID. Matches the caller may not see
+/// (no care relationship, consent or break-glass override) are left out.
+#[utoipa::path(
+    get,
+    path = "/api/v1/patients/lookup",
+    tag = "Patients",
+    params(
+        ("phone" = Option<String>, Query, description = "Phone number in any format"),
+        ("national_id" = Option<String>, Query, description = "National ID")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Matching patients", body = ApiResponse<Vec<Patient>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 400, description = "Neither phone nor national_id given")
+    )
+)]
+pub async fn lookup_patients_handler(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Query(params): Query<PatientLookupQuery>,
+) -> Result<Json<ApiResponse<Vec<Patient>>>, AppError> {
+    if params.phone.is_none() && params.national_id.is_none() {
+        return Err(AppError::BadRequest("Provide a phone or national_id to look up".to_string()));
+    }
+
+    let matches = patient_repo::find_patients(
+        &state.db,
+        &state.pii,
+        params.phone.as_deref(),
+        params.national_id.as_deref(),
+    )
+    .await?;
+
+    let mut patients = Vec::with_capacity(matches.len());
+    for patient in matches {
+        match authorize_patient(&state.db, &ctx, patient.id, "PATIENT_LOOKUP", patient.hospital_id).await {
+            Ok(_) => patients.push(patient),
+            Err(AppError::Forbidden) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(Json(ApiResponse::success(patients, None)))
+}
+
+/// Get a patient with their active allergies and full problem list
+#[utoipa::path(
+    get,
+    path = "/api/v1/patients/{id}",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Patient record", body = ApiResponse<PatientRecord>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override"),
+        (status = 404, description = "Patient not found")
+    )
+)]
+pub async fn get_patient_handler(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(patient_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PatientRecord>>, AppError> {
+    authorize_patient(&state.db, &ctx, patient_id, "PATIENT_RECORD", None).await?;
+    let patient = patient_repo::get_patient_by_id(&state.db, &state.pii, patient_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let allergies = allergy_repo::get_patient_allergies(&state.db, patient_id, false).await?;
+    let problems = problem_repo::get_patient_problems(&state.db, patient_id, None).await?;
+
+    Ok(Json(ApiResponse::success(PatientRecord { patient, allergies, problems }, None)))
+}
+
+/// Get a patient's visits, status changes, diagnoses, observations, prescriptions, lab work
+/// and problem-list entries as one chronological feed. Other hospitals' events need consent
+/// or a break-glass override.
+#[utoipa::path(
+    get,
+    path = "/api/v1/patients/{id}/timeline",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID"),
+        ("page" = Option<i64>, Query, description = "Page number, starting at 1"),
+        ("per_page" = Option<i64>, Query, description = "Events per page (default 50, max 200)"),
+        ("types" = Option<String>, Query, description = "Comma-separated event types, e.g. DIAGNOSIS,LAB_RESULT"),
+        ("order" = Option<String>, Query, description = "asc (default, oldest first) or desc")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "One page of timeline events", body = ApiResponse<PatientTimeline>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override"),
+        (status = 404, description = "Patient not found")
+    )
+)]
+pub async fn get_patient_timeline(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(patient_id): Path<Uuid>,
+    Query(params): Query<TimelineQuery>,
+) -> Result<Json<ApiResponse<PatientTimeline>>, AppError> {
+    if let Err(e) = params.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let scope = authorize_patient(&state.db, &ctx, patient_id, "TIMELINE", None).await?;
+
+    let page = params.page.unwrap_or(1);
+    let per_page = params.per_page.unwrap_or(50);
+    let mut events = timeline_repo::get_patient_timeline(
+        &state.db,
+        patient_id,
+        scope.hospital_filter(),
+        params.event_types(),
+        params.order.as_deref() == Some("desc"),
+        per_page,
+        (page - 1) * per_page,
+    )
+    .await?;
+
+    let has_more = events.len() as i64 > per_page;
+    events.truncate(per_page as usize);
+
+    let mut response = ApiResponse::success(
+        PatientTimeline { patient_id, page, per_page, has_more, events },
+        None,
+    );
+    response.meta.count = response.data.as_ref().map(|t| t.events.len() as u32);
+    Ok(Json(response))
+}
@@ -0,0 +1,57 @@
+use axum::{extract::State, Json};
+use crate::{
+    routes::state::AppState,
+    models::{
+        patient::PiiKeyStatus,
+        api_response::ApiResponse,
+    },
+    db::patient_repo,
+    errors::app::AppError,
+    jobs::pii_rotation,
+};
+
+async fn key_status(state: &AppState) -> Result<PiiKeyStatus, AppError> {
+    let active_key_version = state.pii.active_version();
+    let versions = patient_repo::get_key_version_counts(&state.db).await?;
+    let parked = patient_repo::count_reencrypt_failures(&state.db).await?;
+    let behind: i64 = versions
+        .iter()
+        .filter(|v| v.key_version != Some(active_key_version))
+        .map(|v| v.patients)
+        .sum();
+
+    Ok(PiiKeyStatus { active_key_version, versions, pending: behind - parked, parked })
+}
+
+/// Show how many patients are encrypted under each key version
+#[utoipa::path(
+    get,
+    path = "/api/v1/pii/key-status",
+    tag = "PII",
+    responses(
+        (status = 200, description = "Patients per key version", body = ApiResponse<PiiKeyStatus>)
+    )
+)]
+pub async fn get_pii_key_status(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<PiiKeyStatus>>, AppError> {
+    Ok(Json(ApiResponse::success(key_status(&state).await?, None)))
+}
+
+/// Re-encrypt all patients onto the active key now instead of waiting for the background job
+#[utoipa::path(
+    post,
+    path = "/api/v1/pii/reencrypt",
+    tag = "PII",
+    responses(
+        (status = 200, description = "Re-encryption completed; returns the new key status", body = ApiResponse<PiiKeyStatus>)
+    )
+)]
+pub async fn run_pii_reencryption_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<PiiKeyStatus>>, AppError> {
+    let summary = pii_rotation::run_once(&state.db, &state.pii, &state.pii_rotation).await?;
+
+    let message = format!("{} patient(s) re-encrypted, {} parked as unreadable", summary.rewritten, summary.parked);
+    Ok(Json(ApiResponse::success(key_status(&state).await?, Some(message))))
+}
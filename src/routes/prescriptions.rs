@@ -0,0 +1,223 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        prescription::{
+            Prescription, CreatePrescriptionRequest, PrescriptionResponse, PrescriptionActionRequest,
+            MedicationHistory, prescription_transition,
+        },
+        consent::AccessContext,
+        api_response::ApiResponse,
+    },
+    db::{allergy_repo, credential_repo, prescription_repo, staff_repo, visit_repo},
+    errors::app::AppError,
+    middleware::access::{authorize_patient, authorize_visit},
+};
+
+#[derive(Deserialize)]
+pub struct MedicationQuery {
+    pub status: Option<String>,
+}
+
+/// Prescribe a medication during a visit (doctors only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/visits/{id}/prescriptions",
+    tag = "Visits",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    request_body = CreatePrescriptionRequest,
+    responses(
+        (status = 200, description = "Prescription created, with any allergy or duplicate-therapy warnings", body = ApiResponse<PrescriptionResponse>),
+        (status = 400, description = "Prescriber is not a licensed doctor at the visit's hospital"),
+        (status = 404, description = "Visit not found")
+    )
+)]
+pub async fn create_prescription_handler(
+    State(state): State<AppState>,
+    Path(visit_id): Path<Uuid>,
+    Json(payload): Json<CreatePrescriptionRequest>,
+) -> Result<Json<ApiResponse<PrescriptionResponse>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let visit = visit_repo::get_visit_by_id(&state.db, visit_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let prescriber = staff_repo::get_staff_by_id(&state.db, payload.prescriber_id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Prescriber does not exist".to_string()))?;
+
+    if prescriber.role != "DOCTOR" || !prescriber.is_active {
+        return Err(AppError::BadRequest("Only active doctors may prescribe".to_string()));
+    }
+    if prescriber.hospital_id != visit.hospital_id {
+        return Err(AppError::BadRequest("Prescriber does not work at the visit's hospital".to_string()));
+    }
+    let start_date = payload.start_date.unwrap_or_else(|| Utc::now().date_naive());
+    if !credential_repo::has_valid_credential(&state.db, prescriber.id, start_date).await? {
+        return Err(AppError::BadRequest("Prescriber does not hold a valid licence for this date".to_string()));
+    }
+
+    let mut warnings: Vec<String> = allergy_repo::find_matching_allergies(&state.db, visit.patient_id, &payload.drug_name)
+        .await?
+        .into_iter()
+        .map(|a| match a.reaction {
+            Some(reaction) => format!("Known {} allergy to {} ({})", a.severity, a.substance, reaction),
+            None => format!("Known {} allergy to {}", a.severity, a.substance),
+        })
+        .collect();
+
+    warnings.extend(
+        prescription_repo::find_duplicate_medications(&state.db, visit.patient_id, Some(&payload.drug_name), 1)
+            .await?
+            .into_iter()
+            .map(|d| format!("Patient already has {} current prescription(s) for {}", d.prescription_ids.len(), d.drug_name)),
+    );
+
+    let prescription = prescription_repo::create_prescription(&state.db, visit_id, payload).await?;
+
+    let message = if warnings.is_empty() {
+        "Prescription created".to_string()
+    } else {
+        "Prescription created with warnings".to_string()
+    };
+    Ok(Json(ApiResponse::success(PrescriptionResponse { prescription, warnings }, Some(message))))
+}
+
+/// Get the prescriptions written during a visit
+#[utoipa::path(
+    get,
+    path = "/api/v1/visits/{id}/prescriptions",
+    tag = "Visits",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "List of prescriptions", body = ApiResponse<Vec<Prescription>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override")
+    )
+)]
+pub async fn get_visit_prescriptions(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(visit_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<Prescription>>>, AppError> {
+    authorize_visit(&state.db, &ctx, visit_id, "VISIT_PRESCRIPTIONS").await?;
+    let prescriptions = prescription_repo::get_visit_prescriptions(&state.db, visit_id).await?;
+    Ok(Json(ApiResponse::success(prescriptions, None)))
+}
+
+/// Get a prescription
+#[utoipa::path(
+    get,
+    path = "/api/v1/prescriptions/{id}",
+    tag = "Visits",
+    params(
+        ("id" = Uuid, Path, description = "Prescription UUID")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Prescription", body = ApiResponse<Prescription>),
+        (status = 401, description = "No valid staff session"),
+        (status = 404, description = "Prescription not found"),
+        (status = 403, description = "No care relationship, consent or break-glass override")
+    )
+)]
+pub async fn get_prescription(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(prescription_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Prescription>>, AppError> {
+    let prescription = prescription_repo::get_prescription(&state.db, prescription_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    authorize_patient(&state.db, &ctx, prescription.patient_id, "PRESCRIPTION", Some(prescription.hospital_id)).await?;
+    Ok(Json(ApiResponse::success(prescription, None)))
+}
+
+/// Dispense or discontinue a prescription
+#[utoipa::path(
+    post,
+    path = "/api/v1/prescriptions/{id}/{action}",
+    tag = "Visits",
+    params(
+        ("id" = Uuid, Path, description = "Prescription UUID"),
+        ("action" = String, Path, description = "dispense or discontinue")
+    ),
+    request_body = PrescriptionActionRequest,
+    responses(
+        (status = 200, description = "Prescription updated", body = ApiResponse<Prescription>),
+        (status = 409, description = "Action not allowed from the current status")
+    )
+)]
+pub async fn advance_prescription_handler(
+    State(state): State<AppState>,
+    Path((prescription_id, action)): Path<(Uuid, String)>,
+    Json(payload): Json<PrescriptionActionRequest>,
+) -> Result<Json<ApiResponse<Prescription>>, AppError> {
+    let (allowed_from, next_status) = prescription_transition(&action)
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown prescription action '{}'", action)))?;
+
+    let current = prescription_repo::get_prescription(&state.db, prescription_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let prescription = prescription_repo::advance_prescription(
+        &state.db,
+        prescription_id,
+        allowed_from,
+        next_status,
+        payload.reason.as_deref(),
+    )
+    .await?
+    .ok_or_else(|| AppError::Conflict(format!("Cannot {} a {} prescription", action, current.status)))?;
+
+    Ok(Json(ApiResponse::success(prescription, Some(format!("Prescription {}", next_status)))))
+}
+
+/// Medication history for a patient, with warnings about duplicate current prescriptions.
+/// Other hospitals' prescriptions need consent or a break-glass override.
+#[utoipa::path(
+    get,
+    path = "/api/v1/patients/{id}/medications",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID"),
+        ("status" = Option<String>, Query, description = "Filter by ACTIVE, DISPENSED or DISCONTINUED")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Medication history, newest first", body = ApiResponse<MedicationHistory>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override")
+    )
+)]
+pub async fn get_patient_medications(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(patient_id): Path<Uuid>,
+    Query(params): Query<MedicationQuery>,
+) -> Result<Json<ApiResponse<MedicationHistory>>, AppError> {
+    let scope = authorize_patient(&state.db, &ctx, patient_id, "MEDICATIONS", None).await?;
+    let prescriptions =
+        prescription_repo::get_patient_prescriptions(&state.db, patient_id, scope.hospital_filter(), params.status).await?;
+    let mut duplicate_warnings = prescription_repo::find_duplicate_medications(&state.db, patient_id, None, 2).await?;
+    // Without consent, only warn about prescriptions this hospital can see
+    if scope.hospital_filter().is_some() {
+        duplicate_warnings.retain(|d| d.prescription_ids.iter().all(|id| prescriptions.iter().any(|p| p.id == *id)));
+    }
+
+    Ok(Json(ApiResponse::success(MedicationHistory { prescriptions, duplicate_warnings }, None)))
+}
@@ -0,0 +1,126 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        problem::{Problem, CreateProblemRequest, UpdateProblemRequest},
+        consent::AccessContext,
+        api_response::ApiResponse,
+    },
+    db::{icd10_repo, problem_repo},
+    errors::app::AppError,
+    middleware::access::authorize_patient,
+};
+
+#[derive(Deserialize)]
+pub struct ProblemQuery {
+    pub status: Option<String>,
+}
+
+/// Add a condition to a patient's problem list
+#[utoipa::path(
+    post,
+    path = "/api/v1/patients/{id}/problems",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID")
+    ),
+    request_body = CreateProblemRequest,
+    responses(
+        (status = 200, description = "Problem added", body = ApiResponse<Problem>),
+        (status = 400, description = "Unknown ICD-10 code, or neither code nor description given")
+    )
+)]
+pub async fn create_problem_handler(
+    State(state): State<AppState>,
+    Path(patient_id): Path<Uuid>,
+    Json(payload): Json<CreateProblemRequest>,
+) -> Result<Json<ApiResponse<Problem>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let code = match payload.code.as_deref() {
+        Some(code) => Some(
+            icd10_repo::get_code(&state.db, code.trim())
+                .await?
+                .ok_or_else(|| AppError::BadRequest(format!("Unknown ICD-10 code: {}", code)))?,
+        ),
+        None => None,
+    };
+    let description = payload
+        .description
+        .clone()
+        .or_else(|| code.as_ref().map(|c| c.description.clone()))
+        .ok_or_else(|| AppError::BadRequest("Either a code or a description is required".to_string()))?;
+
+    let problem = problem_repo::create_problem(
+        &state.db,
+        patient_id,
+        code.as_ref().map(|c| c.code.as_str()),
+        &description,
+        &payload,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(problem, Some("Problem added".to_string()))))
+}
+
+/// Get a patient's problem list
+#[utoipa::path(
+    get,
+    path = "/api/v1/patients/{id}/problems",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Patient UUID"),
+        ("status" = Option<String>, Query, description = "Filter by ACTIVE or RESOLVED")
+    ),
+    security(("staff_session" = [])),
+    responses(
+        (status = 200, description = "Problem list, active first", body = ApiResponse<Vec<Problem>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "No care relationship, consent or break-glass override"),
+        (status = 404, description = "Patient not found")
+    )
+)]
+pub async fn get_patient_problems(
+    State(state): State<AppState>,
+    ctx: AccessContext,
+    Path(patient_id): Path<Uuid>,
+    Query(params): Query<ProblemQuery>,
+) -> Result<Json<ApiResponse<Vec<Problem>>>, AppError> {
+    authorize_patient(&state.db, &ctx, patient_id, "PROBLEMS", None).await?;
+    let problems = problem_repo::get_patient_problems(&state.db, patient_id, params.status).await?;
+    Ok(Json(ApiResponse::success(problems, None)))
+}
+
+/// Resolve or re-activate a problem
+#[utoipa::path(
+    put,
+    path = "/api/v1/problems/{id}",
+    tag = "Patients",
+    params(
+        ("id" = Uuid, Path, description = "Problem UUID")
+    ),
+    request_body = UpdateProblemRequest,
+    responses(
+        (status = 200, description = "Problem updated", body = ApiResponse<Problem>),
+        (status = 404, description = "Problem not found")
+    )
+)]
+pub async fn update_problem_handler(
+    State(state): State<AppState>,
+    Path(problem_id): Path<Uuid>,
+    Json(payload): Json<UpdateProblemRequest>,
+) -> Result<Json<ApiResponse<Problem>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let problem = problem_repo::update_problem_status(&state.db, problem_id, payload).await?;
+    Ok(Json(ApiResponse::success(problem, Some("Problem updated".to_string()))))
+}
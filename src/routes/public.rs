@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{Duration, Utc};
+use crate::{
+    routes::state::AppState,
+    models::{
+        public::{PublicHospital, PublicHospitalQuery, PublicVisitCount, PublicVisitCounts, PublicVisitQuery},
+        api_response::ApiResponse,
+    },
+    db::public_repo,
+    errors::app::AppError,
+    jobs::outbreak::week_start,
+};
+
+// The /public/v1 surface is unauthenticated and serves aggregates only: nothing here may
+// return a patient, visit or staff record, and any count of people goes through suppression.
+
+/// List active hospitals with their facilities and free beds
+#[utoipa::path(
+    get,
+    path = "/public/v1/hospitals",
+    tag = "Public",
+    params(
+        ("state" = Option<String>, Query, description = "Filter by state"),
+        ("lga" = Option<String>, Query, description = "Filter by LGA")
+    ),
+    responses(
+        (status = 200, description = "Hospitals and bed availability", body = ApiResponse<Vec<PublicHospital>>)
+    )
+)]
+pub async fn get_public_hospitals(
+    State(state): State<AppState>,
+    Query(params): Query<PublicHospitalQuery>,
+) -> Result<Json<ApiResponse<Vec<PublicHospital>>>, AppError> {
+    let hospitals = public_repo::get_hospitals(&state.db, params.state, params.lga).await?;
+    Ok(Json(ApiResponse::success(hospitals, None)))
+}
+
+/// Weekly visit counts by state and LGA, with small counts suppressed.
+/// The range is widened to whole weeks so overlapping queries cannot isolate single visits.
+#[utoipa::path(
+    get,
+    path = "/public/v1/visits/weekly",
+    tag = "Public",
+    params(
+        ("state" = Option<String>, Query, description = "Filter by state"),
+        ("from" = Option<String>, Query, description = "Start (RFC 3339), defaults to 12 weeks ago"),
+        ("to" = Option<String>, Query, description = "End (RFC 3339), defaults to now")
+    ),
+    responses(
+        (status = 200, description = "Weekly visit counts", body = ApiResponse<PublicVisitCounts>),
+        (status = 400, description = "Range is empty or longer than 52 weeks")
+    )
+)]
+pub async fn get_public_weekly_visits(
+    State(state): State<AppState>,
+    Query(params): Query<PublicVisitQuery>,
+) -> Result<Json<ApiResponse<PublicVisitCounts>>, AppError> {
+    let to = params.to.unwrap_or_else(Utc::now);
+    let from = params.from.unwrap_or(to - Duration::weeks(12));
+    let from = week_start(from.date_naive());
+    let to = week_start(to.date_naive()) + Duration::weeks(1);
+
+    if from >= to {
+        return Err(AppError::BadRequest("'from' must be before 'to'".to_string()));
+    }
+    if to - from > Duration::weeks(52) {
+        return Err(AppError::BadRequest("Range cannot exceed 52 weeks".to_string()));
+    }
+
+    let min_cell_size = state.public_min_cell_size;
+    let counts = public_repo::get_weekly_visit_counts(&state.db, params.state, from, to)
+        .await?
+        .into_iter()
+        .map(|c| PublicVisitCount::from_count(c, min_cell_size))
+        .collect();
+
+    Ok(Json(ApiResponse::success(PublicVisitCounts { from, to, min_cell_size, counts }, None)))
+}
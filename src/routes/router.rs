@@ -1,5 +1,5 @@
 use axum::{
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
     http::Method, 
 };
@@ -11,12 +11,83 @@ use crate::docs::ApiDoc;
 use super::{
     health::health_check,
     hospitals::{create_hospital_handler, get_hospitals, get_hospital_by_id, delete_hospital, update_hospital_handler},
-    auth::login_handler,
+    auth::{login_handler, staff_login_handler},
     departments::{create_department_handler, get_hospital_departments},
-    staff::{create_staff_handler, get_hospital_staff},
-    patients::{create_patient_handler, get_patients_handler},
-    visits::{create_visit_handler, get_hospital_visits},
-    equipment::{create_equipment_handler, get_hospital_equipment},
+    staff::{create_staff_handler, get_hospital_staff, set_staff_account_handler},
+    patients::{
+        create_patient_handler, get_patients_handler, lookup_patients_handler, get_patient_handler,
+        get_patient_timeline,
+    },
+    visits::{create_visit_handler, get_hospital_visits, update_visit_status_handler},
+    equipment::{
+        create_equipment_handler, get_hospital_equipment, update_equipment_condition_handler,
+        get_equipment_condition_history,
+    },
+    shifts::{
+        create_shift_handler, get_hospital_shifts, create_rota_template_handler, get_hospital_rota_templates,
+        apply_rota_handler, upsert_coverage_rule_handler, get_on_duty_staff, get_coverage_gaps,
+    },
+    credentials::{create_credential_handler, get_staff_credentials, get_expiring_credentials},
+    appointments::{
+        create_slots_handler, get_available_slots, book_slot_handler, reschedule_appointment_handler,
+        cancel_appointment_handler, get_appointment_history,
+    },
+    maintenance::{
+        create_plan_handler, get_equipment_plans, create_work_order_handler, close_work_order_handler,
+        get_hospital_work_orders, get_overdue_maintenance,
+    },
+    equipment_transfers::{
+        create_transfer_handler, get_transfer, advance_transfer_handler, get_hospital_transfers,
+        get_custody_history,
+    },
+    inventory::{
+        create_item_handler, get_hospital_inventory, get_low_stock, create_transaction_handler,
+        get_item_transactions, get_capability_summary,
+    },
+    blood_bank::{
+        report_blood_units_handler, get_hospital_blood_stock, issue_blood_units_handler, search_blood_handler,
+    },
+    diagnoses::{add_diagnosis_handler, get_visit_diagnoses, search_icd10_codes, get_icd10_code},
+    surveillance::{
+        get_conditions, create_condition_handler, update_condition_handler, get_cases, get_weekly_counts,
+    },
+    outbreaks::{get_outbreak_alerts, run_outbreak_detection_handler, resolve_outbreak_alert_handler},
+    observations::{
+        record_observations_handler, get_visit_observations, get_patient_trend, get_deteriorating_patients,
+    },
+    prescriptions::{
+        create_prescription_handler, get_visit_prescriptions, get_prescription, advance_prescription_handler,
+        get_patient_medications,
+    },
+    lab::{
+        get_lab_tests, create_lab_test_handler, create_lab_order_handler, get_visit_lab_orders, get_lab_order,
+        collect_specimen_handler, enter_lab_result_handler, verify_lab_result_handler, get_lab_queue,
+    },
+    allergies::{create_allergy_handler, get_patient_allergies, update_allergy_handler},
+    problems::{create_problem_handler, get_patient_problems, update_problem_handler},
+    consents::{grant_consent_handler, get_patient_consents, revoke_consent_handler, get_patient_access_log},
+    pii::{get_pii_key_status, run_pii_reencryption_handler},
+    public::{get_public_hospitals, get_public_weekly_visits},
+    analytics::{get_occupancy_analytics, get_high_occupancy, get_coverage_analytics, get_daily_visit_analytics},
+    forecasts::{get_hospital_forecast, run_forecast_handler},
+    capacity_alerts::{
+        create_alert_rule_handler, get_alert_rules_handler, deactivate_alert_rule_handler, evaluate_alert_rules_handler,
+        get_capacity_alerts_handler, acknowledge_capacity_alert_handler, resolve_capacity_alert_handler,
+        get_alert_deliveries_handler, create_alert_subscription_handler, get_alert_subscriptions_handler,
+        deactivate_alert_subscription_handler,
+    },
+    webhooks::{
+        create_webhook_handler, get_webhooks_handler, deactivate_webhook_handler, get_webhook_deliveries_handler,
+        redeliver_webhook_handler, dispatch_webhooks_handler,
+    },
+    outbox::{get_outbox_status, dispatch_outbox_handler, get_parked_outbox_events, requeue_outbox_event_handler},
+    events::{events_websocket, events_sse},
+    notifications::{
+        get_notification_templates_handler, upsert_notification_template_handler,
+        get_notification_preferences_handler, update_notification_preferences_handler, send_notification_handler,
+        get_notifications_handler, get_notification_handler, retry_notification_handler,
+        dispatch_notifications_handler,
+    },
     state::AppState,
 };
 
@@ -30,6 +101,7 @@ pub fn create_router() -> Router<AppState> {
     Router::new()
         .route("/api/v1/health", get(health_check))
         .route("/api/v1/login", post(login_handler))
+        .route("/api/v1/staff/login", post(staff_login_handler))
         .route("/api/v1/hospitals", get(get_hospitals).post(create_hospital_handler))
         .route(
             "/api/v1/hospitals/:id", 
@@ -41,11 +113,129 @@ pub fn create_router() -> Router<AppState> {
         .route("/api/v1/hospitals/:id/departments", get(get_hospital_departments))
         .route("/api/v1/staff", post(create_staff_handler))
         .route("/api/v1/hospitals/:id/staff", get(get_hospital_staff))
+        .route("/api/v1/staff/:id/account", put(set_staff_account_handler))
         .route("/api/v1/patients", get(get_patients_handler).post(create_patient_handler))
         .route("/api/v1/visits", post(create_visit_handler))
         .route("/api/v1/hospitals/:id/visits", get(get_hospital_visits))
         .route("/api/v1/equipment", post(create_equipment_handler))
         .route("/api/v1/hospitals/:id/equipment", get(get_hospital_equipment))
+        .route("/api/v1/shifts", post(create_shift_handler))
+        .route("/api/v1/hospitals/:id/shifts", get(get_hospital_shifts))
+        .route("/api/v1/rota-templates", post(create_rota_template_handler))
+        .route("/api/v1/hospitals/:id/rota-templates", get(get_hospital_rota_templates))
+        .route("/api/v1/hospitals/:id/rota-templates/apply", post(apply_rota_handler))
+        .route("/api/v1/coverage-rules", put(upsert_coverage_rule_handler))
+        .route("/api/v1/hospitals/:id/on-duty", get(get_on_duty_staff))
+        .route("/api/v1/hospitals/:id/coverage-gaps", get(get_coverage_gaps))
+        .route("/api/v1/staff/:id/credentials", get(get_staff_credentials).post(create_credential_handler))
+        .route("/api/v1/hospitals/:id/credentials/expiring", get(get_expiring_credentials))
+        .route("/api/v1/slots", post(create_slots_handler))
+        .route("/api/v1/slots/available", get(get_available_slots))
+        .route("/api/v1/slots/:id/book", post(book_slot_handler))
+        .route("/api/v1/appointments/:id/reschedule", post(reschedule_appointment_handler))
+        .route("/api/v1/appointments/:id/cancel", post(cancel_appointment_handler))
+        .route("/api/v1/appointments/:id/history", get(get_appointment_history))
+        .route("/api/v1/equipment/:id/condition", put(update_equipment_condition_handler))
+        .route("/api/v1/equipment/:id/condition-history", get(get_equipment_condition_history))
+        .route("/api/v1/equipment/:id/maintenance-plans", get(get_equipment_plans).post(create_plan_handler))
+        .route("/api/v1/equipment/:id/work-orders", post(create_work_order_handler))
+        .route("/api/v1/work-orders/:id/close", post(close_work_order_handler))
+        .route("/api/v1/hospitals/:id/work-orders", get(get_hospital_work_orders))
+        .route("/api/v1/hospitals/:id/maintenance/overdue", get(get_overdue_maintenance))
+        .route("/api/v1/equipment-transfers", post(create_transfer_handler))
+        .route("/api/v1/equipment-transfers/:id", get(get_transfer))
+        .route("/api/v1/equipment-transfers/:id/:action", post(advance_transfer_handler))
+        .route("/api/v1/hospitals/:id/equipment-transfers", get(get_hospital_transfers))
+        .route("/api/v1/equipment/:id/custody-history", get(get_custody_history))
+        .route("/api/v1/hospitals/:id/inventory", get(get_hospital_inventory).post(create_item_handler))
+        .route("/api/v1/hospitals/:id/inventory/low-stock", get(get_low_stock))
+        .route("/api/v1/inventory/:id/transactions", get(get_item_transactions).post(create_transaction_handler))
+        .route("/api/v1/hospitals/:id/capabilities", get(get_capability_summary))
+        .route("/api/v1/hospitals/:id/blood-units", get(get_hospital_blood_stock).post(report_blood_units_handler))
+        .route("/api/v1/blood-units/:id/issue", post(issue_blood_units_handler))
+        .route("/api/v1/blood-bank/search", get(search_blood_handler))
+        .route("/api/v1/visits/:id/status", put(update_visit_status_handler))
+        .route("/api/v1/visits/:id/diagnoses", get(get_visit_diagnoses).post(add_diagnosis_handler))
+        .route("/api/v1/icd10/codes", get(search_icd10_codes))
+        .route("/api/v1/icd10/codes/:code", get(get_icd10_code))
+        .route("/api/v1/surveillance/conditions", get(get_conditions).post(create_condition_handler))
+        .route("/api/v1/surveillance/conditions/:id", put(update_condition_handler))
+        .route("/api/v1/surveillance/cases", get(get_cases))
+        .route("/api/v1/surveillance/weekly", get(get_weekly_counts))
+        .route("/api/v1/surveillance/alerts", get(get_outbreak_alerts))
+        .route("/api/v1/surveillance/alerts/run", post(run_outbreak_detection_handler))
+        .route("/api/v1/surveillance/alerts/:id/resolve", put(resolve_outbreak_alert_handler))
+        .route("/api/v1/visits/:id/observations", get(get_visit_observations).post(record_observations_handler))
+        .route("/api/v1/patients/:id/observations/trend", get(get_patient_trend))
+        .route("/api/v1/hospitals/:id/deteriorating", get(get_deteriorating_patients))
+        .route("/api/v1/visits/:id/prescriptions", get(get_visit_prescriptions).post(create_prescription_handler))
+        .route("/api/v1/prescriptions/:id", get(get_prescription))
+        .route("/api/v1/prescriptions/:id/:action", post(advance_prescription_handler))
+        .route("/api/v1/patients/:id/medications", get(get_patient_medications))
+        .route("/api/v1/lab-tests", get(get_lab_tests).post(create_lab_test_handler))
+        .route("/api/v1/visits/:id/lab-orders", get(get_visit_lab_orders).post(create_lab_order_handler))
+        .route("/api/v1/lab-orders/:id", get(get_lab_order))
+        .route("/api/v1/lab-orders/:id/collect", post(collect_specimen_handler))
+        .route("/api/v1/lab-orders/:id/result", post(enter_lab_result_handler))
+        .route("/api/v1/lab-orders/:id/verify", post(verify_lab_result_handler))
+        .route("/api/v1/hospitals/:id/lab-queue", get(get_lab_queue))
+        .route("/api/v1/patients/lookup", get(lookup_patients_handler))
+        .route("/api/v1/patients/:id", get(get_patient_handler))
+        .route("/api/v1/patients/:id/timeline", get(get_patient_timeline))
+        .route("/api/v1/patients/:id/allergies", get(get_patient_allergies).post(create_allergy_handler))
+        .route("/api/v1/allergies/:id", put(update_allergy_handler))
+        .route("/api/v1/patients/:id/problems", get(get_patient_problems).post(create_problem_handler))
+        .route("/api/v1/problems/:id", put(update_problem_handler))
+        .route("/api/v1/patients/:id/consents", get(get_patient_consents).post(grant_consent_handler))
+        .route("/api/v1/consents/:id/revoke", put(revoke_consent_handler))
+        .route("/api/v1/patients/:id/access-log", get(get_patient_access_log))
+        .route("/api/v1/pii/key-status", get(get_pii_key_status))
+        .route("/api/v1/pii/reencrypt", post(run_pii_reencryption_handler))
+        .route("/api/v1/analytics/occupancy", get(get_occupancy_analytics))
+        .route("/api/v1/analytics/occupancy/high", get(get_high_occupancy))
+        .route("/api/v1/analytics/coverage", get(get_coverage_analytics))
+        .route("/api/v1/analytics/visits/daily", get(get_daily_visit_analytics))
+        .route("/api/v1/hospitals/:id/forecast", get(get_hospital_forecast))
+        .route("/api/v1/forecasts/run", post(run_forecast_handler))
+        // Surge and capacity alerting
+        .route("/api/v1/alert-rules", post(create_alert_rule_handler).get(get_alert_rules_handler))
+        .route("/api/v1/alert-rules/evaluate", post(evaluate_alert_rules_handler))
+        .route("/api/v1/alert-rules/:id/deactivate", put(deactivate_alert_rule_handler))
+        .route("/api/v1/alert-subscriptions", post(create_alert_subscription_handler).get(get_alert_subscriptions_handler))
+        .route("/api/v1/alert-subscriptions/:id/deactivate", put(deactivate_alert_subscription_handler))
+        .route("/api/v1/capacity-alerts", get(get_capacity_alerts_handler))
+        .route("/api/v1/capacity-alerts/:id/acknowledge", put(acknowledge_capacity_alert_handler))
+        .route("/api/v1/capacity-alerts/:id/resolve", put(resolve_capacity_alert_handler))
+        .route("/api/v1/capacity-alerts/:id/deliveries", get(get_alert_deliveries_handler))
+        // Outbound webhooks for partner systems
+        .route("/api/v1/webhooks", post(create_webhook_handler).get(get_webhooks_handler))
+        .route("/api/v1/webhooks/dispatch", post(dispatch_webhooks_handler))
+        .route("/api/v1/webhooks/:id/deactivate", put(deactivate_webhook_handler))
+        .route("/api/v1/webhooks/:id/deliveries", get(get_webhook_deliveries_handler))
+        .route("/api/v1/webhook-deliveries/:id/redeliver", post(redeliver_webhook_handler))
+        // Transactional outbox and the live event feed it drives
+        .route("/api/v1/outbox/status", get(get_outbox_status))
+        .route("/api/v1/outbox/dispatch", post(dispatch_outbox_handler))
+        .route("/api/v1/outbox/parked", get(get_parked_outbox_events))
+        .route("/api/v1/outbox/events/:id/requeue", post(requeue_outbox_event_handler))
+        .route("/api/v1/ws/events", get(events_websocket))
+        .route("/api/v1/sse/events", get(events_sse))
+        // Email and SMS notifications
+        .route(
+            "/api/v1/notification-templates",
+            get(get_notification_templates_handler).put(upsert_notification_template_handler),
+        )
+        .route(
+            "/api/v1/staff/:id/notification-preferences",
+            get(get_notification_preferences_handler).put(update_notification_preferences_handler),
+        )
+        .route("/api/v1/notifications", post(send_notification_handler).get(get_notifications_handler))
+        .route("/api/v1/notifications/dispatch", post(dispatch_notifications_handler))
+        .route("/api/v1/notifications/:id", get(get_notification_handler))
+        .route("/api/v1/notifications/:id/retry", post(retry_notification_handler))
+        // De-identified aggregates for citizens and researchers
+        .route("/public/v1/hospitals", get(get_public_hospitals))
+        .route("/public/v1/visits/weekly", get(get_public_weekly_visits))
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .layer(cors)
 }
\ No newline at end of file
@@ -0,0 +1,254 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::{departments::check_department, state::AppState},
+    models::{
+        shift::{
+            Shift, ShiftRejection, CreateShiftRequest, RotaTemplate, CreateRotaTemplateRequest, ApplyRotaRequest,
+            ApplyRotaResponse, CoverageRule, UpsertCoverageRuleRequest, OnDutyStaff, CoverageGap,
+        },
+        api_response::ApiResponse,
+    },
+    db::shift_repo,
+    errors::app::AppError,
+};
+
+#[derive(Deserialize)]
+pub struct ShiftRangeQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct AtQuery {
+    pub at: Option<DateTime<Utc>>,
+}
+
+/// Create a shift for a staff member
+#[utoipa::path(
+    post,
+    path = "/api/v1/shifts",
+    tag = "Shifts",
+    request_body = CreateShiftRequest,
+    responses(
+        (status = 200, description = "Shift created", body = ApiResponse<Shift>),
+        (status = 400, description = "Invalid times, staff member not active, or department is not in the staff member's hospital"),
+        (status = 404, description = "Staff member not found"),
+        (status = 409, description = "Staff member already has an overlapping shift")
+    )
+)]
+pub async fn create_shift_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateShiftRequest>,
+) -> Result<Json<ApiResponse<Shift>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    if payload.end_time <= payload.start_time {
+        return Err(AppError::BadRequest("Shift end time must be after start time".to_string()));
+    }
+    check_department(&state, payload.staff_id, payload.department_id).await?;
+
+    let shift = shift_repo::create_shift(&state.db, &payload).await?.map_err(|rejection| match rejection {
+        ShiftRejection::StaffInactive => AppError::BadRequest("Staff member is not active".to_string()),
+        ShiftRejection::Overlap => AppError::Conflict("Staff member already has an overlapping shift".to_string()),
+    })?;
+
+    Ok(Json(ApiResponse::success(shift, Some("Shift created".to_string()))))
+}
+
+/// Get shifts for a hospital within a time range (defaults to the next 7 days)
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/shifts",
+    tag = "Shifts",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID"),
+        ("from" = Option<DateTime<Utc>>, Query, description = "Range start (defaults to now)"),
+        ("to" = Option<DateTime<Utc>>, Query, description = "Range end (defaults to 7 days after start)")
+    ),
+    responses(
+        (status = 200, description = "List of shifts", body = ApiResponse<Vec<Shift>>),
+        (status = 400, description = "Range end is not after its start")
+    )
+)]
+pub async fn get_hospital_shifts(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Query(params): Query<ShiftRangeQuery>,
+) -> Result<Json<ApiResponse<Vec<Shift>>>, AppError> {
+    let from = params.from.unwrap_or_else(Utc::now);
+    let to = params.to.unwrap_or(from + Duration::days(7));
+    if to <= from {
+        return Err(AppError::BadRequest("Range end must be after its start".to_string()));
+    }
+
+    let shifts = shift_repo::get_hospital_shifts(&state.db, hospital_id, from, to).await?;
+    Ok(Json(ApiResponse::success(shifts, None)))
+}
+
+/// Create a weekly rota template
+#[utoipa::path(
+    post,
+    path = "/api/v1/rota-templates",
+    tag = "Shifts",
+    request_body = CreateRotaTemplateRequest,
+    responses(
+        (status = 200, description = "Rota template created", body = ApiResponse<RotaTemplate>),
+        (status = 400, description = "Department is not in the staff member's hospital")
+    )
+)]
+pub async fn create_rota_template_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateRotaTemplateRequest>,
+) -> Result<Json<ApiResponse<RotaTemplate>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    check_department(&state, payload.staff_id, payload.department_id).await?;
+
+    let template = shift_repo::create_rota_template(&state.db, payload).await?;
+    Ok(Json(ApiResponse::success(template, Some("Rota template created".to_string()))))
+}
+
+/// Get rota templates for a hospital
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/rota-templates",
+    tag = "Shifts",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID")
+    ),
+    responses(
+        (status = 200, description = "List of rota templates", body = ApiResponse<Vec<RotaTemplate>>)
+    )
+)]
+pub async fn get_hospital_rota_templates(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<RotaTemplate>>>, AppError> {
+    let templates = shift_repo::get_hospital_rota_templates(&state.db, hospital_id).await?;
+    Ok(Json(ApiResponse::success(templates, None)))
+}
+
+/// Expand a hospital's rota templates into concrete shifts for one week
+#[utoipa::path(
+    post,
+    path = "/api/v1/hospitals/{id}/rota-templates/apply",
+    tag = "Shifts",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID")
+    ),
+    request_body = ApplyRotaRequest,
+    responses(
+        (status = 200, description = "Rota applied", body = ApiResponse<ApplyRotaResponse>)
+    )
+)]
+pub async fn apply_rota_handler(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Json(payload): Json<ApplyRotaRequest>,
+) -> Result<Json<ApiResponse<ApplyRotaResponse>>, AppError> {
+    let templates = shift_repo::get_hospital_rota_templates(&state.db, hospital_id).await?;
+
+    let mut created = Vec::new();
+    let mut skipped = 0;
+
+    for offset in 0..7 {
+        let date = payload.week_start + Duration::days(offset);
+        let weekday = date.weekday().num_days_from_monday() as i16;
+
+        for template in templates.iter().filter(|t| t.day_of_week == weekday) {
+            let start_time = date.and_time(template.start_time).and_utc();
+            let shift = CreateShiftRequest {
+                staff_id: template.staff_id,
+                department_id: Some(template.department_id),
+                shift_type: template.shift_type.clone(),
+                start_time,
+                end_time: start_time + Duration::minutes(template.duration_minutes as i64),
+            };
+
+            match shift_repo::create_shift(&state.db, &shift).await? {
+                Ok(s) => created.push(s),
+                Err(_) => skipped += 1,
+            }
+        }
+    }
+
+    let message = format!("{} shifts created, {} skipped", created.len(), skipped);
+    Ok(Json(ApiResponse::success(ApplyRotaResponse { created, skipped }, Some(message))))
+}
+
+/// Set the minimum number of on-duty staff of a role for a department
+#[utoipa::path(
+    put,
+    path = "/api/v1/coverage-rules",
+    tag = "Shifts",
+    request_body = UpsertCoverageRuleRequest,
+    responses(
+        (status = 200, description = "Coverage rule saved", body = ApiResponse<CoverageRule>)
+    )
+)]
+pub async fn upsert_coverage_rule_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<UpsertCoverageRuleRequest>,
+) -> Result<Json<ApiResponse<CoverageRule>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let rule = shift_repo::upsert_coverage_rule(&state.db, payload).await?;
+    Ok(Json(ApiResponse::success(rule, Some("Coverage rule saved".to_string()))))
+}
+
+/// Who is on duty at a hospital (now, or at a given time)
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/on-duty",
+    tag = "Shifts",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID"),
+        ("at" = Option<DateTime<Utc>>, Query, description = "Point in time (defaults to now)")
+    ),
+    responses(
+        (status = 200, description = "Staff on duty", body = ApiResponse<Vec<OnDutyStaff>>)
+    )
+)]
+pub async fn get_on_duty_staff(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Query(params): Query<AtQuery>,
+) -> Result<Json<ApiResponse<Vec<OnDutyStaff>>>, AppError> {
+    let at = params.at.unwrap_or_else(Utc::now);
+    let staff = shift_repo::get_on_duty_staff(&state.db, hospital_id, at).await?;
+    Ok(Json(ApiResponse::success(staff, None)))
+}
+
+/// Departments that are under-staffed against their coverage rules
+#[utoipa::path(
+    get,
+    path = "/api/v1/hospitals/{id}/coverage-gaps",
+    tag = "Shifts",
+    params(
+        ("id" = Uuid, Path, description = "Hospital UUID"),
+        ("at" = Option<DateTime<Utc>>, Query, description = "Point in time (defaults to now)")
+    ),
+    responses(
+        (status = 200, description = "Under-staffed departments", body = ApiResponse<Vec<CoverageGap>>)
+    )
+)]
+pub async fn get_coverage_gaps(
+    State(state): State<AppState>,
+    Path(hospital_id): Path<Uuid>,
+    Query(params): Query<AtQuery>,
+) -> Result<Json<ApiResponse<Vec<CoverageGap>>>, AppError> {
+    let at = params.at.unwrap_or_else(Utc::now);
+    let gaps = shift_repo::get_coverage_gaps(&state.db, hospital_id, at).await?;
+    Ok(Json(ApiResponse::success(gaps, None)))
+}
@@ -4,14 +4,17 @@ use axum::{
 };
 use uuid::Uuid;
 use validator::Validate;
+use bcrypt::{hash, DEFAULT_COST};
 use crate::{
     routes::state::AppState,
     models::{
         staff::{Staff, CreateStaffRequest},
+        staff_account::{SetStaffAccountRequest, StaffAccount},
         api_response::ApiResponse,
     },
-    db::staff_repo,
+    db::{staff_account_repo, staff_repo},
     errors::app::AppError,
+    middleware::auth::AdminSession,
 };
 
 /// Create a new staff member
@@ -21,7 +24,8 @@ use crate::{
     tag = "Staff",
     request_body = CreateStaffRequest,
     responses(
-        (status = 200, description = "Staff created", body = ApiResponse<Staff>)
+        (status = 200, description = "Staff created", body = ApiResponse<Staff>),
+        (status = 400, description = "Hospital or department does not exist")
     )
 )]
 pub async fn create_staff_handler(
@@ -54,4 +58,41 @@ pub async fn get_hospital_staff(
 ) -> Result<Json<ApiResponse<Vec<Staff>>>, AppError> {
     let staff = staff_repo::get_staff_by_hospital(&state.db, hospital_id).await?;
     Ok(Json(ApiResponse::success(staff, None)))
-}
\ No newline at end of file
+}
+
+/// Set a staff member's sign-in email and password (admin only). Changing the password ends
+/// their existing sessions.
+#[utoipa::path(
+    put,
+    path = "/api/v1/staff/{id}/account",
+    tag = "Staff",
+    params(
+        ("id" = Uuid, Path, description = "Staff UUID")
+    ),
+    request_body = SetStaffAccountRequest,
+    security(("admin_session" = [])),
+    responses(
+        (status = 200, description = "Account set", body = ApiResponse<StaffAccount>),
+        (status = 400, description = "Invalid email or password"),
+        (status = 401, description = "No valid admin session"),
+        (status = 404, description = "Staff member not found"),
+        (status = 409, description = "Email already used by another staff account")
+    )
+)]
+pub async fn set_staff_account_handler(
+    State(state): State<AppState>,
+    _admin: AdminSession,
+    Path(staff_id): Path<Uuid>,
+    Json(payload): Json<SetStaffAccountRequest>,
+) -> Result<Json<ApiResponse<StaffAccount>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    staff_repo::get_staff_by_id(&state.db, staff_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let password_hash = hash(&payload.password, DEFAULT_COST).map_err(|_| AppError::Internal)?;
+    let account = staff_account_repo::set_account(&state.db, staff_id, &payload.email, &password_hash).await?;
+    Ok(Json(ApiResponse::success(account, Some("Staff account set".to_string()))))
+}
@@ -1,7 +1,23 @@
 use sqlx::PgPool;
+use crate::crypto::pii::PiiCipher;
+use crate::jobs::{
+    forecast::ForecastConfig, outbreak::OutbreakConfig, outbox::OutboxDispatcher, pii_rotation::PiiRotationConfig,
+    webhooks::WebhookConfig,
+};
+use crate::notify::Notifier;
+use crate::ws::hub::EventHub;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
     pub jwt_secret: String,
+    pub outbreak: OutbreakConfig,
+    pub pii: PiiCipher,
+    pub pii_rotation: PiiRotationConfig,
+    pub public_min_cell_size: i64,
+    pub forecast: ForecastConfig,
+    pub webhooks: WebhookConfig,
+    pub events: EventHub,
+    pub outbox: OutboxDispatcher,
+    pub notifier: Notifier,
 }
\ No newline at end of file
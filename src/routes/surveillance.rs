@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        surveillance::{
+            NotifiableCondition, CreateNotifiableConditionRequest, UpdateNotifiableConditionRequest,
+            SurveillanceCase, WeeklyCaseCount,
+        },
+        api_response::ApiResponse,
+    },
+    db::surveillance_repo,
+    errors::app::AppError,
+};
+
+#[derive(Deserialize)]
+pub struct SurveillanceQuery {
+    pub state: Option<String>,
+    pub condition_id: Option<Uuid>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl SurveillanceQuery {
+    /// Defaults to the last 12 weeks.
+    fn range(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        let to = self.to.unwrap_or_else(Utc::now);
+        let from = self.from.unwrap_or(to - Duration::weeks(12));
+        (from, to)
+    }
+}
+
+/// List notifiable conditions
+#[utoipa::path(
+    get,
+    path = "/api/v1/surveillance/conditions",
+    tag = "Surveillance",
+    responses(
+        (status = 200, description = "Notifiable conditions", body = ApiResponse<Vec<NotifiableCondition>>)
+    )
+)]
+pub async fn get_conditions(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<NotifiableCondition>>>, AppError> {
+    let conditions = surveillance_repo::get_conditions(&state.db).await?;
+    Ok(Json(ApiResponse::success(conditions, None)))
+}
+
+/// Add a notifiable condition
+#[utoipa::path(
+    post,
+    path = "/api/v1/surveillance/conditions",
+    tag = "Surveillance",
+    request_body = CreateNotifiableConditionRequest,
+    responses(
+        (status = 200, description = "Condition added", body = ApiResponse<NotifiableCondition>)
+    )
+)]
+pub async fn create_condition_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateNotifiableConditionRequest>,
+) -> Result<Json<ApiResponse<NotifiableCondition>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let condition = surveillance_repo::create_condition(&state.db, payload).await?;
+    Ok(Json(ApiResponse::success(condition, Some("Condition added".to_string()))))
+}
+
+/// Enable or disable reporting of a condition
+#[utoipa::path(
+    put,
+    path = "/api/v1/surveillance/conditions/{id}",
+    tag = "Surveillance",
+    params(
+        ("id" = Uuid, Path, description = "Condition UUID")
+    ),
+    request_body = UpdateNotifiableConditionRequest,
+    responses(
+        (status = 200, description = "Condition updated", body = ApiResponse<NotifiableCondition>),
+        (status = 404, description = "Condition not found")
+    )
+)]
+pub async fn update_condition_handler(
+    State(state): State<AppState>,
+    Path(condition_id): Path<Uuid>,
+    Json(payload): Json<UpdateNotifiableConditionRequest>,
+) -> Result<Json<ApiResponse<NotifiableCondition>>, AppError> {
+    let condition = surveillance_repo::set_condition_active(&state.db, condition_id, payload.is_active).await?;
+    Ok(Json(ApiResponse::success(condition, Some("Condition updated".to_string()))))
+}
+
+/// Case reports raised from completed visits
+#[utoipa::path(
+    get,
+    path = "/api/v1/surveillance/cases",
+    tag = "Surveillance",
+    params(
+        ("state" = Option<String>, Query, description = "Filter by state"),
+        ("condition_id" = Option<Uuid>, Query, description = "Filter by condition"),
+        ("from" = Option<DateTime<Utc>>, Query, description = "Range start (defaults to 12 weeks before `to`)"),
+        ("to" = Option<DateTime<Utc>>, Query, description = "Range end (defaults to now)")
+    ),
+    responses(
+        (status = 200, description = "Case reports", body = ApiResponse<Vec<SurveillanceCase>>)
+    )
+)]
+pub async fn get_cases(
+    State(state): State<AppState>,
+    Query(params): Query<SurveillanceQuery>,
+) -> Result<Json<ApiResponse<Vec<SurveillanceCase>>>, AppError> {
+    let (from, to) = params.range();
+    let cases = surveillance_repo::get_cases(&state.db, params.state, params.condition_id, from, to).await?;
+    Ok(Json(ApiResponse::success(cases, None)))
+}
+
+/// Weekly case counts per state, LGA and condition (IDSR weekly report)
+#[utoipa::path(
+    get,
+    path = "/api/v1/surveillance/weekly",
+    tag = "Surveillance",
+    params(
+        ("state" = Option<String>, Query, description = "Filter by state"),
+        ("from" = Option<DateTime<Utc>>, Query, description = "Range start (defaults to 12 weeks before `to`)"),
+        ("to" = Option<DateTime<Utc>>, Query, description = "Range end (defaults to now)")
+    ),
+    responses(
+        (status = 200, description = "Weekly counts", body = ApiResponse<Vec<WeeklyCaseCount>>)
+    )
+)]
+pub async fn get_weekly_counts(
+    State(state): State<AppState>,
+    Query(params): Query<SurveillanceQuery>,
+) -> Result<Json<ApiResponse<Vec<WeeklyCaseCount>>>, AppError> {
+    let (from, to) = params.range();
+    let counts = surveillance_repo::get_weekly_counts(&state.db, params.state, from, to).await?;
+    Ok(Json(ApiResponse::success(counts, None)))
+}
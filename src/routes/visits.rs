@@ -2,26 +2,31 @@ use axum::{
     extract::{Path, State},
     Json,
 };
+use chrono::Utc;
 use uuid::Uuid;
 use validator::Validate;
 use crate::{
     routes::state::AppState,
     models::{
-        visit::{Visit, CreateVisitRequest},
+        visit::{Visit, CreateVisitRequest, UpdateVisitStatusRequest},
+        consent::AccessContext,
         api_response::ApiResponse,
     },
-    db::visit_repo,
+    db::{appointment_repo, visit_repo, credential_repo},
     errors::app::AppError,
+    middleware::access::{require_own_hospital, log_list_access},
 };
 
-/// Create a new visit (appointment)
+/// Create a new visit (appointment). A visit inside one of the clinician's open slots books it.
 #[utoipa::path(
     post,
     path = "/api/v1/visits",
     tag = "Visits",
     request_body = CreateVisitRequest,
     responses(
-        (status = 200, description = "Visit created", body = ApiResponse<Visit>)
+        (status = 200, description = "Visit created", body = ApiResponse<Visit>),
+        (status = 400, description = "Staff member is not credentialled"),
+        (status = 409, description = "Clinician is already booked at this time")
     )
 )]
 pub async fn create_visit_handler(
@@ -32,7 +37,15 @@ pub async fn create_visit_handler(
         return Err(AppError::BadRequest(e.to_string()));
     }
 
-    let visit = visit_repo::create_visit(&state.db, payload).await?;
+    // Only staff holding a licence valid on the visit date may be assigned
+    let visit_date = payload.start_time.unwrap_or_else(Utc::now).date_naive();
+    if !credential_repo::has_valid_credential(&state.db, payload.staff_id, visit_date).await? {
+        return Err(AppError::BadRequest("Staff member does not hold a valid licence for this date".to_string()));
+    }
+
+    let visit = appointment_repo::create_visit(&state.db, payload)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Clinician is already booked at this time".to_string()))?;
     Ok(Json(ApiResponse::success(visit, Some("Visit scheduled successfully".to_string()))))
 }
 
@@ -44,14 +57,62 @@ pub async fn create_visit_handler(
     params(
         ("id" = Uuid, Path, description = "Hospital UUID")
     ),
+    security(("staff_session" = [])),
     responses(
-        (status = 200, description = "List of visits", body = ApiResponse<Vec<Visit>>)
+        (status = 200, description = "List of visits", body = ApiResponse<Vec<Visit>>),
+        (status = 401, description = "No valid staff session"),
+        (status = 403, description = "Calling hospital is not this hospital")
     )
 )]
 pub async fn get_hospital_visits(
     State(state): State<AppState>,
+    ctx: AccessContext,
     Path(hospital_id): Path<Uuid>,
 ) -> Result<Json<ApiResponse<Vec<Visit>>>, AppError> {
+    require_own_hospital(&ctx, hospital_id)?;
     let visits = visit_repo::get_hospital_visits(&state.db, hospital_id).await?;
+    log_list_access(&state.db, &ctx, visits.iter().map(|v| v.patient_id), "VISIT_LIST").await?;
     Ok(Json(ApiResponse::success(visits, None)))
-}
\ No newline at end of file
+}
+
+/// Update a visit's status (completion triggers notifiable-condition reporting). Cancelling goes
+/// through `/appointments/{id}/cancel`, which also frees the visit's slot.
+#[utoipa::path(
+    put,
+    path = "/api/v1/visits/{id}/status",
+    tag = "Visits",
+    params(
+        ("id" = Uuid, Path, description = "Visit UUID")
+    ),
+    request_body = UpdateVisitStatusRequest,
+    responses(
+        (status = 200, description = "Visit status updated", body = ApiResponse<Visit>),
+        (status = 400, description = "Invalid status, or CANCELLED"),
+        (status = 409, description = "Transition not allowed from the current status")
+    )
+)]
+pub async fn update_visit_status_handler(
+    State(state): State<AppState>,
+    Path(visit_id): Path<Uuid>,
+    Json(payload): Json<UpdateVisitStatusRequest>,
+) -> Result<Json<ApiResponse<Visit>>, AppError> {
+    if payload.status == "CANCELLED" {
+        return Err(AppError::BadRequest(
+            "Cancel visits with POST /api/v1/appointments/{id}/cancel".to_string(),
+        ));
+    }
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+
+    let current = visit_repo::get_visit_by_id(&state.db, visit_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let visit = visit_repo::update_visit_status(&state.db, visit_id, &payload.status)
+        .await?
+        .ok_or_else(|| AppError::Conflict(format!("Cannot move a {} visit to {}", current.status, payload.status)))?;
+
+    state.outbox.wake();
+    Ok(Json(ApiResponse::success(visit, Some("Visit status updated".to_string()))))
+}
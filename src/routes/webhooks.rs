@@ -0,0 +1,179 @@
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    routes::state::AppState,
+    models::{
+        webhook::{
+            CreateWebhookEndpointRequest, RegisteredWebhookEndpoint, WebhookEndpoint, WebhookDelivery,
+            WebhookDispatchSummary,
+        },
+        api_response::ApiResponse,
+    },
+    db::webhook_repo,
+    errors::app::AppError,
+    jobs::webhooks,
+};
+
+#[derive(Deserialize)]
+pub struct WebhookDeliveryQuery {
+    pub status: Option<String>,
+}
+
+/// Register a partner endpoint for one or more event types. The response carries the signing
+/// secret; it is not shown again.
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks",
+    tag = "Webhooks",
+    request_body = CreateWebhookEndpointRequest,
+    responses(
+        (status = 200, description = "Endpoint registered", body = ApiResponse<RegisteredWebhookEndpoint>),
+        (status = 400, description = "Invalid URL or unknown event type")
+    )
+)]
+pub async fn create_webhook_handler(
+    State(state): State<AppState>,
+    Json(mut payload): Json<CreateWebhookEndpointRequest>,
+) -> Result<Json<ApiResponse<RegisteredWebhookEndpoint>>, AppError> {
+    if let Err(e) = payload.validate() {
+        return Err(AppError::BadRequest(e.to_string()));
+    }
+    payload.event_types.sort();
+    payload.event_types.dedup();
+
+    let secret = payload.secret.take().unwrap_or_else(|| {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        hex::encode(bytes)
+    });
+    let endpoint = webhook_repo::create_endpoint(&state.db, payload, &secret).await?;
+    Ok(Json(ApiResponse::success(
+        RegisteredWebhookEndpoint { endpoint, secret },
+        Some("Webhook endpoint registered".to_string()),
+    )))
+}
+
+/// List registered endpoints, active first
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks",
+    tag = "Webhooks",
+    responses(
+        (status = 200, description = "Webhook endpoints", body = ApiResponse<Vec<WebhookEndpoint>>)
+    )
+)]
+pub async fn get_webhooks_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<WebhookEndpoint>>>, AppError> {
+    let endpoints = webhook_repo::get_endpoints(&state.db).await?;
+    Ok(Json(ApiResponse::success(endpoints, None)))
+}
+
+/// Stop sending events to an endpoint; its pending deliveries are abandoned
+#[utoipa::path(
+    put,
+    path = "/api/v1/webhooks/{id}/deactivate",
+    tag = "Webhooks",
+    params(("id" = Uuid, Path, description = "Endpoint UUID")),
+    responses(
+        (status = 200, description = "Endpoint deactivated", body = ApiResponse<WebhookEndpoint>),
+        (status = 404, description = "Endpoint not found or already inactive")
+    )
+)]
+pub async fn deactivate_webhook_handler(
+    State(state): State<AppState>,
+    Path(endpoint_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<WebhookEndpoint>>, AppError> {
+    let endpoint = webhook_repo::deactivate_endpoint(&state.db, endpoint_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(ApiResponse::success(endpoint, Some("Webhook endpoint deactivated".to_string()))))
+}
+
+/// Delivery log for an endpoint, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/{id}/deliveries",
+    tag = "Webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Endpoint UUID"),
+        ("status" = Option<String>, Query, description = "PENDING, DELIVERED or FAILED")
+    ),
+    responses(
+        (status = 200, description = "Deliveries", body = ApiResponse<Vec<WebhookDelivery>>),
+        (status = 400, description = "Invalid status"),
+        (status = 404, description = "Endpoint not found")
+    )
+)]
+pub async fn get_webhook_deliveries_handler(
+    State(state): State<AppState>,
+    Path(endpoint_id): Path<Uuid>,
+    Query(params): Query<WebhookDeliveryQuery>,
+) -> Result<Json<ApiResponse<Vec<WebhookDelivery>>>, AppError> {
+    if let Some(status) = params.status.as_deref() {
+        if !matches!(status, "PENDING" | "DELIVERED" | "FAILED") {
+            return Err(AppError::BadRequest("Status must be PENDING, DELIVERED or FAILED".to_string()));
+        }
+    }
+    webhook_repo::get_endpoint(&state.db, endpoint_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let deliveries = webhook_repo::get_endpoint_deliveries(&state.db, endpoint_id, params.status).await?;
+    Ok(Json(ApiResponse::success(deliveries, None)))
+}
+
+/// Send an event to its endpoint again, now. The original delivery stays in the log; the new one
+/// shares its event id so receivers can deduplicate.
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhook-deliveries/{id}/redeliver",
+    tag = "Webhooks",
+    params(("id" = Uuid, Path, description = "Delivery UUID")),
+    responses(
+        (status = 200, description = "Redelivery attempted; returns the new delivery", body = ApiResponse<WebhookDelivery>),
+        (status = 404, description = "Delivery not found"),
+        (status = 409, description = "Endpoint has been deactivated")
+    )
+)]
+pub async fn redeliver_webhook_handler(
+    State(state): State<AppState>,
+    Path(delivery_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<WebhookDelivery>>, AppError> {
+    webhook_repo::get_delivery(&state.db, delivery_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let new_id = webhook_repo::redeliver(&state.db, delivery_id)
+        .await?
+        .ok_or_else(|| AppError::Conflict("Endpoint has been deactivated".to_string()))?;
+    webhooks::dispatch(&state.db, &state.webhooks, Some(&[new_id])).await?;
+
+    let delivery = webhook_repo::get_delivery(&state.db, new_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let message = format!("Redelivery {}", delivery.status);
+    Ok(Json(ApiResponse::success(delivery, Some(message))))
+}
+
+/// Send due deliveries and retries now instead of waiting for the background job
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks/dispatch",
+    tag = "Webhooks",
+    responses(
+        (status = 200, description = "Dispatch completed", body = ApiResponse<WebhookDispatchSummary>)
+    )
+)]
+pub async fn dispatch_webhooks_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<WebhookDispatchSummary>>, AppError> {
+    let summary = webhooks::dispatch(&state.db, &state.webhooks, None).await?;
+    Ok(Json(ApiResponse::success(summary, None)))
+}
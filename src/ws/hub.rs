@@ -0,0 +1,142 @@
+use std::{collections::VecDeque, sync::{Arc, Mutex}};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+// Events a slow client may fall behind by before it starts missing them
+const CHANNEL_CAPACITY: usize = 1024;
+
+const DEFAULT_REPLAY_CAPACITY: usize = 1000;
+
+/// A relayed domain event as pushed to connected clients.
+#[derive(Debug, Clone, Serialize)]
+pub struct HubEvent {
+    // Outbox sequence number: increases with commit order
+    pub sequence: i64,
+    pub event_id: Uuid,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+/// An event with its position in this hub's stream. Positions count up from 1 in the order
+/// events were published here, which is not always outbox order (a deferred event is relayed
+/// after later ones), so resuming goes by position rather than sequence.
+#[derive(Debug, Clone)]
+pub struct StreamEvent {
+    pub position: u64,
+    pub event: Arc<HubEvent>,
+}
+
+/// Where a resuming client picks up
+#[derive(Debug)]
+pub enum Resume {
+    /// Buffered events after the client's last one (possibly none)
+    Replay(Vec<StreamEvent>),
+    /// The client's last event is not in the buffer: it was issued before a restart or has
+    /// been evicted. The client has to refetch current state.
+    Gap,
+}
+
+struct ReplayBuffer {
+    events: VecDeque<StreamEvent>,
+    capacity: usize,
+    next_position: u64,
+}
+
+impl ReplayBuffer {
+    /// `None` if events after `position` may have been lost
+    fn after(&self, position: u64) -> Option<Vec<StreamEvent>> {
+        if position >= self.next_position {
+            return None;
+        }
+        let oldest = self.events.front().map_or(self.next_position, |e| e.position);
+        if position + 1 < oldest {
+            return None;
+        }
+        Some(self.events.iter().filter(|e| e.position > position).cloned().collect())
+    }
+}
+
+/// In-process fan-out of relayed events to live client connections, keeping the most recent
+/// ones so reconnecting clients can catch up.
+#[derive(Clone)]
+pub struct EventHub {
+    sender: broadcast::Sender<StreamEvent>,
+    replay: Arc<Mutex<ReplayBuffer>>,
+    // Tells this process's event ids apart from those issued before a restart
+    stream_id: Arc<str>,
+}
+
+impl EventHub {
+    pub fn new() -> Self {
+        Self::with_replay_capacity(DEFAULT_REPLAY_CAPACITY)
+    }
+
+    pub fn with_replay_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let replay = ReplayBuffer { events: VecDeque::with_capacity(capacity), capacity, next_position: 1 };
+        let stream_id = Uuid::new_v4().simple().to_string()[..8].into();
+        Self { sender, replay: Arc::new(Mutex::new(replay)), stream_id }
+    }
+
+    pub fn publish(&self, event: HubEvent) {
+        // Buffering and sending under one lock keeps `subscribe_after` free of gaps and repeats
+        let mut replay = self.replay.lock().expect("event replay buffer poisoned");
+        let event = StreamEvent { position: replay.next_position, event: Arc::new(event) };
+        replay.next_position += 1;
+        if replay.capacity > 0 {
+            if replay.events.len() == replay.capacity {
+                replay.events.pop_front();
+            }
+            replay.events.push_back(event.clone());
+        }
+        // No connected clients is not an error
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<StreamEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Subscribes a client that last saw `last_event_id` (as issued by `event_id`), together
+    /// with the buffered events it missed. Without an id the client starts from live events.
+    pub fn subscribe_after(&self, last_event_id: Option<&str>) -> (broadcast::Receiver<StreamEvent>, Resume) {
+        let replay = self.replay.lock().expect("event replay buffer poisoned");
+        let receiver = self.sender.subscribe();
+        let resume = match last_event_id {
+            None => Resume::Replay(Vec::new()),
+            Some(id) => self
+                .parse_event_id(id)
+                .and_then(|position| replay.after(position))
+                .map_or(Resume::Gap, Resume::Replay),
+        };
+        (receiver, resume)
+    }
+
+    /// Buffered events after `position`, or `None` if some of them have been evicted. Lets a
+    /// client that fell behind the live channel catch up without losing events.
+    pub fn replay_after(&self, position: u64) -> Option<Vec<StreamEvent>> {
+        self.replay.lock().expect("event replay buffer poisoned").after(position)
+    }
+
+    /// The id sent with an event, for clients to hand back when resuming
+    pub fn event_id(&self, position: u64) -> String {
+        format!("{}-{}", self.stream_id, position)
+    }
+
+    fn parse_event_id(&self, id: &str) -> Option<u64> {
+        let (stream_id, position) = id.trim().rsplit_once('-')?;
+        (stream_id == &*self.stream_id).then(|| position.parse().ok()).flatten()
+    }
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,225 @@
+use health_intel_backend::setup_app;
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+// Signs in as a new records clerk at the hospital; patient data is only served to staff sessions
+async fn sign_in_at(client: &Client, addr: &str, pool: &PgPool, hospital_id: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Records", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Records",
+            "last_name": "Clerk",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+
+    let email = format!("clerk_{}@health.gov.ng", Uuid::new_v4());
+    let password_hash = bcrypt::hash("staff-password", 4).unwrap();
+    sqlx::query("INSERT INTO staff_accounts (staff_id, email, password_hash) VALUES ($1::uuid, $2, $3)")
+        .bind(staff["data"]["id"].as_str().unwrap())
+        .bind(&email)
+        .bind(&password_hash)
+        .execute(pool).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email, "password": "staff-password" }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"]["token"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn patient_record_carries_allergies_and_problems_and_prescribing_warns() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Allergy Hospital {}", random_id),
+            "hospital_type": "PUBLIC",
+            "state": "Anambra",
+            "city": "Awka"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let session = sign_in_at(&client, &addr, &pool, &hospital_id).await;
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "first_name": "Emeka",
+            "last_name": "Nwosu",
+            "date_of_birth": "1958-01-15",
+            "gender": "MALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    // 1. Allergies: one active entry per substance
+    let allergies_url = format!("{}/api/v1/patients/{}/allergies", addr, patient_id);
+    let resp = client.post(&allergies_url)
+        .json(&json!({ "substance": "Penicillin", "reaction": "Anaphylaxis", "severity": "LIFE_THREATENING" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.post(&allergies_url)
+        .json(&json!({ "substance": "penicillin", "severity": "MILD" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // 2. Problem list: coded problems default to the ICD-10 description
+    let problems_url = format!("{}/api/v1/patients/{}/problems", addr, patient_id);
+    let resp = client.post(&problems_url)
+        .json(&json!({ "code": "I10", "onset_date": "2015-03-01" }))
+        .send().await.unwrap();
+    let hypertension: Value = resp.json().await.unwrap();
+    assert_eq!(hypertension["data"]["description"], "Essential (primary) hypertension");
+    assert_eq!(hypertension["data"]["status"], "ACTIVE");
+
+    let resp = client.post(&problems_url)
+        .json(&json!({ "description": "Community-acquired pneumonia" }))
+        .send().await.unwrap();
+    let pneumonia: Value = resp.json().await.unwrap();
+    let pneumonia_id = pneumonia["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(&problems_url).json(&json!({ "notes": "?" })).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.put(format!("{}/api/v1/problems/{}", addr, pneumonia_id))
+        .json(&json!({ "status": "RESOLVED" }))
+        .send().await.unwrap();
+    let resolved: Value = resp.json().await.unwrap();
+    assert!(resolved["data"]["resolved_date"].is_string());
+
+    // 3. Patient record includes both
+    let resp = client.get(format!("{}/api/v1/patients/{}", addr, patient_id))
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    let record: Value = resp.json().await.unwrap();
+    assert_eq!(record["data"]["patient"]["last_name"], "Nwosu");
+    assert_eq!(record["data"]["allergies"].as_array().unwrap().len(), 1);
+    assert_eq!(record["data"]["problems"].as_array().unwrap().len(), 2);
+    assert_eq!(record["data"]["problems"][0]["status"], "ACTIVE");
+
+    // 4. Prescribing a penicillin warns about the allergy
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medicine", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Adaeze",
+            "last_name": "Okeke",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let doctor_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, doctor_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", random_id),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "patient_id": patient_id,
+            "staff_id": doctor_id,
+            "reason": "Sore throat"
+        }))
+        .send().await.unwrap();
+    let visit: Value = resp.json().await.unwrap();
+    let visit_id = visit["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/visits/{}/prescriptions", addr, visit_id))
+        .json(&json!({
+            "prescriber_id": doctor_id,
+            "drug_name": "Penicillin V",
+            "dose": "500 mg",
+            "route": "ORAL",
+            "frequency": "QDS",
+            "duration_days": 10
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let prescription: Value = resp.json().await.unwrap();
+    let warnings = prescription["data"]["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].as_str().unwrap().contains("Penicillin"));
+
+    // 5. Matching is on whole words: a sulfa allergy does not flag sulfasalazine
+    client.post(&allergies_url)
+        .json(&json!({ "substance": "Sulfa", "severity": "MODERATE" }))
+        .send().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/visits/{}/prescriptions", addr, visit_id))
+        .json(&json!({
+            "prescriber_id": doctor_id,
+            "drug_name": "Sulfasalazine",
+            "dose": "500 mg",
+            "route": "ORAL",
+            "frequency": "BD",
+            "duration_days": 28
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let prescription: Value = resp.json().await.unwrap();
+    assert!(prescription["data"]["warnings"].as_array().unwrap().is_empty());
+
+    // 6. Ordering a test the patient is allergic to warns as well
+    client.post(&allergies_url)
+        .json(&json!({ "substance": "Tuberculin", "reaction": "Blistering", "severity": "SEVERE" }))
+        .send().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/lab-tests", addr))
+        .json(&json!({
+            "code": format!("TST-{}", &random_id.to_string()[..8]),
+            "name": "Tuberculin skin test",
+            "specimen_type": "OTHER",
+            "result_type": "NUMERIC",
+            "unit": "mm"
+        }))
+        .send().await.unwrap();
+    let test: Value = resp.json().await.unwrap();
+    let test_id = test["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/visits/{}/lab-orders", addr, visit_id))
+        .json(&json!({ "test_type_id": test_id, "ordered_by": doctor_id }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let order: Value = resp.json().await.unwrap();
+    assert_eq!(order["data"]["order"]["status"], "ORDERED");
+    let warnings = order["data"]["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].as_str().unwrap().contains("Tuberculin"));
+}
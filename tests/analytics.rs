@@ -0,0 +1,138 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+fn hospital_body(name: &str, state: &str, hospital_type: &str, occupied: i32, has_oxygen: bool) -> Value {
+    json!({
+        "name": name,
+        "hospital_type": hospital_type,
+        "state": state,
+        "city": "Central",
+        "total_beds": 10,
+        "occupied_beds": occupied,
+        "has_emergency": true,
+        "has_oxygen": has_oxygen
+    })
+}
+
+async fn create_hospital(client: &Client, addr: &str, body: Value) -> String {
+    let resp = client.post(format!("{}/api/v1/hospitals", addr)).json(&body).send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    hospital["data"]["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn occupancy_analytics_are_time_weighted_and_grouped() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+    let state = format!("Analytics {}", random_id);
+
+    let full_name = format!("Full {}", random_id);
+    let full = create_hospital(&client, &addr, hospital_body(&full_name, &state, "PUBLIC", 9, true)).await;
+    create_hospital(&client, &addr, hospital_body(&format!("Half {}", random_id), &state, "PRIVATE", 5, false)).await;
+
+    let resp = client.get(format!("{}/api/v1/analytics/occupancy", addr))
+        .query(&[("state", state.as_str()), ("group_by", "hospital_type")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let groups: Value = resp.json().await.unwrap();
+    let groups = groups["data"].as_array().unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0]["group"], "PUBLIC");
+    assert!((groups[0]["occupancy_rate"].as_f64().unwrap() - 0.9).abs() < 1e-6);
+    assert!((groups[1]["occupancy_rate"].as_f64().unwrap() - 0.5).abs() < 1e-6);
+
+    let resp = client.get(format!("{}/api/v1/analytics/occupancy", addr))
+        .query(&[("state", state.as_str())])
+        .send().await.unwrap();
+    let groups: Value = resp.json().await.unwrap();
+    assert_eq!(groups["data"][0]["hospitals"], 2);
+    assert!((groups["data"][0]["occupancy_rate"].as_f64().unwrap() - 0.7).abs() < 1e-6);
+
+    // The full hospital frees beds; from here on it is only 20% occupied
+    let resp = client.put(format!("{}/api/v1/hospitals/{}", addr, full))
+        .json(&hospital_body(&full_name, &state, "PUBLIC", 2, true))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    let after_update = chrono::Utc::now().to_rfc3339();
+
+    // Over the whole range it still peaked at 90%
+    let resp = client.get(format!("{}/api/v1/analytics/occupancy/high", addr))
+        .query(&[("state", state.as_str()), ("threshold", "0.85")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let summary: Value = resp.json().await.unwrap();
+    assert_eq!(summary["data"]["hospitals_with_beds"], 2);
+    assert_eq!(summary["data"]["peak_at_or_above"], 1);
+    assert_eq!(summary["data"]["hospitals"][0]["name"], full_name.as_str());
+
+    // ...but not since the update
+    let resp = client.get(format!("{}/api/v1/analytics/occupancy/high", addr))
+        .query(&[("state", state.as_str()), ("threshold", "0.85"), ("from", after_update.as_str())])
+        .send().await.unwrap();
+    let summary: Value = resp.json().await.unwrap();
+    assert_eq!(summary["data"]["peak_at_or_above"], 0);
+    assert_eq!(summary["data"]["average_at_or_above"], 0);
+
+    let resp = client.get(format!("{}/api/v1/analytics/occupancy", addr))
+        .query(&[("state", state.as_str()), ("group_by", "ward")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.get(format!("{}/api/v1/analytics/occupancy/high", addr))
+        .query(&[("threshold", "1.5")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn coverage_and_daily_visits_are_aggregated_per_state_and_day() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+    let state = format!("Coverage {}", random_id);
+
+    create_hospital(&client, &addr, hospital_body(&format!("Oxygen {}", random_id), &state, "PUBLIC", 1, true)).await;
+    create_hospital(&client, &addr, hospital_body(&format!("No Oxygen {}", random_id), &state, "PUBLIC", 1, false)).await;
+
+    let resp = client.get(format!("{}/api/v1/analytics/coverage", addr))
+        .query(&[("state", state.as_str())])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let coverage: Value = resp.json().await.unwrap();
+    let row = &coverage["data"][0];
+    assert_eq!(row["hospitals"], 2);
+    assert_eq!(row["with_emergency"], 2);
+    assert_eq!(row["with_oxygen"], 1);
+    assert_eq!(row["oxygen_coverage"], 0.5);
+    assert_eq!(row["ventilator_coverage"], 0.0);
+
+    // Seven whole days, one row each even with no visits
+    let resp = client.get(format!("{}/api/v1/analytics/visits/daily", addr))
+        .query(&[("state", state.as_str()), ("from", "2024-03-01T00:00:00Z"), ("to", "2024-03-08T00:00:00Z")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let days: Value = resp.json().await.unwrap();
+    let days = days["data"].as_array().unwrap();
+    assert_eq!(days.len(), 7);
+    assert_eq!(days[0]["date"], "2024-03-01");
+    assert!(days.iter().all(|d| d["visits"] == 0));
+
+    let resp = client.get(format!("{}/api/v1/analytics/visits/daily", addr))
+        .query(&[("from", "2024-03-08T00:00:00Z"), ("to", "2024-03-01T00:00:00Z")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+}
@@ -0,0 +1,286 @@
+use health_intel_backend::setup_app;
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+// Signs in as a new records clerk at the hospital; patient data is only served to staff sessions
+async fn sign_in_at(client: &Client, addr: &str, pool: &PgPool, hospital_id: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Records", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Records",
+            "last_name": "Clerk",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+
+    let email = format!("clerk_{}@health.gov.ng", Uuid::new_v4());
+    let password_hash = bcrypt::hash("staff-password", 4).unwrap();
+    sqlx::query("INSERT INTO staff_accounts (staff_id, email, password_hash) VALUES ($1::uuid, $2, $3)")
+        .bind(staff["data"]["id"].as_str().unwrap())
+        .bind(&email)
+        .bind(&password_hash)
+        .execute(pool).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email, "password": "staff-password" }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"]["token"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn slot_can_only_be_booked_once_and_can_be_rescheduled() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    // 1. Hospital, department, licensed doctor and a patient
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Clinic {}", random_id),
+            "hospital_type": "PRIVATE",
+            "state": "Enugu",
+            "city": "Enugu"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let session = sign_in_at(&client, &addr, &pool, &hospital_id).await;
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Outpatients", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Chidi",
+            "last_name": "Eze",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", random_id),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "first_name": "Ngozi",
+            "last_name": "Obi",
+            "date_of_birth": "1988-03-14",
+            "gender": "FEMALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    // 2. Two back-to-back 30 minute slots tomorrow
+    let start = Utc::now() + Duration::days(1);
+    let resp = client.post(format!("{}/api/v1/slots", addr))
+        .json(&json!({ "staff_id": staff_id, "start_time": start, "duration_minutes": 30, "count": 2 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let slots: Value = resp.json().await.unwrap();
+    let first_slot = slots["data"][0]["id"].as_str().unwrap().to_string();
+    let second_slot = slots["data"][1]["id"].as_str().unwrap().to_string();
+
+    // Overlapping publication is rejected
+    let resp = client.post(format!("{}/api/v1/slots", addr))
+        .json(&json!({ "staff_id": staff_id, "start_time": start + Duration::minutes(15), "duration_minutes": 30 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // 3. Two concurrent bookings of the same slot: exactly one wins
+    let book = |c: Client| {
+        let url = format!("{}/api/v1/slots/{}/book", addr, first_slot);
+        let body = json!({ "patient_id": patient_id, "reason": "Follow-up consultation" });
+        async move { c.post(url).json(&body).send().await.unwrap().status().as_u16() }
+    };
+    let (a, b) = tokio::join!(book(client.clone()), book(client.clone()));
+    let mut statuses = [a, b];
+    statuses.sort();
+    assert_eq!(statuses, [200, 409]);
+
+    let resp = client.get(format!("{}/api/v1/slots/available?staff_id={}", addr, staff_id))
+        .send().await.unwrap();
+    let available: Value = resp.json().await.unwrap();
+    assert_eq!(available["data"].as_array().unwrap().len(), 1);
+    assert_eq!(available["data"][0]["id"], second_slot.as_str());
+    let visit_id = {
+        let resp = client.get(format!("{}/api/v1/hospitals/{}/visits", addr, hospital_id))
+            .bearer_auth(&session)
+            .send().await.unwrap();
+        let visits: Value = resp.json().await.unwrap();
+        visits["data"][0]["id"].as_str().unwrap().to_string()
+    };
+
+    // 4. Reschedule onto the second slot frees the first
+    let resp = client.post(format!("{}/api/v1/appointments/{}/reschedule", addr, visit_id))
+        .json(&json!({ "new_slot_id": second_slot, "reason": "Patient requested later time" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.get(format!("{}/api/v1/slots/available?staff_id={}", addr, staff_id))
+        .send().await.unwrap();
+    let available: Value = resp.json().await.unwrap();
+    assert_eq!(available["data"][0]["id"], first_slot.as_str());
+
+    // A visit created directly cannot double-book the clinician's booked slot
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "patient_id": patient_id,
+            "staff_id": staff_id,
+            "reason": "Walk-in review",
+            "start_time": start + Duration::minutes(40)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // 5. Cancel, then the history shows every step
+    let resp = client.post(format!("{}/api/v1/appointments/{}/cancel", addr, visit_id))
+        .json(&json!({ "reason": "Travelling" }))
+        .send().await.unwrap();
+    let cancelled: Value = resp.json().await.unwrap();
+    assert_eq!(cancelled["data"]["status"], "CANCELLED");
+
+    let history_url = format!("{}/api/v1/appointments/{}/history", addr, visit_id);
+    let resp = client.get(&history_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+
+    let resp = client.get(&history_url).bearer_auth(&session).send().await.unwrap();
+    let history: Value = resp.json().await.unwrap();
+    let types: Vec<&str> = history["data"].as_array().unwrap().iter()
+        .map(|c| c["change_type"].as_str().unwrap())
+        .collect();
+    assert_eq!(types, ["BOOKED", "RESCHEDULED", "CANCELLED"]);
+
+    let resp = client.post(format!("{}/api/v1/appointments/{}/reschedule", addr, visit_id))
+        .json(&json!({ "new_slot_id": first_slot, "reason": "Changed my mind" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // 6. A visit created directly inside an open slot books it
+    let direct = json!({
+        "hospital_id": hospital_id,
+        "patient_id": patient_id,
+        "staff_id": staff_id,
+        "reason": "Direct booking",
+        "start_time": start + Duration::minutes(10)
+    });
+    let resp = client.post(format!("{}/api/v1/visits", addr)).json(&direct).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let visit: Value = resp.json().await.unwrap();
+    let direct_visit = visit["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.get(format!("{}/api/v1/slots/available?staff_id={}", addr, staff_id))
+        .send().await.unwrap();
+    let available: Value = resp.json().await.unwrap();
+    assert!(available["data"].as_array().unwrap().iter().all(|s| s["id"] != first_slot.as_str()));
+    let resp = client.post(format!("{}/api/v1/visits", addr)).json(&direct).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // 7. Appointments cannot move to a slot at another hospital
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({ "name": format!("Other Clinic {}", random_id), "hospital_type": "PRIVATE", "state": "Enugu", "city": "Nsukka" }))
+        .send().await.unwrap();
+    let other: Value = resp.json().await.unwrap();
+    let other_id = other["data"]["id"].as_str().unwrap().to_string();
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": other_id, "name": "Outpatients", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let other_dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": other_id,
+            "department_id": other_dept["data"]["id"],
+            "first_name": "Ada",
+            "last_name": "Nwosu",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let other_staff: Value = resp.json().await.unwrap();
+    let other_staff_id = other_staff["data"]["id"].as_str().unwrap().to_string();
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, other_staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", Uuid::new_v4()),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/slots", addr))
+        .json(&json!({ "staff_id": other_staff_id, "start_time": start, "duration_minutes": 30 }))
+        .send().await.unwrap();
+    let other_slots: Value = resp.json().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/appointments/{}/reschedule", addr, direct_visit))
+        .json(&json!({ "new_slot_id": other_slots["data"][0]["id"], "reason": "Closer to home" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+    // 8. Slots cannot be published into another hospital's department
+    let resp = client.post(format!("{}/api/v1/slots", addr))
+        .json(&json!({
+            "staff_id": staff_id,
+            "department_id": other_dept["data"]["id"],
+            "start_time": start + Duration::hours(6),
+            "duration_minutes": 30
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // 9. Visits outside any slot take the default half hour and must not overlap
+    let walk_in = |minutes: i64| json!({
+        "hospital_id": hospital_id,
+        "patient_id": patient_id,
+        "staff_id": staff_id,
+        "reason": "Walk-in",
+        "start_time": start + Duration::minutes(minutes)
+    });
+    let resp = client.post(format!("{}/api/v1/visits", addr)).json(&walk_in(180)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let resp = client.post(format!("{}/api/v1/visits", addr)).json(&walk_in(185)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+    let resp = client.post(format!("{}/api/v1/visits", addr)).json(&walk_in(210)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // ...nor run into one of the clinician's slots
+    let resp = client.post(format!("{}/api/v1/visits", addr)).json(&walk_in(-10)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // ...and new slots must not cover them either
+    let resp = client.post(format!("{}/api/v1/slots", addr))
+        .json(&json!({ "staff_id": staff_id, "start_time": start + Duration::minutes(225), "duration_minutes": 30 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+}
@@ -1,7 +1,7 @@
 use health_intel_backend::setup_app;
 use tokio::net::TcpListener;
 use reqwest::Client;
-use serde_json::json;
+use serde_json::{json, Value};
 use sqlx::PgPool;
 use uuid::Uuid;
 use bcrypt::{hash, DEFAULT_COST};
@@ -95,4 +95,122 @@ async fn login_returns_401_for_invalid_password() {
 
     // 3. Assert
     assert_eq!(response.status().as_u16(), 401);
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn staff_accounts_are_set_by_admins_and_password_changes_end_sessions() {
+    // 1. Arrange: an admin and a staff member without an account
+    let (app_address, pool) = spawn_app().await;
+    let client = Client::new();
+
+    let admin_email = format!("test_admin_{}@health.gov.ng", Uuid::new_v4());
+    let admin_hash = hash("admin_password", 4).unwrap();
+    sqlx::query!(
+        "INSERT INTO admins (email, password_hash) VALUES ($1, $2)",
+        admin_email,
+        admin_hash
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to create test user");
+    let response = client
+        .post(format!("{}/api/v1/login", app_address))
+        .json(&json!({ "email": admin_email, "password": "admin_password" }))
+        .send().await.unwrap();
+    let json: Value = response.json().await.unwrap();
+    let admin_token = json["data"]["token"].as_str().unwrap().to_string();
+
+    let response = client
+        .post(format!("{}/api/v1/hospitals", app_address))
+        .json(&json!({
+            "name": format!("Account Hospital {}", Uuid::new_v4()),
+            "hospital_type": "PUBLIC",
+            "state": "Oyo",
+            "city": "Ibadan"
+        }))
+        .send().await.unwrap();
+    let json: Value = response.json().await.unwrap();
+    let hospital_id = json["data"]["id"].as_str().unwrap().to_string();
+    let response = client
+        .post(format!("{}/api/v1/departments", app_address))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Records", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let json: Value = response.json().await.unwrap();
+    let department_id = json["data"]["id"].as_str().unwrap().to_string();
+    let response = client
+        .post(format!("{}/api/v1/staff", app_address))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": department_id,
+            "first_name": "Records",
+            "last_name": "Clerk",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let json: Value = response.json().await.unwrap();
+    let staff_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    let account_url = format!("{}/api/v1/staff/{}/account", app_address, staff_id);
+    let staff_email = format!("test_staff_{}@health.gov.ng", Uuid::new_v4());
+    let account = json!({ "email": staff_email, "password": "first_password" });
+
+    // 2. Only an admin session may set the account
+    let response = client.put(&account_url).json(&account).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+
+    let response = client
+        .put(format!("{}/api/v1/staff/{}/account", app_address, Uuid::new_v4()))
+        .bearer_auth(&admin_token)
+        .json(&account)
+        .send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+
+    let response = client
+        .put(&account_url)
+        .bearer_auth(&admin_token)
+        .json(&json!({ "email": staff_email, "password": "short" }))
+        .send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = client.put(&account_url).bearer_auth(&admin_token).json(&account).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["email"], staff_email);
+    assert!(json["data"].get("password_hash").is_none());
+
+    // 3. The staff member signs in; their token opens patient routes, the admin token does not
+    let response = client
+        .post(format!("{}/api/v1/staff/login", app_address))
+        .json(&account)
+        .send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["data"]["staff"]["id"], staff_id.as_str());
+    let staff_token = json["data"]["token"].as_str().unwrap().to_string();
+
+    let patients_url = format!("{}/api/v1/patients", app_address);
+    let response = client.get(&patients_url).bearer_auth(&staff_token).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    let response = client.get(&patients_url).bearer_auth(&admin_token).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+
+    // A staff token is no admin session either
+    let response = client.put(&account_url).bearer_auth(&staff_token).json(&account).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+
+    // 4. A new password ends the sessions issued before it
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    let response = client
+        .put(&account_url)
+        .bearer_auth(&admin_token)
+        .json(&json!({ "email": staff_email, "password": "second_password" }))
+        .send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = client.get(&patients_url).bearer_auth(&staff_token).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+    let response = client
+        .post(format!("{}/api/v1/staff/login", app_address))
+        .json(&account)
+        .send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+}
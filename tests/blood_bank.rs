@@ -0,0 +1,114 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+async fn create_hospital(client: &Client, addr: &str, lat: f64, lng: f64) -> String {
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Blood Bank Hospital {}", Uuid::new_v4()),
+            "hospital_type": "PUBLIC",
+            "state": "Lagos",
+            "city": "Ikeja",
+            "latitude": lat,
+            "longitude": lng
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    hospital["data"]["id"].as_str().unwrap().to_string()
+}
+
+async fn report(client: &Client, addr: &str, hospital_id: &str, group: &str, units: i32, expires_in_days: i64) -> String {
+    let resp = client.post(format!("{}/api/v1/hospitals/{}/blood-units", addr, hospital_id))
+        .json(&json!({
+            "blood_group": group,
+            "component": "WHOLE_BLOOD",
+            "units": units,
+            "expires_on": Utc::now().date_naive() + Duration::days(expires_in_days)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let batch: Value = resp.json().await.unwrap();
+    batch["data"]["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn search_returns_nearest_hospitals_with_unexpired_units() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+
+    // Random origin so earlier runs don't crowd the results
+    let seed = Uuid::new_v4().as_u128();
+    let lat = -60.0 + (seed % 120) as f64;
+    let lng = -170.0 + ((seed >> 8) % 340) as f64;
+
+    let expired_only = create_hospital(&client, &addr, lat + 0.01, lng).await;
+    let near = create_hospital(&client, &addr, lat + 0.05, lng).await;
+    let far = create_hospital(&client, &addr, lat + 0.5, lng).await;
+
+    report(&client, &addr, &expired_only, "O-", 5, -1).await;
+    report(&client, &addr, &near, "O-", 2, 10).await;
+    report(&client, &addr, &near, "A+", 9, 10).await;
+    report(&client, &addr, &far, "O-", 4, 20).await;
+
+    let resp = client.get(format!("{}/api/v1/blood-bank/search", addr))
+        .query(&[("blood_group", "O-"), ("lat", &lat.to_string()), ("lng", &lng.to_string()), ("limit", "50")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let results: Value = resp.json().await.unwrap();
+
+    let ours: Vec<&Value> = results["data"].as_array().unwrap().iter()
+        .filter(|r| [&expired_only, &near, &far].iter().any(|id| r["hospital_id"] == id.as_str()))
+        .collect();
+    assert_eq!(ours.len(), 2);
+    assert_eq!(ours[0]["hospital_id"], near.as_str());
+    assert_eq!(ours[0]["units_available"], 2);
+    assert_eq!(ours[1]["hospital_id"], far.as_str());
+    assert!(ours[0]["distance_km"].as_f64().unwrap() < ours[1]["distance_km"].as_f64().unwrap());
+
+    // Asking for three units skips the nearer hospital
+    let resp = client.get(format!("{}/api/v1/blood-bank/search", addr))
+        .query(&[("blood_group", "O-"), ("lat", &lat.to_string()), ("lng", &lng.to_string()), ("min_units", "3"), ("limit", "50")])
+        .send().await.unwrap();
+    let results: Value = resp.json().await.unwrap();
+    assert!(results["data"].as_array().unwrap().iter().all(|r| r["hospital_id"] != near.as_str()));
+
+    // Invalid group is rejected
+    let resp = client.get(format!("{}/api/v1/blood-bank/search?blood_group=Z&lat=0&lng=0", addr))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn units_are_issued_only_from_known_unexpired_batches() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let hospital_id = create_hospital(&client, &addr, 6.6, 3.3).await;
+    let fresh = report(&client, &addr, &hospital_id, "B+", 3, 5).await;
+    let expired = report(&client, &addr, &hospital_id, "B+", 3, -1).await;
+    let issue = |batch: String, units: i32| {
+        let c = client.clone();
+        let url = format!("{}/api/v1/blood-units/{}/issue", addr, batch);
+        async move { c.post(url).json(&json!({ "units": units })).send().await.unwrap() }
+    };
+
+    let resp = issue(fresh.clone(), 2).await;
+    assert_eq!(resp.status().as_u16(), 200);
+    let batch: Value = resp.json().await.unwrap();
+    assert_eq!(batch["data"]["units"], 1);
+
+    assert_eq!(issue(fresh, 2).await.status().as_u16(), 409);
+    assert_eq!(issue(expired, 1).await.status().as_u16(), 409);
+    assert_eq!(issue(Uuid::new_v4().to_string(), 1).await.status().as_u16(), 404);
+}
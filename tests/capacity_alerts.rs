@@ -0,0 +1,242 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+fn hospital_body(name: &str, state: &str, occupied_beds: i32) -> Value {
+    json!({
+        "name": name,
+        "hospital_type": "PUBLIC",
+        "state": state,
+        "city": "Capital",
+        "total_beds": 10,
+        "occupied_beds": occupied_beds
+    })
+}
+
+async fn open_alerts(client: &Client, addr: &str, hospital_id: &str) -> Vec<Value> {
+    let resp = client.get(format!("{}/api/v1/capacity-alerts", addr))
+        .query(&[("hospital_id", hospital_id)])
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"].as_array().unwrap().clone()
+}
+
+// Alerts are evaluated by the outbox relay after the response, so give it a moment
+async fn wait_for_open_alerts(client: &Client, addr: &str, hospital_id: &str, count: usize) -> Vec<Value> {
+    let mut alerts = Vec::new();
+    for _ in 0..50 {
+        alerts = open_alerts(client, addr, hospital_id).await;
+        if alerts.len() == count {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    alerts
+}
+
+#[tokio::test]
+async fn occupancy_alerts_are_raised_delivered_acknowledged_and_resolved() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    // A state of its own keeps other tests' hospitals out of scope
+    let state = format!("Surge {}", Uuid::new_v4());
+    let name = format!("Surge Hospital {}", Uuid::new_v4());
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&hospital_body(&name, &state, 2))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    // Thresholds are validated per metric
+    let resp = client.post(format!("{}/api/v1/alert-rules", addr))
+        .json(&json!({ "name": "No threshold", "state": state, "metric": "OCCUPANCY_ABOVE" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(format!("{}/api/v1/alert-rules", addr))
+        .json(&json!({ "name": "Beds nearly full", "state": state, "metric": "OCCUPANCY_ABOVE", "threshold": 90.0, "severity": "CRITICAL" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // Partner systems register a webhook endpoint rather than an alert subscription
+    let resp = client.post(format!("{}/api/v1/alert-subscriptions", addr))
+        .json(&json!({ "state": state, "channel": "WEBHOOK", "target": "https://partner.example/alerts" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(format!("{}/api/v1/alert-subscriptions", addr))
+        .json(&json!({ "state": state, "channel": "LOG", "target": "state-eoc", "min_severity": "WARNING" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // 1. Below the threshold: nothing raised
+    assert!(open_alerts(&client, &addr, &hospital_id).await.is_empty());
+
+    // 2. Filling the beds raises a single alert, and a repeat update does not duplicate it
+    for occupied in [9, 10] {
+        let resp = client.put(format!("{}/api/v1/hospitals/{}", addr, hospital_id))
+            .json(&hospital_body(&name, &state, occupied))
+            .send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+    }
+    let alerts = wait_for_open_alerts(&client, &addr, &hospital_id, 1).await;
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["status"], "ACTIVE");
+    assert_eq!(alerts[0]["severity"], "CRITICAL");
+    assert_eq!(alerts[0]["observed_value"].as_f64().unwrap(), 100.0);
+    let alert_id = alerts[0]["id"].as_str().unwrap().to_string();
+
+    // 3. The subscriber receives it (delivery runs in the background)
+    let mut deliveries = Vec::new();
+    for _ in 0..50 {
+        let resp = client.get(format!("{}/api/v1/capacity-alerts/{}/deliveries", addr, alert_id))
+            .send().await.unwrap();
+        let body: Value = resp.json().await.unwrap();
+        deliveries = body["data"].as_array().unwrap().clone();
+        if !deliveries.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0]["channel"], "LOG");
+    assert_eq!(deliveries[0]["status"], "DELIVERED");
+
+    // 4. Acknowledge, then resolve
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Administration", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Chidi",
+            "last_name": "Obi",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.put(format!("{}/api/v1/capacity-alerts/{}/acknowledge", addr, alert_id))
+        .json(&json!({ "staff_id": Uuid::new_v4() }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.put(format!("{}/api/v1/capacity-alerts/{}/acknowledge", addr, alert_id))
+        .json(&json!({ "staff_id": staff_id }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"]["status"], "ACKNOWLEDGED");
+    assert_eq!(body["data"]["acknowledged_by"], staff_id.as_str());
+
+    let resp = client.put(format!("{}/api/v1/capacity-alerts/{}/resolve", addr, alert_id))
+        .json(&json!({ "staff_id": staff_id, "note": "Overflow ward opened" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"]["status"], "RESOLVED");
+    assert_eq!(body["data"]["resolution_note"], "Overflow ward opened");
+
+    let resp = client.put(format!("{}/api/v1/capacity-alerts/{}/resolve", addr, alert_id))
+        .json(&json!({ "staff_id": staff_id }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // 5. Still full: the next change raises a fresh alert, which clears once beds free up
+    let resp = client.put(format!("{}/api/v1/hospitals/{}", addr, hospital_id))
+        .json(&hospital_body(&name, &state, 10))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let alerts = wait_for_open_alerts(&client, &addr, &hospital_id, 1).await;
+    assert_eq!(alerts.len(), 1);
+    assert_ne!(alerts[0]["id"], alert_id.as_str());
+    let second_id = alerts[0]["id"].as_str().unwrap().to_string();
+
+    client.put(format!("{}/api/v1/hospitals/{}", addr, hospital_id))
+        .json(&hospital_body(&name, &state, 4))
+        .send().await.unwrap();
+    assert!(wait_for_open_alerts(&client, &addr, &hospital_id, 0).await.is_empty());
+
+    let resp = client.get(format!("{}/api/v1/capacity-alerts", addr))
+        .query(&[("hospital_id", hospital_id.as_str()), ("status", "RESOLVED")])
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    let cleared = body["data"].as_array().unwrap().iter().find(|a| a["id"] == second_id.as_str()).unwrap();
+    assert_eq!(cleared["resolution_note"], "Condition cleared");
+    assert!(cleared["resolved_by"].is_null());
+}
+
+#[tokio::test]
+async fn broken_equipment_rule_tracks_condition_changes() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let name = format!("Equipment Alert Hospital {}", Uuid::new_v4());
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&hospital_body(&name, "Kwara", 1))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/alert-rules", addr))
+        .json(&json!({ "name": "Broken kit", "hospital_id": hospital_id, "metric": "BROKEN_EQUIPMENT_ABOVE", "threshold": 1 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let rule: Value = resp.json().await.unwrap();
+    assert_eq!(rule["data"]["severity"], "WARNING");
+    let rule_id = rule["data"]["id"].as_str().unwrap().to_string();
+
+    let mut equipment_ids = Vec::new();
+    for serial in ["BRK-1", "BRK-2"] {
+        let resp = client.post(format!("{}/api/v1/equipment", addr))
+            .json(&json!({
+                "hospital_id": hospital_id,
+                "name": "Oxygen concentrator",
+                "serial_number": format!("{}-{}", serial, Uuid::new_v4()),
+                "condition": "GOOD",
+                "is_operational": true
+            }))
+            .send().await.unwrap();
+        let equipment: Value = resp.json().await.unwrap();
+        equipment_ids.push(equipment["data"]["id"].as_str().unwrap().to_string());
+    }
+
+    // One broken item is not above the threshold; two are
+    for (i, equipment_id) in equipment_ids.iter().enumerate() {
+        client.put(format!("{}/api/v1/equipment/{}/condition", addr, equipment_id))
+            .json(&json!({ "condition": "BROKEN", "is_operational": false }))
+            .send().await.unwrap();
+        assert_eq!(wait_for_open_alerts(&client, &addr, &hospital_id, i).await.len(), i);
+    }
+    let alerts = open_alerts(&client, &addr, &hospital_id).await;
+    assert_eq!(alerts[0]["metric"], "BROKEN_EQUIPMENT_ABOVE");
+    assert_eq!(alerts[0]["observed_value"].as_f64().unwrap(), 2.0);
+
+    // Deactivating the rule clears its open alert on the next evaluation
+    let resp = client.put(format!("{}/api/v1/alert-rules/{}/deactivate", addr, rule_id))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let resp = client.post(format!("{}/api/v1/alert-rules/evaluate", addr))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert!(open_alerts(&client, &addr, &hospital_id).await.is_empty());
+
+    let resp = client.put(format!("{}/api/v1/alert-rules/{}/deactivate", addr, rule_id))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
@@ -68,6 +68,17 @@ async fn full_clinical_workflow_success() {
     let staff: Value = resp.json().await.unwrap();
     let staff_id = staff["data"]["id"].as_str().expect("Staff ID not found");
 
+    // 3b. Register the doctor's licence (required before assigning visits)
+    let resp = client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", random_id),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
     // 4. Create Patient
     let resp = client.post(format!("{}/api/v1/patients", addr))
         .json(&json!({
@@ -0,0 +1,291 @@
+use health_intel_backend::setup_app;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+// Gives a staff member a sign-in and returns a session token for them
+async fn sign_in(client: &Client, addr: &str, pool: &PgPool, staff_id: &str) -> String {
+    let email = format!("staff_{}@health.gov.ng", Uuid::new_v4());
+    let password_hash = bcrypt::hash("staff-password", 4).unwrap();
+    sqlx::query("INSERT INTO staff_accounts (staff_id, email, password_hash) VALUES ($1::uuid, $2, $3)")
+        .bind(staff_id)
+        .bind(&email)
+        .bind(&password_hash)
+        .execute(pool).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email, "password": "staff-password" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    body["data"]["token"].as_str().unwrap().to_string()
+}
+
+// Creates a hospital with one licensed doctor and returns (hospital_id, staff_id)
+async fn create_hospital_with_doctor(client: &Client, addr: &str, name: &str) -> (String, String) {
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": name,
+            "hospital_type": "PUBLIC",
+            "state": "Oyo",
+            "city": "Ibadan"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Emergency", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Tunde",
+            "last_name": "Adeyemi",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", Uuid::new_v4()),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    (hospital_id, staff_id)
+}
+
+#[tokio::test]
+async fn cross_hospital_reads_need_consent_or_break_glass_and_are_logged() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let (home, home_doctor) = create_hospital_with_doctor(&client, &addr, &format!("Consent Home {}", random_id)).await;
+    let (other, other_doctor) = create_hospital_with_doctor(&client, &addr, &format!("Consent Other {}", random_id)).await;
+    let (_, research_doctor) = create_hospital_with_doctor(&client, &addr, &format!("Consent Research {}", random_id)).await;
+    let home_session = sign_in(&client, &addr, &pool, &home_doctor).await;
+    let other_session = sign_in(&client, &addr, &pool, &other_doctor).await;
+    let research_session = sign_in(&client, &addr, &pool, &research_doctor).await;
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "hospital_id": home,
+            "first_name": "Bisi",
+            "last_name": "Ogunleye",
+            "date_of_birth": "1990-09-09",
+            "gender": "FEMALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": home,
+            "patient_id": patient_id,
+            "staff_id": home_doctor,
+            "reason": "Asthma review"
+        }))
+        .send().await.unwrap();
+    let visit: Value = resp.json().await.unwrap();
+    let visit_id = visit["data"]["id"].as_str().unwrap().to_string();
+
+    let record_url = format!("{}/api/v1/patients/{}", addr, patient_id);
+    let diagnoses_url = format!("{}/api/v1/visits/{}/diagnoses", addr, visit_id);
+
+    // 1. The caller must be signed in; naming a hospital is not enough
+    let resp = client.get(&record_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+    let resp = client.get(&record_url).header("X-Hospital-Id", &home).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+
+    // 2. The home hospital reads freely; another hospital is refused
+    let resp = client.get(&record_url).bearer_auth(&home_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.get(&record_url).bearer_auth(&other_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+    let resp = client.get(&diagnoses_url).bearer_auth(&other_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+
+    let resp = client.get(format!("{}/api/v1/patients", addr)).bearer_auth(&other_session).send().await.unwrap();
+    let listed: Value = resp.json().await.unwrap();
+    assert!(listed["data"].as_array().unwrap().iter().all(|p| p["id"] != patient_id.as_str()));
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/visits", addr, home))
+        .bearer_auth(&other_session)
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+
+    // Claiming the home hospital or its doctor in headers changes nothing
+    let resp = client.get(&record_url)
+        .bearer_auth(&other_session)
+        .header("X-Hospital-Id", &home)
+        .header("X-Staff-Id", &home_doctor)
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+
+    // 3. Break-glass is logged against the signed-in staff member
+    let resp = client.get(&record_url)
+        .bearer_auth(&other_session)
+        .header("X-Break-Glass-Reason", "Unconscious in ED")
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // 4. Consent is recorded by staff at a hospital caring for the patient, not by the grantee
+    let consents_url = format!("{}/api/v1/patients/{}/consents", addr, patient_id);
+    let resp = client.post(&consents_url)
+        .bearer_auth(&other_session)
+        .json(&json!({ "hospital_id": other }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+
+    // 5. Consent for a hospital opens its reads until revoked
+    let resp = client.post(&consents_url)
+        .bearer_auth(&home_session)
+        .json(&json!({ "notes": "verbal" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(&consents_url)
+        .bearer_auth(&home_session)
+        .json(&json!({ "hospital_id": other }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let consent: Value = resp.json().await.unwrap();
+    let consent_id = consent["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(consent["data"]["recorded_by"], home_doctor.as_str());
+
+    let resp = client.get(&diagnoses_url).bearer_auth(&other_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let revoke_url = format!("{}/api/v1/consents/{}/revoke", addr, consent_id);
+    let resp = client.put(&revoke_url)
+        .bearer_auth(&research_session)
+        .json(&json!({ "reason": "Patient request" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+    let resp = client.put(&revoke_url)
+        .bearer_auth(&home_session)
+        .json(&json!({ "reason": "Patient request" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let resp = client.put(&revoke_url).bearer_auth(&home_session).json(&json!({})).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    let resp = client.get(&diagnoses_url).bearer_auth(&other_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+
+    // 6. Consent for a purpose applies to any hospital reading for that purpose
+    client.post(&consents_url)
+        .bearer_auth(&home_session)
+        .json(&json!({ "purpose": "RESEARCH" }))
+        .send().await.unwrap();
+
+    let resp = client.get(&record_url)
+        .bearer_auth(&research_session)
+        .header("X-Access-Purpose", "research")
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.get(&record_url).bearer_auth(&research_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+
+    let resp = client.get(&consents_url)
+        .bearer_auth(&home_session)
+        .query(&[("include_revoked", "true")])
+        .send().await.unwrap();
+    let consents: Value = resp.json().await.unwrap();
+    assert_eq!(consents["data"].as_array().unwrap().len(), 2);
+
+    // 7. Every attempt is in the patient's access report, which is itself a patient read
+    let log_url = format!("{}/api/v1/patients/{}/access-log", addr, patient_id);
+    let resp = client.get(&log_url).bearer_auth(&other_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+
+    let resp = client.get(&log_url).bearer_auth(&home_session).send().await.unwrap();
+    let log: Value = resp.json().await.unwrap();
+    let bases: Vec<&str> = log["data"].as_array().unwrap().iter().map(|e| e["basis"].as_str().unwrap()).collect();
+    assert!(bases.contains(&"OWN_HOSPITAL"));
+    assert!(bases.contains(&"CONSENT"));
+    assert_eq!(bases.iter().filter(|b| **b == "DENIED").count(), 6);
+
+    let resp = client.get(&log_url).bearer_auth(&home_session).query(&[("basis", "BREAK_GLASS")]).send().await.unwrap();
+    let break_glass: Value = resp.json().await.unwrap();
+    let rows = break_glass["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["break_glass_reason"], "Unconscious in ED");
+    assert_eq!(rows[0]["staff_name"], "Tunde Adeyemi");
+    assert_eq!(rows[0]["resource"], "PATIENT_RECORD");
+}
+
+#[tokio::test]
+async fn staff_sessions_must_be_genuine_current_and_of_active_staff() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let (hospital, doctor) = create_hospital_with_doctor(&client, &addr, &format!("Session Hospital {}", Uuid::new_v4())).await;
+    let session = sign_in(&client, &addr, &pool, &doctor).await;
+    let patients_url = format!("{}/api/v1/patients", addr);
+
+    let resp = client.get(&patients_url).bearer_auth(&session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // A token for this doctor signed with another key is refused
+    let now = chrono::Utc::now().timestamp() as usize;
+    let forged = encode(
+        &Header::default(),
+        &json!({ "sub": doctor, "aud": "staff", "iat": now, "exp": now + 3600 }),
+        &EncodingKey::from_secret(b"not-the-server-secret"),
+    ).unwrap();
+    let resp = client.get(&patients_url).bearer_auth(&forged).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+
+    // So is a malformed one, and a wrong password at login
+    let resp = client.get(&patients_url).bearer_auth("not-a-token").send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+    let email: (String,) = sqlx::query_as("SELECT email FROM staff_accounts WHERE staff_id = $1::uuid")
+        .bind(&doctor)
+        .fetch_one(&pool).await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email.0, "password": "wrong-password" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+
+    // Deactivating the staff member ends the session, and they cannot sign in again
+    sqlx::query("UPDATE staff SET is_active = FALSE WHERE id = $1::uuid")
+        .bind(&doctor)
+        .execute(&pool).await.unwrap();
+    let resp = client.get(&patients_url).bearer_auth(&session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email.0, "password": "staff-password" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/visits", addr, hospital))
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+}
@@ -0,0 +1,100 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+#[tokio::test]
+async fn uncredentialled_staff_cannot_take_visits_and_expiry_is_reported() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Licence Hospital {}", random_id),
+            "hospital_type": "PUBLIC",
+            "state": "Kaduna",
+            "city": "Zaria"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Paediatrics", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Musa",
+            "last_name": "Bello",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "first_name": "Amina",
+            "last_name": "Yusuf",
+            "date_of_birth": "2015-06-01",
+            "gender": "FEMALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    let visit = json!({
+        "hospital_id": hospital_id,
+        "patient_id": patient_id,
+        "staff_id": staff_id,
+        "reason": "Fever and cough"
+    });
+
+    // 1. No licence yet -> rejected
+    let resp = client.post(format!("{}/api/v1/visits", addr)).json(&visit).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // 2. Licence expiring in 10 days -> visit allowed, licence shows up in the 30-day report
+    let today = Utc::now().date_naive();
+    let resp = client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", random_id),
+            "specialty": "Paediatrics",
+            "issued_on": today - Duration::days(365),
+            "expires_on": today + Duration::days(10)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.post(format!("{}/api/v1/visits", addr)).json(&visit).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/credentials/expiring?days=30", addr, hospital_id))
+        .send().await.unwrap();
+    let expiring: Value = resp.json().await.unwrap();
+    assert_eq!(expiring["data"].as_array().unwrap().len(), 1);
+    assert_eq!(expiring["data"][0]["days_remaining"], 10);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/credentials/expiring?days=5", addr, hospital_id))
+        .send().await.unwrap();
+    let expiring: Value = resp.json().await.unwrap();
+    assert!(expiring["data"].as_array().unwrap().is_empty());
+}
@@ -0,0 +1,68 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+#[tokio::test]
+async fn records_for_a_missing_parent_are_not_found() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let start = chrono::Utc::now() + chrono::Duration::days(1);
+
+    // Both lock the staff row first; no row means no such staff member
+    let resp = client.post(format!("{}/api/v1/shifts", addr))
+        .json(&json!({
+            "staff_id": Uuid::new_v4(),
+            "shift_type": "REGULAR",
+            "start_time": start,
+            "end_time": start + chrono::Duration::hours(8)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    let resp = client.post(format!("{}/api/v1/slots", addr))
+        .json(&json!({ "staff_id": Uuid::new_v4(), "start_time": start, "duration_minutes": 30 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn references_to_missing_records_are_bad_requests() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": Uuid::new_v4(), "name": "Outpatients", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["meta"]["message"], "Referenced record does not exist.");
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({ "name": format!("FK Hospital {}", Uuid::new_v4()), "hospital_type": "PUBLIC", "state": "Oyo", "city": "Ibadan" }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital["data"]["id"],
+            "department_id": Uuid::new_v4(),
+            "first_name": "Ada",
+            "last_name": "Okafor",
+            "role": "NURSE"
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["meta"]["message"], "Referenced record does not exist.");
+}
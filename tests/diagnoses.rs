@@ -0,0 +1,177 @@
+use health_intel_backend::setup_app;
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+// Signs in as a new records clerk at the hospital; patient data is only served to staff sessions
+async fn sign_in_at(client: &Client, addr: &str, pool: &PgPool, hospital_id: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Records", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Records",
+            "last_name": "Clerk",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+
+    let email = format!("clerk_{}@health.gov.ng", Uuid::new_v4());
+    let password_hash = bcrypt::hash("staff-password", 4).unwrap();
+    sqlx::query("INSERT INTO staff_accounts (staff_id, email, password_hash) VALUES ($1::uuid, $2, $3)")
+        .bind(staff["data"]["id"].as_str().unwrap())
+        .bind(&email)
+        .bind(&password_hash)
+        .execute(pool).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email, "password": "staff-password" }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"]["token"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn icd10_search_matches_codes_and_descriptions() {
+    let (addr, _pool) = spawn_app().await;
+    let client = Client::new();
+    let url = format!("{}/api/v1/icd10/codes", addr);
+
+    // Code prefix
+    let resp = client.get(&url).query(&[("q", "b50")]).send().await.unwrap();
+    let codes: Value = resp.json().await.unwrap();
+    let rows = codes["data"].as_array().unwrap();
+    assert!(!rows.is_empty());
+    assert!(rows.iter().all(|c| c["code"].as_str().unwrap().starts_with("B50")));
+    assert_eq!(rows[0]["category"], "B50");
+
+    // Description substring
+    let resp = client.get(&url).query(&[("q", "malaria"), ("limit", "3")]).send().await.unwrap();
+    let codes: Value = resp.json().await.unwrap();
+    assert_eq!(codes["data"].as_array().unwrap().len(), 3);
+
+    let resp = client.get(format!("{}/api/v1/icd10/codes/j18.9", addr)).send().await.unwrap();
+    let code: Value = resp.json().await.unwrap();
+    assert_eq!(code["data"]["description"], "Pneumonia, unspecified");
+
+    let resp = client.get(format!("{}/api/v1/icd10/codes/ZZZ.9", addr)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn visit_takes_one_primary_and_many_secondary_diagnoses() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Coding Hospital {}", random_id),
+            "hospital_type": "PUBLIC",
+            "state": "Enugu",
+            "city": "Enugu"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let session = sign_in_at(&client, &addr, &pool, &hospital_id).await;
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Paediatrics", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Chidi",
+            "last_name": "Eze",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", random_id),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "first_name": "Ngozi",
+            "last_name": "Obi",
+            "date_of_birth": "2019-04-12",
+            "gender": "FEMALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "patient_id": patient_id,
+            "staff_id": staff_id,
+            "reason": "Fever and fast breathing"
+        }))
+        .send().await.unwrap();
+    let visit: Value = resp.json().await.unwrap();
+    let visit_id = visit["data"]["id"].as_str().unwrap().to_string();
+    let diagnoses_url = format!("{}/api/v1/visits/{}/diagnoses", addr, visit_id);
+
+    // 1. Codes outside the table are rejected
+    let resp = client.post(&diagnoses_url)
+        .json(&json!({ "code": "XYZ.1", "diagnosis_type": "PRIMARY" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // 2. Primary diagnosis picks up the table description
+    let resp = client.post(&diagnoses_url)
+        .json(&json!({ "code": "b50.9", "diagnosis_type": "PRIMARY" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let primary: Value = resp.json().await.unwrap();
+    assert_eq!(primary["data"]["code"], "B50.9");
+    assert_eq!(primary["data"]["description"], "Plasmodium falciparum malaria, unspecified");
+
+    // 3. A second primary is a conflict
+    let resp = client.post(&diagnoses_url)
+        .json(&json!({ "code": "J18.9", "diagnosis_type": "PRIMARY" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // 4. Secondary diagnoses are unlimited (default type)
+    for code in ["J18.9", "D64.9"] {
+        let resp = client.post(&diagnoses_url).json(&json!({ "code": code })).send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+    }
+
+    let resp = client.get(&diagnoses_url).bearer_auth(&session).send().await.unwrap();
+    let diagnoses: Value = resp.json().await.unwrap();
+    let rows = diagnoses["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0]["diagnosis_type"], "PRIMARY");
+    assert_eq!(rows[1]["diagnosis_type"], "SECONDARY");
+}
@@ -0,0 +1,95 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+#[tokio::test]
+async fn condition_changes_are_logged_and_overdue_maintenance_is_cleared_by_work_order() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Equipment Hospital {}", Uuid::new_v4()),
+            "hospital_type": "PUBLIC",
+            "state": "Kano",
+            "city": "Kano"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/equipment", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "name": "Ventilator V60",
+            "serial_number": "VNT-001",
+            "condition": "GOOD",
+            "is_operational": true
+        }))
+        .send().await.unwrap();
+    let equipment: Value = resp.json().await.unwrap();
+    let equipment_id = equipment["data"]["id"].as_str().unwrap().to_string();
+
+    // 1. GOOD -> BROKEN is recorded
+    let resp = client.put(format!("{}/api/v1/equipment/{}/condition", addr, equipment_id))
+        .json(&json!({ "condition": "BROKEN", "is_operational": false, "note": "Compressor failure" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.get(format!("{}/api/v1/equipment/{}/condition-history", addr, equipment_id))
+        .send().await.unwrap();
+    let history: Value = resp.json().await.unwrap();
+    let entries = history["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1]["old_condition"], "GOOD");
+    assert_eq!(entries[1]["new_condition"], "BROKEN");
+
+    // 2. Monthly service last done 100 days ago is overdue
+    let last_done = Utc::now().date_naive() - Duration::days(100);
+    let resp = client.post(format!("{}/api/v1/equipment/{}/maintenance-plans", addr, equipment_id))
+        .json(&json!({ "task": "Monthly service", "interval_days": 30, "last_performed_on": last_done }))
+        .send().await.unwrap();
+    let plan: Value = resp.json().await.unwrap();
+    let plan_id = plan["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/maintenance/overdue", addr, hospital_id))
+        .send().await.unwrap();
+    let overdue: Value = resp.json().await.unwrap();
+    assert_eq!(overdue["data"].as_array().unwrap().len(), 1);
+    assert_eq!(overdue["data"][0]["days_overdue"], 70);
+
+    // 3. Closing the plan's work order reschedules it
+    let resp = client.post(format!("{}/api/v1/equipment/{}/work-orders", addr, equipment_id))
+        .json(&json!({ "plan_id": plan_id, "description": "Monthly service" }))
+        .send().await.unwrap();
+    let order: Value = resp.json().await.unwrap();
+    let order_id = order["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(order["data"]["status"], "OPEN");
+
+    let resp = client.post(format!("{}/api/v1/work-orders/{}/close", addr, order_id))
+        .json(&json!({ "resolution_notes": "Filters replaced" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.post(format!("{}/api/v1/work-orders/{}/close", addr, order_id))
+        .json(&json!({ "resolution_notes": "Filters replaced" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/maintenance/overdue", addr, hospital_id))
+        .send().await.unwrap();
+    let overdue: Value = resp.json().await.unwrap();
+    assert!(overdue["data"].as_array().unwrap().is_empty());
+}
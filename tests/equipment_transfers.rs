@@ -0,0 +1,99 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+async fn create_hospital(client: &Client, addr: &str, has_ventilators: bool) -> String {
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Loan Hospital {}", Uuid::new_v4()),
+            "hospital_type": "PUBLIC",
+            "state": "Lagos",
+            "city": "Surulere",
+            "has_ventilators": has_ventilators
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    hospital["data"]["id"].as_str().unwrap().to_string()
+}
+
+async fn has_ventilators(client: &Client, addr: &str, hospital_id: &str) -> bool {
+    let resp = client.get(format!("{}/api/v1/hospitals/{}", addr, hospital_id)).send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    hospital["data"]["has_ventilators"].as_bool().unwrap()
+}
+
+#[tokio::test]
+async fn ventilator_loan_moves_custody_and_capability_flags() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+
+    let lender = create_hospital(&client, &addr, true).await;
+    let borrower = create_hospital(&client, &addr, false).await;
+
+    let resp = client.post(format!("{}/api/v1/equipment", addr))
+        .json(&json!({
+            "hospital_id": lender,
+            "name": "Ventilator",
+            "condition": "GOOD",
+            "is_operational": true,
+            "equipment_type": "VENTILATOR"
+        }))
+        .send().await.unwrap();
+    let equipment: Value = resp.json().await.unwrap();
+    let equipment_id = equipment["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/equipment-transfers", addr))
+        .json(&json!({
+            "equipment_id": equipment_id,
+            "to_hospital_id": borrower,
+            "transfer_type": "LOAN",
+            "reason": "Surge in respiratory admissions"
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let transfer: Value = resp.json().await.unwrap();
+    let transfer_id = transfer["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(transfer["data"]["from_hospital_id"], lender.as_str());
+
+    let step = |action: &'static str| {
+        let client = client.clone();
+        let url = format!("{}/api/v1/equipment-transfers/{}/{}", addr, transfer_id, action);
+        async move { client.post(url).json(&json!({})).send().await.unwrap().status().as_u16() }
+    };
+
+    // Cannot dispatch before approval
+    assert_eq!(step("dispatch").await, 409);
+    assert_eq!(step("approve").await, 200);
+    assert_eq!(step("dispatch").await, 200);
+    assert!(!has_ventilators(&client, &addr, &lender).await);
+
+    assert_eq!(step("receive").await, 200);
+    assert!(has_ventilators(&client, &addr, &borrower).await);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/equipment", addr, borrower)).send().await.unwrap();
+    let held: Value = resp.json().await.unwrap();
+    assert_eq!(held["data"][0]["custodian_hospital_id"], borrower.as_str());
+    assert_eq!(held["data"][0]["hospital_id"], lender.as_str());
+
+    assert_eq!(step("return").await, 200);
+    assert!(has_ventilators(&client, &addr, &lender).await);
+    assert!(!has_ventilators(&client, &addr, &borrower).await);
+
+    let resp = client.get(format!("{}/api/v1/equipment/{}/custody-history", addr, equipment_id)).send().await.unwrap();
+    let history: Value = resp.json().await.unwrap();
+    let statuses: Vec<&str> = history["data"].as_array().unwrap().iter()
+        .map(|e| e["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["REQUESTED", "APPROVED", "DISPATCHED", "RECEIVED", "RETURNED"]);
+}
@@ -0,0 +1,190 @@
+use std::time::Duration;
+use chrono::Utc;
+use health_intel_backend::{
+    setup_app,
+    ws::hub::{EventHub, HubEvent, Resume},
+};
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+fn hospital_body(name: &str, occupied_beds: i32) -> Value {
+    json!({
+        "name": name,
+        "hospital_type": "PUBLIC",
+        "state": "Lagos",
+        "city": "Ikeja",
+        "total_beds": 20,
+        "occupied_beds": occupied_beds
+    })
+}
+
+#[derive(Debug)]
+struct SseEvent {
+    id: Option<String>,
+    event: Option<String>,
+    data: Value,
+}
+
+/// Reads an SSE response one event at a time, skipping keep-alive comments
+struct SseReader {
+    response: reqwest::Response,
+    buffer: String,
+}
+
+impl SseReader {
+    async fn next(&mut self) -> SseEvent {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let raw: String = self.buffer.drain(..end + 2).collect();
+                let (mut id, mut event, mut data) = (None, None, String::new());
+                for line in raw.lines() {
+                    if let Some(value) = line.strip_prefix("id:") {
+                        id = Some(value.trim().to_string());
+                    } else if let Some(value) = line.strip_prefix("event:") {
+                        event = Some(value.trim().to_string());
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data.push_str(value.trim_start());
+                    }
+                }
+                if data.is_empty() {
+                    continue;
+                }
+                return SseEvent { id, event, data: serde_json::from_str(&data).unwrap() };
+            }
+            let chunk = tokio::time::timeout(Duration::from_secs(5), self.response.chunk())
+                .await
+                .expect("timed out waiting for an event")
+                .unwrap()
+                .expect("stream ended");
+            self.buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+    }
+
+    /// Next event about `aggregate_id`; other tests' events share the feed
+    async fn next_for(&mut self, aggregate_id: &str) -> SseEvent {
+        loop {
+            let event = self.next().await;
+            if event.event.is_some() || event.data["aggregate_id"] == aggregate_id {
+                return event;
+            }
+        }
+    }
+}
+
+async fn connect(client: &Client, addr: &str, last_event_id: Option<&str>) -> SseReader {
+    let mut request = client.get(format!("{}/api/v1/sse/events", addr));
+    if let Some(id) = last_event_id {
+        request = request.header("Last-Event-ID", id);
+    }
+    let response = request.send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/event-stream"));
+    SseReader { response, buffer: String::new() }
+}
+
+fn hub_event(n: i64) -> HubEvent {
+    HubEvent {
+        sequence: n,
+        event_id: Uuid::new_v4(),
+        event_type: "capacity.changed".to_string(),
+        aggregate_type: "hospital".to_string(),
+        aggregate_id: Uuid::new_v4(),
+        occurred_at: Utc::now(),
+        data: json!({}),
+    }
+}
+
+fn replayed_positions(resume: Resume) -> Option<Vec<u64>> {
+    match resume {
+        Resume::Replay(events) => Some(events.iter().map(|e| e.position).collect()),
+        Resume::Gap => None,
+    }
+}
+
+#[tokio::test]
+async fn replay_buffer_keeps_the_most_recent_events() {
+    let hub = EventHub::with_replay_capacity(2);
+    for n in 1..=4 {
+        hub.publish(hub_event(n));
+    }
+
+    // Positions 3 and 4 are kept; 2 has been evicted
+    assert_eq!(replayed_positions(hub.subscribe_after(None).1), Some(vec![]));
+    assert_eq!(replayed_positions(hub.subscribe_after(Some(&hub.event_id(2))).1), Some(vec![3, 4]));
+    assert_eq!(replayed_positions(hub.subscribe_after(Some(&hub.event_id(3))).1), Some(vec![4]));
+    assert_eq!(replayed_positions(hub.subscribe_after(Some(&hub.event_id(4))).1), Some(vec![]));
+    assert_eq!(replayed_positions(hub.subscribe_after(Some(&hub.event_id(1))).1), None);
+
+    // Ids from the future, another process or nowhere cannot be resumed
+    assert_eq!(replayed_positions(hub.subscribe_after(Some(&hub.event_id(9))).1), None);
+    assert_eq!(replayed_positions(hub.subscribe_after(Some("0badcafe-3")).1), None);
+    assert_eq!(replayed_positions(hub.subscribe_after(Some("garbage")).1), None);
+
+    // Receivers only see what is published after they subscribe
+    let (mut receiver, _) = hub.subscribe_after(Some(&hub.event_id(4)));
+    hub.publish(hub_event(5));
+    assert_eq!(receiver.recv().await.unwrap().position, 5);
+}
+
+#[tokio::test]
+async fn sse_clients_resume_from_last_event_id() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let name = format!("SSE Hospital {}", Uuid::new_v4());
+
+    // 1. A live client sees the new hospital's capacity event, with an id to resume from
+    let mut feed = connect(&client, &addr, None).await;
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&hospital_body(&name, 5))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let created = feed.next_for(&hospital_id).await;
+    assert_eq!(created.data["type"], "capacity.changed");
+    let last_seen = created.id.expect("events carry an id");
+    drop(feed);
+
+    // 2. While disconnected, the hospital changes
+    let resp = client.put(format!("{}/api/v1/hospitals/{}", addr, hospital_id))
+        .json(&hospital_body(&name, 15))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // 3. Reconnecting with Last-Event-ID replays what was missed, in order
+    let mut feed = connect(&client, &addr, Some(&last_seen)).await;
+    let updated = feed.next_for(&hospital_id).await;
+    assert_eq!(updated.event, None);
+    assert_eq!(updated.data["type"], "hospital.updated");
+    assert_eq!(updated.data["data"]["occupied_beds"], 15);
+    let changed = feed.next_for(&hospital_id).await;
+    assert_eq!(changed.data["type"], "capacity.changed");
+    assert!(changed.data["sequence"].as_i64() > updated.data["sequence"].as_i64());
+
+    // 4. An id that cannot be resumed gets a resync notice first, then live events
+    let resp = client.get(format!("{}/api/v1/sse/events", addr))
+        .query(&[("last_event_id", "0badcafe-1")])
+        .send().await.unwrap();
+    let mut feed = SseReader { response: resp, buffer: String::new() };
+    let first = feed.next().await;
+    assert_eq!(first.event.as_deref(), Some("resync"));
+    assert_eq!(first.data["reason"], "unknown_last_event_id");
+
+    let resp = client.put(format!("{}/api/v1/hospitals/{}", addr, hospital_id))
+        .json(&hospital_body(&name, 16))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let live = feed.next_for(&hospital_id).await;
+    assert_eq!(live.data["type"], "hospital.updated");
+}
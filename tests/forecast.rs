@@ -0,0 +1,114 @@
+use health_intel_backend::setup_app;
+use chrono::{Duration, DurationRound, Timelike, Utc};
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+#[tokio::test]
+async fn forecasts_follow_the_daily_pattern_and_are_compared_with_actuals() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Forecast {}", random_id),
+            "hospital_type": "PUBLIC",
+            "state": "Enugu",
+            "city": "Enugu",
+            "total_beds": 10,
+            "occupied_beds": 3
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let hospital_uuid = Uuid::parse_str(&hospital_id).unwrap();
+
+    // Six days of history: 8 beds occupied during the day (08:00-19:59 UTC), 3 at night
+    let now = Utc::now().duration_trunc(Duration::hours(1)).unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO bed_occupancy_snapshots (hospital_id, total_beds, occupied_beds, recorded_at)
+        SELECT $1, 10, CASE WHEN EXTRACT(HOUR FROM h AT TIME ZONE 'UTC') BETWEEN 8 AND 19 THEN 8 ELSE 3 END, h
+        FROM generate_series($2::TIMESTAMPTZ - INTERVAL '6 days', $2::TIMESTAMPTZ - INTERVAL '1 hour', INTERVAL '1 hour') AS h
+        "#,
+    )
+    .bind(hospital_uuid)
+    .bind(now)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // Cutoffs outside the backtest window are rejected, not forecast from
+    for as_of in ["1600-01-01T00:00:00Z", "2000-01-01T00:00:00Z"] {
+        let resp = client.post(format!("{}/api/v1/forecasts/run", addr))
+            .query(&[("as_of", as_of), ("hospital_id", hospital_id.as_str())])
+            .send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 400);
+    }
+
+    // Backtest: forecast from the first four days, then compare with the last two
+    let as_of = now - Duration::days(2);
+    let resp = client.post(format!("{}/api/v1/forecasts/run", addr))
+        .query(&[("as_of", as_of.to_rfc3339()), ("hospital_id", hospital_id.clone())])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let run: Value = resp.json().await.unwrap();
+    assert_eq!(run["data"]["hospitals"], 1);
+    assert_eq!(run["data"]["predictions"], 168);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/forecast", addr, hospital_id))
+        .query(&[("from", as_of.to_rfc3339()), ("to", now.to_rfc3339())])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let data = &body["data"];
+    assert_eq!(data["compared_hours"], 47);
+
+    let points = data["points"].as_array().unwrap();
+    for p in points {
+        let predicted = p["predicted_occupied"].as_f64().unwrap();
+        assert!(p["lower_bound"].as_f64().unwrap() <= predicted);
+        assert!(predicted <= p["upper_bound"].as_f64().unwrap());
+        assert!(p["upper_bound"].as_f64().unwrap() <= 10.0);
+    }
+
+    // A perfectly regular pattern is learned almost exactly
+    let noon = points.iter().find(|p| {
+        chrono::DateTime::parse_from_rfc3339(p["target_time"].as_str().unwrap()).unwrap().hour() == 12
+    }).unwrap();
+    let night = points.iter().find(|p| {
+        chrono::DateTime::parse_from_rfc3339(p["target_time"].as_str().unwrap()).unwrap().hour() == 2
+    }).unwrap();
+    assert!((noon["predicted_occupied"].as_f64().unwrap() - 8.0).abs() < 0.5);
+    assert!((night["predicted_occupied"].as_f64().unwrap() - 3.0).abs() < 0.5);
+    assert!(data["mean_absolute_error"].as_f64().unwrap() < 0.5);
+    assert!(data["interval_coverage"].as_f64().unwrap() > 0.9);
+
+    // Only predictions made within a day of their target
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/forecast", addr, hospital_id))
+        .query(&[("from", as_of.to_rfc3339()), ("to", now.to_rfc3339()), ("horizon", "24h".to_string())])
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"]["points"].as_array().unwrap().len(), 24);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/forecast", addr, hospital_id))
+        .query(&[("horizon", "1y")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/forecast", addr, Uuid::new_v4()))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
@@ -0,0 +1,134 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+#[tokio::test]
+async fn oxygen_stock_drives_low_stock_status_and_capabilities() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Stock Hospital {}", Uuid::new_v4()),
+            "hospital_type": "PUBLIC",
+            "state": "Plateau",
+            "city": "Jos"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    // Untracked hospitals report UNKNOWN oxygen
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/capabilities", addr, hospital_id)).send().await.unwrap();
+    let summary: Value = resp.json().await.unwrap();
+    assert_eq!(summary["data"]["oxygen_status"], "UNKNOWN");
+
+    // 1. Ten cylinders, reorder at four
+    let resp = client.post(format!("{}/api/v1/hospitals/{}/inventory", addr, hospital_id))
+        .json(&json!({
+            "category": "OXYGEN",
+            "name": "Oxygen cylinder (size J)",
+            "unit": "cylinder",
+            "quantity_on_hand": 10,
+            "reorder_level": 4
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let item: Value = resp.json().await.unwrap();
+    let item_id = item["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(item["data"]["stock_status"], "OK");
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/capabilities", addr, hospital_id)).send().await.unwrap();
+    let summary: Value = resp.json().await.unwrap();
+    assert_eq!(summary["data"]["has_oxygen"], true);
+    assert_eq!(summary["data"]["oxygen_status"], "OK");
+
+    // 2. Use seven -> LOW
+    let resp = client.post(format!("{}/api/v1/inventory/{}/transactions", addr, item_id))
+        .json(&json!({ "transaction_type": "STOCK_OUT", "quantity": 7, "note": "Ward 3" }))
+        .send().await.unwrap();
+    let movement: Value = resp.json().await.unwrap();
+    assert_eq!(movement["data"]["item"]["quantity_on_hand"], 3);
+    assert_eq!(movement["data"]["item"]["stock_status"], "LOW");
+    assert_eq!(movement["data"]["transaction"]["balance_after"], 3);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/inventory/low-stock", addr, hospital_id)).send().await.unwrap();
+    let low: Value = resp.json().await.unwrap();
+    assert_eq!(low["data"].as_array().unwrap().len(), 1);
+
+    // 3. Cannot issue more than is on hand
+    let resp = client.post(format!("{}/api/v1/inventory/{}/transactions", addr, item_id))
+        .json(&json!({ "transaction_type": "STOCK_OUT", "quantity": 5 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // 4. Run out -> has_oxygen drops
+    client.post(format!("{}/api/v1/inventory/{}/transactions", addr, item_id))
+        .json(&json!({ "transaction_type": "STOCK_OUT", "quantity": 3 }))
+        .send().await.unwrap();
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/capabilities", addr, hospital_id)).send().await.unwrap();
+    let summary: Value = resp.json().await.unwrap();
+    assert_eq!(summary["data"]["has_oxygen"], false);
+    assert_eq!(summary["data"]["oxygen_status"], "OUT");
+    assert_eq!(summary["data"]["out_of_stock_items"], 1);
+
+    let resp = client.get(format!("{}/api/v1/inventory/{}/transactions", addr, item_id)).send().await.unwrap();
+    let history: Value = resp.json().await.unwrap();
+    assert_eq!(history["data"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn blood_is_left_to_the_blood_bank_and_movements_are_capped() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Supply Hospital {}", Uuid::new_v4()),
+            "hospital_type": "PUBLIC",
+            "state": "Enugu",
+            "city": "Enugu"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let inventory_url = format!("{}/api/v1/hospitals/{}/inventory", addr, hospital_id);
+
+    // 1. Blood stock belongs in the blood bank, not the general inventory
+    let resp = client.post(&inventory_url)
+        .json(&json!({ "category": "BLOOD", "name": "Whole blood O-", "unit": "unit" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(&inventory_url)
+        .json(&json!({ "category": "MEDICINE", "name": "Paracetamol 500 mg", "unit": "tablet", "quantity_on_hand": 4 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let item: Value = resp.json().await.unwrap();
+    let item_id = item["data"]["id"].as_str().unwrap().to_string();
+
+    // 2. Quantities past the cap are rejected rather than overflowing the balance
+    let transactions_url = format!("{}/api/v1/inventory/{}/transactions", addr, item_id);
+    let resp = client.post(&transactions_url)
+        .json(&json!({ "transaction_type": "STOCK_IN", "quantity": 2147483647 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(&transactions_url)
+        .json(&json!({ "transaction_type": "STOCK_IN", "quantity": 6 }))
+        .send().await.unwrap();
+    let movement: Value = resp.json().await.unwrap();
+    assert_eq!(movement["data"]["item"]["quantity_on_hand"], 10);
+}
@@ -0,0 +1,197 @@
+use health_intel_backend::setup_app;
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+// Signs in as a new records clerk at the hospital; patient data is only served to staff sessions
+async fn sign_in_at(client: &Client, addr: &str, pool: &PgPool, hospital_id: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Records", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Records",
+            "last_name": "Clerk",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+
+    let email = format!("clerk_{}@health.gov.ng", Uuid::new_v4());
+    let password_hash = bcrypt::hash("staff-password", 4).unwrap();
+    sqlx::query("INSERT INTO staff_accounts (staff_id, email, password_hash) VALUES ($1::uuid, $2, $3)")
+        .bind(staff["data"]["id"].as_str().unwrap())
+        .bind(&email)
+        .bind(&password_hash)
+        .execute(pool).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email, "password": "staff-password" }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"]["token"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn lab_order_moves_through_collection_result_and_verification() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Lab Hospital {}", random_id),
+            "hospital_type": "PUBLIC",
+            "state": "Plateau",
+            "city": "Jos"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let session = sign_in_at(&client, &addr, &pool, &hospital_id).await;
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Laboratory", "department_type": "SUPPORT" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let mut staff_ids = Vec::new();
+    for (first_name, role) in [("Dung", "DOCTOR"), ("Pam", "SUPPORT"), ("Kaneng", "SUPPORT")] {
+        let resp = client.post(format!("{}/api/v1/staff", addr))
+            .json(&json!({
+                "hospital_id": hospital_id,
+                "department_id": dept_id,
+                "first_name": first_name,
+                "last_name": "Gyang",
+                "role": role
+            }))
+            .send().await.unwrap();
+        let staff: Value = resp.json().await.unwrap();
+        staff_ids.push(staff["data"]["id"].as_str().unwrap().to_string());
+    }
+    let (doctor_id, scientist_id, senior_id) = (staff_ids[0].clone(), staff_ids[1].clone(), staff_ids[2].clone());
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, doctor_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", random_id),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "first_name": "Nanret",
+            "last_name": "Dabo",
+            "date_of_birth": "1995-06-30",
+            "gender": "FEMALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "patient_id": patient_id,
+            "staff_id": doctor_id,
+            "reason": "Tiredness and pallor"
+        }))
+        .send().await.unwrap();
+    let visit: Value = resp.json().await.unwrap();
+    let visit_id = visit["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.get(format!("{}/api/v1/lab-tests", addr)).send().await.unwrap();
+    let tests: Value = resp.json().await.unwrap();
+    let test_id = |code: &str| tests["data"].as_array().unwrap().iter()
+        .find(|t| t["code"] == code).unwrap()["id"].as_str().unwrap().to_string();
+
+    // 1. Support staff cannot order; the doctor orders a routine Hb and a STAT malaria RDT
+    let orders_url = format!("{}/api/v1/visits/{}/lab-orders", addr, visit_id);
+    let resp = client.post(&orders_url)
+        .json(&json!({ "test_type_id": test_id("HB"), "ordered_by": scientist_id }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(&orders_url)
+        .json(&json!({ "test_type_id": test_id("HB"), "ordered_by": doctor_id }))
+        .send().await.unwrap();
+    let hb: Value = resp.json().await.unwrap();
+    assert_eq!(hb["data"]["order"]["status"], "ORDERED");
+    assert_eq!(hb["data"]["order"]["specimen_type"], "BLOOD");
+    assert!(hb["data"]["warnings"].as_array().unwrap().is_empty());
+    let hb_id = hb["data"]["order"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(&orders_url)
+        .json(&json!({ "test_type_id": test_id("MRDT"), "ordered_by": doctor_id, "priority": "STAT" }))
+        .send().await.unwrap();
+    let mrdt: Value = resp.json().await.unwrap();
+    let mrdt_id = mrdt["data"]["order"]["id"].as_str().unwrap().to_string();
+
+    let queue_url = format!("{}/api/v1/hospitals/{}/lab-queue", addr, hospital_id);
+    let resp = client.get(&queue_url).bearer_auth(&session).send().await.unwrap();
+    let queue: Value = resp.json().await.unwrap();
+    assert_eq!(queue["data"].as_array().unwrap().len(), 2);
+    assert_eq!(queue["data"][0]["id"], mrdt_id.as_str());
+
+    // 2. No result before collection
+    let result_url = format!("{}/api/v1/lab-orders/{}/result", addr, hb_id);
+    let resp = client.post(&result_url)
+        .json(&json!({ "resulted_by": scientist_id, "value": 8.4 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    client.post(format!("{}/api/v1/lab-orders/{}/collect", addr, hb_id))
+        .json(&json!({ "collected_by": scientist_id }))
+        .send().await.unwrap();
+
+    // 3. Numeric test needs a value; low Hb is flagged
+    let resp = client.post(&result_url)
+        .json(&json!({ "resulted_by": scientist_id, "text": "LOW" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(&result_url)
+        .json(&json!({ "resulted_by": scientist_id, "value": 8.4 }))
+        .send().await.unwrap();
+    let resulted: Value = resp.json().await.unwrap();
+    assert_eq!(resulted["data"]["status"], "RESULTED");
+    assert_eq!(resulted["data"]["abnormal_flag"], "LOW");
+    assert_eq!(resulted["data"]["reference_low"], 12.0);
+    assert_eq!(resulted["data"]["result_unit"], "g/dL");
+
+    // 4. Verification needs a second person
+    let verify_url = format!("{}/api/v1/lab-orders/{}/verify", addr, hb_id);
+    let resp = client.post(&verify_url)
+        .json(&json!({ "verified_by": scientist_id }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(&verify_url)
+        .json(&json!({ "verified_by": senior_id }))
+        .send().await.unwrap();
+    let verified: Value = resp.json().await.unwrap();
+    assert_eq!(verified["data"]["status"], "VERIFIED");
+
+    // 5. Only the malaria RDT is still pending
+    let resp = client.get(&queue_url).bearer_auth(&session).send().await.unwrap();
+    let queue: Value = resp.json().await.unwrap();
+    assert_eq!(queue["data"].as_array().unwrap().len(), 1);
+    assert_eq!(queue["data"][0]["test_code"], "MRDT");
+}
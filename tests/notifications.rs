@@ -0,0 +1,377 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{atomic::{AtomicUsize, Ordering}, Arc, Once},
+    time::Duration,
+};
+use async_trait::async_trait;
+use health_intel_backend::{
+    config::Settings,
+    models::notification::DueNotification,
+    notify::{providers::LogSink, NotificationProvider, Notifier},
+    setup_app,
+};
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+static CONFIGURE: Once = Once::new();
+
+fn sink_path() -> PathBuf {
+    std::env::temp_dir().join(format!("notifications-{}.jsonl", std::process::id()))
+}
+
+// Helper to spawn app, sending both channels to a file sink
+async fn spawn_app() -> (String, sqlx::PgPool) {
+    CONFIGURE.call_once(|| {
+        std::env::set_var("NOTIFY_EMAIL_PROVIDER", "file");
+        std::env::set_var("NOTIFY_SMS_PROVIDER", "file");
+        std::env::set_var("NOTIFY_SINK_PATH", sink_path());
+    });
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+/// Creates a hospital (in a state of its own), a department and a staff member
+async fn create_staff(client: &Client, addr: &str, email: Option<&str>, phone: Option<&str>) -> (String, String, String) {
+    let state = format!("Notify {}", Uuid::new_v4());
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Notify Hospital {}", Uuid::new_v4()),
+            "hospital_type": "PUBLIC",
+            "state": state,
+            "city": "Capital",
+            "total_beds": 10,
+            "occupied_beds": 2
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Administration", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Amaka",
+            "last_name": "Eze",
+            "role": "ADMIN",
+            "email": email,
+            "contact_phone": phone
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    (hospital_id, staff["data"]["id"].as_str().unwrap().to_string(), state)
+}
+
+async fn send(client: &Client, addr: &str, body: Value) -> (u16, Value) {
+    let resp = client.post(format!("{}/api/v1/notifications", addr)).json(&body).send().await.unwrap();
+    let status = resp.status().as_u16();
+    (status, resp.json().await.unwrap())
+}
+
+/// Polls until the notification has left PENDING (first attempts run in the background)
+async fn wait_until_settled(client: &Client, addr: &str, id: &str) -> Value {
+    let mut notification = Value::Null;
+    for _ in 0..50 {
+        let resp = client.get(format!("{}/api/v1/notifications/{}", addr, id)).send().await.unwrap();
+        let body: Value = resp.json().await.unwrap();
+        notification = body["data"].clone();
+        if notification["status"] != "PENDING" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    notification
+}
+
+fn sink_line(id: &str) -> Option<Value> {
+    std::fs::read_to_string(sink_path())
+        .unwrap_or_default()
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .find(|record| record["id"] == id)
+}
+
+#[tokio::test]
+async fn templates_render_per_recipient_preferences_into_the_delivery_log() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let (_, staff_id, _) = create_staff(&client, &addr, Some("amaka@example.org"), Some("+2348000000001")).await;
+    let key = format!("shift.reminder.{}", Uuid::new_v4());
+
+    // 1. Templates: email needs a subject; SMS ignores one
+    let resp = client.put(format!("{}/api/v1/notification-templates", addr))
+        .json(&json!({ "template_key": key, "channel": "EMAIL", "body": "Hi" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.put(format!("{}/api/v1/notification-templates", addr))
+        .json(&json!({ "template_key": key, "channel": "EMAIL", "subject": "Shift at {{ ward }}", "body": "Hello {{name}}, you are on {{ward}} at {{time}}." }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let resp = client.put(format!("{}/api/v1/notification-templates", addr))
+        .json(&json!({ "template_key": key, "channel": "SMS", "subject": "ignored", "body": "{{name}}: {{ward}} {{time}}" }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"]["subject"], Value::Null);
+
+    // Application-only templates cannot be reworded through the API
+    let resp = client.put(format!("{}/api/v1/notification-templates", addr))
+        .json(&json!({ "template_key": "password_reset", "channel": "EMAIL", "subject": "Reset", "body": "Go to {{reset_url}}" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // 2. Defaults before any preferences are saved
+    let resp = client.get(format!("{}/api/v1/staff/{}/notification-preferences", addr, staff_id)).send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"]["email_enabled"], true);
+    assert_eq!(body["data"]["sms_enabled"], true);
+
+    // 3. Every placeholder needs a value, and the recipient must exist
+    let (status, body) = send(&client, &addr, json!({ "template_key": key, "staff_id": staff_id, "variables": { "name": "Amaka" } })).await;
+    assert_eq!(status, 400);
+    assert!(body["meta"]["message"].as_str().unwrap().contains("ward"));
+    let (status, _) = send(&client, &addr, json!({ "template_key": key, "staff_id": Uuid::new_v4(), "variables": {} })).await;
+    assert_eq!(status, 404);
+    let (status, _) = send(&client, &addr, json!({ "template_key": "no.such.template", "staff_id": staff_id, "variables": {} })).await;
+    assert_eq!(status, 400);
+
+    // 4. Both channels are queued and sent through the sink; bodies stay out of the log API
+    let variables = json!({ "name": "Amaka", "ward": "Ward 3", "time": "07:00" });
+    let (status, body) = send(&client, &addr, json!({ "template_key": key, "staff_id": staff_id, "variables": variables })).await;
+    assert_eq!(status, 200);
+    let queued = body["data"].as_array().unwrap().clone();
+    assert_eq!(queued.len(), 2);
+    for notification in &queued {
+        let id = notification["id"].as_str().unwrap();
+        let settled = wait_until_settled(&client, &addr, id).await;
+        assert_eq!(settled["status"], "SENT");
+        assert_eq!(settled["provider"], "file");
+        assert_eq!(settled["attempts"], 1);
+        assert!(settled.get("body").is_none());
+
+        let line = sink_line(id).expect("notification written to the sink");
+        match settled["channel"].as_str().unwrap() {
+            "EMAIL" => {
+                assert_eq!(line["to"], "amaka@example.org");
+                assert_eq!(line["subject"], "Shift at Ward 3");
+                assert_eq!(line["body"], "Hello Amaka, you are on Ward 3 at 07:00.");
+            }
+            _ => {
+                assert_eq!(line["to"], "+2348000000001");
+                assert_eq!(line["body"], "Amaka: Ward 3 07:00");
+            }
+        }
+    }
+
+    // 5. Switching SMS off leaves email; muting the template silences both
+    let resp = client.put(format!("{}/api/v1/staff/{}/notification-preferences", addr, staff_id))
+        .json(&json!({ "email_enabled": true, "sms_enabled": false }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let (_, body) = send(&client, &addr, json!({ "template_key": key, "staff_id": staff_id, "variables": variables })).await;
+    let queued = body["data"].as_array().unwrap();
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0]["channel"], "EMAIL");
+
+    let resp = client.put(format!("{}/api/v1/staff/{}/notification-preferences", addr, staff_id))
+        .json(&json!({ "email_enabled": true, "sms_enabled": false, "muted_templates": [key, "password_reset"] }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let (status, body) = send(&client, &addr, json!({ "template_key": key, "staff_id": staff_id, "variables": variables })).await;
+    assert_eq!(status, 200);
+    assert!(body["data"].as_array().unwrap().is_empty());
+
+    // 6. Password resets are only sent by the application, and ignore opt-outs since they are
+    //    asked for
+    let (status, _) = send(&client, &addr, json!({
+        "template_key": "password_reset",
+        "staff_id": staff_id,
+        "channel": "SMS",
+        "variables": { "code": "482913", "expires_in": "15 minutes" }
+    })).await;
+    assert_eq!(status, 400);
+
+    let notifier = Notifier::from_settings(pool, &Settings::from_env().unwrap()).unwrap();
+    let reset_variables = HashMap::from([
+        ("code".to_string(), "482913".to_string()),
+        ("expires_in".to_string(), "15 minutes".to_string()),
+    ]);
+    let staff_uuid = Uuid::parse_str(&staff_id).unwrap();
+    let queued = notifier
+        .send_to_staff(staff_uuid, "password_reset", Some("SMS"), &reset_variables, None)
+        .await
+        .unwrap();
+    assert_eq!(queued.len(), 1);
+
+    // 7. Arbitrary addresses are not accepted
+    let resp = client.post(format!("{}/api/v1/notifications", addr))
+        .json(&json!({ "template_key": key, "to": "ops@example.org", "channel": "EMAIL", "variables": variables }))
+        .send().await.unwrap();
+    assert!(resp.status().is_client_error());
+
+    // 8. The log filters by recipient and template
+    let resp = client.get(format!("{}/api/v1/notifications", addr))
+        .query(&[("staff_id", staff_id.as_str()), ("template_key", key.as_str())])
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"].as_array().unwrap().len(), 3);
+
+    let resp = client.get(format!("{}/api/v1/notifications", addr))
+        .query(&[("status", "LOST")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+}
+
+/// Fails the first `failures` sends, then succeeds
+struct FlakyProvider {
+    failures: usize,
+    calls: AtomicUsize,
+}
+
+#[async_trait]
+impl NotificationProvider for FlakyProvider {
+    fn name(&self) -> &'static str {
+        "flaky"
+    }
+
+    async fn send(&self, _notification: &DueNotification) -> Result<Option<String>, String> {
+        if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+            Err("gateway unavailable".to_string())
+        } else {
+            Ok(Some("msg-1".to_string()))
+        }
+    }
+}
+
+#[tokio::test]
+async fn failed_sends_are_retried_with_back_off_then_given_up() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let mut settings = Settings::from_env().unwrap();
+    settings.notify_max_attempts = 2;
+    settings.notify_retry_base_secs = 1;
+    let variables = HashMap::from([
+        ("reset_url".to_string(), "https://example.org/reset/abc".to_string()),
+        ("expires_in".to_string(), "1 hour".to_string()),
+    ]);
+
+    // 1. A plugged-in provider that fails once: retried after the back-off, then sent
+    let flaky = Arc::new(FlakyProvider { failures: 1, calls: AtomicUsize::new(0) });
+    let notifier = Notifier::new(pool.clone(), flaky.clone(), Arc::new(LogSink), &settings);
+    let queued = notifier
+        .send_to_address("EMAIL", "admin@example.org", "password_reset", &variables, None)
+        .await
+        .unwrap();
+    let id = queued[0].id.to_string();
+
+    let pending = loop {
+        let resp = client.get(format!("{}/api/v1/notifications/{}", addr, id)).send().await.unwrap();
+        let body: Value = resp.json().await.unwrap();
+        if body["data"]["attempts"] == 1 {
+            break body["data"].clone();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    };
+    assert_eq!(pending["status"], "PENDING");
+    assert_eq!(pending["provider"], "flaky");
+    assert_eq!(pending["last_error"], "gateway unavailable");
+
+    // Not due again until the back-off has passed
+    let summary = notifier.dispatch(Some(&[queued[0].id])).await.unwrap();
+    assert_eq!(summary.attempted, 0);
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let summary = notifier.dispatch(Some(&[queued[0].id])).await.unwrap();
+    assert_eq!(summary.sent, 1);
+
+    let sent = wait_until_settled(&client, &addr, &id).await;
+    assert_eq!(sent["status"], "SENT");
+    assert_eq!(sent["attempts"], 2);
+    assert_eq!(sent["provider_message_id"], "msg-1");
+    assert_eq!(sent["last_error"], Value::Null);
+
+    // 2. A provider that keeps failing: given up after the last attempt
+    let broken = Arc::new(FlakyProvider { failures: usize::MAX, calls: AtomicUsize::new(0) });
+    let notifier = Notifier::new(pool.clone(), broken, Arc::new(LogSink), &settings);
+    let queued = notifier
+        .send_to_address("EMAIL", "admin@example.org", "password_reset", &variables, None)
+        .await
+        .unwrap();
+    let id = queued[0].id.to_string();
+    wait_until_settled(&client, &addr, &id).await; // stays PENDING after the first failure
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let summary = notifier.dispatch(Some(&[queued[0].id])).await.unwrap();
+    assert_eq!(summary.failed, 1);
+    let failed = wait_until_settled(&client, &addr, &id).await;
+    assert_eq!(failed["status"], "FAILED");
+    assert_eq!(failed["attempts"], 2);
+
+    // 3. A manual retry goes through the server's own providers (the file sink here)
+    let resp = client.post(format!("{}/api/v1/notifications/{}/retry", addr, id)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"]["status"], "SENT");
+    assert_eq!(body["data"]["provider"], "file");
+    assert!(sink_line(&id).unwrap()["body"].as_str().unwrap().contains("https://example.org/reset/abc"));
+
+    let resp = client.post(format!("{}/api/v1/notifications/{}/retry", addr, id)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+}
+
+#[tokio::test]
+async fn surge_alerts_reach_subscribed_staff_once() {
+    let (addr, _pool) = spawn_app().await;
+    let client = Client::new();
+    let (hospital_id, staff_id, state) = create_staff(&client, &addr, Some("eoc.lead@example.org"), None).await;
+
+    let resp = client.post(format!("{}/api/v1/alert-subscriptions", addr))
+        .json(&json!({ "state": state, "channel": "STAFF", "target": "not-a-staff-id" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+    let resp = client.post(format!("{}/api/v1/alert-subscriptions", addr))
+        .json(&json!({ "state": state, "channel": "STAFF", "target": staff_id }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.post(format!("{}/api/v1/alert-rules", addr))
+        .json(&json!({ "name": "Beds nearly full", "state": state, "metric": "OCCUPANCY_ABOVE", "threshold": 90.0, "severity": "CRITICAL" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}", addr, hospital_id)).send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let mut update = hospital["data"].clone();
+    update["occupied_beds"] = json!(10);
+    let resp = client.put(format!("{}/api/v1/hospitals/{}", addr, hospital_id)).json(&update).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // Delivery runs in the background; the staff member has no phone, so email only
+    let mut notifications = Vec::new();
+    for _ in 0..50 {
+        let resp = client.get(format!("{}/api/v1/notifications", addr))
+            .query(&[("staff_id", staff_id.as_str()), ("template_key", "capacity_alert.raised"), ("status", "SENT")])
+            .send().await.unwrap();
+        let body: Value = resp.json().await.unwrap();
+        notifications = body["data"].as_array().unwrap().clone();
+        if !notifications.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0]["channel"], "EMAIL");
+    assert_eq!(notifications[0]["recipient"], "eoc.lead@example.org");
+    assert!(notifications[0]["subject"].as_str().unwrap().starts_with("[CRITICAL] Capacity alert"));
+
+    let line = sink_line(notifications[0]["id"].as_str().unwrap()).unwrap();
+    assert!(line["body"].as_str().unwrap().contains("bed occupancy 100.0%"));
+}
@@ -0,0 +1,226 @@
+use health_intel_backend::{models::observation::{news2_score, News2Input}, setup_app};
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+// Signs in as a new records clerk at the hospital; patient data is only served to staff sessions
+async fn sign_in_at(client: &Client, addr: &str, pool: &PgPool, hospital_id: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Records", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Records",
+            "last_name": "Clerk",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+
+    let email = format!("clerk_{}@health.gov.ng", Uuid::new_v4());
+    let password_hash = bcrypt::hash("staff-password", 4).unwrap();
+    sqlx::query("INSERT INTO staff_accounts (staff_id, email, password_hash) VALUES ($1::uuid, $2, $3)")
+        .bind(staff["data"]["id"].as_str().unwrap())
+        .bind(&email)
+        .bind(&password_hash)
+        .execute(pool).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email, "password": "staff-password" }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"]["token"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn news2_bands_follow_the_aggregate_and_red_scores() {
+    let normal = News2Input {
+        respiratory_rate: Some(16.0),
+        spo2: Some(98.0),
+        on_supplemental_oxygen: false,
+        systolic_bp: Some(120.0),
+        heart_rate: Some(72.0),
+        consciousness: Some("ALERT".to_string()),
+        temperature: Some(37.0),
+    };
+    assert_eq!(news2_score(&normal), Some((0, "LOW")));
+
+    // New confusion alone scores 3
+    let confused = News2Input { consciousness: Some("CONFUSION".to_string()), ..normal.clone() };
+    assert_eq!(news2_score(&confused), Some((3, "LOW_MEDIUM")));
+
+    // Septic picture: RR 3 + SpO2 2 + O2 2 + SBP 2 + HR 2 + temp 2
+    let septic = News2Input {
+        respiratory_rate: Some(26.0),
+        spo2: Some(93.0),
+        on_supplemental_oxygen: true,
+        systolic_bp: Some(95.0),
+        heart_rate: Some(118.0),
+        consciousness: Some("ALERT".to_string()),
+        temperature: Some(39.4),
+    };
+    assert_eq!(news2_score(&septic), Some((13, "HIGH")));
+
+    assert_eq!(news2_score(&News2Input { heart_rate: None, ..normal }), None);
+}
+
+#[tokio::test]
+async fn observations_are_scored_and_deteriorating_patients_flagged() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Ward Hospital {}", random_id),
+            "hospital_type": "PUBLIC",
+            "state": "Kano",
+            "city": "Kano"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let session = sign_in_at(&client, &addr, &pool, &hospital_id).await;
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Ward", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Hauwa",
+            "last_name": "Musa",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", random_id),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "first_name": "Sani",
+            "last_name": "Garba",
+            "date_of_birth": "1965-02-20",
+            "gender": "MALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "patient_id": patient_id,
+            "staff_id": staff_id,
+            "reason": "Fever and breathlessness"
+        }))
+        .send().await.unwrap();
+    let visit: Value = resp.json().await.unwrap();
+    let visit_id = visit["data"]["id"].as_str().unwrap().to_string();
+    let observations_url = format!("{}/api/v1/visits/{}/observations", addr, visit_id);
+
+    // 1. Wrong unit for the type is rejected
+    let resp = client.post(&observations_url)
+        .json(&json!({
+            "staff_id": staff_id,
+            "measurements": [{ "observation_type": "SPO2", "value": 97, "unit": "mmHg" }]
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // 2. Normal set an hour ago; Fahrenheit is converted
+    let earlier = chrono::Utc::now() - chrono::Duration::hours(1);
+    let resp = client.post(&observations_url)
+        .json(&json!({
+            "staff_id": staff_id,
+            "recorded_at": earlier,
+            "consciousness": "ALERT",
+            "measurements": [
+                { "observation_type": "RESPIRATORY_RATE", "value": 16, "unit": "/min" },
+                { "observation_type": "SPO2", "value": 97, "unit": "%" },
+                { "observation_type": "SYSTOLIC_BP", "value": 124, "unit": "mmHg" },
+                { "observation_type": "HEART_RATE", "value": 84, "unit": "bpm" },
+                { "observation_type": "TEMPERATURE", "value": 98.6, "unit": "F" }
+            ]
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let first: Value = resp.json().await.unwrap();
+    assert_eq!(first["data"]["set"]["news2_score"], 0);
+    let temperature = first["data"]["measurements"].as_array().unwrap().iter()
+        .find(|m| m["observation_type"] == "TEMPERATURE").unwrap().clone();
+    assert_eq!(temperature["value"], 37.0);
+    assert_eq!(temperature["unit"], "C");
+
+    // 3. Now deteriorating
+    let resp = client.post(&observations_url)
+        .json(&json!({
+            "staff_id": staff_id,
+            "consciousness": "ALERT",
+            "on_supplemental_oxygen": true,
+            "measurements": [
+                { "observation_type": "RESPIRATORY_RATE", "value": 26, "unit": "/min" },
+                { "observation_type": "SPO2", "value": 93, "unit": "%" },
+                { "observation_type": "SYSTOLIC_BP", "value": 98, "unit": "mmHg" },
+                { "observation_type": "HEART_RATE", "value": 118, "unit": "/min" },
+                { "observation_type": "TEMPERATURE", "value": 39.4, "unit": "C" }
+            ]
+        }))
+        .send().await.unwrap();
+    let second: Value = resp.json().await.unwrap();
+    assert_eq!(second["data"]["set"]["news2_risk"], "HIGH");
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/deteriorating", addr, hospital_id))
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    let flagged: Value = resp.json().await.unwrap();
+    let rows = flagged["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["patient_id"], patient_id.as_str());
+    assert_eq!(rows[0]["score_change"], 13);
+
+    // 4. Trends
+    let trend_url = format!("{}/api/v1/patients/{}/observations/trend", addr, patient_id);
+    let resp = client.get(&trend_url).bearer_auth(&session).query(&[("type", "HEART_RATE")]).send().await.unwrap();
+    let trend: Value = resp.json().await.unwrap();
+    let points = trend["data"].as_array().unwrap();
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[0]["value"], 84.0);
+    assert_eq!(points[1]["value"], 118.0);
+
+    let resp = client.get(&trend_url).bearer_auth(&session).query(&[("type", "NEWS2")]).send().await.unwrap();
+    let trend: Value = resp.json().await.unwrap();
+    assert_eq!(trend["data"][1]["value"], 13.0);
+
+    let resp = client.get(&observations_url).bearer_auth(&session).send().await.unwrap();
+    let sets: Value = resp.json().await.unwrap();
+    assert_eq!(sets["data"].as_array().unwrap().len(), 2);
+    assert_eq!(sets["data"][0]["measurements"].as_array().unwrap().len(), 5);
+}
@@ -0,0 +1,218 @@
+use health_intel_backend::{jobs::backoff, setup_app};
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+async fn outbox_rows(pool: &PgPool, aggregate_id: Uuid) -> Vec<(String, bool)> {
+    sqlx::query_as("SELECT event_type, published_at IS NOT NULL FROM outbox_events WHERE aggregate_id = $1 ORDER BY id")
+        .bind(aggregate_id)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+async fn open_alert_count(client: &Client, addr: &str, hospital_id: &str) -> usize {
+    let resp = client.get(format!("{}/api/v1/capacity-alerts", addr))
+        .query(&[("hospital_id", hospital_id)])
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"].as_array().unwrap().len()
+}
+
+#[tokio::test]
+async fn changes_and_their_events_commit_together_and_are_relayed() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let name = format!("Outbox Hospital {}", Uuid::new_v4());
+    let body = |occupied: i32| json!({ "name": name, "hospital_type": "PUBLIC", "state": "Kano", "city": "Kano", "total_beds": 8, "occupied_beds": occupied });
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr)).json(&body(1)).send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = Uuid::parse_str(hospital["data"]["id"].as_str().unwrap()).unwrap();
+
+    let resp = client.put(format!("{}/api/v1/hospitals/{}", addr, hospital_id)).json(&body(2)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // Written with the change, and relayed in the background straight after
+    let rows = outbox_rows(&pool, hospital_id).await;
+    let types: Vec<&str> = rows.iter().map(|(t, _)| t.as_str()).collect();
+    assert_eq!(types, ["capacity.changed", "hospital.updated", "capacity.changed"]);
+    let mut rows = rows;
+    for _ in 0..50 {
+        if rows.iter().all(|(_, published)| *published) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        rows = outbox_rows(&pool, hospital_id).await;
+    }
+    assert!(rows.iter().all(|(_, published)| *published));
+
+    // A change that rolls back leaves no event behind
+    let missing = Uuid::new_v4();
+    let resp = client.put(format!("{}/api/v1/hospitals/{}", addr, missing)).json(&body(3)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+    assert!(outbox_rows(&pool, missing).await.is_empty());
+}
+
+#[tokio::test]
+async fn unrelayed_events_are_picked_up_in_order_per_aggregate() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({ "name": format!("Relay Hospital {}", Uuid::new_v4()), "hospital_type": "PUBLIC", "state": "Kaduna", "city": "Zaria", "total_beds": 10, "occupied_beds": 1 }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let hospital_uuid = Uuid::parse_str(&hospital_id).unwrap();
+
+    let resp = client.post(format!("{}/api/v1/alert-rules", addr))
+        .json(&json!({ "name": "Relay surge", "hospital_id": hospital_id, "metric": "OCCUPANCY_ABOVE", "threshold": 90.0 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // Simulate a crash after commit but before relay: the beds filled up and the event was
+    // written, but nothing consumed it. An earlier, broken event for the same hospital is stuck.
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("UPDATE hospitals SET occupied_beds = 10 WHERE id = $1")
+        .bind(hospital_uuid)
+        .execute(&mut *tx).await.unwrap();
+    let (broken_id,): (i64,) = sqlx::query_as(
+        r#"
+        INSERT INTO outbox_events (aggregate_type, aggregate_id, event_type, payload)
+        VALUES ('hospital', $1, 'capacity.changed', '{"hospital_id": "not-a-uuid"}')
+        RETURNING id
+        "#,
+    )
+    .bind(hospital_uuid)
+    .fetch_one(&mut *tx).await.unwrap();
+    sqlx::query("INSERT INTO outbox_events (aggregate_type, aggregate_id, event_type, payload) VALUES ('hospital', $1, 'capacity.changed', $2)")
+        .bind(hospital_uuid)
+        .bind(json!({ "hospital_id": hospital_id }).to_string())
+        .execute(&mut *tx).await.unwrap();
+    tx.commit().await.unwrap();
+
+    // 1. The broken event fails, is scheduled for a retry, and holds back the one behind it
+    let resp = client.post(format!("{}/api/v1/outbox/dispatch", addr)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(open_alert_count(&client, &addr, &hospital_id).await, 0);
+
+    let (attempts, last_error, retry_pending): (i32, Option<String>, bool) =
+        sqlx::query_as("SELECT attempts, last_error, next_attempt_at > NOW() FROM outbox_events WHERE id = $1")
+            .bind(broken_id)
+            .fetch_one(&pool).await.unwrap();
+    assert_eq!(attempts, 1);
+    assert!(last_error.unwrap().starts_with("invalid event payload"));
+    assert!(retry_pending);
+    let rows = outbox_rows(&pool, hospital_uuid).await;
+    assert!(!rows.last().unwrap().1);
+
+    // Until the back-off has passed, relaying again leaves it alone
+    client.post(format!("{}/api/v1/outbox/dispatch", addr)).send().await.unwrap();
+    let (attempts,): (i32,) = sqlx::query_as("SELECT attempts FROM outbox_events WHERE id = $1")
+        .bind(broken_id)
+        .fetch_one(&pool).await.unwrap();
+    assert_eq!(attempts, 1);
+
+    let resp = client.get(format!("{}/api/v1/outbox/status", addr)).send().await.unwrap();
+    let status: Value = resp.json().await.unwrap();
+    assert!(status["data"]["failing"].as_i64().unwrap() >= 1);
+
+    // 2. Once fixed and due again, both go through in order and the alert is raised
+    sqlx::query("UPDATE outbox_events SET payload = $2, next_attempt_at = NOW() WHERE id = $1")
+        .bind(broken_id)
+        .bind(json!({ "hospital_id": hospital_id }).to_string())
+        .execute(&pool).await.unwrap();
+    client.post(format!("{}/api/v1/outbox/dispatch", addr)).send().await.unwrap();
+
+    let rows = outbox_rows(&pool, hospital_uuid).await;
+    assert!(rows.iter().all(|(_, published)| *published));
+    assert_eq!(open_alert_count(&client, &addr, &hospital_id).await, 1);
+}
+
+#[tokio::test]
+async fn events_that_keep_failing_are_parked_and_stop_holding_back_their_aggregate() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({ "name": format!("Parking Hospital {}", Uuid::new_v4()), "hospital_type": "PUBLIC", "state": "Benue", "city": "Makurdi", "total_beds": 10, "occupied_beds": 1 }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let hospital_uuid = Uuid::parse_str(&hospital_id).unwrap();
+
+    // A broken event on its last attempt, with a good one behind it
+    let mut tx = pool.begin().await.unwrap();
+    let (broken_id,): (i64,) = sqlx::query_as(
+        r#"
+        INSERT INTO outbox_events (aggregate_type, aggregate_id, event_type, payload, attempts)
+        VALUES ('hospital', $1, 'capacity.changed', '{"hospital_id": "not-a-uuid"}', 1000)
+        RETURNING id
+        "#,
+    )
+    .bind(hospital_uuid)
+    .fetch_one(&mut *tx).await.unwrap();
+    sqlx::query("INSERT INTO outbox_events (aggregate_type, aggregate_id, event_type, payload) VALUES ('hospital', $1, 'capacity.changed', $2)")
+        .bind(hospital_uuid)
+        .bind(json!({ "hospital_id": hospital_id }).to_string())
+        .execute(&mut *tx).await.unwrap();
+    tx.commit().await.unwrap();
+
+    // 1. It is parked, and the event behind it is relayed
+    let resp = client.post(format!("{}/api/v1/outbox/dispatch", addr)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let rows = outbox_rows(&pool, hospital_uuid).await;
+    assert!(!rows[rows.len() - 2].1);
+    assert!(rows.last().unwrap().1);
+
+    let resp = client.get(format!("{}/api/v1/outbox/parked", addr)).send().await.unwrap();
+    let parked: Value = resp.json().await.unwrap();
+    let event = parked["data"].as_array().unwrap().iter().find(|e| e["id"] == broken_id).unwrap();
+    assert!(event["last_error"].is_string());
+    assert!(event.get("payload").is_none());
+
+    let resp = client.get(format!("{}/api/v1/outbox/status", addr)).send().await.unwrap();
+    let status: Value = resp.json().await.unwrap();
+    assert!(status["data"]["parked"].as_i64().unwrap() >= 1);
+
+    // 2. Requeueing gives it a fresh set of attempts; it fails again and waits for a retry
+    let requeue_url = format!("{}/api/v1/outbox/events/{}/requeue", addr, broken_id);
+    let resp = client.post(&requeue_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let (attempts, parked): (i32, bool) =
+        sqlx::query_as("SELECT attempts, parked_at IS NOT NULL FROM outbox_events WHERE id = $1")
+            .bind(broken_id)
+            .fetch_one(&pool).await.unwrap();
+    assert_eq!(attempts, 1);
+    assert!(!parked);
+
+    let resp = client.post(&requeue_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+    let resp = client.post(format!("{}/api/v1/outbox/events/{}/requeue", addr, i64::MAX)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    // Leave nothing behind for other runs
+    sqlx::query("DELETE FROM outbox_events WHERE id = $1").bind(broken_id).execute(&pool).await.unwrap();
+}
+
+#[test]
+fn retries_back_off_exponentially_up_to_the_cap() {
+    let delays: Vec<i64> = (0..6).map(|attempts| backoff(attempts, 30, 200).num_seconds()).collect();
+    assert_eq!(delays, vec![30, 30, 60, 120, 200, 200]);
+    // Absurd settings are capped at a year rather than overflowing
+    assert_eq!(backoff(i32::MAX, i64::MAX, i64::MAX).num_days(), 365);
+}
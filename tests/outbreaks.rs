@@ -0,0 +1,142 @@
+use health_intel_backend::{jobs::outbreak::detect, setup_app};
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+#[test]
+fn detector_flags_counts_above_the_baseline_band() {
+    // Steady 2-4 cases a week: mean 3, SD 1, threshold 5
+    let baseline = [2, 4, 2, 4, 2, 4, 2, 4];
+    let quiet = detect(5, &baseline, 2.0, 3);
+    assert_eq!(quiet.threshold, 5.0);
+    assert!(!quiet.alert);
+    assert!(detect(6, &baseline, 2.0, 3).alert);
+
+    // A silent area needs at least `min_cases` before it alerts
+    assert!(!detect(2, &[0; 8], 2.0, 3).alert);
+    assert!(detect(3, &[0; 8], 2.0, 3).alert);
+}
+
+#[tokio::test]
+async fn cluster_of_cholera_cases_raises_an_alert() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+    // A state name unique to this run keeps the baseline empty
+    let state = format!("Outbreak State {}", random_id);
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Outbreak Hospital {}", random_id),
+            "hospital_type": "PUBLIC",
+            "state": state,
+            "city": "Yola",
+            "lga": "Yola North"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "General Medicine", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Zainab",
+            "last_name": "Bello",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", random_id),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    // 1. Three completed cholera visits this week
+    for i in 0..3 {
+        let resp = client.post(format!("{}/api/v1/patients", addr))
+            .json(&json!({
+                "first_name": format!("Patient{}", i),
+                "last_name": "Adamu",
+                "date_of_birth": "1990-01-01",
+                "gender": "FEMALE"
+            }))
+            .send().await.unwrap();
+        let patient: Value = resp.json().await.unwrap();
+        let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+        let resp = client.post(format!("{}/api/v1/visits", addr))
+            .json(&json!({
+                "hospital_id": hospital_id,
+                "patient_id": patient_id,
+                "staff_id": staff_id,
+                "reason": "Acute watery diarrhoea"
+            }))
+            .send().await.unwrap();
+        let visit: Value = resp.json().await.unwrap();
+        let visit_id = visit["data"]["id"].as_str().unwrap().to_string();
+
+        client.post(format!("{}/api/v1/visits/{}/diagnoses", addr, visit_id))
+            .json(&json!({ "code": "A00.0", "description": "Cholera due to Vibrio cholerae 01, biovar cholerae" }))
+            .send().await.unwrap();
+        let resp = client.put(format!("{}/api/v1/visits/{}/status", addr, visit_id))
+            .json(&json!({ "status": "COMPLETED" }))
+            .send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+    }
+
+    // 2. Detection raises one alert for the area
+    let resp = client.post(format!("{}/api/v1/surveillance/alerts/run", addr))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let alerts_url = format!("{}/api/v1/surveillance/alerts", addr);
+    let resp = client.get(&alerts_url).query(&[("state", &state)]).send().await.unwrap();
+    let alerts: Value = resp.json().await.unwrap();
+    let rows = alerts["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["condition_name"], "Cholera");
+    assert_eq!(rows[0]["lga"], "Yola North");
+    assert_eq!(rows[0]["observed_cases"], 3);
+    assert_eq!(rows[0]["status"], "ACTIVE");
+    let alert_id = rows[0]["id"].as_str().unwrap().to_string();
+
+    // 3. Re-running refreshes the same alert instead of duplicating it
+    client.post(format!("{}/api/v1/surveillance/alerts/run", addr)).send().await.unwrap();
+    let resp = client.get(&alerts_url).query(&[("state", &state)]).send().await.unwrap();
+    let alerts: Value = resp.json().await.unwrap();
+    assert_eq!(alerts["data"].as_array().unwrap().len(), 1);
+
+    // 4. Resolving removes it from the active list
+    let resolve_url = format!("{}/api/v1/surveillance/alerts/{}/resolve", addr, alert_id);
+    let resp = client.put(&resolve_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let resp = client.put(&resolve_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    let resp = client.get(&alerts_url).query(&[("state", &state)]).send().await.unwrap();
+    let alerts: Value = resp.json().await.unwrap();
+    assert!(alerts["data"].as_array().unwrap().is_empty());
+}
@@ -0,0 +1,329 @@
+use health_intel_backend::{config::Settings, crypto::pii::PiiCipher, setup_app};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+// Signs in as a new records clerk at the hospital; patient data is only served to staff sessions
+async fn sign_in_at(client: &Client, addr: &str, pool: &PgPool, hospital_id: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Records", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Records",
+            "last_name": "Clerk",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+
+    let email = format!("clerk_{}@health.gov.ng", Uuid::new_v4());
+    let password_hash = bcrypt::hash("staff-password", 4).unwrap();
+    sqlx::query("INSERT INTO staff_accounts (staff_id, email, password_hash) VALUES ($1::uuid, $2, $3)")
+        .bind(staff["data"]["id"].as_str().unwrap())
+        .bind(&email)
+        .bind(&password_hash)
+        .execute(pool).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email, "password": "staff-password" }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"]["token"].as_str().unwrap().to_string()
+}
+
+async fn create_hospital(client: &Client, addr: &str, name: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": name,
+            "hospital_type": "PUBLIC",
+            "state": "Kano",
+            "city": "Kano"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    hospital["data"]["id"].as_str().unwrap().to_string()
+}
+
+// A phone number unique to this test run, as local digits (e.g. 08031234567)
+fn unique_phone() -> String {
+    format!("0803{:07}", Uuid::new_v4().as_u128() % 10_000_000)
+}
+
+#[tokio::test]
+async fn patient_pii_is_encrypted_at_rest_and_found_by_blind_index() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let hospital_id = create_hospital(&client, &addr, &format!("PII Home {}", random_id)).await;
+    let session = sign_in_at(&client, &addr, &pool, &hospital_id).await;
+    let stranger_id = create_hospital(&client, &addr, &format!("PII Stranger {}", random_id)).await;
+    let stranger_session = sign_in_at(&client, &addr, &pool, &stranger_id).await;
+    let phone = unique_phone();
+    let national_id = format!("NIN-{}", &random_id.simple().to_string()[..11]);
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "first_name": "Halima",
+            "last_name": "Bello",
+            "date_of_birth": "1988-02-14",
+            "gender": "FEMALE",
+            "contact_phone": phone,
+            "emergency_contact": "Musa Bello",
+            "address": "12 Zoo Road, Kano",
+            "national_id": national_id
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let patient: Value = resp.json().await.unwrap();
+    assert_eq!(patient["data"]["first_name"], "Halima");
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    // Nothing readable is stored
+    let row: (String, Option<String>, Option<String>, Option<i16>, Option<String>) = sqlx::query_as(
+        "SELECT first_name, contact_phone, address, pii_key_version, contact_phone_index FROM patients WHERE id = $1",
+    )
+    .bind(Uuid::parse_str(&patient_id).unwrap())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_ne!(row.0, "Halima");
+    assert_ne!(row.1.as_deref(), Some(phone.as_str()));
+    assert!(!row.2.unwrap().contains("Zoo Road"));
+    assert!(row.3.is_some());
+    assert!(row.4.is_some());
+
+    // ...but the API returns plaintext
+    let resp = client.get(format!("{}/api/v1/patients/{}", addr, patient_id))
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let record: Value = resp.json().await.unwrap();
+    assert_eq!(record["data"]["patient"]["last_name"], "Bello");
+    assert_eq!(record["data"]["patient"]["contact_phone"], phone.as_str());
+    assert_eq!(record["data"]["patient"]["national_id"], national_id.as_str());
+
+    // Phone lookup matches across formats (+234 vs leading 0, spaces)
+    let international = format!("+234 {} {}", &phone[1..4], &phone[4..]);
+    let resp = client.get(format!("{}/api/v1/patients/lookup", addr))
+        .query(&[("phone", international.as_str())])
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let found: Value = resp.json().await.unwrap();
+    let found = found["data"].as_array().unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0]["id"], patient_id.as_str());
+
+    let resp = client.get(format!("{}/api/v1/patients/lookup", addr))
+        .query(&[("national_id", national_id.to_lowercase().as_str())])
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    let found: Value = resp.json().await.unwrap();
+    assert_eq!(found["data"].as_array().unwrap().len(), 1);
+
+    // A hospital with no relationship to the patient gets no matches
+    let resp = client.get(format!("{}/api/v1/patients/lookup", addr))
+        .query(&[("phone", phone.as_str())])
+        .bearer_auth(&stranger_session)
+        .send().await.unwrap();
+    let found: Value = resp.json().await.unwrap();
+    assert!(found["data"].as_array().unwrap().is_empty());
+
+    // A lookup needs something to look up
+    let resp = client.get(format!("{}/api/v1/patients/lookup", addr))
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn reencryption_moves_plaintext_rows_onto_the_active_key() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let hospital_id = create_hospital(&client, &addr, &format!("PII Legacy {}", random_id)).await;
+    let session = sign_in_at(&client, &addr, &pool, &hospital_id).await;
+    let phone = unique_phone();
+
+    // A row written before encryption was enabled
+    let patient_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO patients (hospital_id, first_name, last_name, date_of_birth, gender, contact_phone)
+        VALUES ($1, 'Emeka', 'Nwosu', '1975-06-30', 'MALE', $2)
+        RETURNING id
+        "#,
+    )
+    .bind(Uuid::parse_str(&hospital_id).unwrap())
+    .bind(&phone)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    // Legacy rows stay readable, but have no blind index yet
+    let resp = client.get(format!("{}/api/v1/patients/{}", addr, patient_id))
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    let record: Value = resp.json().await.unwrap();
+    assert_eq!(record["data"]["patient"]["first_name"], "Emeka");
+
+    let resp = client.get(format!("{}/api/v1/patients/lookup", addr))
+        .query(&[("phone", phone.as_str())])
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    let found: Value = resp.json().await.unwrap();
+    assert!(found["data"].as_array().unwrap().is_empty());
+
+    let resp = client.post(format!("{}/api/v1/pii/reencrypt", addr)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let status: Value = resp.json().await.unwrap();
+    assert_eq!(status["data"]["pending"], 0);
+
+    let (first_name, version): (String, Option<i16>) =
+        sqlx::query_as("SELECT first_name, pii_key_version FROM patients WHERE id = $1")
+            .bind(patient_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_ne!(first_name, "Emeka");
+    assert_eq!(version, status["data"]["active_key_version"].as_i64().map(|v| v as i16));
+
+    let resp = client.get(format!("{}/api/v1/patients/{}", addr, patient_id))
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    let record: Value = resp.json().await.unwrap();
+    assert_eq!(record["data"]["patient"]["first_name"], "Emeka");
+    assert_eq!(record["data"]["patient"]["contact_phone"], phone.as_str());
+
+    let resp = client.get(format!("{}/api/v1/patients/lookup", addr))
+        .query(&[("phone", phone.as_str())])
+        .bearer_auth(&session)
+        .send().await.unwrap();
+    let found: Value = resp.json().await.unwrap();
+    assert_eq!(found["data"].as_array().unwrap().len(), 1);
+
+    let resp = client.get(format!("{}/api/v1/pii/key-status", addr)).send().await.unwrap();
+    let status: Value = resp.json().await.unwrap();
+    assert!(status["data"]["versions"].as_array().unwrap().iter().all(|v| !v["key_version"].is_null()));
+}
+
+#[tokio::test]
+async fn unreadable_rows_are_parked_without_stalling_reencryption() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let hospital_id = create_hospital(&client, &addr, &format!("PII Parked {}", random_id)).await;
+    let hospital_uuid = Uuid::parse_str(&hospital_id).unwrap();
+
+    // The oldest row in the table, under a key version that is no longer loaded...
+    let lost_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO patients (hospital_id, first_name, last_name, date_of_birth, gender, pii_key_version, created_at)
+        VALUES ($1, 'AAAA', 'BBBB', '1960-01-01', 'FEMALE', 99, '1900-01-01')
+        RETURNING id
+        "#,
+    )
+    .bind(hospital_uuid)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    // ...ahead of a plaintext row that can be rotated
+    let legacy_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO patients (hospital_id, first_name, last_name, date_of_birth, gender)
+        VALUES ($1, 'Hauwa', 'Sani', '1990-02-02', 'FEMALE')
+        RETURNING id
+        "#,
+    )
+    .bind(hospital_uuid)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let resp = client.post(format!("{}/api/v1/pii/reencrypt", addr)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let status: Value = resp.json().await.unwrap();
+    assert_eq!(status["data"]["pending"], 0);
+    assert!(status["data"]["parked"].as_i64().unwrap() >= 1);
+
+    let legacy_version: Option<i16> = sqlx::query_scalar("SELECT pii_key_version FROM patients WHERE id = $1")
+        .bind(legacy_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(legacy_version, status["data"]["active_key_version"].as_i64().map(|v| v as i16));
+
+    let parked: bool = sqlx::query_scalar("SELECT pii_reencrypt_failed_at IS NOT NULL FROM patients WHERE id = $1")
+        .bind(lost_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(parked);
+
+    // Parked rows are not picked up again
+    let resp = client.post(format!("{}/api/v1/pii/reencrypt", addr)).send().await.unwrap();
+    let rerun: Value = resp.json().await.unwrap();
+    assert!(rerun["meta"]["message"].as_str().unwrap().ends_with(", 0 parked as unreadable"));
+
+    sqlx::query("DELETE FROM patients WHERE id = $1").bind(lost_id).execute(&pool).await.unwrap();
+}
+
+#[tokio::test]
+async fn rotated_keys_still_decrypt_older_versions() {
+    let k1 = BASE64.encode([1u8; 32]);
+    let k2 = BASE64.encode([2u8; 32]);
+    let mut settings = Settings::from_env().expect("Failed to load configuration");
+    let patient_id = Uuid::new_v4();
+
+    // Without keys (and without the explicit development flag) the cipher refuses to start
+    settings.pii_dev_keys = false;
+    settings.pii_encryption_keys = None;
+    settings.pii_blind_index_key = None;
+    assert!(PiiCipher::from_settings(&settings).is_err());
+
+    settings.pii_blind_index_key = Some(BASE64.encode([9u8; 32]));
+    assert!(PiiCipher::from_settings(&settings).is_err());
+
+    settings.pii_encryption_keys = Some(format!("1:{}", k1));
+    let old = PiiCipher::from_settings(&settings).unwrap();
+    assert_eq!(old.active_version(), 1);
+    let stored = old.encrypt(patient_id, "first_name", "Ngozi");
+
+    settings.pii_encryption_keys = Some(format!("1:{},2:{}", k1, k2));
+    let rotated = PiiCipher::from_settings(&settings).unwrap();
+    assert_eq!(rotated.active_version(), 2);
+    assert_eq!(rotated.decrypt(Some(1), patient_id, "first_name", &stored).unwrap(), "Ngozi");
+
+    // Blind indexes do not depend on the encryption key, so lookups survive rotation
+    assert_eq!(old.phone_index("08030000001"), rotated.phone_index("+2348030000001"));
+
+    // Ciphertext is bound to its row and column
+    assert!(rotated.decrypt(Some(1), Uuid::new_v4(), "first_name", &stored).is_err());
+    assert!(rotated.decrypt(Some(1), patient_id, "last_name", &stored).is_err());
+    // ...and unknown versions are refused rather than misread
+    assert!(rotated.decrypt(Some(3), patient_id, "first_name", &stored).is_err());
+
+    // An active version that has no key is a configuration error
+    settings.pii_active_key_version = Some(3);
+    assert!(PiiCipher::from_settings(&settings).is_err());
+}
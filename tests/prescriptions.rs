@@ -0,0 +1,176 @@
+use health_intel_backend::setup_app;
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+// Signs in as a new records clerk at the hospital; patient data is only served to staff sessions
+async fn sign_in_at(client: &Client, addr: &str, pool: &PgPool, hospital_id: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Records", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Records",
+            "last_name": "Clerk",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+
+    let email = format!("clerk_{}@health.gov.ng", Uuid::new_v4());
+    let password_hash = bcrypt::hash("staff-password", 4).unwrap();
+    sqlx::query("INSERT INTO staff_accounts (staff_id, email, password_hash) VALUES ($1::uuid, $2, $3)")
+        .bind(staff["data"]["id"].as_str().unwrap())
+        .bind(&email)
+        .bind(&password_hash)
+        .execute(pool).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email, "password": "staff-password" }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"]["token"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn doctors_prescribe_and_duplicates_are_flagged() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Pharmacy Hospital {}", random_id),
+            "hospital_type": "PUBLIC",
+            "state": "Rivers",
+            "city": "Port Harcourt"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    let session = sign_in_at(&client, &addr, &pool, &hospital_id).await;
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Outpatients", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let mut staff_ids = Vec::new();
+    for (first_name, role) in [("Tamuno", "DOCTOR"), ("Ibiere", "NURSE")] {
+        let resp = client.post(format!("{}/api/v1/staff", addr))
+            .json(&json!({
+                "hospital_id": hospital_id,
+                "department_id": dept_id,
+                "first_name": first_name,
+                "last_name": "Briggs",
+                "role": role
+            }))
+            .send().await.unwrap();
+        let staff: Value = resp.json().await.unwrap();
+        let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+        client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+            .json(&json!({
+                "licence_body": if role == "DOCTOR" { "MDCN" } else { "NMCN" },
+                "licence_number": format!("{}-{}", role, random_id),
+                "issued_on": "2020-01-01",
+                "expires_on": "2099-12-31"
+            }))
+            .send().await.unwrap();
+        staff_ids.push(staff_id);
+    }
+    let (doctor_id, nurse_id) = (staff_ids[0].clone(), staff_ids[1].clone());
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "first_name": "Boma",
+            "last_name": "George",
+            "date_of_birth": "1988-11-03",
+            "gender": "FEMALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "patient_id": patient_id,
+            "staff_id": doctor_id,
+            "reason": "Dysuria and fever"
+        }))
+        .send().await.unwrap();
+    let visit: Value = resp.json().await.unwrap();
+    let visit_id = visit["data"]["id"].as_str().unwrap().to_string();
+    let prescriptions_url = format!("{}/api/v1/visits/{}/prescriptions", addr, visit_id);
+
+    let order = |prescriber_id: &str| json!({
+        "prescriber_id": prescriber_id,
+        "drug_name": "Ciprofloxacin",
+        "dose": "500 mg",
+        "route": "ORAL",
+        "frequency": "BD",
+        "duration_days": 5
+    });
+
+    // 1. Nurses cannot prescribe
+    let resp = client.post(&prescriptions_url).json(&order(&nurse_id)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // 2. Doctor prescribes; no warnings the first time
+    let resp = client.post(&prescriptions_url).json(&order(&doctor_id)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let first: Value = resp.json().await.unwrap();
+    assert_eq!(first["data"]["prescription"]["status"], "ACTIVE");
+    assert!(first["data"]["warnings"].as_array().unwrap().is_empty());
+    let first_id = first["data"]["prescription"]["id"].as_str().unwrap().to_string();
+
+    // 3. Same drug again (different case) warns about duplicate therapy
+    let mut duplicate = order(&doctor_id);
+    duplicate["drug_name"] = json!("ciprofloxacin");
+    let resp = client.post(&prescriptions_url).json(&duplicate).send().await.unwrap();
+    let second: Value = resp.json().await.unwrap();
+    assert_eq!(second["data"]["warnings"].as_array().unwrap().len(), 1);
+    let second_id = second["data"]["prescription"]["id"].as_str().unwrap().to_string();
+
+    let medications_url = format!("{}/api/v1/patients/{}/medications", addr, patient_id);
+    let resp = client.get(&medications_url).bearer_auth(&session).send().await.unwrap();
+    let history: Value = resp.json().await.unwrap();
+    assert_eq!(history["data"]["prescriptions"].as_array().unwrap().len(), 2);
+    assert_eq!(history["data"]["duplicate_warnings"][0]["prescription_ids"].as_array().unwrap().len(), 2);
+
+    // 4. Status flow: dispense, then discontinue the duplicate
+    let resp = client.post(format!("{}/api/v1/prescriptions/{}/dispense", addr, first_id))
+        .json(&json!({}))
+        .send().await.unwrap();
+    let dispensed: Value = resp.json().await.unwrap();
+    assert_eq!(dispensed["data"]["status"], "DISPENSED");
+    assert!(dispensed["data"]["dispensed_at"].is_string());
+
+    let discontinue_url = format!("{}/api/v1/prescriptions/{}/discontinue", addr, second_id);
+    let resp = client.post(&discontinue_url)
+        .json(&json!({ "reason": "Duplicate order" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let resp = client.post(&discontinue_url).json(&json!({})).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    let resp = client.get(&medications_url).bearer_auth(&session).send().await.unwrap();
+    let history: Value = resp.json().await.unwrap();
+    assert!(history["data"]["duplicate_warnings"].as_array().unwrap().is_empty());
+}
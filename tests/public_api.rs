@@ -0,0 +1,143 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+// Creates a hospital in `state`/`lga` with one licensed doctor and returns (hospital_id, staff_id)
+async fn create_hospital_with_doctor(client: &Client, addr: &str, name: &str, state: &str, lga: &str) -> (String, String) {
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": name,
+            "hospital_type": "PUBLIC",
+            "state": state,
+            "city": "Capital",
+            "lga": lga,
+            "total_beds": 40,
+            "occupied_beds": 31,
+            "has_oxygen": true
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Outpatients", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Aisha",
+            "last_name": "Yusuf",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", Uuid::new_v4()),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    (hospital_id, staff_id)
+}
+
+async fn create_visits(client: &Client, addr: &str, hospital_id: &str, staff_id: &str, count: usize) {
+    // One clinician sees one patient at a time, so the visits are an hour apart
+    for i in 0..count {
+        let resp = client.post(format!("{}/api/v1/patients", addr))
+            .json(&json!({
+                "hospital_id": hospital_id,
+                "first_name": "Public",
+                "last_name": "Tester",
+                "date_of_birth": "1995-01-01",
+                "gender": "OTHER"
+            }))
+            .send().await.unwrap();
+        let patient: Value = resp.json().await.unwrap();
+
+        let resp = client.post(format!("{}/api/v1/visits", addr))
+            .json(&json!({
+                "hospital_id": hospital_id,
+                "patient_id": patient["data"]["id"],
+                "staff_id": staff_id,
+                "reason": "Fever",
+                "start_time": Utc::now() - Duration::hours(i as i64 + 1)
+            }))
+            .send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+    }
+}
+
+#[tokio::test]
+async fn public_api_serves_suppressed_aggregates_only() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+    // A state of our own so other tests' visits do not change the counts
+    let state = format!("Public {}", random_id);
+
+    let (busy, busy_doctor) = create_hospital_with_doctor(&client, &addr, &format!("Busy {}", random_id), &state, "Alpha").await;
+    let (quiet, quiet_doctor) = create_hospital_with_doctor(&client, &addr, &format!("Quiet {}", random_id), &state, "Beta").await;
+    create_visits(&client, &addr, &busy, &busy_doctor, 5).await;
+    create_visits(&client, &addr, &quiet, &quiet_doctor, 2).await;
+
+    // Facilities and free beds, no caller identity needed
+    let resp = client.get(format!("{}/public/v1/hospitals", addr))
+        .query(&[("state", state.as_str())])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let hospitals: Value = resp.json().await.unwrap();
+    let hospitals = hospitals["data"].as_array().unwrap();
+    assert_eq!(hospitals.len(), 2);
+    assert_eq!(hospitals[0]["lga"], "Alpha");
+    assert_eq!(hospitals[0]["available_beds"], 9);
+    assert_eq!(hospitals[0]["has_oxygen"], true);
+    assert_eq!(hospitals[0]["oxygen_status"], "UNKNOWN");
+
+    let resp = client.get(format!("{}/public/v1/visits/weekly", addr))
+        .query(&[("state", state.as_str())])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"]["min_cell_size"], 5);
+    let counts = body["data"]["counts"].as_array().unwrap();
+    assert_eq!(counts.len(), 2);
+
+    let alpha = counts.iter().find(|c| c["lga"] == "Alpha").unwrap();
+    assert_eq!(alpha["visits"], 5);
+    assert_eq!(alpha["suppressed"], false);
+
+    // Two visits is below the threshold: the cell is reported but its count is withheld
+    let beta = counts.iter().find(|c| c["lga"] == "Beta").unwrap();
+    assert!(beta["visits"].is_null());
+    assert_eq!(beta["suppressed"], true);
+
+    // Nothing record-level leaks through
+    let text = body.to_string();
+    assert!(!text.contains("patient_id") && !text.contains(&busy) && !text.contains("Tester"));
+
+    let resp = client.get(format!("{}/public/v1/visits/weekly", addr))
+        .query(&[("from", "2020-01-01T00:00:00Z"), ("to", "2022-01-01T00:00:00Z")])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+}
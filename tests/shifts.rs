@@ -0,0 +1,235 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+#[tokio::test]
+async fn overlapping_shift_is_rejected_and_gaps_are_reported() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+
+    // 1. Hospital -> Department -> Nurse
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Roster Hospital {}", Uuid::new_v4()),
+            "hospital_type": "PUBLIC",
+            "state": "Oyo",
+            "city": "Ibadan"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Emergency", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Ada",
+            "last_name": "Okafor",
+            "role": "NURSE"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    // 2. A shift covering "now"
+    let start = Utc::now() - Duration::hours(1);
+    let resp = client.post(format!("{}/api/v1/shifts", addr))
+        .json(&json!({
+            "staff_id": staff_id,
+            "shift_type": "REGULAR",
+            "start_time": start,
+            "end_time": start + Duration::hours(8)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let shift: Value = resp.json().await.unwrap();
+    assert_eq!(shift["data"]["role"], "NURSE");
+    assert_eq!(shift["data"]["department_id"], dept_id.as_str());
+
+    // 3. Overlapping shift for the same nurse
+    let resp = client.post(format!("{}/api/v1/shifts", addr))
+        .json(&json!({
+            "staff_id": staff_id,
+            "shift_type": "ON_CALL",
+            "start_time": start + Duration::hours(4),
+            "end_time": start + Duration::hours(12)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // 4. She is on duty right now
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/on-duty", addr, hospital_id))
+        .send().await.unwrap();
+    let on_duty: Value = resp.json().await.unwrap();
+    assert_eq!(on_duty["data"].as_array().unwrap().len(), 1);
+    assert_eq!(on_duty["data"][0]["staff_id"], staff_id.as_str());
+
+    // 5. Emergency needs two nurses, so it is under-staffed
+    let resp = client.put(format!("{}/api/v1/coverage-rules", addr))
+        .json(&json!({ "department_id": dept_id, "role": "NURSE", "min_staff": 2 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/coverage-gaps", addr, hospital_id))
+        .send().await.unwrap();
+    let gaps: Value = resp.json().await.unwrap();
+    assert_eq!(gaps["data"].as_array().unwrap().len(), 1);
+    assert_eq!(gaps["data"][0]["on_duty"], 1);
+    assert_eq!(gaps["data"][0]["min_staff"], 2);
+
+    // 6. A second nurse closes the gap, until she is deactivated
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Bisi",
+            "last_name": "Adeyemi",
+            "role": "NURSE"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let second_nurse_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/shifts", addr))
+        .json(&json!({
+            "staff_id": second_nurse_id,
+            "shift_type": "REGULAR",
+            "start_time": start,
+            "end_time": start + Duration::hours(8)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/coverage-gaps", addr, hospital_id))
+        .send().await.unwrap();
+    let gaps: Value = resp.json().await.unwrap();
+    assert!(gaps["data"].as_array().unwrap().is_empty());
+
+    sqlx::query("UPDATE staff SET is_active = FALSE WHERE id = $1::uuid")
+        .bind(&second_nurse_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/coverage-gaps", addr, hospital_id))
+        .send().await.unwrap();
+    let gaps: Value = resp.json().await.unwrap();
+    assert_eq!(gaps["data"].as_array().unwrap().len(), 1);
+    assert_eq!(gaps["data"][0]["on_duty"], 1);
+
+    // 7. A deactivated nurse cannot be rostered again
+    let resp = client.post(format!("{}/api/v1/shifts", addr))
+        .json(&json!({
+            "staff_id": second_nurse_id,
+            "shift_type": "REGULAR",
+            "start_time": start + Duration::days(1),
+            "end_time": start + Duration::days(1) + Duration::hours(8)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // 8. Shift listings need a range that ends after it starts
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/shifts", addr, hospital_id))
+        .query(&[("from", start.to_rfc3339()), ("to", start.to_rfc3339())])
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.get(format!("{}/api/v1/hospitals/{}/shifts", addr, hospital_id))
+        .query(&[("from", start.to_rfc3339()), ("to", (start + Duration::hours(1)).to_rfc3339())])
+        .send().await.unwrap();
+    let shifts: Value = resp.json().await.unwrap();
+    assert_eq!(shifts["data"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn shifts_cannot_be_placed_in_another_hospitals_department() {
+    let (addr, _) = spawn_app().await;
+    let client = Client::new();
+
+    let mut dept_ids = Vec::new();
+    for city in ["Kano", "Kaduna"] {
+        let resp = client.post(format!("{}/api/v1/hospitals", addr))
+            .json(&json!({
+                "name": format!("{} Roster Hospital {}", city, Uuid::new_v4()),
+                "hospital_type": "PUBLIC",
+                "state": city,
+                "city": city
+            }))
+            .send().await.unwrap();
+        let hospital: Value = resp.json().await.unwrap();
+        let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+        let resp = client.post(format!("{}/api/v1/departments", addr))
+            .json(&json!({ "hospital_id": hospital_id, "name": "Theatre", "department_type": "MEDICAL" }))
+            .send().await.unwrap();
+        let dept: Value = resp.json().await.unwrap();
+        dept_ids.push((hospital_id, dept["data"]["id"].as_str().unwrap().to_string()));
+    }
+    let ((hospital_id, own_dept_id), (_, foreign_dept_id)) = (dept_ids[0].clone(), dept_ids[1].clone());
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": own_dept_id,
+            "first_name": "Musa",
+            "last_name": "Bello",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    let start = Utc::now() + Duration::days(1);
+    let resp = client.post(format!("{}/api/v1/shifts", addr))
+        .json(&json!({
+            "staff_id": staff_id,
+            "department_id": foreign_dept_id,
+            "shift_type": "REGULAR",
+            "start_time": start,
+            "end_time": start + Duration::hours(8)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(format!("{}/api/v1/rota-templates", addr))
+        .json(&json!({
+            "staff_id": staff_id,
+            "department_id": foreign_dept_id,
+            "shift_type": "REGULAR",
+            "day_of_week": 0,
+            "start_time": "08:00:00",
+            "duration_minutes": 480
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(format!("{}/api/v1/shifts", addr))
+        .json(&json!({
+            "staff_id": staff_id,
+            "department_id": own_dept_id,
+            "shift_type": "REGULAR",
+            "start_time": start,
+            "end_time": start + Duration::hours(8)
+        }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+}
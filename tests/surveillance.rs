@@ -0,0 +1,139 @@
+use health_intel_backend::setup_app;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> String {
+    let (app, _) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    format!("http://127.0.0.1:{}", port)
+}
+
+#[tokio::test]
+async fn completing_a_cholera_visit_raises_a_case_report() {
+    let addr = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+    // A state name unique to this run keeps the weekly counts isolated
+    let state = format!("Test State {}", random_id);
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": format!("Surveillance Hospital {}", random_id),
+            "hospital_type": "PUBLIC",
+            "state": state,
+            "city": "Maiduguri",
+            "lga": "Jere"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(hospital["data"]["lga"], "Jere");
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "General Medicine", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Fatima",
+            "last_name": "Ali",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", random_id),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "first_name": "Bukar",
+            "last_name": "Modu",
+            "date_of_birth": "1979-09-09",
+            "gender": "MALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "patient_id": patient_id,
+            "staff_id": staff_id,
+            "reason": "Profuse watery diarrhoea"
+        }))
+        .send().await.unwrap();
+    let visit: Value = resp.json().await.unwrap();
+    let visit_id = visit["data"]["id"].as_str().unwrap().to_string();
+
+    // 1. Diagnosis on an open visit is not reported yet
+    let resp = client.post(format!("{}/api/v1/visits/{}/diagnoses", addr, visit_id))
+        .json(&json!({ "code": "a00.9", "description": "Cholera, unspecified" }))
+        .send().await.unwrap();
+    let diagnosis: Value = resp.json().await.unwrap();
+    assert_eq!(diagnosis["data"]["code"], "A00.9");
+
+    let weekly_url = format!("{}/api/v1/surveillance/weekly", addr);
+    let resp = client.get(&weekly_url).query(&[("state", &state)]).send().await.unwrap();
+    let weekly: Value = resp.json().await.unwrap();
+    assert!(weekly["data"].as_array().unwrap().is_empty());
+
+    // 2. Completing the visit raises the case
+    let resp = client.put(format!("{}/api/v1/visits/{}/status", addr, visit_id))
+        .json(&json!({ "status": "COMPLETED" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let completed: Value = resp.json().await.unwrap();
+    assert!(completed["data"]["end_time"].is_string());
+
+    let resp = client.get(&weekly_url).query(&[("state", &state)]).send().await.unwrap();
+    let weekly: Value = resp.json().await.unwrap();
+    let rows = weekly["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["condition_name"], "Cholera");
+    assert_eq!(rows[0]["lga"], "Jere");
+    assert_eq!(rows[0]["cases"], 1);
+
+    // Case reports do not identify the patient
+    let resp = client.get(format!("{}/api/v1/surveillance/cases", addr)).query(&[("state", &state)]).send().await.unwrap();
+    let cases: Value = resp.json().await.unwrap();
+    let cases = cases["data"].as_array().unwrap();
+    assert_eq!(cases.len(), 1);
+    assert_eq!(cases[0]["visit_id"], visit_id.as_str());
+    assert!(cases[0].get("patient_id").is_none());
+
+    // 3. A completed visit cannot be reopened
+    let resp = client.put(format!("{}/api/v1/visits/{}/status", addr, visit_id))
+        .json(&json!({ "status": "IN_PROGRESS" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+
+    // Cancelling is not a status update: it goes through the appointments API
+    let resp = client.put(format!("{}/api/v1/visits/{}/status", addr, visit_id))
+        .json(&json!({ "status": "CANCELLED" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // Nor can a visit be put back to PENDING
+    let resp = client.put(format!("{}/api/v1/visits/{}/status", addr, visit_id))
+        .json(&json!({ "status": "PENDING" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+}
@@ -0,0 +1,211 @@
+use health_intel_backend::setup_app;
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+// Signs in as a new records clerk at the hospital; patient data is only served to staff sessions
+async fn sign_in_at(client: &Client, addr: &str, pool: &PgPool, hospital_id: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Medical Records", "department_type": "ADMIN" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept["data"]["id"],
+            "first_name": "Records",
+            "last_name": "Clerk",
+            "role": "ADMIN"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+
+    let email = format!("clerk_{}@health.gov.ng", Uuid::new_v4());
+    let password_hash = bcrypt::hash("staff-password", 4).unwrap();
+    sqlx::query("INSERT INTO staff_accounts (staff_id, email, password_hash) VALUES ($1::uuid, $2, $3)")
+        .bind(staff["data"]["id"].as_str().unwrap())
+        .bind(&email)
+        .bind(&password_hash)
+        .execute(pool).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/staff/login", addr))
+        .json(&json!({ "email": email, "password": "staff-password" }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"]["token"].as_str().unwrap().to_string()
+}
+
+// Creates a hospital with one licensed doctor and returns (hospital_id, staff_id)
+async fn create_hospital_with_doctor(client: &Client, addr: &str, name: &str) -> (String, String) {
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({
+            "name": name,
+            "hospital_type": "PUBLIC",
+            "state": "Kano",
+            "city": "Kano"
+        }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "General OPD", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let dept_id = dept["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({
+            "hospital_id": hospital_id,
+            "department_id": dept_id,
+            "first_name": "Musa",
+            "last_name": "Bello",
+            "role": "DOCTOR"
+        }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", Uuid::new_v4()),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    (hospital_id, staff_id)
+}
+
+#[tokio::test]
+async fn timeline_merges_events_across_hospitals_in_order() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let random_id = Uuid::new_v4();
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({
+            "first_name": "Hauwa",
+            "last_name": "Sani",
+            "date_of_birth": "1979-06-02",
+            "gender": "FEMALE"
+        }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    let (first_hospital, first_doctor) =
+        create_hospital_with_doctor(&client, &addr, &format!("Timeline General {}", random_id)).await;
+    let first_session = sign_in_at(&client, &addr, &pool, &first_hospital).await;
+    let (second_hospital, second_doctor) =
+        create_hospital_with_doctor(&client, &addr, &format!("Timeline Teaching {}", random_id)).await;
+    let second_session = sign_in_at(&client, &addr, &pool, &second_hospital).await;
+
+    // 1. A visit at each hospital, the first completed with a diagnosis
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": first_hospital,
+            "patient_id": patient_id,
+            "staff_id": first_doctor,
+            "reason": "Fever",
+            "start_time": "2024-01-10T09:00:00Z"
+        }))
+        .send().await.unwrap();
+    let visit: Value = resp.json().await.unwrap();
+    let first_visit = visit["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/visits/{}/diagnoses", addr, first_visit))
+        .json(&json!({ "code": "B50.9", "diagnosis_type": "PRIMARY" }))
+        .send().await.unwrap();
+
+    let resp = client.put(format!("{}/api/v1/visits/{}/status", addr, first_visit))
+        .json(&json!({ "status": "COMPLETED" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({
+            "hospital_id": second_hospital,
+            "patient_id": patient_id,
+            "staff_id": second_doctor,
+            "reason": "Follow-up",
+            "start_time": "2024-02-01T09:00:00Z"
+        }))
+        .send().await.unwrap();
+
+    // 2. Patient-level entries have no visit
+    client.post(format!("{}/api/v1/patients/{}/allergies", addr, patient_id))
+        .json(&json!({ "substance": "Sulfonamides", "severity": "MODERATE" }))
+        .send().await.unwrap();
+
+    // 3. Without consent the first hospital only sees its own events
+    let timeline_url = format!("{}/api/v1/patients/{}/timeline", addr, patient_id);
+    let resp = client.get(&timeline_url).bearer_auth(&first_session).send().await.unwrap();
+    let own: Value = resp.json().await.unwrap();
+    let types: Vec<&str> = own["data"]["events"].as_array().unwrap().iter().map(|e| e["event_type"].as_str().unwrap()).collect();
+    assert_eq!(types, ["VISIT", "DIAGNOSIS", "STATUS_CHANGE", "ALLERGY"]);
+
+    // The second hospital records the patient's consent to share with the first
+    let resp = client.post(format!("{}/api/v1/patients/{}/consents", addr, patient_id))
+        .bearer_auth(&second_session)
+        .json(&json!({ "hospital_id": first_hospital }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.get(&timeline_url).bearer_auth(&first_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let timeline: Value = resp.json().await.unwrap();
+    let events = timeline["data"]["events"].as_array().unwrap();
+    let types: Vec<&str> = events.iter().map(|e| e["event_type"].as_str().unwrap()).collect();
+    assert_eq!(types, ["VISIT", "VISIT", "DIAGNOSIS", "STATUS_CHANGE", "ALLERGY"]);
+    assert_eq!(events[0]["hospital_name"], format!("Timeline General {}", random_id));
+    assert_eq!(events[1]["hospital_name"], format!("Timeline Teaching {}", random_id));
+    assert_eq!(events[2]["status"], "PRIMARY");
+    assert_eq!(events[3]["summary"], "Visit PENDING -> COMPLETED");
+    assert!(events[4]["visit_id"].is_null());
+    assert_eq!(timeline["data"]["has_more"], false);
+    assert_eq!(timeline["meta"]["count"], 5);
+
+    // 4. Pagination, newest first
+    let resp = client.get(format!("{}?per_page=2&order=desc", timeline_url)).bearer_auth(&first_session).send().await.unwrap();
+    let page_one: Value = resp.json().await.unwrap();
+    assert_eq!(page_one["data"]["has_more"], true);
+    assert_eq!(page_one["data"]["events"][0]["event_type"], "ALLERGY");
+
+    let resp = client.get(format!("{}?per_page=2&page=3&order=desc", timeline_url)).bearer_auth(&first_session).send().await.unwrap();
+    let page_three: Value = resp.json().await.unwrap();
+    assert_eq!(page_three["data"]["has_more"], false);
+    assert_eq!(page_three["data"]["events"].as_array().unwrap().len(), 1);
+    assert_eq!(page_three["data"]["events"][0]["summary"], "Fever");
+
+    // 5. Filtering by event type
+    let resp = client.get(format!("{}?types=visit,diagnosis", timeline_url)).bearer_auth(&first_session).send().await.unwrap();
+    let filtered: Value = resp.json().await.unwrap();
+    assert_eq!(filtered["data"]["events"].as_array().unwrap().len(), 3);
+
+    let resp = client.get(format!("{}?types=REFERRAL", timeline_url)).bearer_auth(&first_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.get(format!("{}?per_page=500", timeline_url)).bearer_auth(&first_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // A page far enough out to overflow the offset is rejected up front
+    let resp = client.get(format!("{}?page={}", timeline_url, i64::MAX)).bearer_auth(&first_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // 6. Unknown patient
+    let resp = client.get(format!("{}/api/v1/patients/{}/timeline", addr, Uuid::new_v4())).bearer_auth(&first_session).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
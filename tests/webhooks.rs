@@ -0,0 +1,274 @@
+use std::sync::{Arc, Mutex};
+use axum::{extract::State, http::{HeaderMap, StatusCode}, routing::post, Router};
+use health_intel_backend::{jobs::webhooks::sign, setup_app};
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use reqwest::Client;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// Helper to spawn app
+async fn spawn_app() -> (String, PgPool) {
+    let (app, pool) = setup_app().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}", port), pool)
+}
+
+#[derive(Clone, Default)]
+struct Receiver {
+    requests: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+    // Number of requests to reject with a 500 before accepting
+    fail_first: usize,
+}
+
+async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: String) -> StatusCode {
+    let mut requests = receiver.requests.lock().unwrap();
+    requests.push((headers, body));
+    if requests.len() <= receiver.fail_first {
+        StatusCode::INTERNAL_SERVER_ERROR
+    } else {
+        StatusCode::OK
+    }
+}
+
+// Local stand-in for a partner system
+async fn spawn_receiver(fail_first: usize) -> (String, Receiver) {
+    let receiver = Receiver { fail_first, ..Default::default() };
+    let app = Router::new().route("/hook", post(receive)).with_state(receiver.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+    (format!("http://127.0.0.1:{}/hook", port), receiver)
+}
+
+async fn deliveries(client: &Client, addr: &str, endpoint_id: &str) -> Vec<Value> {
+    let resp = client.get(format!("{}/api/v1/webhooks/{}/deliveries", addr, endpoint_id))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    body["data"].as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn hospital_updates_are_signed_retried_and_redeliverable() {
+    let (addr, pool) = spawn_app().await;
+    let client = Client::new();
+    let (url, receiver) = spawn_receiver(1).await;
+    let secret = "partner-shared-secret-0001";
+    let name = format!("Webhook Hospital {}", Uuid::new_v4());
+
+    let resp = client.post(format!("{}/api/v1/webhooks", addr))
+        .json(&json!({ "name": "Ministry dashboard", "url": url, "event_types": ["hospital.created"] }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client.post(format!("{}/api/v1/webhooks", addr))
+        .json(&json!({ "name": "Ministry dashboard", "url": url, "event_types": ["hospital.updated"], "secret": secret }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"]["secret"], secret);
+    let endpoint_id = body["data"]["endpoint"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({ "name": name, "hospital_type": "PUBLIC", "state": "Ogun", "city": "Abeokuta", "total_beds": 20, "occupied_beds": 5 }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.put(format!("{}/api/v1/hospitals/{}", addr, hospital_id))
+        .json(&json!({ "name": name, "hospital_type": "PUBLIC", "state": "Ogun", "city": "Abeokuta", "total_beds": 20, "occupied_beds": 6 }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // 1. The first attempt is rejected and scheduled for a retry
+    let mut log = Vec::new();
+    for _ in 0..50 {
+        log = deliveries(&client, &addr, &endpoint_id).await;
+        if log.first().is_some_and(|d| d["attempts"] == 1) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(log.len(), 1);
+    let delivery = &log[0];
+    assert_eq!(delivery["event_type"], "hospital.updated");
+    assert_eq!(delivery["status"], "PENDING");
+    assert_eq!(delivery["last_status_code"], 500);
+    let delivery_id = delivery["id"].as_str().unwrap().to_string();
+    let event_id = delivery["event_id"].as_str().unwrap().to_string();
+    let next_attempt: chrono::DateTime<chrono::Utc> = delivery["next_attempt_at"].as_str().unwrap().parse().unwrap();
+    assert!(next_attempt > chrono::Utc::now());
+
+    // Not due yet: the dispatcher leaves it alone
+    client.post(format!("{}/api/v1/webhooks/dispatch", addr)).send().await.unwrap();
+    assert_eq!(receiver.requests.lock().unwrap().len(), 1);
+
+    // 2. Once the back-off has passed, the retry succeeds
+    sqlx::query("UPDATE webhook_deliveries SET next_attempt_at = NOW() WHERE id = $1")
+        .bind(Uuid::parse_str(&delivery_id).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    client.post(format!("{}/api/v1/webhooks/dispatch", addr)).send().await.unwrap();
+
+    let log = deliveries(&client, &addr, &endpoint_id).await;
+    assert_eq!(log[0]["status"], "DELIVERED");
+    assert_eq!(log[0]["attempts"], 2);
+    assert_eq!(log[0]["last_status_code"], 200);
+
+    // 3. The request is signed over the timestamp and exact body
+    {
+        let requests = receiver.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        let (headers, body) = &requests[1];
+        let timestamp: i64 = headers["x-webhook-timestamp"].to_str().unwrap().parse().unwrap();
+        assert_eq!(headers["x-webhook-signature"].to_str().unwrap(), sign(secret, timestamp, body));
+        assert_eq!(headers["x-webhook-event"], "hospital.updated");
+        assert_eq!(headers["x-webhook-event-id"].to_str().unwrap(), event_id);
+
+        let event: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(event["type"], "hospital.updated");
+        assert_eq!(event["data"]["id"], hospital_id.as_str());
+        assert_eq!(event["data"]["occupied_beds"], 6);
+    }
+
+    // 4. Manual redelivery sends the same event again as a new log entry
+    let resp = client.post(format!("{}/api/v1/webhook-deliveries/{}/redeliver", addr, delivery_id))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"]["status"], "DELIVERED");
+    assert_eq!(body["data"]["event_id"], event_id.as_str());
+    assert_ne!(body["data"]["id"], delivery_id.as_str());
+    assert_eq!(receiver.requests.lock().unwrap().len(), 3);
+    assert_eq!(deliveries(&client, &addr, &endpoint_id).await.len(), 2);
+
+    // 5. A deactivated endpoint gets nothing more
+    let resp = client.put(format!("{}/api/v1/webhooks/{}/deactivate", addr, endpoint_id))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let resp = client.post(format!("{}/api/v1/webhook-deliveries/{}/redeliver", addr, delivery_id))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+}
+
+#[tokio::test]
+async fn completed_visits_are_published_without_patient_details() {
+    let (addr, _pool) = spawn_app().await;
+    let client = Client::new();
+    let (url, receiver) = spawn_receiver(0).await;
+
+    let resp = client.post(format!("{}/api/v1/webhooks", addr))
+        .json(&json!({ "name": "NHIS claims", "url": url, "event_types": ["visit.completed"] }))
+        .send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    let endpoint_id = body["data"]["endpoint"]["id"].as_str().unwrap().to_string();
+    // A secret is generated when none is supplied
+    assert_eq!(body["data"]["secret"].as_str().unwrap().len(), 64);
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({ "name": format!("Visit Hook Hospital {}", Uuid::new_v4()), "hospital_type": "PUBLIC", "state": "Oyo", "city": "Ibadan" }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/departments", addr))
+        .json(&json!({ "hospital_id": hospital_id, "name": "Outpatients", "department_type": "MEDICAL" }))
+        .send().await.unwrap();
+    let dept: Value = resp.json().await.unwrap();
+    let resp = client.post(format!("{}/api/v1/staff", addr))
+        .json(&json!({ "hospital_id": hospital_id, "department_id": dept["data"]["id"], "first_name": "Bola", "last_name": "Ade", "role": "DOCTOR" }))
+        .send().await.unwrap();
+    let staff: Value = resp.json().await.unwrap();
+    let staff_id = staff["data"]["id"].as_str().unwrap().to_string();
+    client.post(format!("{}/api/v1/staff/{}/credentials", addr, staff_id))
+        .json(&json!({
+            "licence_body": "MDCN",
+            "licence_number": format!("MDCN-{}", Uuid::new_v4()),
+            "issued_on": "2020-01-01",
+            "expires_on": "2099-12-31"
+        }))
+        .send().await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/patients", addr))
+        .json(&json!({ "first_name": "Kemi", "last_name": "Ojo", "date_of_birth": "1990-01-01", "gender": "FEMALE" }))
+        .send().await.unwrap();
+    let patient: Value = resp.json().await.unwrap();
+    let patient_id = patient["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/visits", addr))
+        .json(&json!({ "hospital_id": hospital_id, "patient_id": patient_id, "staff_id": staff_id, "reason": "Follow-up" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let visit: Value = resp.json().await.unwrap();
+    let visit_id = visit["data"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.put(format!("{}/api/v1/visits/{}/status", addr, visit_id))
+        .json(&json!({ "status": "COMPLETED" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let mut log = Vec::new();
+    for _ in 0..50 {
+        log = deliveries(&client, &addr, &endpoint_id).await;
+        if log.iter().any(|d| d["status"] == "DELIVERED") {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    let delivered = log.iter().find(|d| d["status"] == "DELIVERED").unwrap();
+    let event: Value = serde_json::from_str(delivered["payload"].as_str().unwrap()).unwrap();
+    assert_eq!(event["data"]["visit_id"], visit_id.as_str());
+    assert_eq!(event["data"]["hospital_id"], hospital_id.as_str());
+    assert!(event["data"].get("patient_id").is_none());
+    assert!(!receiver.requests.lock().unwrap()[0].1.contains("Follow-up"));
+
+    client.put(format!("{}/api/v1/webhooks/{}/deactivate", addr, endpoint_id))
+        .send().await.unwrap();
+}
+
+#[tokio::test]
+async fn raised_capacity_alerts_reach_subscribed_endpoints() {
+    let (addr, _pool) = spawn_app().await;
+    let client = Client::new();
+    let (url, _receiver) = spawn_receiver(0).await;
+    let state = format!("Alert Hook {}", Uuid::new_v4());
+
+    let resp = client.post(format!("{}/api/v1/webhooks", addr))
+        .json(&json!({ "name": "State EOC", "url": url, "event_types": ["capacity_alert.raised"] }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let endpoint_id = body["data"]["endpoint"]["id"].as_str().unwrap().to_string();
+
+    let resp = client.post(format!("{}/api/v1/alert-rules", addr))
+        .json(&json!({ "name": "Beds nearly full", "state": state, "metric": "OCCUPANCY_ABOVE", "threshold": 90.0, "severity": "CRITICAL" }))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client.post(format!("{}/api/v1/hospitals", addr))
+        .json(&json!({ "name": format!("Alert Hook Hospital {}", Uuid::new_v4()), "hospital_type": "PUBLIC", "state": state, "city": "Capital", "total_beds": 10, "occupied_beds": 10 }))
+        .send().await.unwrap();
+    let hospital: Value = resp.json().await.unwrap();
+    let hospital_id = hospital["data"]["id"].as_str().unwrap().to_string();
+
+    let mut log = Vec::new();
+    for _ in 0..50 {
+        log = deliveries(&client, &addr, &endpoint_id).await;
+        if log.iter().any(|d| d["status"] == "DELIVERED") {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    let delivered = log.iter().find(|d| d["status"] == "DELIVERED").unwrap();
+    let event: Value = serde_json::from_str(delivered["payload"].as_str().unwrap()).unwrap();
+    assert_eq!(event["type"], "capacity_alert.raised");
+    assert_eq!(event["data"]["hospital_id"], hospital_id.as_str());
+    assert_eq!(event["data"]["severity"], "CRITICAL");
+    assert_eq!(event["data"]["status"], "ACTIVE");
+
+    client.put(format!("{}/api/v1/webhooks/{}/deactivate", addr, endpoint_id))
+        .send().await.unwrap();
+}